@@ -0,0 +1,71 @@
+use napi::{Error, Result};
+use napi_derive::napi;
+use rpack::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+
+/// The size of a rectangle or container, as `{ width, height }` in JavaScript.
+#[napi(object, js_name = "Size")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A packed rectangle, as `{ x, y, width, height }` in JavaScript.
+#[napi(object, js_name = "Rectangle")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeRectangle {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The options of `pack`, mirroring `RectanglePackerConfig`. All fields are optional in JavaScript.
+#[napi(object, js_name = "PackOptions")]
+#[derive(Debug, Clone, Default)]
+pub struct NodePackOptions {
+    pub max_size: Option<NodeSize>,
+    pub rectangle_padding: Option<u32>,
+    pub border_padding: Option<u32>,
+}
+
+/// The result of `pack`, as `{ size, rectangles }` in JavaScript.
+#[napi(object, js_name = "PackingResult")]
+#[derive(Debug, Clone)]
+pub struct NodePackingResult {
+    pub size: NodeSize,
+    pub rectangles: Vec<NodeRectangle>,
+}
+
+/// Packs rectangles with the `HeightRectPacker`.
+///
+/// Returns the size of the container and the packed rectangles, in the same order as the sizes.
+/// Throws an `Error` if the rectangles can not be packed.
+#[napi]
+pub fn pack(sizes: Vec<NodeSize>, options: Option<NodePackOptions>) -> Result<NodePackingResult> {
+    let options = options.unwrap_or_default();
+    let sizes: Vec<Size> = sizes.iter().map(|s| Size::new(s.width as usize, s.height as usize)).collect();
+    let config = RectanglePackerConfig {
+        max_size: options.max_size.map(|s| Size::new(s.width as usize, s.height as usize)),
+        rectangle_padding: options.rectangle_padding.unwrap_or(0) as usize,
+        border_padding: options.border_padding.unwrap_or(0) as usize,
+        ..Default::default()
+    };
+
+    let result = HeightRectPacker::pack(&sizes, &config).map_err(|e| Error::from_reason(e.message))?;
+    Ok(NodePackingResult {
+        size: NodeSize {
+            width: result.size.width as u32,
+            height: result.size.height as u32,
+        },
+        rectangles: result.rectangles
+            .iter()
+            .map(|r| NodeRectangle {
+                x: r.x as u32,
+                y: r.y as u32,
+                width: r.width as u32,
+                height: r.height as u32,
+            })
+            .collect(),
+    })
+}
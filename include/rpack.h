@@ -0,0 +1,321 @@
+/* C interface of the rpack rectangle packer, built with the `ffi` feature. */
+
+#ifndef RPACK_H
+#define RPACK_H
+
+/* Generated with cbindgen from src/ffi.rs, do not edit by hand. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/*
+ The page written by `rpack_pack_pages` for sizes that were skipped or not placed.
+ */
+#define RPACK_NOT_PLACED SIZE_MAX
+
+/*
+ The rectangles were packed successfully.
+ */
+#define RPACK_OK 0
+
+/*
+ A required pointer argument was null.
+ */
+#define RPACK_ERROR_NULL_POINTER 1
+
+/*
+ The rectangles could not be packed, e.g. because they do not fit into the maximum size.
+ */
+#define RPACK_ERROR_PACKING_FAILED 2
+
+/*
+ The packer panicked.
+ */
+#define RPACK_ERROR_PANIC 3
+
+/*
+ An argument was not one of the values it can take, e.g. an unknown policy.
+ */
+#define RPACK_ERROR_INVALID_ARGUMENT 4
+
+/*
+ Sizes with a width or height of zero are placed like any other size.
+ */
+#define RPACK_ZERO_SIZE_PLACE 0
+
+/*
+ Packing fails if any size has a width or height of zero.
+ */
+#define RPACK_ZERO_SIZE_REJECT 1
+
+/*
+ Sizes with a width or height of zero are skipped and reported as such.
+ */
+#define RPACK_ZERO_SIZE_SKIP 2
+
+/*
+ `rpack_pack_pages` fails if the sizes do not fit into a single page.
+ */
+#define RPACK_OVERFLOW_ERROR 0
+
+/*
+ `rpack_pack_pages` opens new pages for the sizes that do not fit.
+ */
+#define RPACK_OVERFLOW_NEW_PAGE 1
+
+/*
+ `rpack_pack_pages` packs as many sizes as fit into a single page and leaves the others unplaced.
+ */
+#define RPACK_OVERFLOW_PARTIAL 2
+
+/*
+ `rpack_pack_pages` grows the single page beyond the maximum size.
+ */
+#define RPACK_OVERFLOW_GROW 3
+
+/*
+ New pages are filled one after the other.
+ */
+#define RPACK_PAGE_FILL_SEQUENTIAL 0
+
+/*
+ The sizes are distributed evenly over the pages.
+ */
+#define RPACK_PAGE_FILL_BALANCED 1
+
+/*
+ Every size is placed on the first page it fits on.
+ */
+#define RPACK_PAGE_FILL_FIRST_FIT 2
+
+/*
+ Every size is placed on the fullest page it fits on.
+ */
+#define RPACK_PAGE_FILL_BEST_FIT 3
+
+/*
+ Every size is placed on the emptiest page it fits on.
+ */
+#define RPACK_PAGE_FILL_WORST_FIT 4
+
+/*
+ The configuration of the packers, an opaque handle to a `RectanglePackerConfig`.
+
+ Created with `rpack_config_new`, changed with the `rpack_config_set_*` functions and freed with `rpack_config_free`.
+ */
+typedef struct RpackConfig RpackConfig;
+
+/*
+ The size of a rectangle or container.
+ */
+typedef struct RpackSize {
+  size_t width;
+  size_t height;
+} RpackSize;
+
+/*
+ A packed rectangle, given by its top-left corner and its size.
+ */
+typedef struct RpackRect {
+  size_t x;
+  size_t y;
+  size_t width;
+  size_t height;
+} RpackRect;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Creates a config with the default values of `RectanglePackerConfig`, to be freed with `rpack_config_free`.
+ */
+struct RpackConfig *rpack_config_new(void);
+
+/*
+ Frees a config created with `rpack_config_new`. Does nothing if `config` is null.
+
+ # Safety
+ `config` must be null or a config created with `rpack_config_new` that was not freed yet.
+ */
+void rpack_config_free(struct RpackConfig *config);
+
+/*
+ Sets the maximum size of the container. A `width` or `height` of `0` means that the container size is determined dynamically.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_max_size(struct RpackConfig *config,
+                              size_t width,
+                              size_t height);
+
+/*
+ Sets the margin kept free around each rectangle, the `rectangle_padding` of the config.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_rectangle_padding(struct RpackConfig *config, size_t padding);
+
+/*
+ Sets the padding added to every side of each rectangle, which is part of the packed rectangles.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_owned_padding(struct RpackConfig *config, size_t padding);
+
+/*
+ Sets the padding kept free along the edges of the container.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_border_padding(struct RpackConfig *config, size_t padding);
+
+/*
+ Sets the space added between the columns and between the rows of rectangles.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_gutters(struct RpackConfig *config, size_t column, size_t row);
+
+/*
+ Sets the multiple the height of every row is rounded up to. A `multiple` of `0` leaves the heights unrounded.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_row_height_multiple(struct RpackConfig *config,
+                                         size_t multiple);
+
+/*
+ Sets whether the space left below the shorter rectangles of every row is filled with smaller rectangles.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_fill_row_gaps(struct RpackConfig *config,
+                                   bool fill);
+
+/*
+ Sets how sizes with a width or height of zero are treated, one of the `RPACK_ZERO_SIZE_*` values.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_zero_size_policy(struct RpackConfig *config, int policy);
+
+/*
+ Sets whether identical sizes are packed only once, sharing the same rectangle.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_deduplicate_sizes(struct RpackConfig *config, bool deduplicate);
+
+/*
+ Sets the weights the packers trade off the dimensions of the container with, if there is no maximum size.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_objective(struct RpackConfig *config,
+                               double height,
+                               double squareness,
+                               double perimeter,
+                               double area);
+
+/*
+ Sets the maximum number of bytes of temporary memory the packers may use. A `bytes` of `0` removes the limit.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_memory_budget(struct RpackConfig *config,
+                                   size_t bytes);
+
+/*
+ Sets what `rpack_pack_pages` does if the sizes do not fit into the maximum size, one of the `RPACK_OVERFLOW_*` values.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_overflow(struct RpackConfig *config,
+                              int overflow);
+
+/*
+ Sets the maximum number of pages `rpack_pack_pages` may open. A `max_pages` of `0` removes the limit.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_max_pages(struct RpackConfig *config,
+                               size_t max_pages);
+
+/*
+ Sets how `rpack_pack_pages` distributes the sizes over the pages, one of the `RPACK_PAGE_FILL_*` values.
+
+ # Safety
+ `config` must be null or a valid config created with `rpack_config_new`.
+ */
+int rpack_config_set_page_fill(struct RpackConfig *config,
+                               int fill);
+
+/*
+ Packs `count` rectangle sizes with the `HeightRectPacker`.
+
+ On success, the packed rectangles are written to `out_rects` in the order of the sizes,
+ the size of the container is written to `out_size` and `RPACK_OK` is returned. If `out_skipped` is not null,
+ it receives whether each size was skipped by the zero size policy, in which case its rectangle is empty.
+ Otherwise one of the `RPACK_ERROR_*` codes is returned and the outputs are left untouched.
+
+ # Safety
+ `sizes`, `out_rects` and `out_skipped`, unless it is null, must point to arrays of at least `count` elements and
+ `out_size` to a single `RpackSize`. If `config` is null, the default config is used; otherwise it must be a valid
+ config created with `rpack_config_new`. If `count` is `0`, `sizes` and `out_rects` may be null.
+ */
+int rpack_pack(const struct RpackSize *sizes,
+               size_t count,
+               const struct RpackConfig *config,
+               struct RpackRect *out_rects,
+               bool *out_skipped,
+               struct RpackSize *out_size);
+
+/*
+ Packs `count` rectangle sizes with the `HeightRectPacker` onto pages, applying the overflow policy,
+ the maximum number of pages and the page fill of the config.
+
+ On success, the page of every size is written to `out_pages` and its rectangle on that page to `out_rects`,
+ in the order of the sizes, and `RPACK_OK` is returned. Sizes that were skipped by the zero size policy or not
+ placed get the page `RPACK_NOT_PLACED`. The size of every page is written to `out_page_sizes`,
+ and the number of pages to `out_page_count`. Otherwise one of the `RPACK_ERROR_*` codes is returned
+ and the outputs are left untouched.
+
+ # Safety
+ `sizes`, `out_pages` and `out_rects` must point to arrays of at least `count` elements, `out_page_sizes` to an
+ array of at least `count` elements, or one if `count` is `0`, and `out_page_count` to a single `size_t`.
+ If `config` is null, the default config is used; otherwise it must be a valid config created with `rpack_config_new`.
+ If `count` is `0`, `sizes`, `out_pages` and `out_rects` may be null.
+ */
+int rpack_pack_pages(const struct RpackSize *sizes,
+                     size_t count,
+                     const struct RpackConfig *config,
+                     size_t *out_pages,
+                     struct RpackRect *out_rects,
+                     struct RpackSize *out_page_sizes,
+                     size_t *out_page_count);
+
+/*
+ Returns a static, null-terminated description of a status code returned by the functions of this interface.
+ */
+const char *rpack_status_message(int status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RPACK_H */
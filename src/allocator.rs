@@ -0,0 +1,1087 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::free_rectangles::FreeRectangles;
+use crate::{Rectangle, Size};
+
+/// Identifies an allocation of an `AtlasAllocator`.
+///
+/// Identifiers are never reused by the allocator that handed them out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AllocationId(usize);
+
+/// A rectangle allocated by an `AtlasAllocator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Allocation {
+    /// The identifier to look up or deallocate the rectangle with.
+    pub id: AllocationId,
+
+    /// The allocated rectangle, excluding its padding.
+    pub rectangle: Rectangle,
+}
+
+/// The allocations to evict to make room for a rectangle, computed by `AtlasAllocator::plan_eviction`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvictionPlan {
+    /// The allocations to deallocate, ordered by their identifiers.
+    pub evicted: Vec<AllocationId>,
+
+    /// The rectangle to reserve for the new size once the allocations are deallocated.
+    pub rectangle: Rectangle,
+
+    /// The total cost of the evicted allocations.
+    pub cost: f64,
+}
+
+/// A stateful allocator placing rectangles one at a time into a container of fixed size.
+///
+/// Unlike the `RectanglePacker`s, which pack a known list of sizes at once, the allocator
+/// supports adding and removing rectangles over time, as needed by texture atlases filled at runtime.
+/// The free space is tracked as a list of maximal free rectangles, and every rectangle is placed into
+/// the free rectangle it fits best along its shorter side. With the `rayon` feature, large lists of
+/// free rectangles are scored in parallel.
+///
+/// # Examples
+/// ```
+/// use rpack::{AtlasAllocator, Size};
+///
+/// let mut allocator = AtlasAllocator::new(Size::new(64, 64)).with_padding(1, 0);
+///
+/// let a = allocator.allocate(Size::new(30, 30)).unwrap();
+/// let b = allocator.allocate(Size::new(30, 30)).unwrap();
+/// assert!(!a.rectangle.intersects(&b.rectangle));
+///
+/// allocator.deallocate(a.id);
+/// assert!(allocator.get(a.id).is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct AtlasAllocator {
+    size: Size,
+    rectangle_padding: usize,
+    owned_padding: usize,
+    border_padding: usize,
+    free: FreeRectangles,
+    merging: bool,
+    max_free_rectangles: Option<usize>,
+    allocations: BTreeMap<AllocationId, Rectangle>,
+    next_id: usize,
+    transaction: Option<Transaction>,
+}
+
+/// A read-only view of an `AtlasAllocator`, given to the scoring function of `AtlasAllocator::allocate_scored`.
+pub struct PackerState<'a> {
+    allocator: &'a AtlasAllocator,
+}
+
+impl PackerState<'_> {
+    /// Returns the size of the container.
+    pub fn size(&self) -> Size {
+        self.allocator.size
+    }
+
+    /// Returns the maximal free rectangles, including the padding to keep around rectangles placed into them,
+    /// in no particular order.
+    pub fn free_rectangles(&self) -> impl Iterator<Item = Rectangle> + '_ {
+        self.allocator.free.iter()
+    }
+
+    /// Returns all allocations, ordered by their identifiers.
+    pub fn allocations(&self) -> impl Iterator<Item = Allocation> + '_ {
+        self.allocator.allocations()
+    }
+}
+
+/// A saved state of the allocations of an `AtlasAllocator`, taken with `AtlasAllocator::snapshot`.
+///
+/// With the `serde` feature, the state can be serialized, e.g. to restore an atlas on the next launch.
+/// Only the allocations and the next identifier are stored, and the free space is rebuilt from them when deserializing.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(into = "AllocatorStateDef", try_from = "AllocatorStateDef"))]
+pub struct AllocatorState {
+    size: Size,
+    rectangle_padding: usize,
+    owned_padding: usize,
+    border_padding: usize,
+    free: FreeRectangles,
+    allocations: BTreeMap<AllocationId, Rectangle>,
+    next_id: usize,
+}
+
+/// The serialized form of an `AllocatorState`.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct AllocatorStateDef {
+    width: usize,
+    height: usize,
+    rectangle_padding: usize,
+    #[serde(default)]
+    owned_padding: usize,
+    border_padding: usize,
+    allocations: Vec<AllocationDef>,
+    #[serde(default)]
+    next_id: usize,
+}
+
+/// The serialized form of an allocation.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct AllocationDef {
+    id: usize,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+#[cfg(feature = "serde")]
+impl From<AllocatorState> for AllocatorStateDef {
+    fn from(state: AllocatorState) -> AllocatorStateDef {
+        AllocatorStateDef {
+            width: state.size.width,
+            height: state.size.height,
+            rectangle_padding: state.rectangle_padding,
+            owned_padding: state.owned_padding,
+            border_padding: state.border_padding,
+            allocations: state
+                .allocations
+                .iter()
+                .map(|(id, r)| AllocationDef { id: id.0, x: r.x, y: r.y, width: r.width, height: r.height })
+                .collect(),
+            next_id: state.next_id,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AllocatorStateDef> for AllocatorState {
+    type Error = String;
+
+    fn try_from(def: AllocatorStateDef) -> Result<AllocatorState, String> {
+        let mut allocator = AtlasAllocator::new(Size::new(def.width, def.height))
+            .with_padding(def.rectangle_padding, def.border_padding)
+            .with_owned_padding(def.owned_padding);
+        let padding = allocator.rectangle_padding;
+        let bounds = allocator.inner_bounds();
+
+        // The allocations keep their identifiers, so they are inserted directly and the free space is rebuilt once.
+        let mut regions = Vec::with_capacity(def.allocations.len());
+        for a in def.allocations {
+            let rectangle = Rectangle::new(a.x, a.y, a.width, a.height);
+            if rectangle.x < padding || rectangle.y < padding || !bounds.contains(&rectangle.expanded(padding)) {
+                return Err(format!("Allocation {} reaches into the padding or beyond the border of the container", a.id));
+            }
+            if allocator.allocations.insert(AllocationId(a.id), rectangle).is_some() {
+                return Err(format!("Allocation {} occurs more than once", a.id));
+            }
+            regions.push((rectangle.expanded(padding), a.id));
+        }
+
+        // Sweep over the padded regions from left to right, only comparing those overlapping horizontally.
+        regions.sort_unstable_by_key(|(region, _)| region.x);
+        for (i, (region, id)) in regions.iter().enumerate() {
+            let overlapping = regions[i + 1..]
+                .iter()
+                .take_while(|(other, _)| other.x < region.x + region.width)
+                .find(|(other, _)| region.intersects(other));
+            if let Some((_, other)) = overlapping {
+                return Err(format!("Allocation {} overlaps allocation {} or its padding", id, other));
+            }
+        }
+
+        // States saved without the next identifier still never hand out the ones of their allocations again.
+        allocator.next_id = def.next_id.max(allocator.allocations.keys().last().map_or(0, |id| id.0 + 1));
+        allocator.rebuild();
+        Ok(allocator.snapshot())
+    }
+}
+
+/// The changes made since `AtlasAllocator::begin`, to be undone by `AtlasAllocator::rollback`.
+#[derive(Debug, Clone)]
+struct Transaction {
+    /// The free space when the transaction began.
+    free: FreeRectangles,
+
+    /// The changed allocations in the order of the changes, with their rectangle before the change,
+    /// or `None` if they were added.
+    journal: Vec<(AllocationId, Option<Rectangle>)>,
+}
+
+impl AtlasAllocator {
+    /// Creates an empty allocator for a container of the given size, without any padding.
+    pub fn new(size: Size) -> AtlasAllocator {
+        AtlasAllocator {
+            size,
+            rectangle_padding: 0,
+            owned_padding: 0,
+            border_padding: 0,
+            free: FreeRectangles::new(Rectangle::from_size(0, 0, &size)),
+            merging: false,
+            max_free_rectangles: None,
+            allocations: BTreeMap::new(),
+            next_id: 0,
+            transaction: None,
+        }
+    }
+
+    /// Sets the padding kept free around each rectangle and along the edges of the container,
+    /// with the same meaning as in `RectanglePackerConfig`.
+    ///
+    /// Any existing allocations are removed.
+    pub fn with_padding(mut self, rectangle_padding: usize, border_padding: usize) -> AtlasAllocator {
+        self.rectangle_padding = rectangle_padding;
+        self.border_padding = border_padding;
+        self.clear();
+        self
+    }
+
+    /// Sets the padding owned by each rectangle, which is added to the allocated size on every side and is part of
+    /// the allocated rectangle, with the same meaning as `owned_padding` in `RectanglePackerConfig`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasAllocator, Rectangle, Size};
+    ///
+    /// let mut allocator = AtlasAllocator::new(Size::new(64, 64)).with_owned_padding(1);
+    /// assert_eq!(allocator.allocate(Size::new(16, 8)).unwrap().rectangle, Rectangle::new(0, 0, 18, 10));
+    /// ```
+    pub fn with_owned_padding(mut self, owned_padding: usize) -> AtlasAllocator {
+        self.owned_padding = owned_padding;
+        self
+    }
+
+    /// Sets whether freed rectangles are merged into the adjacent free space instead of rebuilding it.
+    ///
+    /// By default, `deallocate` rebuilds the list of maximal free rectangles from the remaining allocations,
+    /// which takes time proportional to the number of allocations and can let the list grow quadratically.
+    /// With merging, a freed rectangle is only merged with the free rectangles sharing a whole edge with it,
+    /// which is fast and keeps the list short, but may leave free space that later allocations can not use
+    /// until `clear` is called.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasAllocator, Size};
+    ///
+    /// let mut allocator = AtlasAllocator::new(Size::new(64, 64)).with_merging(true);
+    /// let a = allocator.allocate(Size::new(64, 32)).unwrap();
+    /// allocator.allocate(Size::new(64, 32)).unwrap();
+    ///
+    /// allocator.deallocate(a.id);
+    /// assert!(allocator.allocate(Size::new(64, 32)).is_some());
+    /// ```
+    pub fn with_merging(mut self, merging: bool) -> AtlasAllocator {
+        self.merging = merging;
+        self
+    }
+
+    /// Limits the memory of the list of free rectangles to about the given number of bytes,
+    /// e.g. to bound the memory an untrusted sequence of allocations can use in a service.
+    ///
+    /// Once the list grows beyond the budget, only the largest free rectangles are kept. The space of the dropped
+    /// rectangles can not be allocated until the free space is rebuilt, so allocations may fail earlier than without a budget.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasAllocator, Rectangle, Size};
+    ///
+    /// let budget = 2 * std::mem::size_of::<Rectangle>();
+    /// let mut allocator = AtlasAllocator::new(Size::new(64, 64)).with_memory_budget(budget);
+    /// allocator.allocate(Size::new(16, 16)).unwrap();
+    ///
+    /// // The free rectangles to the right of and below the first rectangle fit into the budget.
+    /// assert!(allocator.allocate(Size::new(48, 64)).is_some());
+    /// ```
+    pub fn with_memory_budget(mut self, bytes: usize) -> AtlasAllocator {
+        self.max_free_rectangles = Some(bytes / std::mem::size_of::<Rectangle>());
+        self.trim();
+        self
+    }
+
+    /// Returns the size of the container.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Returns the number of allocated rectangles.
+    pub fn len(&self) -> usize {
+        self.allocations.len()
+    }
+
+    /// Returns `true` if no rectangles are allocated.
+    pub fn is_empty(&self) -> bool {
+        self.allocations.is_empty()
+    }
+
+    /// Allocates a rectangle of the given size.
+    ///
+    /// # Returns
+    /// The `Allocation` with the placed rectangle, or `None` if there is no free space large enough for it.
+    pub fn allocate(&mut self, size: Size) -> Option<Allocation> {
+        let size = self.padded_size(size);
+        let padded_width = size.width + 2 * self.rectangle_padding;
+        let padded_height = size.height + 2 * self.rectangle_padding;
+
+        let target = self.free.best_fit(padded_width, padded_height)?;
+        Some(self.place(target, size))
+    }
+
+    /// Allocates a rectangle of the given size at the candidate with the lowest score by the given function,
+    /// instead of the free rectangle it fits best along its shorter side.
+    ///
+    /// The candidates are the rectangles in the four corners of all free rectangles large enough for the size and its padding.
+    /// This makes domain-specific preferences possible, e.g. keeping a strip of the atlas empty or
+    /// placing rectangles close to a point. Ties are broken by the position of the candidates, from top to bottom
+    /// and left to right, and `NaN` scores lose against all other scores.
+    ///
+    /// # Arguments
+    /// * `size` - The size of the rectangle to allocate.
+    /// * `score` - Scores the rectangle the size would be placed at, given the current state of the allocator.
+    ///   Lower scores are better.
+    ///
+    /// # Returns
+    /// The `Allocation` with the placed rectangle, or `None` if there is no free space large enough for it.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasAllocator, Rectangle, Size};
+    ///
+    /// let mut allocator = AtlasAllocator::new(Size::new(64, 64));
+    ///
+    /// // Prefer the bottom right corner.
+    /// let a = allocator.allocate_scored(Size::new(16, 16), |r, _| -((r.x + r.y) as f64)).unwrap();
+    /// assert_eq!(a.rectangle, Rectangle::new(48, 48, 16, 16));
+    ///
+    /// // Prefer the candidate leaving the largest free rectangle.
+    /// let b = allocator.allocate_scored(Size::new(16, 16), |r, state| {
+    ///     let largest = state.free_rectangles().filter(|free| !free.intersects(r)).map(|free| free.width * free.height).max();
+    ///     -(largest.unwrap_or(0) as f64)
+    /// });
+    /// assert!(b.is_some());
+    /// ```
+    pub fn allocate_scored<F>(&mut self, size: Size, score: F) -> Option<Allocation>
+    where
+        F: Fn(&Rectangle, &PackerState) -> f64,
+    {
+        let size = self.padded_size(size);
+        let padded_width = size.width + 2 * self.rectangle_padding;
+        let padded_height = size.height + 2 * self.rectangle_padding;
+
+        let state = PackerState { allocator: self };
+        let (x, y) = self.free
+            .fitting(padded_width, padded_height)
+            .flat_map(|free| {
+                let right = free.x + free.width - padded_width;
+                let bottom = free.y + free.height - padded_height;
+                [(free.x, free.y), (right, free.y), (free.x, bottom), (right, bottom)]
+            })
+            .map(|(x, y)| {
+                let rectangle = Rectangle::from_size(x + self.rectangle_padding, y + self.rectangle_padding, &size);
+                let score = score(&rectangle, &state);
+                (match score.is_nan() { true => f64::INFINITY, false => score }, (x, y))
+            })
+            .min_by(|(a, (x, y)), (b, (u, v))| a.total_cmp(b).then((y, x).cmp(&(v, u))))?
+            .1;
+        Some(self.place(Rectangle::new(x, y, padded_width, padded_height), size))
+    }
+
+    /// Places a rectangle of the given size at the top left corner of `target`, which lies within the free space.
+    fn place(&mut self, target: Rectangle, size: Size) -> Allocation {
+        let padded = Rectangle::new(
+            target.x,
+            target.y,
+            size.width + 2 * self.rectangle_padding,
+            size.height + 2 * self.rectangle_padding,
+        );
+        self.free.occupy(&padded);
+        self.trim();
+
+        let id = AllocationId(self.next_id);
+        self.next_id += 1;
+        let rectangle = Rectangle::from_size(
+            target.x + self.rectangle_padding,
+            target.y + self.rectangle_padding,
+            &size,
+        );
+        self.allocations.insert(id, rectangle);
+        self.record(id, None);
+
+        Allocation { id, rectangle }
+    }
+
+    /// Reserves the given region, e.g. an area of the texture that is managed elsewhere.
+    ///
+    /// The region is treated like an allocated rectangle, so padding is kept free around it
+    /// and it can be freed again with `deallocate`.
+    ///
+    /// # Returns
+    /// The `Allocation` of the region, or `None` if the region or its padding overlaps existing allocations,
+    /// their padding or the border padding of the container.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasAllocator, Rectangle, Size};
+    ///
+    /// let mut allocator = AtlasAllocator::new(Size::new(64, 64));
+    /// allocator.reserve(Rectangle::new(0, 0, 32, 64)).unwrap();
+    ///
+    /// assert!(allocator.reserve(Rectangle::new(16, 16, 32, 32)).is_none());
+    /// assert_eq!(allocator.allocate(Size::new(32, 64)).unwrap().rectangle, Rectangle::new(32, 0, 32, 64));
+    /// ```
+    pub fn reserve(&mut self, region: Rectangle) -> Option<Allocation> {
+        let padding = self.rectangle_padding;
+        if region.x < padding || region.y < padding {
+            return None;
+        }
+
+        let padded = region.expanded(padding);
+        if !self.free.iter().any(|free| free.contains(&padded)) {
+            return None;
+        }
+        self.free.occupy(&padded);
+        self.trim();
+
+        let id = AllocationId(self.next_id);
+        self.next_id += 1;
+        self.allocations.insert(id, region);
+        self.record(id, None);
+
+        Some(Allocation { id, rectangle: region })
+    }
+
+    /// Frees the rectangle of the given allocation.
+    ///
+    /// The free space is rebuilt from the remaining allocations, so this takes time proportional
+    /// to the number of allocations, unless merging is enabled with `with_merging`.
+    ///
+    /// # Returns
+    /// The freed rectangle, or `None` if the allocation does not exist (anymore).
+    pub fn deallocate(&mut self, id: AllocationId) -> Option<Rectangle> {
+        let rectangle = self.allocations.remove(&id)?;
+        self.record(id, Some(rectangle));
+        if self.merging {
+            self.free.release(&rectangle.expanded(self.rectangle_padding));
+            self.trim();
+        } else {
+            self.rebuild();
+        }
+        Some(rectangle)
+    }
+
+    /// Computes the allocations with the smallest total area to evict to make room for a rectangle of the given size,
+    /// instead of failing like `allocate` if it does not fit.
+    ///
+    /// See `plan_eviction_by` for the details.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasAllocator, Size};
+    ///
+    /// let mut allocator = AtlasAllocator::new(Size::new(64, 32));
+    /// allocator.allocate(Size::new(32, 32)).unwrap();
+    /// let small = allocator.allocate(Size::new(16, 32)).unwrap();
+    /// allocator.allocate(Size::new(16, 32)).unwrap();
+    /// assert!(allocator.allocate(Size::new(16, 32)).is_none());
+    ///
+    /// let plan = allocator.plan_eviction(Size::new(16, 32)).unwrap();
+    /// assert_eq!(plan.evicted, vec![small.id]);
+    ///
+    /// for id in &plan.evicted {
+    ///     allocator.deallocate(*id);
+    /// }
+    /// assert!(allocator.reserve(plan.rectangle).is_some());
+    /// ```
+    pub fn plan_eviction(&self, size: Size) -> Option<EvictionPlan> {
+        self.plan_eviction_by(size, |allocation| (allocation.rectangle.width * allocation.rectangle.height) as f64)
+    }
+
+    /// Computes the allocations with the lowest total cost by the given function to evict to make room for a rectangle
+    /// of the given size. Nothing is changed, so the caller decides whether to evict the allocations.
+    ///
+    /// All positions of the rectangle and its padding within the border padding are considered, and the allocations
+    /// overlapping it with their padding at the position with the lowest total cost are returned.
+    /// Ties are broken by the position, from top to bottom and left to right, and `NaN` costs count as infinite.
+    /// To use the plan, deallocate the evicted allocations and reserve its rectangle. With merging enabled,
+    /// the freed space may not be merged into a single free rectangle, so `allocate` may still fail, but `reserve` works.
+    /// This takes time proportional to the cube of the number of allocations.
+    ///
+    /// # Arguments
+    /// * `size` - The size of the rectangle to make room for.
+    /// * `cost` - The cost of evicting an allocation, e.g. the time to re-upload it.
+    ///
+    /// # Returns
+    /// The `EvictionPlan`, which evicts no allocations if the size fits already,
+    /// or `None` if the size does not fit into the container even if it is empty.
+    pub fn plan_eviction_by<F>(&self, size: Size, cost: F) -> Option<EvictionPlan>
+    where
+        F: Fn(&Allocation) -> f64,
+    {
+        let size = self.padded_size(size);
+        let padding = self.rectangle_padding;
+        let padded_width = size.width + 2 * padding;
+        let padded_height = size.height + 2 * padding;
+        let inner = self.inner_bounds();
+        if padded_width > inner.width || padded_height > inner.height {
+            return None;
+        }
+
+        if let Some(target) = self.free.best_fit(padded_width, padded_height) {
+            let rectangle = Rectangle::from_size(target.x + padding, target.y + padding, &size);
+            return Some(EvictionPlan { evicted: Vec::new(), rectangle, cost: 0.0 });
+        }
+
+        let occupied: Vec<(AllocationId, Rectangle, f64)> = self
+            .allocations()
+            .map(|allocation| {
+                let cost = cost(&allocation);
+                let cost = match cost.is_nan() { true => f64::INFINITY, false => cost };
+                (allocation.id, allocation.rectangle.expanded(padding), cost)
+            })
+            .collect();
+
+        // The set of overlapped allocations only changes when an edge of the rectangle crosses an edge of an allocation,
+        // so it suffices to try the positions along the border and next to the right and bottom edges of allocations.
+        let positions = |start: usize, length: usize, end: usize, edges: &mut dyn Iterator<Item = usize>| {
+            let mut positions: Vec<usize> = std::iter::once(start).chain(edges).filter(|&p| p >= start && p + length <= end).collect();
+            positions.sort_unstable();
+            positions.dedup();
+            positions
+        };
+        let xs = positions(inner.x, padded_width, inner.x + inner.width, &mut occupied.iter().map(|(_, r, _)| r.x + r.width));
+        let ys = positions(inner.y, padded_height, inner.y + inner.height, &mut occupied.iter().map(|(_, r, _)| r.y + r.height));
+
+        let mut best: Option<EvictionPlan> = None;
+        for &y in &ys {
+            for &x in &xs {
+                let region = Rectangle::new(x, y, padded_width, padded_height);
+                let overlapped = occupied.iter().filter(|(_, r, _)| r.intersects(&region));
+                let total: f64 = overlapped.clone().map(|(_, _, cost)| cost).sum();
+                if best.as_ref().is_none_or(|best| total < best.cost) {
+                    best = Some(EvictionPlan {
+                        evicted: overlapped.map(|(id, _, _)| *id).collect(),
+                        rectangle: Rectangle::from_size(x + padding, y + padding, &size),
+                        cost: total,
+                    });
+                }
+            }
+        }
+        best
+    }
+
+    /// Returns the rectangle of the given allocation, or `None` if it does not exist (anymore).
+    pub fn get(&self, id: AllocationId) -> Option<Rectangle> {
+        self.allocations.get(&id).copied()
+    }
+
+    /// Returns all allocations, ordered by their identifiers.
+    pub fn allocations(&self) -> impl Iterator<Item = Allocation> + '_ {
+        self.allocations.iter().map(|(&id, &rectangle)| Allocation { id, rectangle })
+    }
+
+    /// Removes all allocations.
+    pub fn clear(&mut self) {
+        for (id, rectangle) in std::mem::take(&mut self.allocations) {
+            self.record(id, Some(rectangle));
+        }
+        self.rebuild();
+    }
+
+    /// Begins a transaction, so that the following changes can be undone together with `rollback`,
+    /// e.g. to try whether a group of rectangles fits.
+    ///
+    /// # Panics
+    /// Panics if a transaction is already in progress.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasAllocator, Size};
+    ///
+    /// let mut allocator = AtlasAllocator::new(Size::new(64, 64));
+    /// allocator.allocate(Size::new(32, 32)).unwrap();
+    ///
+    /// // Try to fit a whole panel of rectangles, and undo the attempt if one of them does not fit.
+    /// allocator.begin();
+    /// let fits = (0..4).all(|_| allocator.allocate(Size::new(30, 30)).is_some());
+    /// match fits {
+    ///     true => allocator.commit(),
+    ///     false => allocator.rollback(),
+    /// }
+    ///
+    /// assert!(!fits);
+    /// assert_eq!(allocator.len(), 1);
+    /// ```
+    pub fn begin(&mut self) {
+        assert!(self.transaction.is_none(), "A transaction is already in progress");
+        self.transaction = Some(Transaction { free: self.free.clone(), journal: Vec::new() });
+    }
+
+    /// Ends the transaction in progress, keeping its changes.
+    ///
+    /// # Panics
+    /// Panics if no transaction is in progress.
+    pub fn commit(&mut self) {
+        assert!(self.transaction.take().is_some(), "No transaction is in progress");
+    }
+
+    /// Ends the transaction in progress, undoing its changes.
+    ///
+    /// The allocations and the free space are restored to their state when the transaction began.
+    /// The identifiers of the undone allocations are not reused.
+    ///
+    /// # Panics
+    /// Panics if no transaction is in progress.
+    pub fn rollback(&mut self) {
+        let transaction = self.transaction.take().expect("No transaction is in progress");
+        for (id, previous) in transaction.journal.into_iter().rev() {
+            match previous {
+                Some(rectangle) => self.allocations.insert(id, rectangle),
+                None => self.allocations.remove(&id),
+            };
+        }
+        self.free = transaction.free;
+    }
+
+    /// Returns `true` if a transaction is in progress.
+    pub fn in_transaction(&self) -> bool {
+        self.transaction.is_some()
+    }
+
+    /// Saves the current allocations and free space, to be restored later with `restore`.
+    ///
+    /// Unlike a transaction, any number of snapshots can be kept and restored in any order,
+    /// e.g. as the undo history of an editor. Taking a snapshot copies the allocations and the free space.
+    pub fn snapshot(&self) -> AllocatorState {
+        AllocatorState {
+            size: self.size,
+            rectangle_padding: self.rectangle_padding,
+            owned_padding: self.owned_padding,
+            border_padding: self.border_padding,
+            free: self.free.clone(),
+            allocations: self.allocations.clone(),
+            next_id: self.next_id,
+        }
+    }
+
+    /// Restores the allocations and free space saved in a snapshot.
+    ///
+    /// The identifiers handed out after the snapshot was taken are not reused, and neither are the ones of the snapshot.
+    ///
+    /// # Panics
+    /// Panics if the snapshot was taken from an allocator with a different size or padding,
+    /// or if a transaction is in progress.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasAllocator, Size};
+    ///
+    /// let mut allocator = AtlasAllocator::new(Size::new(64, 64));
+    /// let a = allocator.allocate(Size::new(16, 16)).unwrap();
+    /// let snapshot = allocator.snapshot();
+    ///
+    /// let b = allocator.allocate(Size::new(16, 16)).unwrap();
+    /// allocator.deallocate(a.id);
+    /// allocator.restore(snapshot);
+    ///
+    /// assert_eq!(allocator.get(a.id), Some(a.rectangle));
+    /// assert!(allocator.get(b.id).is_none());
+    /// ```
+    pub fn restore(&mut self, state: AllocatorState) {
+        assert!(self.transaction.is_none(), "Can not restore a snapshot during a transaction");
+        assert!(
+            state.size == self.size
+                && state.rectangle_padding == self.rectangle_padding
+                && state.owned_padding == self.owned_padding
+                && state.border_padding == self.border_padding,
+            "The snapshot was taken from an allocator with a different size or padding"
+        );
+        self.next_id = self.next_id.max(state.next_id);
+        self.free = state.free;
+        self.allocations = state.allocations;
+    }
+
+    /// Records a change of an allocation in the transaction in progress, if any.
+    fn record(&mut self, id: AllocationId, previous: Option<Rectangle>) {
+        if let Some(transaction) = &mut self.transaction {
+            transaction.journal.push((id, previous));
+        }
+    }
+
+    /// Returns the size of the rectangle allocated for a size, including the owned padding unless the size is empty.
+    fn padded_size(&self, size: Size) -> Size {
+        match size.width == 0 || size.height == 0 {
+            true => size,
+            false => Size::new(size.width + 2 * self.owned_padding, size.height + 2 * self.owned_padding),
+        }
+    }
+
+    /// Returns the area within the border padding of the container.
+    fn inner_bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.border_padding,
+            self.border_padding,
+            self.size.width.saturating_sub(2 * self.border_padding),
+            self.size.height.saturating_sub(2 * self.border_padding),
+        )
+    }
+
+    /// Rebuilds the free space from the current allocations.
+    fn rebuild(&mut self) {
+        let padding = self.rectangle_padding;
+        self.free.reset(self.inner_bounds());
+        for rectangle in self.allocations.values() {
+            self.free.occupy(&rectangle.expanded(padding));
+            if let Some(max_len) = self.max_free_rectangles {
+                self.free.retain_largest(max_len);
+            }
+        }
+    }
+
+    /// Drops the smallest free rectangles if the list of free rectangles exceeds the memory budget.
+    fn trim(&mut self) {
+        if let Some(max_len) = self.max_free_rectangles {
+            self.free.retain_largest(max_len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RectanglePackerConfig, RectanglePackingResult};
+
+    use super::*;
+
+    /// Validates the current allocations as a packing result.
+    fn validate(allocator: &AtlasAllocator, config: &RectanglePackerConfig) {
+        let result = RectanglePackingResult {
+            rectangles: allocator.allocations().map(|a| a.rectangle).collect(),
+            size: allocator.size(),
+            skipped: Vec::new(),
+        };
+        assert_eq!(result.validate(config), Ok(()));
+    }
+
+    #[test]
+    fn allocate_works() {
+        let mut allocator = AtlasAllocator::new(Size::new(10, 10));
+
+        let a = allocator.allocate(Size::new(10, 5)).unwrap();
+        let b = allocator.allocate(Size::new(5, 5)).unwrap();
+        let c = allocator.allocate(Size::new(5, 5)).unwrap();
+
+        assert_eq!(a.rectangle, Rectangle::new(0, 0, 10, 5));
+        assert_eq!(b.rectangle, Rectangle::new(0, 5, 5, 5));
+        assert_eq!(c.rectangle, Rectangle::new(5, 5, 5, 5));
+        assert!(allocator.allocate(Size::new(1, 1)).is_none());
+        assert_eq!(allocator.len(), 3);
+    }
+
+    #[test]
+    fn allocate_with_padding_works() {
+        let config = RectanglePackerConfig {
+            rectangle_padding: 1,
+            border_padding: 2,
+            ..Default::default()
+        };
+        let mut allocator = AtlasAllocator::new(Size::new(40, 40)).with_padding(1, 2);
+
+        while allocator.allocate(Size::new(5, 3)).is_some() {}
+
+        // The padded rectangles are 7 x 5 and the area within the border is 36 x 36.
+        assert_eq!(allocator.len(), 5 * 7);
+        validate(&allocator, &config);
+    }
+
+    #[test]
+    fn deallocate_frees_space() {
+        let mut allocator = AtlasAllocator::new(Size::new(10, 10));
+        let a = allocator.allocate(Size::new(10, 10)).unwrap();
+        assert!(allocator.allocate(Size::new(5, 5)).is_none());
+
+        assert_eq!(allocator.deallocate(a.id), Some(Rectangle::new(0, 0, 10, 10)));
+        assert_eq!(allocator.deallocate(a.id), None);
+        assert!(allocator.is_empty());
+
+        let b = allocator.allocate(Size::new(5, 5)).unwrap();
+        assert_ne!(a.id, b.id);
+        assert_eq!(b.rectangle, Rectangle::new(0, 0, 5, 5));
+    }
+
+    #[test]
+    fn reserve_works() {
+        let mut allocator = AtlasAllocator::new(Size::new(20, 20)).with_padding(1, 1);
+
+        assert!(allocator.reserve(Rectangle::new(0, 0, 4, 4)).is_none());
+        let reserved = allocator.reserve(Rectangle::new(2, 2, 4, 4)).unwrap();
+        assert_eq!(allocator.get(reserved.id), Some(Rectangle::new(2, 2, 4, 4)));
+
+        // The padding of the reserved region overlaps the padding of this one.
+        assert!(allocator.reserve(Rectangle::new(7, 2, 4, 4)).is_none());
+        assert!(allocator.reserve(Rectangle::new(8, 2, 4, 4)).is_some());
+        assert!(allocator.reserve(Rectangle::new(16, 2, 4, 4)).is_none());
+
+        while allocator.allocate(Size::new(3, 3)).is_some() {}
+        validate(&allocator, &RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() });
+
+        allocator.deallocate(reserved.id);
+        assert!(allocator.reserve(Rectangle::new(2, 2, 4, 4)).is_some());
+    }
+
+    #[test]
+    fn rollback_restores_allocations() {
+        let mut allocator = AtlasAllocator::new(Size::new(32, 32)).with_padding(1, 0);
+        let a = allocator.allocate(Size::new(10, 10)).unwrap();
+        let b = allocator.allocate(Size::new(10, 10)).unwrap();
+        let before: Vec<Allocation> = allocator.allocations().collect();
+
+        allocator.begin();
+        assert!(allocator.in_transaction());
+        allocator.deallocate(a.id);
+        allocator.reserve(Rectangle::new(a.rectangle.x, a.rectangle.y, 4, 4)).unwrap();
+        let c = allocator.allocate(Size::new(10, 10)).unwrap();
+        allocator.clear();
+        allocator.rollback();
+
+        assert!(!allocator.in_transaction());
+        assert_eq!(allocator.allocations().collect::<Vec<_>>(), before);
+        assert!(allocator.get(c.id).is_none());
+        assert_eq!(allocator.get(b.id), Some(b.rectangle));
+        while allocator.allocate(Size::new(4, 4)).is_some() {}
+        validate(&allocator, &RectanglePackerConfig { rectangle_padding: 1, ..Default::default() });
+    }
+
+    #[test]
+    fn commit_keeps_changes() {
+        let mut allocator = AtlasAllocator::new(Size::new(32, 32));
+
+        allocator.begin();
+        let a = allocator.allocate(Size::new(10, 10)).unwrap();
+        allocator.commit();
+
+        assert_eq!(allocator.get(a.id), Some(a.rectangle));
+        allocator.begin();
+        allocator.rollback();
+        assert_eq!(allocator.len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn nested_transactions_panic() {
+        let mut allocator = AtlasAllocator::new(Size::new(32, 32));
+        allocator.begin();
+        allocator.begin();
+    }
+
+    #[test]
+    fn snapshots_can_be_restored_in_any_order() {
+        let mut allocator = AtlasAllocator::new(Size::new(32, 32)).with_padding(1, 1);
+        let empty = allocator.snapshot();
+        let a = allocator.allocate(Size::new(8, 8)).unwrap();
+        let one = allocator.snapshot();
+        let b = allocator.allocate(Size::new(8, 8)).unwrap();
+        let two = allocator.snapshot();
+
+        allocator.restore(one.clone());
+        assert_eq!(allocator.allocations().collect::<Vec<_>>(), vec![a]);
+        allocator.restore(two);
+        assert_eq!(allocator.allocations().collect::<Vec<_>>(), vec![a, b]);
+        allocator.restore(empty);
+        assert!(allocator.is_empty());
+
+        // New identifiers are not reused after restoring an older snapshot.
+        let c = allocator.allocate(Size::new(8, 8)).unwrap();
+        assert!(c.id > b.id);
+        allocator.restore(one);
+        while allocator.allocate(Size::new(3, 3)).is_some() {}
+        validate(&allocator, &RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() });
+    }
+
+    #[test]
+    #[should_panic]
+    fn restoring_snapshots_of_other_allocators_panics() {
+        let snapshot = AtlasAllocator::new(Size::new(32, 32)).snapshot();
+        AtlasAllocator::new(Size::new(16, 16)).restore(snapshot);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn state_can_be_serialized() {
+        let mut allocator = AtlasAllocator::new(Size::new(32, 32)).with_padding(1, 1);
+        let a = allocator.allocate(Size::new(8, 8)).unwrap();
+        let b = allocator.allocate(Size::new(4, 12)).unwrap();
+        allocator.deallocate(a.id);
+        let json = serde_json::to_string(&allocator.snapshot()).unwrap();
+
+        let mut restored = AtlasAllocator::new(Size::new(32, 32)).with_padding(1, 1);
+        restored.restore(serde_json::from_str(&json).unwrap());
+
+        assert_eq!(restored.allocations().collect::<Vec<_>>(), vec![b]);
+        assert!(restored.allocate(Size::new(1, 1)).unwrap().id > b.id);
+        while restored.allocate(Size::new(3, 3)).is_some() {}
+        validate(&restored, &RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() });
+
+        let overlapping = json.replace(&format!("\"x\":{}", b.rectangle.x), "\"x\":0");
+        assert!(serde_json::from_str::<AllocatorState>(&overlapping).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_allocations_keep_their_identifiers() {
+        let config = RectanglePackerConfig { rectangle_padding: 1, owned_padding: 1, border_padding: 1, ..Default::default() };
+        let new = || AtlasAllocator::new(Size::new(64, 64)).with_padding(1, 1).with_owned_padding(1);
+        let mut allocator = new();
+        let ids: Vec<_> = (0..8).map(|i| allocator.allocate(Size::new(4 + i, 6)).unwrap().id).collect();
+        for id in [ids[0], ids[3], ids[4], ids[6]] {
+            allocator.deallocate(id);
+        }
+        let allocations: Vec<_> = allocator.allocations().collect();
+        let json = serde_json::to_string(&allocator.snapshot()).unwrap();
+
+        let mut restored = new();
+        restored.restore(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.allocations().collect::<Vec<_>>(), allocations);
+        assert!(restored.allocate(Size::new(1, 1)).unwrap().id > ids[7]);
+        while restored.allocate(Size::new(3, 3)).is_some() {}
+        validate(&restored, &config);
+
+        // Identifiers occurring twice are rejected.
+        let duplicate = json.replace(&format!("\"id\":{}", ids[5].0), &format!("\"id\":{}", ids[1].0));
+        assert!(serde_json::from_str::<AllocatorState>(&duplicate).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deallocated_identifiers_are_not_reused_after_reloading() {
+        let mut allocator = AtlasAllocator::new(Size::new(64, 64));
+        let ids: Vec<_> = (0..4).map(|_| allocator.allocate(Size::new(8, 8)).unwrap().id).collect();
+        allocator.deallocate(ids[3]);
+        let json = serde_json::to_string(&allocator.snapshot()).unwrap();
+
+        let mut reloaded = AtlasAllocator::new(Size::new(64, 64));
+        reloaded.restore(serde_json::from_str(&json).unwrap());
+        let fresh = reloaded.allocate(Size::new(8, 8)).unwrap().id;
+        assert!(!ids.contains(&fresh));
+        assert!(fresh > ids[3]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    #[should_panic]
+    fn restoring_states_with_other_owned_padding_panics() {
+        let json = serde_json::to_string(&AtlasAllocator::new(Size::new(32, 32)).with_owned_padding(1).snapshot()).unwrap();
+        AtlasAllocator::new(Size::new(32, 32)).restore(serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn mixed_allocations_are_valid() {
+        let mut allocator = AtlasAllocator::new(Size::new(64, 64)).with_padding(1, 1);
+        let mut ids = Vec::new();
+        for i in 0..60 {
+            if let Some(allocation) = allocator.allocate(Size::new(i % 7 + 2, i % 5 + 3)) {
+                ids.push(allocation.id);
+            }
+            if i % 3 == 0 {
+                allocator.deallocate(ids[i / 2]);
+            }
+        }
+
+        validate(&allocator, &RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() });
+    }
+
+    #[test]
+    fn merging_keeps_allocations_valid() {
+        let mut allocator = AtlasAllocator::new(Size::new(64, 64)).with_padding(1, 1).with_merging(true);
+        let mut ids = Vec::new();
+        for i in 0..200 {
+            if let Some(allocation) = allocator.allocate(Size::new(i % 7 + 2, i % 5 + 3)) {
+                ids.push(allocation.id);
+            }
+            if i % 3 == 0 {
+                allocator.deallocate(ids[i / 4 % ids.len()]);
+            }
+        }
+
+        validate(&allocator, &RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() });
+        assert!(allocator.free.iter().all(|free| allocator.allocations().all(|a| !free.intersects(&a.rectangle))));
+    }
+
+    #[test]
+    fn allocate_scored_works() {
+        let mut allocator = AtlasAllocator::new(Size::new(32, 32)).with_padding(1, 1);
+        let config = RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() };
+
+        // Keep the top strip empty.
+        let keep_top_empty = |r: &Rectangle, state: &PackerState| match r.y < 8 {
+            true => f64::INFINITY,
+            false => (state.size().height - r.y - r.height) as f64,
+        };
+        let a = allocator.allocate_scored(Size::new(8, 8), keep_top_empty).unwrap();
+        let b = allocator.allocate_scored(Size::new(8, 8), keep_top_empty).unwrap();
+
+        assert_eq!(a.rectangle, Rectangle::new(2, 22, 8, 8));
+        assert_eq!(b.rectangle, Rectangle::new(12, 22, 8, 8));
+        validate(&allocator, &config);
+    }
+
+    #[test]
+    fn allocate_scored_ignores_nan_scores() {
+        let mut allocator = AtlasAllocator::new(Size::new(16, 16));
+        allocator.allocate(Size::new(8, 16)).unwrap();
+
+        let a = allocator.allocate_scored(Size::new(4, 4), |r, _| match r.y {
+            0 => f64::NAN,
+            _ => 1.0,
+        });
+
+        assert_eq!(a.unwrap().rectangle, Rectangle::new(8, 12, 4, 4));
+        assert!(allocator.allocate_scored(Size::new(16, 1), |_, _| 0.0).is_none());
+    }
+
+    #[test]
+    fn plan_eviction_finds_the_cheapest_allocations() {
+        let mut allocator = AtlasAllocator::new(Size::new(34, 34)).with_padding(1, 0);
+        let ids: Vec<AllocationId> = (0..4).map(|_| allocator.allocate(Size::new(15, 15)).unwrap().id).collect();
+        assert!(allocator.allocate(Size::new(20, 10)).is_none());
+
+        // Making room for a wide rectangle evicts two allocations, the ones with the lowest cost.
+        let plan = allocator.plan_eviction_by(Size::new(20, 10), |a| if a.id == ids[0] { 100.0 } else { 1.0 }).unwrap();
+        assert_eq!(plan.evicted.len(), 2);
+        assert!(!plan.evicted.contains(&ids[0]));
+        assert_eq!(plan.cost, 2.0);
+
+        for id in &plan.evicted {
+            allocator.deallocate(*id);
+        }
+        assert_eq!(allocator.reserve(plan.rectangle).unwrap().rectangle.to_size(), Size::new(20, 10));
+        validate(&allocator, &RectanglePackerConfig { rectangle_padding: 1, ..Default::default() });
+    }
+
+    #[test]
+    fn plan_eviction_handles_fitting_and_oversized_sizes() {
+        let mut allocator = AtlasAllocator::new(Size::new(32, 32)).with_padding(0, 2);
+        allocator.allocate(Size::new(8, 8)).unwrap();
+
+        assert!(allocator.plan_eviction(Size::new(8, 8)).unwrap().evicted.is_empty());
+        assert_eq!(allocator.plan_eviction(Size::new(28, 28)).unwrap().evicted.len(), 1);
+        assert!(allocator.plan_eviction(Size::new(30, 8)).is_none());
+    }
+
+    #[test]
+    fn memory_budget_limits_the_free_rectangles() {
+        let config = RectanglePackerConfig { rectangle_padding: 1, ..Default::default() };
+        let mut allocator = AtlasAllocator::new(Size::new(256, 256))
+            .with_padding(1, 0)
+            .with_memory_budget(8 * std::mem::size_of::<Rectangle>());
+        let mut ids = Vec::new();
+        for i in 0..200 {
+            if let Some(allocation) = allocator.allocate(Size::new(3 + i % 11, 2 + i % 7)) {
+                ids.push(allocation.id);
+            }
+            assert!(allocator.free.iter().count() <= 8);
+        }
+        for id in ids.iter().step_by(3) {
+            allocator.deallocate(*id);
+            assert!(allocator.free.iter().count() <= 8);
+        }
+
+        assert!(allocator.len() > 50);
+        validate(&allocator, &config);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::RectanglePackingResult;
+
+/// The characters of the rectangles, in the order of their indices.
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// The character of rectangles whose index is beyond the letters.
+const OTHER: char = '#';
+
+/// The character of empty space.
+const EMPTY: char = '.';
+
+impl RectanglePackingResult {
+    /// Renders the packing as a grid of characters, e.g. to print it in a terminal or a failing test.
+    ///
+    /// Every character covers `scale` by `scale` units of the container. Rectangles are drawn with the letters
+    /// `A` to `Z`, `a` to `z` and the digits `0` to `9` in the order of their indices, and with `#` beyond that.
+    /// A character shows the rectangle covering most of its area, or `.` if it is empty,
+    /// so rectangles smaller than the scale can be hidden by larger neighbours. Skipped rectangles are not drawn.
+    ///
+    /// # Arguments
+    /// * `scale` - The number of units along each axis shown by one character.
+    ///
+    /// # Returns
+    /// The rows of the grid, each ending with a line break.
+    ///
+    /// # Panics
+    /// Panics if `scale` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{Rectangle, RectanglePackingResult, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 4, 4), Rectangle::new(4, 0, 2, 2)],
+    ///     size: Size::new(8, 4),
+    ///     skipped: Vec::new(),
+    /// };
+    ///
+    /// assert_eq!(result.to_ascii(2), "AAB.\nAA..\n");
+    /// ```
+    pub fn to_ascii(&self, scale: usize) -> String {
+        assert!(scale > 0, "Expected a scale greater than zero");
+
+        let columns = self.size.width.div_ceil(scale);
+        let rows = self.size.height.div_ceil(scale);
+
+        // The index of the rectangle shown by every character and the area of the character it covers.
+        let mut cells: Vec<Option<(usize, usize)>> = vec![None; columns * rows];
+        for (index, r) in self.rectangles.iter().enumerate() {
+            if self.skipped.binary_search(&index).is_ok() || r.width == 0 || r.height == 0 {
+                continue;
+            }
+            for row in r.y / scale..(r.y + r.height).div_ceil(scale).min(rows) {
+                for column in r.x / scale..(r.x + r.width).div_ceil(scale).min(columns) {
+                    let width = (r.x + r.width).min((column + 1) * scale) - r.x.max(column * scale);
+                    let height = (r.y + r.height).min((row + 1) * scale) - r.y.max(row * scale);
+                    let cell = &mut cells[row * columns + column];
+                    if cell.is_none_or(|(_, covered)| width * height > covered) {
+                        *cell = Some((index, width * height));
+                    }
+                }
+            }
+        }
+
+        let mut ascii = String::with_capacity((columns + 1) * rows);
+        for row in cells.chunks(columns.max(1)).take(rows) {
+            ascii.extend(row.iter().map(|cell| match cell {
+                Some((index, _)) => LETTERS.get(*index).map_or(OTHER, |&letter| letter as char),
+                None => EMPTY,
+            }));
+            ascii.push('\n');
+        }
+        ascii
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeightRectPacker, Rectangle, RectanglePacker, RectanglePackerConfig, Size, ZeroSizePolicy};
+
+    use super::*;
+
+    #[test]
+    fn to_ascii_works() {
+        let sizes = vec![Size::new(3, 2), Size::new(2, 2), Size::new(0, 1), Size::new(1, 1)];
+        let config = RectanglePackerConfig {
+            max_size: Some(Size::new(7, 5)),
+            border_padding: 1,
+            zero_size_policy: ZeroSizePolicy::Skip,
+            ..Default::default()
+        };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        assert_eq!(result.to_ascii(1), ".......\n.AAABB.\n.AAABB.\n.D.....\n.......\n");
+    }
+
+    #[test]
+    fn to_ascii_shows_the_largest_rectangle_of_each_character() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 3, 2), Rectangle::new(4, 0, 2, 2), Rectangle::new(3, 0, 1, 1)],
+            size: Size::new(6, 3),
+            skipped: Vec::new(),
+        };
+
+        assert_eq!(result.to_ascii(2), "AAB\n...\n");
+        assert_eq!(result.to_ascii(4), "AB\n");
+    }
+
+    #[test]
+    fn to_ascii_runs_out_of_letters() {
+        let result = RectanglePackingResult {
+            rectangles: (0..64).map(|i| Rectangle::new(i, 0, 1, 1)).collect(),
+            size: Size::new(64, 1),
+            skipped: Vec::new(),
+        };
+        let ascii = result.to_ascii(1);
+
+        assert!(ascii.starts_with("ABC"));
+        assert!(ascii.ends_with("89##\n"));
+    }
+
+    #[test]
+    fn to_ascii_handles_empty_containers() {
+        let result = RectanglePackingResult { rectangles: Vec::new(), size: Size::new(0, 0), skipped: Vec::new() };
+
+        assert_eq!(result.to_ascii(1), "");
+    }
+}
@@ -0,0 +1,1014 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Range;
+use image::{imageops, imageops::FilterType, Rgba, RgbaImage};
+use crate::{pack_pages, page_name, AtlasLayout, AtlasPage, AtlasPages, NineSlice, PagingError, Rectangle, RectanglePacker, RectanglePackerConfig, RectanglePackingError, RectanglePackingResult, Size};
+
+/// A packed atlas: the combined image and the placement metadata of every sprite in it.
+#[derive(Debug, Clone)]
+pub struct Atlas {
+    /// The combined image.
+    pub image: RgbaImage,
+
+    /// The region of every sprite in the image.
+    pub layout: AtlasLayout,
+}
+
+/// A packed atlas spread over several pages, see `AtlasBuilder::build_pages`.
+#[derive(Debug, Clone)]
+pub struct PagedAtlas {
+    /// The image of every page, in the order of the pages.
+    pub images: Vec<RgbaImage>,
+
+    /// The name of the image and the region of every sprite of every page.
+    pub pages: AtlasPages,
+
+    /// The names of the images that are on no page, in the order they were added.
+    pub leftovers: Vec<String>,
+}
+
+/// The images of an `AtlasBuilder` grouped into the units that are packed.
+struct Units<'a> {
+    /// The region of every image that is placed into the atlas.
+    sources: Vec<Rectangle>,
+
+    /// Every image cropped to its region.
+    images: Vec<Cow<'a, RgbaImage>>,
+
+    /// The size of every unit, in compression blocks.
+    sizes: Vec<Size>,
+
+    /// The unit of every image and the offset of the image within it.
+    placements: Vec<(usize, usize, usize)>,
+
+    /// The images drawn into the atlas, the first image of every unit or every frame of an animation.
+    drawn: Vec<usize>,
+
+    /// The config the units are packed with, in compression blocks.
+    config: RectanglePackerConfig,
+}
+
+/// Packs a set of named images into a single atlas image.
+///
+/// # Examples
+/// ```
+/// use image::{Rgba, RgbaImage};
+/// use rpack::{AtlasBuilder, HeightRectPacker, RectanglePackerConfig};
+///
+/// let mut builder = AtlasBuilder::new(RectanglePackerConfig { rectangle_padding: 1, ..Default::default() });
+/// builder
+///     .extrusion(1)
+///     .add_image("red", RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255])))
+///     .add_image("blue", RgbaImage::from_pixel(4, 12, Rgba([0, 0, 255, 255])));
+///
+/// let atlas = builder.build::<HeightRectPacker>().unwrap();
+/// let red = atlas.layout.get("red").unwrap().rectangle;
+/// assert_eq!(*atlas.image.get_pixel(red.x as u32, red.y as u32), Rgba([255, 0, 0, 255]));
+/// ```
+#[derive(Default)]
+pub struct AtlasBuilder {
+    config: RectanglePackerConfig,
+    extrusion: usize,
+    extrude_into_padding: bool,
+    block_size: usize,
+    deduplicate: bool,
+    trim: bool,
+    images: Vec<(String, RgbaImage, Option<NineSlice>)>,
+    animations: Vec<(Range<usize>, usize)>,
+}
+
+impl AtlasBuilder {
+    /// Creates a builder for an atlas packed with the given config.
+    ///
+    /// The padding of the config is kept free around each image and its extrusion,
+    /// unless the extrusion is drawn into the padding, see [`extrude_into_padding`](Self::extrude_into_padding).
+    pub fn new(config: RectanglePackerConfig) -> AtlasBuilder {
+        AtlasBuilder {
+            config,
+            extrusion: 0,
+            extrude_into_padding: false,
+            block_size: 1,
+            deduplicate: false,
+            trim: false,
+            images: Vec::new(),
+            animations: Vec::new(),
+        }
+    }
+
+    /// Sets the number of pixels by which the edge pixels of each image are repeated outwards.
+    ///
+    /// Extruding the edges prevents neighbouring sprites or the transparent background from bleeding
+    /// into a sprite when the atlas is sampled with linear filtering.
+    pub fn extrusion(&mut self, extrusion: usize) -> &mut Self {
+        self.extrusion = extrusion;
+        self
+    }
+
+    /// Sets whether the extrusion is drawn into the padding around each image instead of taking up additional space.
+    ///
+    /// If `true`, the images are packed at their own size and their edge pixels are repeated outwards
+    /// into the owned padding and the rectangle padding of the config, so the extrusion is limited to both paddings.
+    /// Since the padding of neighbouring images never overlaps, the extrusions of different images do not either.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{Rgba, RgbaImage};
+    /// use rpack::{AtlasBuilder, HeightRectPacker, RectanglePackerConfig};
+    ///
+    /// let mut builder = AtlasBuilder::new(RectanglePackerConfig { rectangle_padding: 1, ..Default::default() });
+    /// builder
+    ///     .extrusion(1)
+    ///     .extrude_into_padding(true)
+    ///     .add_image("red", RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255])));
+    ///
+    /// let atlas = builder.build::<HeightRectPacker>().unwrap();
+    /// assert_eq!(atlas.image.dimensions(), (10, 10));
+    /// assert_eq!(*atlas.image.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+    /// ```
+    pub fn extrude_into_padding(&mut self, extrude_into_padding: bool) -> &mut Self {
+        self.extrude_into_padding = extrude_into_padding;
+        self
+    }
+
+    /// Sets the size of the blocks of a block compression format, such as 4 for BCn/DXT and most ASTC formats.
+    ///
+    /// The region of every image, including its extrusion, starts at a multiple of the block size and its size
+    /// is rounded up to a multiple of the block size, so that no compression block is shared by two images.
+    /// The padding of the config is rounded up and the max size rounded down to multiples of the block size,
+    /// which makes the size of the atlas a multiple of the block size as well. Block sizes of 0 and 1 have no effect.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{Rgba, RgbaImage};
+    /// use rpack::{AtlasBuilder, HeightRectPacker};
+    ///
+    /// let mut builder = AtlasBuilder::default();
+    /// builder
+    ///     .block_size(4)
+    ///     .add_image("a", RgbaImage::from_pixel(5, 3, Rgba([255, 0, 0, 255])))
+    ///     .add_image("b", RgbaImage::from_pixel(2, 2, Rgba([0, 0, 255, 255])));
+    ///
+    /// let atlas = builder.build::<HeightRectPacker>().unwrap();
+    /// assert_eq!(atlas.image.width() % 4, 0);
+    /// assert_eq!(atlas.image.height() % 4, 0);
+    /// for sprite in &atlas.layout.sprites {
+    ///     assert_eq!((sprite.rectangle.x % 4, sprite.rectangle.y % 4), (0, 0));
+    /// }
+    /// ```
+    pub fn block_size(&mut self, block_size: usize) -> &mut Self {
+        self.block_size = block_size;
+        self
+    }
+
+    /// Sets whether identical images are packed only once.
+    ///
+    /// If `true`, images with the same dimensions and pixels are placed into the atlas once,
+    /// and all their sprites share the same region in the layout.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{Rgba, RgbaImage};
+    /// use rpack::{AtlasBuilder, HeightRectPacker};
+    ///
+    /// let icon = RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255]));
+    /// let mut builder = AtlasBuilder::default();
+    /// builder.deduplicate(true).add_image("save", icon.clone()).add_image("save-copy", icon);
+    ///
+    /// let atlas = builder.build::<HeightRectPacker>().unwrap();
+    /// assert_eq!(atlas.layout.get("save").unwrap().rectangle, atlas.layout.get("save-copy").unwrap().rectangle);
+    /// assert_eq!(atlas.image.dimensions(), (8, 8));
+    /// ```
+    pub fn deduplicate(&mut self, deduplicate: bool) -> &mut Self {
+        self.deduplicate = deduplicate;
+        self
+    }
+
+    /// Sets whether the fully transparent margins of each image are trimmed before packing.
+    ///
+    /// Only the trimmed image is placed into the atlas. The layout records the trimmed region
+    /// and the size of the original image of every sprite, so that renderers can restore its original bounds.
+    /// Fully transparent images are trimmed to their top left pixel. Nine-slice images are never trimmed,
+    /// as trimming would move their insets.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{Rgba, RgbaImage};
+    /// use rpack::{AtlasBuilder, HeightRectPacker, Rectangle, Size};
+    ///
+    /// let mut image = RgbaImage::new(16, 16);
+    /// image.put_pixel(4, 6, Rgba([255, 0, 0, 255]));
+    /// let mut builder = AtlasBuilder::default();
+    /// builder.trim(true).add_image("dot", image);
+    ///
+    /// let atlas = builder.build::<HeightRectPacker>().unwrap();
+    /// let dot = atlas.layout.get("dot").unwrap();
+    /// assert_eq!(dot.source_rectangle, Rectangle::new(4, 6, 1, 1));
+    /// assert_eq!(dot.source_size, Size::new(16, 16));
+    /// assert_eq!(atlas.image.dimensions(), (1, 1));
+    /// ```
+    pub fn trim(&mut self, trim: bool) -> &mut Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Adds an image to the atlas.
+    ///
+    /// # Arguments
+    /// * `name` - The name the image can be looked up with in the layout of the atlas.
+    /// * `image` - The image to add.
+    pub fn add_image<S: Into<String>>(&mut self, name: S, image: RgbaImage) -> &mut Self {
+        self.images.push((name.into(), image, None));
+        self
+    }
+
+    /// Adds an image that is stretched as a nine-slice to the atlas.
+    ///
+    /// The insets are recorded in the layout of the atlas and written by the exporters of formats that support nine-slices.
+    /// Nine-slice images are never trimmed.
+    ///
+    /// # Arguments
+    /// * `name` - The name the image can be looked up with in the layout of the atlas.
+    /// * `image` - The image to add.
+    /// * `nine_slice` - The insets of the nine-slice from the edges of the image.
+    ///
+    /// # Panics
+    /// Panics if the insets are wider or higher than the image.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{Rgba, RgbaImage};
+    /// use rpack::{AtlasBuilder, HeightRectPacker, NineSlice};
+    ///
+    /// let mut builder = AtlasBuilder::default();
+    /// builder.add_nine_slice_image("button", RgbaImage::from_pixel(16, 16, Rgba([255, 0, 0, 255])), NineSlice::new(4, 4, 4, 4));
+    ///
+    /// let atlas = builder.build::<HeightRectPacker>().unwrap();
+    /// assert_eq!(atlas.layout.get("button").unwrap().nine_slice, Some(NineSlice::new(4, 4, 4, 4)));
+    /// ```
+    pub fn add_nine_slice_image<S: Into<String>>(&mut self, name: S, image: RgbaImage, nine_slice: NineSlice) -> &mut Self {
+        assert!(
+            nine_slice.left + nine_slice.right <= image.width() as usize && nine_slice.top + nine_slice.bottom <= image.height() as usize,
+            "Nine-slice insets {:?} exceed the image size {:?}", nine_slice, image.dimensions()
+        );
+        self.images.push((name.into(), image, Some(nine_slice)));
+        self
+    }
+
+    /// Adds the frames of an animation to the atlas, keeping them together in row-major order.
+    ///
+    /// The frames are packed as a single grid with the given number of columns, whose cells are as large as the largest frame
+    /// and separated by the padding of the config. The sprites of the frames are named `<name>_<index>` and follow each other
+    /// in the layout, so engines can look up the frames by index without a remap table.
+    /// Frames are never trimmed or deduplicated, and an animation without frames adds nothing.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the animation, used as the prefix of the names of its frames.
+    /// * `frames` - The frames of the animation, in order.
+    /// * `columns` - The number of frames per row of the grid, at least one and at most the number of frames.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{Rgba, RgbaImage};
+    /// use rpack::{AtlasBuilder, HeightRectPacker, Rectangle};
+    ///
+    /// let frames = vec![RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255])); 3];
+    /// let mut builder = AtlasBuilder::default();
+    /// builder
+    ///     .add_image("icon", RgbaImage::from_pixel(4, 4, Rgba([0, 0, 255, 255])))
+    ///     .add_animation("walk", frames, 2);
+    ///
+    /// let atlas = builder.build::<HeightRectPacker>().unwrap();
+    /// let first = atlas.layout.get("walk_0").unwrap().rectangle;
+    /// assert_eq!(atlas.layout.get("walk_1").unwrap().rectangle, Rectangle::new(first.x + 8, first.y, 8, 8));
+    /// assert_eq!(atlas.layout.get("walk_2").unwrap().rectangle, Rectangle::new(first.x, first.y + 8, 8, 8));
+    /// assert_eq!(atlas.layout.sprites[1].name, "walk_0");
+    /// ```
+    pub fn add_animation<S: AsRef<str>>(&mut self, name: S, frames: Vec<RgbaImage>, columns: usize) -> &mut Self {
+        let start = self.images.len();
+        let count = frames.len();
+        for (index, frame) in frames.into_iter().enumerate() {
+            self.images.push((format!("{}_{}", name.as_ref(), index), frame, None));
+        }
+        if count > 0 {
+            self.animations.push((start..start + count, columns.clamp(1, count)));
+        }
+        self
+    }
+
+    /// Packs the added images with the packer `P` and composes them into a single image.
+    ///
+    /// The area of the atlas not covered by any image or extrusion is transparent.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - The `Atlas` with the combined image and the region of every image in it, in the order the images were added.
+    ///   - A `RectanglePackingError` if the images could not be packed.
+    pub fn build<P: RectanglePacker>(&self) -> Result<Atlas, RectanglePackingError> {
+        let units = self.units();
+        let result = P::pack(&units.sizes, &units.config).map_err(|mut error| {
+            scale(&mut error.result, self.block_size.max(1));
+            error
+        })?;
+        Ok(self.compose(&units, result))
+    }
+
+    /// Packs the added images onto pages with the packer `P`, see `pack_pages`, and composes an image per page.
+    ///
+    /// The `OverflowPolicy` of the config decides what happens to the images that do not fit into its max size.
+    /// Images left over by `OverflowPolicy::Partial` or split into tiles by `split_oversized` are on no page.
+    ///
+    /// # Arguments
+    /// * `template` - The template of the image names of the pages, see `page_name`.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - The `PagedAtlas` with the image and the layout of every page.
+    ///   - A `PagingError` if the images could not be packed.
+    ///
+    /// # Panics
+    /// Panics if there are several pages and the template does not contain `{n}`, as the pages would share the same image.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{Rgba, RgbaImage};
+    /// use rpack::{AtlasBuilder, HeightRectPacker, OverflowPolicy, RectanglePackerConfig, Size, DEFAULT_PAGE_TEMPLATE};
+    ///
+    /// let config = RectanglePackerConfig { max_size: Some(Size::new(8, 8)), overflow: OverflowPolicy::NewPage, ..Default::default() };
+    /// let mut builder = AtlasBuilder::new(config);
+    /// builder
+    ///     .add_image("red", RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255])))
+    ///     .add_image("blue", RgbaImage::from_pixel(8, 8, Rgba([0, 0, 255, 255])));
+    ///
+    /// let atlas = builder.build_pages::<HeightRectPacker>(DEFAULT_PAGE_TEMPLATE).unwrap();
+    /// assert_eq!(atlas.images.len(), 2);
+    /// assert_eq!(atlas.pages.page_of("blue").unwrap().image, "atlas_1.png");
+    /// ```
+    pub fn build_pages<P: RectanglePacker>(&self, template: &str) -> Result<PagedAtlas, PagingError> {
+        let units = self.units();
+        let result = pack_pages::<P>(&units.sizes, &units.config)?;
+        assert!(
+            result.page_count() <= 1 || template.contains("{n}"),
+            "The page template must contain {{n}} for atlases with several pages"
+        );
+
+        let (images, pages) = (0..result.page_count())
+            .map(|page| {
+                let on_page = |unit: usize| result.placements[unit].filter(|placement| placement.page == page);
+                let page_result = RectanglePackingResult {
+                    rectangles: (0..units.sizes.len())
+                        .map(|unit| on_page(unit).map_or(Rectangle::new(0, 0, 0, 0), |placement| placement.rectangle))
+                        .collect(),
+                    size: result.pages[page],
+                    skipped: (0..units.sizes.len()).filter(|&unit| on_page(unit).is_none()).collect(),
+                };
+                let atlas = self.compose(&units, page_result);
+                (atlas.image, AtlasPage { index: page, image: page_name(template, page), layout: atlas.layout })
+            })
+            .unzip();
+        let leftovers = units.placements
+            .iter()
+            .enumerate()
+            .filter(|&(_, &(unit, _, _))| result.placements[unit].is_none() && result.skipped.binary_search(&unit).is_err())
+            .map(|(index, _)| self.images[index].0.clone())
+            .collect();
+
+        Ok(PagedAtlas { images, pages: AtlasPages { pages }, leftovers })
+    }
+
+    /// Groups the added images into the units that are packed, with the config to pack them with.
+    fn units(&self) -> Units<'_> {
+        let (reserved, _) = self.extrusions();
+
+        // The region of every image that is placed into the atlas.
+        let sources: Vec<Rectangle> = self.images
+            .iter()
+            .enumerate()
+            .map(|(index, (_, image, nine_slice))| match self.trim && nine_slice.is_none() && !self.is_frame(index) {
+                true => opaque_bounds(image),
+                false => Rectangle::new(0, 0, image.width() as usize, image.height() as usize),
+            })
+            .collect();
+        let images: Vec<Cow<RgbaImage>> = self.images
+            .iter()
+            .zip(&sources)
+            .map(|((_, image, _), source)| match (source.width as u32, source.height as u32) == image.dimensions() {
+                true => Cow::Borrowed(image),
+                false => Cow::Owned(
+                    imageops::crop_imm(image, source.x as u32, source.y as u32, source.width as u32, source.height as u32).to_image()
+                ),
+            })
+            .collect();
+
+        // Group the images into the units that are packed: every animation is packed as a single grid of its frames,
+        // and every other image is mapped to the first image with the same content, which is the only one that is packed.
+        // Every image is placed at an offset within its unit.
+        // The owned padding is part of the region of every image that is not empty, see `RectanglePackerConfig::padded_size`.
+        let owned = |width: usize, height: usize| self.owned_padding(width, height);
+        let block = self.block_size.max(1);
+        let padding = self.config.rectangle_padding.div_ceil(block) * block;
+        let mut units: Vec<Size> = Vec::new();
+        let mut placements: Vec<(usize, usize, usize)> = Vec::with_capacity(images.len());
+        let mut drawn: Vec<usize> = Vec::new();
+        let mut seen: HashMap<(u32, u32, &[u8]), usize> = HashMap::new();
+        let mut index = 0;
+        while index < images.len() {
+            if let Some((frames, columns)) = self.animations.iter().find(|(frames, _)| frames.start == index) {
+                let largest = Size::new(
+                    frames.clone().map(|frame| images[frame].width() as usize).max().unwrap(),
+                    frames.clone().map(|frame| images[frame].height() as usize).max().unwrap(),
+                );
+                let reserved = reserved + owned(largest.width, largest.height);
+                let cell = Size::new(largest.width + 2 * reserved, largest.height + 2 * reserved);
+                let stride = Size::new((cell.width + 2 * padding).next_multiple_of(block), (cell.height + 2 * padding).next_multiple_of(block));
+                let rows = frames.len().div_ceil(*columns);
+                for (position, frame) in frames.clone().enumerate() {
+                    placements.push((units.len(), position % columns * stride.width, position / columns * stride.height));
+                    drawn.push(frame);
+                }
+                units.push(Size::new((columns - 1) * stride.width + cell.width, (rows - 1) * stride.height + cell.height));
+                index = frames.end;
+                continue;
+            }
+
+            let image = &images[index];
+            let unit = match self.deduplicate {
+                true => *seen.entry((image.width(), image.height(), image.as_raw())).or_insert(units.len()),
+                false => units.len(),
+            };
+            if unit == units.len() {
+                let reserved = reserved + owned(image.width() as usize, image.height() as usize);
+                units.push(Size::new(image.width() as usize + 2 * reserved, image.height() as usize + 2 * reserved));
+                drawn.push(index);
+            }
+            placements.push((unit, 0, 0));
+            index += 1;
+        }
+
+        // Pack in units of compression blocks, so that every region is aligned to the blocks.
+        let sizes: Vec<Size> = units
+            .iter()
+            .map(|size| Size::new(size.width.div_ceil(block), size.height.div_ceil(block)))
+            .collect();
+        let config = RectanglePackerConfig {
+            max_size: self.config.max_size.map(|size| Size::new(size.width / block, size.height / block)),
+            rectangle_padding: self.config.rectangle_padding.div_ceil(block),
+            owned_padding: 0,
+            border_padding: self.config.border_padding.div_ceil(block),
+            column_gutter: self.config.column_gutter.div_ceil(block),
+            row_gutter: self.config.row_gutter.div_ceil(block),
+            row_height_multiple: self.config.row_height_multiple.map(|multiple| multiple.div_ceil(block)),
+            ..self.config.clone()
+        };
+
+        Units { sources, images, sizes, placements, drawn, config }
+    }
+
+    /// Composes the images into the atlas at the rectangles of their units packed in compression blocks.
+    /// The images of skipped units are left out.
+    fn compose(&self, units: &Units, mut result: RectanglePackingResult) -> Atlas {
+        let (reserved, extrusion) = self.extrusions();
+        let owned = |width: usize, height: usize| self.owned_padding(width, height);
+        let Units { sources, images, placements, drawn, .. } = units;
+
+        scale(&mut result, self.block_size.max(1));
+        let skipped_units = std::mem::take(&mut result.skipped);
+
+        let mut atlas = RgbaImage::from_pixel(
+            result.size.width as u32,
+            result.size.height as u32,
+            Rgba([0, 0, 0, 0]),
+        );
+        result.rectangles = placements
+            .iter()
+            .zip(images)
+            .map(|(&(unit, x, y), image)| {
+                let owned = owned(image.width() as usize, image.height() as usize);
+                Rectangle::new(
+                    result.rectangles[unit].x + x + reserved,
+                    result.rectangles[unit].y + y + reserved,
+                    image.width() as usize + 2 * owned,
+                    image.height() as usize + 2 * owned,
+                )
+            })
+            .collect();
+        // The images of skipped units are empty, and left out of the layout.
+        result.skipped = (0..placements.len()).filter(|&index| skipped_units.binary_search(&placements[index].0).is_ok()).collect();
+        for &index in drawn.iter().filter(|index| result.skipped.binary_search(index).is_err()) {
+            let rectangle = &result.rectangles[index];
+            let offset = owned(images[index].width() as usize, images[index].height() as usize);
+            let target = Rectangle::new(
+                rectangle.x + offset - extrusion,
+                rectangle.y + offset - extrusion,
+                images[index].width() as usize + 2 * extrusion,
+                images[index].height() as usize + 2 * extrusion,
+            );
+            blit(&mut atlas, &images[index], &target, extrusion);
+        }
+
+        let names: Vec<&str> = self.images.iter().map(|(name, _, _)| name.as_str()).collect();
+        let mut layout = AtlasLayout::from_result(&result, &names);
+        // The owned padding is part of the regions, so it grows the source image around the trimmed region as well.
+        let kept = (0..self.images.len()).filter(|index| result.skipped.binary_search(index).is_err());
+        for (sprite, index) in layout.sprites.iter_mut().zip(kept) {
+            let (source, (_, image, nine_slice)) = (sources[index], &self.images[index]);
+            let owned = owned(source.width, source.height);
+            sprite.source_rectangle = Rectangle::new(source.x, source.y, source.width + 2 * owned, source.height + 2 * owned);
+            sprite.source_size = Size::new(image.width() as usize + 2 * owned, image.height() as usize + 2 * owned);
+            sprite.nine_slice = nine_slice.map(|n| NineSlice::new(n.left + owned, n.top + owned, n.right + owned, n.bottom + owned));
+        }
+
+        Atlas {
+            image: atlas,
+            layout,
+        }
+    }
+
+    /// Packs the added images once and composes atlases at several scale factors with the same relative layout.
+    ///
+    /// The atlas at scale factor 1 is the one returned by [`build`](Self::build). For every other factor,
+    /// the regions, trim information and size of the layout are scaled, and every sprite is resized into its scaled region,
+    /// so that hi-dpi and low-end variants of the atlas share the names and proportions of their sprites.
+    /// Coordinates are rounded down, so the size of a sprite may differ by one pixel from its scaled size.
+    /// The extrusion and nine-slice insets are scaled as well, while the alignment to compression blocks only holds at scale factor 1.
+    ///
+    /// # Arguments
+    /// * `scales` - The scale factors, e.g. `[1.0, 2.0, 0.5]` for the @1x, @2x and @0.5x variants.
+    /// * `filter` - The filter used to resize the sprites.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - One `Atlas` per scale factor, in the order of the scale factors.
+    ///   - A `RectanglePackingError` if the images could not be packed.
+    ///
+    /// # Panics
+    /// Panics if any scale factor is not a positive finite number.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{imageops::FilterType, Rgba, RgbaImage};
+    /// use rpack::{AtlasBuilder, HeightRectPacker, Rectangle};
+    ///
+    /// let mut builder = AtlasBuilder::default();
+    /// builder
+    ///     .add_image("red", RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255])))
+    ///     .add_image("blue", RgbaImage::from_pixel(4, 8, Rgba([0, 0, 255, 255])));
+    ///
+    /// let atlases = builder.build_scaled::<HeightRectPacker>(&[1.0, 2.0], FilterType::Nearest).unwrap();
+    /// let blue = atlases[0].layout.get("blue").unwrap().rectangle;
+    /// let blue_2x = atlases[1].layout.get("blue").unwrap().rectangle;
+    /// assert_eq!(blue_2x, Rectangle::new(2 * blue.x, 2 * blue.y, 8, 16));
+    /// ```
+    pub fn build_scaled<P: RectanglePacker>(&self, scales: &[f64], filter: FilterType) -> Result<Vec<Atlas>, RectanglePackingError> {
+        assert!(scales.iter().all(|scale| scale.is_finite() && *scale > 0.0), "Scale factors must be positive: {:?}", scales);
+
+        let base = self.build::<P>()?;
+        let (_, extrusion) = self.extrusions();
+        Ok(scales
+            .iter()
+            .map(|&scale| match scale == 1.0 {
+                true => base.clone(),
+                false => scale_atlas(&base, scale, extrusion, filter),
+            })
+            .collect())
+    }
+
+    /// Returns `true` if the image with the given index is a frame of an animation.
+    fn is_frame(&self, index: usize) -> bool {
+        self.animations.iter().any(|(frames, _)| frames.contains(&index))
+    }
+
+    /// Returns the owned padding of an image of the given size, see `RectanglePackerConfig::padded_size`.
+    fn owned_padding(&self, width: usize, height: usize) -> usize {
+        match width == 0 || height == 0 {
+            true => 0,
+            false => self.config.owned_padding,
+        }
+    }
+
+    /// Returns the extrusion reserved in the size of every packed image, and the extrusion that is drawn around it.
+    fn extrusions(&self) -> (usize, usize) {
+        match self.extrude_into_padding {
+            true => (0, self.extrusion.min(self.config.rectangle_padding + self.config.owned_padding)),
+            false => (self.extrusion, self.extrusion),
+        }
+    }
+}
+
+/// Composes a copy of the atlas with all coordinates scaled by the given factor and every sprite resized accordingly.
+///
+/// Since the coordinates of the edges are rounded down, the gaps between the scaled regions are at least as large
+/// as the scaled gaps, which leaves room for the scaled extrusion.
+fn scale_atlas(base: &Atlas, scale: f64, extrusion: usize, filter: FilterType) -> Atlas {
+    let scaled = |value: usize| (value as f64 * scale).floor() as usize;
+    let extrusion = scaled(extrusion);
+
+    let mut layout = base.layout.clone();
+    layout.size = Size::new(scaled(layout.size.width), scaled(layout.size.height));
+    let mut image = RgbaImage::new(layout.size.width as u32, layout.size.height as u32);
+
+    for sprite in &mut layout.sprites {
+        let r = sprite.rectangle;
+        let x = scaled(r.x);
+        let y = scaled(r.y);
+        let target = Rectangle::new(x, y, scaled(r.x + r.width) - x, scaled(r.y + r.height) - y);
+
+        if target.width > 0 && target.height > 0 {
+            let region = imageops::crop_imm(&base.image, r.x as u32, r.y as u32, r.width as u32, r.height as u32).to_image();
+            let resized = imageops::resize(&region, target.width as u32, target.height as u32, filter);
+            let extruded = Rectangle::new(
+                target.x - extrusion,
+                target.y - extrusion,
+                target.width + 2 * extrusion,
+                target.height + 2 * extrusion,
+            );
+            blit(&mut image, &resized, &extruded, extrusion);
+        }
+
+        // Keep the source region as large as the region in the atlas, and the source image large enough to contain it.
+        let source = Rectangle::new(scaled(sprite.source_rectangle.x), scaled(sprite.source_rectangle.y), target.width, target.height);
+        sprite.source_size = Size::new(
+            scaled(sprite.source_size.width).max(source.x + source.width),
+            scaled(sprite.source_size.height).max(source.y + source.height),
+        );
+        sprite.source_rectangle = source;
+        sprite.rectangle = target;
+        if let Some(nine_slice) = &mut sprite.nine_slice {
+            *nine_slice = NineSlice::new(scaled(nine_slice.left), scaled(nine_slice.top), scaled(nine_slice.right), scaled(nine_slice.bottom));
+        }
+    }
+
+    Atlas { image, layout }
+}
+
+/// Scales the rectangles and the container of a result packed in units of `factor` pixels to pixels.
+fn scale(result: &mut RectanglePackingResult, factor: usize) {
+    for rectangle in &mut result.rectangles {
+        *rectangle = Rectangle::new(rectangle.x * factor, rectangle.y * factor, rectangle.width * factor, rectangle.height * factor);
+    }
+    result.size = Size::new(result.size.width * factor, result.size.height * factor);
+}
+
+/// Returns the smallest region of the image containing all pixels that are not fully transparent,
+/// or its top left pixel if all pixels are fully transparent.
+fn opaque_bounds(image: &RgbaImage) -> Rectangle {
+    let (mut left, mut top, mut right, mut bottom) = (u32::MAX, u32::MAX, 0, 0);
+    for (x, y, pixel) in image.enumerate_pixels() {
+        if pixel[3] != 0 {
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x + 1);
+            bottom = bottom.max(y + 1);
+        }
+    }
+
+    match left {
+        u32::MAX => Rectangle::new(0, 0, image.width().min(1) as usize, image.height().min(1) as usize),
+        _ => Rectangle::new(left as usize, top as usize, (right - left) as usize, (bottom - top) as usize),
+    }
+}
+
+/// Copies the image into the target region of the atlas, repeating its edge pixels into the outer `extrusion` pixels of the region.
+fn blit(atlas: &mut RgbaImage, image: &RgbaImage, target: &Rectangle, extrusion: usize) {
+    if image.width() == 0 || image.height() == 0 {
+        return;
+    }
+
+    for y in 0..target.height {
+        let source_y = y.saturating_sub(extrusion).min(image.height() as usize - 1);
+        for x in 0..target.width {
+            let source_x = x.saturating_sub(extrusion).min(image.width() as usize - 1);
+            let pixel = *image.get_pixel(source_x as u32, source_y as u32);
+            atlas.put_pixel((target.x + x) as u32, (target.y + y) as u32, pixel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeightRectPacker, OverflowPolicy, ZeroSizePolicy};
+
+    use super::*;
+
+    const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+    const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+
+    #[test]
+    fn build_works() {
+        let config = RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() };
+        let mut builder = AtlasBuilder::new(config);
+        builder
+            .add_image("red", RgbaImage::from_pixel(4, 4, RED))
+            .add_image("blue", RgbaImage::from_pixel(2, 6, BLUE));
+        let atlas = builder.build::<HeightRectPacker>().unwrap();
+
+        assert_eq!(atlas.image.dimensions(), (atlas.layout.size.width as u32, atlas.layout.size.height as u32));
+        assert_eq!(atlas.layout.sprites[0].name, "red");
+        assert_eq!(atlas.layout.sprites[1].name, "blue");
+
+        for (sprite, color) in atlas.layout.sprites.iter().zip([RED, BLUE]) {
+            let r = sprite.rectangle;
+            assert_eq!(*atlas.image.get_pixel(r.x as u32, r.y as u32), color);
+            assert_eq!(*atlas.image.get_pixel((r.x + r.width - 1) as u32, (r.y + r.height - 1) as u32), color);
+            assert_eq!(*atlas.image.get_pixel((r.x - 1) as u32, r.y as u32), Rgba([0, 0, 0, 0]));
+        }
+    }
+
+    #[test]
+    fn owned_padding_is_part_of_the_regions() {
+        let config = RectanglePackerConfig { rectangle_padding: 1, owned_padding: 2, ..Default::default() };
+        let mut builder = AtlasBuilder::new(config);
+        builder.trim(true).add_image("red", {
+            let mut image = RgbaImage::new(6, 6);
+            for (x, y) in [(1, 1), (4, 4)] {
+                image.put_pixel(x, y, RED);
+            }
+            image
+        });
+        let atlas = builder.build::<HeightRectPacker>().unwrap();
+
+        let sprite = &atlas.layout.sprites[0];
+        assert_eq!(atlas.image.dimensions(), (10, 10));
+        assert_eq!(sprite.rectangle, Rectangle::new(1, 1, 8, 8));
+        assert_eq!(sprite.source_rectangle, Rectangle::new(1, 1, 8, 8));
+        assert_eq!(sprite.source_size, Size::new(10, 10));
+        assert_eq!(*atlas.image.get_pixel(2, 2), Rgba([0, 0, 0, 0]));
+        assert_eq!(*atlas.image.get_pixel(3, 3), RED);
+        assert_eq!(*atlas.image.get_pixel(6, 6), RED);
+    }
+
+    #[test]
+    fn skipped_images_are_left_out_of_the_layout() {
+        let mut builder = AtlasBuilder::new(RectanglePackerConfig { zero_size_policy: ZeroSizePolicy::Skip, ..Default::default() });
+        builder
+            .add_image("empty", RgbaImage::new(0, 4))
+            .add_image("red", RgbaImage::from_pixel(4, 4, RED));
+        let atlas = builder.build::<HeightRectPacker>().unwrap();
+
+        assert_eq!(atlas.layout.sprites.len(), 1);
+        assert!(atlas.layout.get("empty").is_none());
+        let red = atlas.layout.get("red").unwrap();
+        assert_eq!(red.source_size, Size::new(4, 4));
+        assert_eq!(*atlas.image.get_pixel(red.rectangle.x as u32, red.rectangle.y as u32), RED);
+    }
+
+    #[test]
+    fn images_are_spread_over_pages() {
+        let config = |overflow| RectanglePackerConfig { max_size: Some(Size::new(8, 8)), overflow, ..Default::default() };
+        let mut builder = AtlasBuilder::new(config(OverflowPolicy::NewPage));
+        builder
+            .add_image("red", RgbaImage::from_pixel(8, 8, RED))
+            .add_image("blue", RgbaImage::from_pixel(8, 4, BLUE))
+            .add_image("wide", RgbaImage::from_pixel(12, 4, BLUE));
+        let atlas = builder.build_pages::<HeightRectPacker>("page_{n}.png");
+
+        assert!(atlas.is_err());
+
+        let mut builder = AtlasBuilder::new(config(OverflowPolicy::NewPage));
+        builder
+            .add_image("red", RgbaImage::from_pixel(8, 8, RED))
+            .add_image("blue", RgbaImage::from_pixel(8, 4, BLUE));
+        let atlas = builder.build_pages::<HeightRectPacker>("page_{n}.png").unwrap();
+
+        assert_eq!(atlas.images.len(), 2);
+        for (image, page) in atlas.images.iter().zip(&atlas.pages.pages) {
+            let sprite = &page.layout.sprites[0];
+            let color = if sprite.name == "red" { RED } else { BLUE };
+            assert_eq!(page.layout.sprites.len(), 1);
+            assert_eq!(page.image, format!("page_{}.png", page.index));
+            assert_eq!(*image.get_pixel(sprite.rectangle.x as u32, sprite.rectangle.y as u32), color);
+        }
+
+        let mut builder = AtlasBuilder::new(config(OverflowPolicy::Partial));
+        builder
+            .add_image("red", RgbaImage::from_pixel(8, 8, RED))
+            .add_image("blue", RgbaImage::from_pixel(8, 4, BLUE));
+        let atlas = builder.build_pages::<HeightRectPacker>("atlas.png").unwrap();
+
+        assert_eq!(atlas.pages.pages.len(), 1);
+        assert_eq!(atlas.pages.pages[0].layout.sprites.len(), 1);
+        assert_eq!(atlas.leftovers, vec!["blue".to_string()]);
+    }
+
+    #[test]
+    fn deduplication_works() {
+        let mut builder = AtlasBuilder::default();
+        builder
+            .deduplicate(true)
+            .add_image("a", RgbaImage::from_pixel(4, 4, RED))
+            .add_image("b", RgbaImage::from_pixel(4, 4, BLUE))
+            .add_image("c", RgbaImage::from_pixel(4, 4, RED))
+            .add_image("d", RgbaImage::from_pixel(2, 8, RED));
+        let atlas = builder.build::<HeightRectPacker>().unwrap();
+        let rectangle = |name| atlas.layout.get(name).unwrap().rectangle;
+
+        assert_eq!(rectangle("a"), rectangle("c"));
+        assert_ne!(rectangle("a"), rectangle("b"));
+        assert_eq!(atlas.layout.sprites.len(), 4);
+        assert_eq!(*atlas.image.get_pixel(rectangle("c").x as u32, rectangle("c").y as u32), RED);
+        assert_eq!(*atlas.image.get_pixel(rectangle("b").x as u32, rectangle("b").y as u32), BLUE);
+    }
+
+    #[test]
+    fn trimming_works() {
+        let mut image = RgbaImage::new(8, 6);
+        for (x, y) in [(2, 1), (5, 3)] {
+            image.put_pixel(x, y, RED);
+        }
+
+        let mut builder = AtlasBuilder::default();
+        builder
+            .trim(true)
+            .add_image("image", image)
+            .add_image("empty", RgbaImage::new(3, 3))
+            .add_image("full", RgbaImage::from_pixel(2, 2, BLUE));
+        let atlas = builder.build::<HeightRectPacker>().unwrap();
+
+        let sprite = atlas.layout.get("image").unwrap();
+        assert_eq!(sprite.source_rectangle, Rectangle::new(2, 1, 4, 3));
+        assert_eq!(sprite.source_size, Size::new(8, 6));
+        assert_eq!(sprite.rectangle.to_size(), Size::new(4, 3));
+        assert!(sprite.is_trimmed());
+        let r = sprite.rectangle;
+        assert_eq!(*atlas.image.get_pixel(r.x as u32, r.y as u32), RED);
+        assert_eq!(*atlas.image.get_pixel((r.x + 3) as u32, (r.y + 2) as u32), RED);
+
+        assert_eq!(atlas.layout.get("empty").unwrap().source_rectangle, Rectangle::new(0, 0, 1, 1));
+        assert!(!atlas.layout.get("full").unwrap().is_trimmed());
+    }
+
+    #[test]
+    fn nine_slices_are_not_trimmed() {
+        let mut image = RgbaImage::new(8, 8);
+        image.put_pixel(3, 3, RED);
+
+        let mut builder = AtlasBuilder::default();
+        builder
+            .trim(true)
+            .add_nine_slice_image("panel", image.clone(), NineSlice::new(2, 2, 2, 2))
+            .add_image("dot", image);
+        let atlas = builder.build::<HeightRectPacker>().unwrap();
+
+        let panel = atlas.layout.get("panel").unwrap();
+        assert_eq!(panel.nine_slice, Some(NineSlice::new(2, 2, 2, 2)));
+        assert_eq!(panel.rectangle.to_size(), Size::new(8, 8));
+        assert!(!panel.is_trimmed());
+        let dot = atlas.layout.get("dot").unwrap();
+        assert_eq!(dot.nine_slice, None);
+        assert!(dot.is_trimmed());
+    }
+
+    #[test]
+    #[should_panic]
+    fn nine_slice_insets_must_fit() {
+        AtlasBuilder::default().add_nine_slice_image("panel", RgbaImage::new(4, 4), NineSlice::new(2, 0, 3, 0));
+    }
+
+    #[test]
+    fn animations_are_kept_in_order() {
+        let config = RectanglePackerConfig { rectangle_padding: 1, ..Default::default() };
+        let mut builder = AtlasBuilder::new(config);
+        let mut frames = vec![RgbaImage::from_pixel(3, 3, RED); 4];
+        frames[2] = RgbaImage::from_pixel(5, 2, BLUE);
+        frames[3] = RgbaImage::new(3, 3);
+        builder
+            .trim(true)
+            .deduplicate(true)
+            .extrusion(1)
+            .block_size(2)
+            .add_image("a", RgbaImage::from_pixel(3, 3, RED))
+            .add_animation("run", frames, 3)
+            .add_animation("empty", Vec::new(), 2)
+            .add_image("b", RgbaImage::from_pixel(6, 6, BLUE));
+        let atlas = builder.build::<HeightRectPacker>().unwrap();
+
+        let names: Vec<&str> = atlas.layout.sprites.iter().map(|sprite| sprite.name.as_str()).collect();
+        assert_eq!(names, ["a", "run_0", "run_1", "run_2", "run_3", "b"]);
+
+        // The cells are 7x5 pixels including the extrusion. With the padding rounded up to 2 pixels on each side,
+        // and the distance between the cells rounded up to whole blocks, they are 12x10 pixels apart.
+        let run: Vec<Rectangle> = atlas.layout.sprites[1..5].iter().map(|sprite| sprite.rectangle).collect();
+        let (x, y) = (run[0].x, run[0].y);
+        assert_eq!(run, [
+            Rectangle::new(x, y, 3, 3),
+            Rectangle::new(x + 12, y, 3, 3),
+            Rectangle::new(x + 24, y, 5, 2),
+            Rectangle::new(x, y + 10, 3, 3),
+        ]);
+        assert_eq!(((x - 1) % 2, (y - 1) % 2), (0, 0));
+        assert_ne!(atlas.layout.get("a").unwrap().rectangle, run[0]);
+        assert!(!atlas.layout.get("run_3").unwrap().is_trimmed());
+        assert_eq!(*atlas.image.get_pixel(run[2].x as u32 - 1, run[2].y as u32), BLUE);
+        for sprite in &atlas.layout.sprites[..5] {
+            assert!(!sprite.rectangle.intersects(&atlas.layout.get("b").unwrap().rectangle));
+        }
+    }
+
+    #[test]
+    fn extrusion_works() {
+        let mut image = RgbaImage::from_pixel(2, 2, RED);
+        image.put_pixel(1, 1, BLUE);
+
+        let mut builder = AtlasBuilder::default();
+        builder.extrusion(2).add_image("image", image);
+        let atlas = builder.build::<HeightRectPacker>().unwrap();
+
+        assert_eq!(atlas.image.dimensions(), (6, 6));
+        assert_eq!(atlas.layout.sprites[0].rectangle, Rectangle::new(2, 2, 2, 2));
+        assert_eq!(*atlas.image.get_pixel(0, 0), RED);
+        assert_eq!(*atlas.image.get_pixel(5, 5), BLUE);
+        assert_eq!(*atlas.image.get_pixel(5, 0), RED);
+        assert_eq!(*atlas.image.get_pixel(0, 5), RED);
+    }
+
+    #[test]
+    fn block_alignment_works() {
+        let config = RectanglePackerConfig { rectangle_padding: 1, border_padding: 2, ..Default::default() };
+        let mut builder = AtlasBuilder::new(config);
+        builder.block_size(4).extrusion(1);
+        for (index, (width, height)) in [(5, 3), (2, 2), (7, 9), (1, 6)].into_iter().enumerate() {
+            builder.add_image(index.to_string(), RgbaImage::from_pixel(width, height, RED));
+        }
+        let atlas = builder.build::<HeightRectPacker>().unwrap();
+
+        assert_eq!((atlas.image.width() % 4, atlas.image.height() % 4), (0, 0));
+        for sprite in &atlas.layout.sprites {
+            let r = sprite.rectangle;
+            assert_eq!(((r.x - 1) % 4, (r.y - 1) % 4), (0, 0));
+            assert_eq!(*atlas.image.get_pixel(r.x as u32, r.y as u32), RED);
+        }
+        assert!(!atlas.layout.sprites[0].rectangle.intersects(&atlas.layout.sprites[1].rectangle));
+    }
+
+    #[test]
+    fn build_scaled_works() {
+        let config = RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() };
+        let mut builder = AtlasBuilder::new(config);
+        let mut image = RgbaImage::new(6, 5);
+        for (x, y) in [(1, 1), (4, 3)] {
+            image.put_pixel(x, y, BLUE);
+        }
+        builder
+            .extrusion(1)
+            .trim(true)
+            .add_image("red", RgbaImage::from_pixel(4, 4, RED))
+            .add_image("blue", image)
+            .add_image("green", RgbaImage::from_pixel(3, 7, Rgba([0, 255, 0, 255])));
+
+        let atlases = builder.build_scaled::<HeightRectPacker>(&[1.0, 2.0, 0.5], FilterType::Nearest).unwrap();
+        assert_eq!(atlases.len(), 3);
+        assert_eq!(atlases[0].layout, builder.build::<HeightRectPacker>().unwrap().layout);
+
+        let (base, double, half) = (&atlases[0], &atlases[1], &atlases[2]);
+        assert_eq!(double.layout.size, Size::new(2 * base.layout.size.width, 2 * base.layout.size.height));
+        assert_eq!(half.image.dimensions(), (half.layout.size.width as u32, half.layout.size.height as u32));
+        for (sprite, double_sprite) in base.layout.sprites.iter().zip(&double.layout.sprites) {
+            let r = sprite.rectangle;
+            assert_eq!(double_sprite.name, sprite.name);
+            assert_eq!(double_sprite.rectangle, Rectangle::new(2 * r.x, 2 * r.y, 2 * r.width, 2 * r.height));
+            assert_eq!(double_sprite.source_size, Size::new(2 * sprite.source_size.width, 2 * sprite.source_size.height));
+            let d = double_sprite.rectangle;
+            assert_eq!(double.image.get_pixel(d.x as u32 - 2, d.y as u32), base.image.get_pixel(r.x as u32, r.y as u32));
+        }
+
+        let blue = half.layout.get("blue").unwrap();
+        assert_eq!(blue.source_rectangle.to_size(), blue.rectangle.to_size());
+        for (index, sprite) in half.layout.sprites.iter().enumerate() {
+            for other in &half.layout.sprites[index + 1..] {
+                assert!(!sprite.rectangle.intersects(&other.rectangle));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn build_scaled_panics_for_invalid_scales() {
+        let _ = AtlasBuilder::default().build_scaled::<HeightRectPacker>(&[1.0, 0.0], FilterType::Nearest);
+    }
+
+    #[test]
+    fn extrusion_into_padding_works() {
+        let config = RectanglePackerConfig {
+            max_size: Some(Size::new(10, 6)),
+            rectangle_padding: 1,
+            border_padding: 1,
+            ..Default::default()
+        };
+        let mut builder = AtlasBuilder::new(config);
+        builder
+            .extrusion(3)
+            .extrude_into_padding(true)
+            .add_image("red", RgbaImage::from_pixel(2, 2, RED))
+            .add_image("blue", RgbaImage::from_pixel(2, 2, BLUE));
+        let atlas = builder.build::<HeightRectPacker>().unwrap();
+
+        assert_eq!(atlas.image.dimensions(), (10, 6));
+        assert_eq!(atlas.layout.get("red").unwrap().rectangle, Rectangle::new(2, 2, 2, 2));
+        assert_eq!(atlas.layout.get("blue").unwrap().rectangle, Rectangle::new(6, 2, 2, 2));
+        assert_eq!(*atlas.image.get_pixel(0, 0), Rgba([0, 0, 0, 0]));
+        assert_eq!(*atlas.image.get_pixel(1, 1), RED);
+        assert_eq!(*atlas.image.get_pixel(4, 2), RED);
+        assert_eq!(*atlas.image.get_pixel(5, 2), BLUE);
+        assert_eq!(*atlas.image.get_pixel(8, 4), BLUE);
+        assert_eq!(*atlas.image.get_pixel(9, 5), Rgba([0, 0, 0, 0]));
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{Rectangle, RectanglePacker, RectanglePackerConfig, RectanglePackingError, RectanglePackingResult, Size, UvRect};
+
+/// A named rectangle placed in an atlas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtlasSprite {
+    /// The name of the sprite, usually the file name of its source image.
+    pub name: String,
+
+    /// The region of the atlas the sprite occupies, excluding any extrusion around it.
+    pub rectangle: Rectangle,
+
+    /// The region of the source image the sprite shows, which is the whole image unless its transparent margins were trimmed.
+    /// It has the same size as `rectangle`.
+    pub source_rectangle: Rectangle,
+
+    /// The size of the source image before trimming.
+    pub source_size: Size,
+
+    /// The nine-slice insets of the sprite, if it is stretched as a nine-slice.
+    pub nine_slice: Option<NineSlice>,
+}
+
+/// The insets of a nine-slice sprite from the edges of its source image.
+///
+/// The insets divide the sprite into a 3x3 grid: the corners keep their size,
+/// the edges are stretched along one axis and the center along both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NineSlice {
+    /// The width of the left column.
+    pub left: usize,
+
+    /// The height of the top row.
+    pub top: usize,
+
+    /// The width of the right column.
+    pub right: usize,
+
+    /// The height of the bottom row.
+    pub bottom: usize,
+}
+
+impl NineSlice {
+    /// Creates new nine-slice insets.
+    pub fn new(left: usize, top: usize, right: usize, bottom: usize) -> NineSlice {
+        NineSlice { left, top, right, bottom }
+    }
+
+    /// Returns the stretched center of the nine-slice within a source image of the given size.
+    pub fn center(&self, size: &Size) -> Rectangle {
+        Rectangle::new(
+            self.left,
+            self.top,
+            size.width.saturating_sub(self.left + self.right),
+            size.height.saturating_sub(self.top + self.bottom),
+        )
+    }
+}
+
+impl AtlasSprite {
+    /// Returns `true` if transparent margins were trimmed from the source image.
+    pub fn is_trimmed(&self) -> bool {
+        self.source_rectangle != Rectangle::from_size(0, 0, &self.source_size)
+    }
+}
+
+/// The placement metadata of an atlas: its size and the region of every sprite in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtlasLayout {
+    /// The size of the atlas.
+    pub size: Size,
+
+    /// The sprites in the atlas, in the order they were added.
+    pub sprites: Vec<AtlasSprite>,
+}
+
+impl AtlasLayout {
+    /// Creates the layout of an atlas from a packing result and the names of the packed rectangles.
+    ///
+    /// The sprites are not trimmed, so each source image is as large as its rectangle, and have no nine-slice insets.
+    /// The rectangles skipped by the zero size policy are left out, so that no empty sprites are exported.
+    ///
+    /// # Arguments
+    /// * `result` - The packing result.
+    /// * `names` - The names of the packed rectangles, in the same order as the rectangles of the result, including the skipped ones.
+    ///
+    /// # Panics
+    /// Panics if the number of names differs from the number of packed rectangles.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+    ///
+    /// let sizes = vec![Size::new(10, 20), Size::new(30, 5)];
+    /// let result = HeightRectPacker::pack(&sizes, &RectanglePackerConfig::default()).unwrap();
+    ///
+    /// let layout = AtlasLayout::from_result(&result, &["player", "ground"]);
+    /// assert_eq!(layout.get("ground").unwrap().rectangle, result.rectangles[1]);
+    /// ```
+    pub fn from_result<S: AsRef<str>>(result: &RectanglePackingResult, names: &[S]) -> AtlasLayout {
+        assert_eq!(
+            names.len(),
+            result.rectangles.len(),
+            "Expected one name per packed rectangle"
+        );
+
+        let sprites = names
+            .iter()
+            .zip(&result.rectangles)
+            .enumerate()
+            .filter(|(index, _)| result.skipped.binary_search(index).is_err())
+            .map(|(_, (name, rectangle))| AtlasSprite {
+                name: name.as_ref().to_string(),
+                rectangle: *rectangle,
+                source_rectangle: Rectangle::from_size(0, 0, &rectangle.to_size()),
+                source_size: rectangle.to_size(),
+                nine_slice: None,
+            })
+            .collect();
+
+        AtlasLayout {
+            size: result.size,
+            sprites,
+        }
+    }
+
+    /// Packs named sizes with the packer `P` and returns the layout of the sprites.
+    ///
+    /// The names are carried through packing, so the sprites can be looked up by name and are written
+    /// by every exporter. Items without a name of their own can be named by their index, e.g. with `index.to_string()`.
+    ///
+    /// # Arguments
+    /// * `items` - The name and size of every sprite.
+    /// * `config` - The configuration of the packer.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - The `AtlasLayout` with the sprites in the order of the items, leaving out the items skipped by the zero size policy.
+    ///   - A `RectanglePackingError` if the sizes could not be packed.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, HeightRectPacker, RectanglePackerConfig, Size};
+    ///
+    /// let items = [("player", Size::new(16, 32)), ("coin", Size::new(8, 8))];
+    /// let layout = AtlasLayout::pack::<HeightRectPacker, _>(&items, &RectanglePackerConfig::default()).unwrap();
+    ///
+    /// assert_eq!(layout.get("coin").unwrap().rectangle.to_size(), Size::new(8, 8));
+    /// assert!(layout.to_css("atlas.png", "sprite").contains(".sprite-coin"));
+    /// ```
+    pub fn pack<P: RectanglePacker, S: AsRef<str>>(items: &[(S, Size)], config: &RectanglePackerConfig) -> Result<AtlasLayout, RectanglePackingError> {
+        let sizes: Vec<Size> = items.iter().map(|(_, size)| *size).collect();
+        let names: Vec<&str> = items.iter().map(|(name, _)| name.as_ref()).collect();
+        let result = P::pack(&sizes, config)?;
+        Ok(AtlasLayout::from_result(&result, &names))
+    }
+
+    /// Returns the index of the sprite with the given name.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.sprites.iter().position(|s| s.name == name)
+    }
+
+    /// Returns the sprite with the given name.
+    pub fn get(&self, name: &str) -> Option<&AtlasSprite> {
+        self.sprites.iter().find(|s| s.name == name)
+    }
+
+    /// Returns the normalized texture coordinates of the sprite with the given name within the atlas.
+    pub fn uv_rect(&self, name: &str) -> Option<UvRect> {
+        self.uv_rect_with_inset(name, 0.0)
+    }
+
+    /// Returns the normalized texture coordinates of the sprite with the given name within the atlas,
+    /// with every side moved inwards by `inset` texels. See [`UvRect::with_inset`].
+    pub fn uv_rect_with_inset(&self, name: &str, inset: f32) -> Option<UvRect> {
+        self.get(name).map(|sprite| sprite.rectangle.uv_rect_with_inset(&self.size, inset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_result_works() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 4, 4), Rectangle::new(4, 0, 2, 2)],
+            size: Size::new(6, 4),
+            skipped: Vec::new(),
+        };
+        let layout = AtlasLayout::from_result(&result, &["a", "b"]);
+
+        assert_eq!(layout.size, Size::new(6, 4));
+        assert_eq!(layout.sprites[0], AtlasSprite {
+            name: "a".to_string(),
+            rectangle: Rectangle::new(0, 0, 4, 4),
+            source_rectangle: Rectangle::new(0, 0, 4, 4),
+            source_size: Size::new(4, 4),
+            nine_slice: None,
+        });
+        assert!(!layout.sprites[0].is_trimmed());
+        assert_eq!(layout.get("b").unwrap().rectangle, Rectangle::new(4, 0, 2, 2));
+        assert!(layout.get("c").is_none());
+        assert_eq!(layout.uv_rect("b"), Some(UvRect { min: [4.0 / 6.0, 0.0], max: [1.0, 0.5] }));
+        assert!(layout.uv_rect("c").is_none());
+    }
+
+    #[test]
+    fn from_result_leaves_out_skipped_rectangles() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 4, 4), Rectangle::new(0, 0, 0, 0), Rectangle::new(4, 0, 2, 2)],
+            size: Size::new(6, 4),
+            skipped: vec![1],
+        };
+        let layout = AtlasLayout::from_result(&result, &["a", "empty", "b"]);
+
+        assert_eq!(layout.sprites.iter().map(|sprite| sprite.name.as_str()).collect::<Vec<_>>(), vec!["a", "b"]);
+        assert!(layout.get("empty").is_none());
+    }
+
+    #[test]
+    fn pack_works() {
+        let items = vec![("a".to_string(), Size::new(4, 4)), ("b".to_string(), Size::new(2, 6)), ("c".to_string(), Size::new(3, 1))];
+        let config = RectanglePackerConfig { rectangle_padding: 1, ..Default::default() };
+        let layout = AtlasLayout::pack::<crate::HeightRectPacker, _>(&items, &config).unwrap();
+        let result = <crate::HeightRectPacker as RectanglePacker>::pack(&[Size::new(4, 4), Size::new(2, 6), Size::new(3, 1)], &config).unwrap();
+
+        assert_eq!(layout, AtlasLayout::from_result(&result, &["a", "b", "c"]));
+        assert_eq!(layout.position("b"), Some(1));
+        assert_eq!(layout.position("d"), None);
+    }
+
+    #[test]
+    fn nine_slice_center_works() {
+        assert_eq!(NineSlice::new(2, 3, 4, 1).center(&Size::new(10, 8)), Rectangle::new(2, 3, 4, 4));
+        assert_eq!(NineSlice::new(6, 0, 6, 0).center(&Size::new(10, 8)), Rectangle::new(6, 0, 0, 8));
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_result_panics_on_missing_names() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 4, 4)],
+            size: Size::new(4, 4),
+            skipped: Vec::new(),
+        };
+        AtlasLayout::from_result(&result, &Vec::<String>::new());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{pack_pages, AtlasLayout, PagedPackingResult, PagingError, RectanglePacker, RectanglePackerConfig, RectanglePackingResult, Size};
+
+/// The template of the image file names of the pages used by default, e.g. `atlas_0.png` for the first page.
+pub const DEFAULT_PAGE_TEMPLATE: &str = "atlas_{n}.png";
+
+/// Returns the name of the page with the given index, replacing every `{n}` in the template by the index.
+///
+/// # Examples
+/// ```
+/// use rpack::page_name;
+///
+/// assert_eq!(page_name("sprites_{n}.png", 2), "sprites_2.png");
+/// assert_eq!(page_name("sprites.png", 2), "sprites.png");
+/// ```
+pub fn page_name(template: &str, index: usize) -> String {
+    template.replace("{n}", &index.to_string())
+}
+
+/// A page of an atlas with several pages, with the name of its image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtlasPage {
+    /// The index of the page, starting at `0`.
+    pub index: usize,
+
+    /// The file name of the image of the page.
+    pub image: String,
+
+    /// The layout of the sprites on the page.
+    pub layout: AtlasLayout,
+}
+
+/// The layout of an atlas spread over several pages, each with its own image.
+///
+/// The pages are named once, from a template, and every exporter takes the image names from here,
+/// so that the metadata and the image files written for the pages always agree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtlasPages {
+    /// The pages, in the order of their indices.
+    pub pages: Vec<AtlasPage>,
+}
+
+impl AtlasPages {
+    /// Creates the pages of an atlas from a paged packing result and the names of the packed rectangles.
+    ///
+    /// The sprites of every page are in the order of the sizes. Skipped and leftover sizes are on no page,
+    /// and neither are sizes split into tiles, whose tiles are only reported by the result.
+    ///
+    /// # Arguments
+    /// * `result` - The paged packing result.
+    /// * `names` - The names of the packed rectangles, in the same order as the placements of the result.
+    /// * `template` - The template of the image names, see `page_name`.
+    ///
+    /// # Panics
+    /// Panics if the number of names differs from the number of placements, or if there are several pages
+    /// and the template does not contain `{n}`, as the pages would share the same image.
+    pub fn from_result<S: AsRef<str>>(result: &PagedPackingResult, names: &[S], template: &str) -> AtlasPages {
+        assert_eq!(names.len(), result.placements.len(), "Expected one name per placement");
+        assert!(
+            result.page_count() <= 1 || template.contains("{n}"),
+            "The page template must contain {{n}} for atlases with several pages"
+        );
+
+        let pages = result
+            .pages
+            .iter()
+            .enumerate()
+            .map(|(index, &size)| {
+                let items = result.items_on(index);
+                let page = RectanglePackingResult {
+                    rectangles: items.iter().map(|&i| result.placements[i].unwrap().rectangle).collect(),
+                    size,
+                    skipped: Vec::new(),
+                };
+                let names: Vec<&str> = items.iter().map(|&i| names[i].as_ref()).collect();
+                AtlasPage { index, image: page_name(template, index), layout: AtlasLayout::from_result(&page, &names) }
+            })
+            .collect();
+
+        AtlasPages { pages }
+    }
+
+    /// Packs named sizes onto pages with the packer `P`, see `pack_pages`, and returns the layout of the pages.
+    ///
+    /// # Arguments
+    /// * `items` - The name and size of every sprite.
+    /// * `config` - The configuration of the packer.
+    /// * `template` - The template of the image names, see `page_name`.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - The `AtlasPages` with the sprites of every page in the order of the items.
+    ///   - A `PagingError` if the sizes could not be packed.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasPages, HeightRectPacker, OverflowPolicy, RectanglePackerConfig, Size, DEFAULT_PAGE_TEMPLATE};
+    ///
+    /// let items = [("player", Size::new(64, 64)), ("enemy", Size::new(64, 64)), ("coin", Size::new(16, 16))];
+    /// let config = RectanglePackerConfig { max_size: Some(Size::new(64, 64)), overflow: OverflowPolicy::NewPage, ..Default::default() };
+    /// let atlas = AtlasPages::pack::<HeightRectPacker, _>(&items, &config, DEFAULT_PAGE_TEMPLATE).unwrap();
+    ///
+    /// assert_eq!(atlas.pages.len(), 3);
+    /// assert_eq!(atlas.page_of("enemy").unwrap().image, "atlas_1.png");
+    /// for (image, metadata) in atlas.export(|layout, image| layout.to_spine_atlas(image)) {
+    ///     assert!(metadata.contains(&image));
+    /// }
+    /// ```
+    pub fn pack<P: RectanglePacker, S: AsRef<str>>(
+        items: &[(S, Size)],
+        config: &RectanglePackerConfig,
+        template: &str,
+    ) -> Result<AtlasPages, PagingError> {
+        let sizes: Vec<Size> = items.iter().map(|(_, size)| *size).collect();
+        let names: Vec<&str> = items.iter().map(|(name, _)| name.as_ref()).collect();
+        let result = pack_pages::<P>(&sizes, config)?;
+        Ok(AtlasPages::from_result(&result, &names, template))
+    }
+
+    /// Packs named sizes onto pages of the same size with the packer `P`, e.g. for the layers of a GPU texture array.
+    ///
+    /// The pages are packed like by `pack`, and are then grown to the largest width and height of all pages,
+    /// see `PagedPackingResult::make_uniform`. The index of every page is its layer in the texture array.
+    ///
+    /// # Arguments
+    /// * `items` - The name and size of every sprite.
+    /// * `config` - The configuration of the packer.
+    /// * `template` - The template of the image names of the layers, see `page_name`.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - The `AtlasPages` with a page for every layer.
+    ///   - A `PagingError` if the sizes could not be packed.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasPages, HeightRectPacker, OverflowPolicy, RectanglePackerConfig, Size};
+    ///
+    /// let items = [("sky", Size::new(64, 40)), ("tree", Size::new(30, 60))];
+    /// let config = RectanglePackerConfig { max_size: Some(Size::new(64, 64)), overflow: OverflowPolicy::NewPage, ..Default::default() };
+    /// let atlas = AtlasPages::pack_texture_array::<HeightRectPacker, _>(&items, &config, "layer_{n}.png").unwrap();
+    ///
+    /// assert_eq!(atlas.layer_size(), Some(Size::new(64, 64)));
+    /// assert!(atlas.to_texture_array_json().contains(r#""sky": {"layer": 1"#));
+    /// ```
+    pub fn pack_texture_array<P: RectanglePacker, S: AsRef<str>>(
+        items: &[(S, Size)],
+        config: &RectanglePackerConfig,
+        template: &str,
+    ) -> Result<AtlasPages, PagingError> {
+        let sizes: Vec<Size> = items.iter().map(|(_, size)| *size).collect();
+        let names: Vec<&str> = items.iter().map(|(name, _)| name.as_ref()).collect();
+        let mut result = pack_pages::<P>(&sizes, config)?;
+        result.make_uniform();
+        Ok(AtlasPages::from_result(&result, &names, template))
+    }
+
+    /// Returns the size shared by all pages, or `None` if there are no pages or their sizes differ.
+    pub fn layer_size(&self) -> Option<Size> {
+        let size = self.pages.first()?.layout.size;
+        self.pages.iter().all(|page| page.layout.size == size).then_some(size)
+    }
+
+    /// Returns the page holding the sprite with the given name.
+    pub fn page_of(&self, name: &str) -> Option<&AtlasPage> {
+        self.pages.iter().find(|page| page.layout.get(name).is_some())
+    }
+
+    /// Exports the layout of every page with a single-page exporter, passing it the image name of the page.
+    ///
+    /// # Returns
+    /// The image name and the exported metadata of every page, in the order of the pages.
+    pub fn export<F: Fn(&AtlasLayout, &str) -> String>(&self, exporter: F) -> Vec<(String, String)> {
+        self.pages.iter().map(|page| (page.image.clone(), exporter(&page.layout, &page.image))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeightRectPacker, OverflowPolicy, Rectangle, ZeroSizePolicy};
+
+    use super::*;
+
+    #[test]
+    fn from_result_splits_the_sprites_into_pages() {
+        let sizes = vec![Size::new(8, 8), Size::new(6, 6), Size::new(8, 8), Size::new(0, 0)];
+        let config = RectanglePackerConfig {
+            max_size: Some(Size::new(8, 8)),
+            overflow: OverflowPolicy::NewPage,
+            zero_size_policy: ZeroSizePolicy::Skip,
+            ..Default::default()
+        };
+        let result = pack_pages::<HeightRectPacker>(&sizes, &config).unwrap();
+        let atlas = AtlasPages::from_result(&result, &["a", "b", "c", "d"], "page-{n}.png");
+
+        let images: Vec<&str> = atlas.pages.iter().map(|page| page.image.as_str()).collect();
+        assert_eq!(images, vec!["page-0.png", "page-1.png", "page-2.png"]);
+        assert_eq!(atlas.pages.iter().map(|page| page.index).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(atlas.page_of("b").unwrap().index, 2);
+        assert_eq!(atlas.page_of("b").unwrap().layout.sprites[0].rectangle, Rectangle::new(0, 0, 6, 6));
+        assert!(atlas.page_of("d").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "must contain {n}")]
+    fn from_result_rejects_templates_without_index_for_several_pages() {
+        let config = RectanglePackerConfig { max_size: Some(Size::new(8, 8)), overflow: OverflowPolicy::NewPage, ..Default::default() };
+        let result = pack_pages::<HeightRectPacker>(&[Size::new(8, 8); 2], &config).unwrap();
+        AtlasPages::from_result(&result, &["a", "b"], "atlas.png");
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use crate::{PackingScratch, RectanglePacker, RectanglePackerConfig, RectanglePackingError, RectanglePackingResult, Size};
+
+/// An independent packing job of a batch, see `pack_batch`.
+#[derive(Debug)]
+pub struct PackJob {
+    /// The sizes of the rectangles to pack.
+    pub sizes: Vec<Size>,
+
+    /// The config to pack the rectangles with.
+    pub config: RectanglePackerConfig,
+}
+
+impl PackJob {
+    /// Creates a job packing the sizes with the config.
+    pub fn new(sizes: Vec<Size>, config: RectanglePackerConfig) -> PackJob {
+        PackJob { sizes, config }
+    }
+}
+
+/// Packs many independent jobs with the packer `P`, e.g. the small atlases an asset server creates for a request.
+///
+/// With the `rayon` feature, the jobs run in parallel on the current rayon thread pool, so the number of threads
+/// is configured by running the batch inside `rayon::ThreadPool::install`. Every thread reuses the buffers of
+/// a single `PackingScratch` for all of its jobs.
+///
+/// # Arguments
+/// * `jobs` - The jobs to pack.
+///
+/// # Returns
+/// The result of every job, in the order of the jobs.
+///
+/// # Examples
+/// ```
+/// use rpack::{pack_batch, HeightRectPacker, PackJob, RectanglePackerConfig, Size};
+///
+/// let jobs = vec![
+///     PackJob::new(vec![Size::new(16, 16), Size::new(8, 8)], RectanglePackerConfig::default()),
+///     PackJob::new(vec![Size::new(16, 16)], RectanglePackerConfig { max_size: Some(Size::new(8, 8)), ..Default::default() }),
+/// ];
+/// let results = pack_batch::<HeightRectPacker>(jobs);
+///
+/// assert!(results[0].is_ok());
+/// assert!(results[1].is_err());
+/// ```
+pub fn pack_batch<P: RectanglePacker>(jobs: Vec<PackJob>) -> Vec<Result<RectanglePackingResult, RectanglePackingError>> {
+    #[cfg(feature = "rayon")]
+    let results = jobs
+        .into_par_iter()
+        .map_init(PackingScratch::new, |scratch, job| P::pack_with_scratch(&job.sizes, &job.config, scratch))
+        .collect();
+
+    #[cfg(not(feature = "rayon"))]
+    let results = {
+        let mut scratch = PackingScratch::new();
+        jobs.into_iter().map(|job| P::pack_with_scratch(&job.sizes, &job.config, &mut scratch)).collect()
+    };
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeightRectPacker, Rectangle};
+
+    use super::*;
+
+    #[test]
+    fn pack_batch_works() {
+        let jobs: Vec<PackJob> = (0..100)
+            .map(|job| {
+                let sizes = (0..job % 13).map(|i| Size::new(i % 5 + job % 3 + 1, i % 4 + 1)).collect();
+                let max_size = (job % 10 == 0).then_some(Size::new(2, 2));
+                PackJob::new(sizes, RectanglePackerConfig { max_size, rectangle_padding: job % 2, ..Default::default() })
+            })
+            .collect();
+        let layout = |result: Result<RectanglePackingResult, RectanglePackingError>| {
+            result.map(|result| (result.rectangles, result.size)).map_err(|error| error.message)
+        };
+        let expected: Vec<Result<(Vec<Rectangle>, Size), String>> = jobs
+            .iter()
+            .map(|job| layout(HeightRectPacker::pack(&job.sizes, &job.config)))
+            .collect();
+
+        let results: Vec<Result<(Vec<Rectangle>, Size), String>> = pack_batch::<HeightRectPacker>(jobs).into_iter().map(layout).collect();
+
+        assert_eq!(results, expected);
+        assert!(results.iter().any(|result| result.is_err()));
+    }
+}
@@ -0,0 +1,190 @@
+//! Parsers for classic strip packing benchmark instances from the literature.
+//!
+//! Supported are the files of the instances by Hopper and Turton (C1 to C7) and by Burke, Kendall and Whitwell (N1 to N13),
+//! which are distributed as plain text files with the number of items, the strip dimensions and one item per line.
+
+use std::error::Error;
+use std::fmt;
+use crate::Size;
+
+/// A strip packing instance: the rectangles have to be packed into a strip of fixed width with minimal height.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchmarkInstance {
+    /// The width of the strip.
+    pub strip_width: usize,
+
+    /// The height of the optimal packing, if given by the file.
+    pub optimal_height: Option<usize>,
+
+    /// The sizes of the rectangles to pack.
+    pub sizes: Vec<Size>,
+}
+
+impl BenchmarkInstance {
+    /// Returns a lower bound of the height of any packing: the total area divided by the strip width, rounded up.
+    pub fn lower_bound_height(&self) -> usize {
+        let area: usize = self.sizes.iter().map(|s| s.area()).sum();
+        match self.strip_width {
+            0 => 0,
+            width => area.div_ceil(width),
+        }
+    }
+
+    /// Returns how much higher a packing is than the optimal height, or the lower bound if the optimum is not known,
+    /// e.g. `0.05` for a packing `5%` higher.
+    pub fn gap(&self, height: usize) -> f64 {
+        let best = self.optimal_height.unwrap_or_else(|| self.lower_bound_height());
+        match best {
+            0 => 0.0,
+            best => height as f64 / best as f64 - 1.0,
+        }
+    }
+}
+
+/// An error raised while parsing a benchmark file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchmarkParseError {
+    /// The line the error occurred in, starting at `1`.
+    pub line: usize,
+
+    /// The error message.
+    pub message: String,
+}
+
+impl fmt::Display for BenchmarkParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for BenchmarkParseError {}
+
+/// The non-empty lines of a file, split into numbers.
+struct Lines<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    last: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Lines<'a> {
+        Lines { lines: text.lines().enumerate(), last: 0 }
+    }
+
+    /// Returns the numbers of the next non-empty line.
+    fn next(&mut self, what: &str) -> Result<Vec<usize>, BenchmarkParseError> {
+        for (index, line) in self.lines.by_ref() {
+            self.last = index + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            return line
+                .split_whitespace()
+                .map(|token| {
+                    token.parse().map_err(|_| BenchmarkParseError {
+                        line: index + 1,
+                        message: format!("expected a number, found {:?}", token),
+                    })
+                })
+                .collect();
+        }
+        Err(self.error(format!("unexpected end of file, expected {}", what)))
+    }
+
+    fn error(&self, message: String) -> BenchmarkParseError {
+        BenchmarkParseError { line: self.last, message }
+    }
+
+    /// Parses `count` item lines of the form `width height` or `index width height`.
+    fn items(&mut self, count: usize) -> Result<Vec<Size>, BenchmarkParseError> {
+        (0..count)
+            .map(|_| match self.next("an item")?.as_slice() {
+                [width, height] | [_, width, height] => Ok(Size::new(*width, *height)),
+                _ => Err(self.error("expected an item as `width height` or `index width height`".to_string())),
+            })
+            .collect()
+    }
+}
+
+/// Parses an instance of Hopper and Turton.
+///
+/// The file starts with the number of items, followed by the width and the optimal height of the strip,
+/// and one line per item with its width and height, optionally preceded by its index.
+///
+/// # Examples
+/// ```
+/// use rpack::benchmarks::parse_hopper_turton;
+/// use rpack::Size;
+///
+/// let instance = parse_hopper_turton("3\n20 10\n10 10\n10 5\n10 5\n").unwrap();
+/// assert_eq!(instance.strip_width, 20);
+/// assert_eq!(instance.optimal_height, Some(10));
+/// assert_eq!(instance.sizes[1], Size::new(10, 5));
+/// ```
+pub fn parse_hopper_turton(text: &str) -> Result<BenchmarkInstance, BenchmarkParseError> {
+    let mut lines = Lines::new(text);
+    let count = match lines.next("the number of items")?.as_slice() {
+        [count] => *count,
+        _ => return Err(lines.error("expected the number of items".to_string())),
+    };
+    let (strip_width, optimal_height) = match lines.next("the strip dimensions")?.as_slice() {
+        [width, height] => (*width, Some(*height)),
+        [width] => (*width, None),
+        _ => return Err(lines.error("expected the strip dimensions as `width height`".to_string())),
+    };
+    let sizes = lines.items(count)?;
+    Ok(BenchmarkInstance { strip_width, optimal_height, sizes })
+}
+
+/// Parses an instance of Burke, Kendall and Whitwell.
+///
+/// The file starts with the number of items, followed by the width of the strip
+/// and one line per item with its width and height, optionally preceded by its index.
+/// The instances are perfect packings, so the optimal height is the lower bound of the instance.
+pub fn parse_burke(text: &str) -> Result<BenchmarkInstance, BenchmarkParseError> {
+    let mut lines = Lines::new(text);
+    let count = match lines.next("the number of items")?.as_slice() {
+        [count] => *count,
+        _ => return Err(lines.error("expected the number of items".to_string())),
+    };
+    let strip_width = match lines.next("the strip width")?.as_slice() {
+        [width] => *width,
+        _ => return Err(lines.error("expected the strip width".to_string())),
+    };
+    let sizes = lines.items(count)?;
+    let mut instance = BenchmarkInstance { strip_width, optimal_height: None, sizes };
+    instance.optimal_height = Some(instance.lower_bound_height());
+    Ok(instance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hopper_turton_works() {
+        let instance = parse_hopper_turton("2\n\n 8 4 \n1 8 2\n2 8 2\n").unwrap();
+
+        assert_eq!(instance, BenchmarkInstance {
+            strip_width: 8,
+            optimal_height: Some(4),
+            sizes: vec![Size::new(8, 2), Size::new(8, 2)],
+        });
+        assert_eq!(instance.gap(5), 0.25);
+    }
+
+    #[test]
+    fn parse_burke_works() {
+        let instance = parse_burke("3\n10\n5 4\n5 4\n10 2\n").unwrap();
+
+        assert_eq!(instance.strip_width, 10);
+        assert_eq!(instance.optimal_height, Some(6));
+        assert_eq!(instance.sizes.len(), 3);
+    }
+
+    #[test]
+    fn parse_errors_are_reported() {
+        assert_eq!(parse_burke("2\n10\n5 4\n").unwrap_err().message, "unexpected end of file, expected an item");
+        assert_eq!(parse_burke("2\n10\n5 x\n").unwrap_err().line, 3);
+        assert_eq!(parse_hopper_turton("1\n10 10\n1 2 3 4\n").unwrap_err().line, 3);
+    }
+}
@@ -0,0 +1,129 @@
+use bevy_asset::RenderAssetUsages;
+use bevy_image::{Image, IntoDynamicImageError, TextureAtlasLayout};
+use bevy_math::{URect, UVec2};
+use image::DynamicImage;
+use crate::{Atlas, AtlasBuilder, AtlasLayout, Rectangle, RectanglePackingResult, Size};
+
+/// Creates an empty texture atlas layout of the given size and adds the rectangles as textures, in their order.
+fn texture_atlas_layout<'a, I: Iterator<Item = &'a Rectangle>>(size: &Size, rectangles: I) -> TextureAtlasLayout {
+    let mut layout = TextureAtlasLayout::new_empty(UVec2::new(size.width as u32, size.height as u32));
+    for r in rectangles {
+        layout.add_texture(URect::new(
+            r.x as u32,
+            r.y as u32,
+            (r.x + r.width) as u32,
+            (r.y + r.height) as u32,
+        ));
+    }
+    layout
+}
+
+impl RectanglePackingResult {
+    /// Converts the result into a Bevy `TextureAtlasLayout`.
+    ///
+    /// The texture indices of the layout are the indices of the packed rectangles.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+    ///
+    /// let sizes = vec![Size::new(10, 20), Size::new(30, 5)];
+    /// let result = HeightRectPacker::pack(&sizes, &RectanglePackerConfig::default()).unwrap();
+    ///
+    /// let layout = result.to_texture_atlas_layout();
+    /// assert_eq!(layout.len(), 2);
+    /// assert_eq!(layout.textures[1].size().x, 30);
+    /// ```
+    pub fn to_texture_atlas_layout(&self) -> TextureAtlasLayout {
+        texture_atlas_layout(&self.size, self.rectangles.iter())
+    }
+}
+
+impl AtlasLayout {
+    /// Converts the layout into a Bevy `TextureAtlasLayout`.
+    ///
+    /// The texture indices of the layout are the indices of the sprites.
+    pub fn to_texture_atlas_layout(&self) -> TextureAtlasLayout {
+        texture_atlas_layout(&self.size, self.sprites.iter().map(|s| &s.rectangle))
+    }
+}
+
+impl AtlasBuilder {
+    /// Adds a Bevy image to the atlas, e.g. one loaded by an asset loader or processor.
+    ///
+    /// The image is converted to 8 bit RGBA, see `Image::try_into_dynamic` for the supported texture formats.
+    ///
+    /// # Arguments
+    /// * `name` - The name the image can be looked up with in the layout of the atlas.
+    /// * `image` - The image to add.
+    ///
+    /// # Returns
+    /// A `Result` containing either the builder or the error raised while converting the image.
+    pub fn add_bevy_image<S: Into<String>>(&mut self, name: S, image: &Image) -> Result<&mut Self, IntoDynamicImageError> {
+        let image = image.clone().try_into_dynamic()?.to_rgba8();
+        Ok(self.add_image(name, image))
+    }
+}
+
+impl Atlas {
+    /// Converts the atlas into a Bevy `TextureAtlasLayout` and an sRGB `Image`
+    /// that can be added to the `Assets` of a Bevy app.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{Rgba, RgbaImage};
+    /// use rpack::{AtlasBuilder, HeightRectPacker, RectanglePackerConfig};
+    ///
+    /// let mut builder = AtlasBuilder::new(RectanglePackerConfig { rectangle_padding: 1, ..Default::default() });
+    /// builder
+    ///     .add_image("red", RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255])))
+    ///     .add_image("blue", RgbaImage::from_pixel(4, 12, Rgba([0, 0, 255, 255])));
+    ///
+    /// let (layout, image) = builder.build::<HeightRectPacker>().unwrap().into_bevy();
+    /// assert_eq!(layout.len(), 2);
+    /// assert_eq!(layout.size, image.size());
+    /// ```
+    pub fn into_bevy(self) -> (TextureAtlasLayout, Image) {
+        let layout = self.layout.to_texture_atlas_layout();
+        let image = Image::from_dynamic(DynamicImage::ImageRgba8(self.image), true, RenderAssetUsages::default());
+        (layout, image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+    use crate::{HeightRectPacker, RectanglePackerConfig};
+
+    use super::*;
+
+    #[test]
+    fn to_texture_atlas_layout_works() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 4, 2), Rectangle::new(4, 0, 2, 2)],
+            size: Size::new(6, 2),
+            skipped: Vec::new(),
+        };
+        let layout = result.to_texture_atlas_layout();
+
+        assert_eq!(layout.size, UVec2::new(6, 2));
+        assert_eq!(layout.textures, vec![URect::new(0, 0, 4, 2), URect::new(4, 0, 6, 2)]);
+    }
+
+    #[test]
+    fn bevy_images_are_packed() {
+        let red = Image::from_dynamic(
+            DynamicImage::ImageRgba8(RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]))),
+            true,
+            RenderAssetUsages::default(),
+        );
+
+        let mut builder = AtlasBuilder::new(RectanglePackerConfig::default());
+        builder.add_bevy_image("red", &red).unwrap();
+        let (layout, image) = builder.build::<HeightRectPacker>().unwrap().into_bevy();
+
+        assert_eq!(layout.textures, vec![URect::new(0, 0, 4, 4)]);
+        assert_eq!(image.size(), UVec2::new(4, 4));
+        assert_eq!(&image.data.unwrap()[..4], &[255, 0, 0, 255]);
+    }
+}
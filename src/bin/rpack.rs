@@ -0,0 +1,293 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, ValueEnum};
+use rpack::export::TexturePackerFormat;
+use rpack::{AtlasBuilder, AtlasLayout, HeightRectPacker, OverflowPolicy, RectanglePackerConfig, Size};
+
+/// Packs a directory of PNG images into a texture atlas and writes the atlas image and its metadata.
+#[derive(Parser)]
+#[command(name = "rpack", version, about)]
+struct Args {
+    /// The directory containing the PNG images to pack.
+    input: PathBuf,
+
+    /// The path of the atlas image to write. The metadata is written next to it.
+    #[arg(short, long, default_value = "atlas.png")]
+    output: PathBuf,
+
+    /// The packing algorithm.
+    #[arg(short, long, value_enum, default_value_t = Algorithm::Height)]
+    algorithm: Algorithm,
+
+    /// The maximum size of the atlas, given as WIDTHxHEIGHT.
+    #[arg(short, long, value_parser = parse_size)]
+    max_size: Option<Size>,
+
+    /// The margin kept free around each image, which is not part of the regions of the images.
+    #[arg(short, long, default_value_t = 0)]
+    padding: usize,
+
+    /// The amount of transparent padding around each image that is part of its region in the atlas.
+    #[arg(long, default_value_t = 0)]
+    owned_padding: usize,
+
+    /// The amount of padding along the edges of the atlas.
+    #[arg(short, long, default_value_t = 0)]
+    border_padding: usize,
+
+    /// The space added between the columns of images.
+    #[arg(long, default_value_t = 0)]
+    column_gutter: usize,
+
+    /// The space added between the rows of images.
+    #[arg(long, default_value_t = 0)]
+    row_gutter: usize,
+
+    /// Round the height of every row of images up to a multiple of this number of pixels.
+    #[arg(long)]
+    row_height_multiple: Option<usize>,
+
+    /// Fill the space below the shorter images of every row with smaller images.
+    #[arg(long)]
+    fill_row_gaps: bool,
+
+    /// What to do with the images that do not fit into the maximum size.
+    #[arg(long, value_enum, default_value_t = Overflow::Error, requires = "max_size")]
+    overflow: Overflow,
+
+    /// The maximum number of pages of the atlas with `--overflow new-page`.
+    #[arg(long)]
+    max_pages: Option<usize>,
+
+    /// The file name of the image of every page with `--overflow new-page`, in which `{n}` is replaced by the index
+    /// of the page. Defaults to the name of the output with `_{n}` appended, e.g. `atlas_{n}.png`.
+    #[arg(long)]
+    page_name: Option<String>,
+
+    /// The number of pixels by which the edges of each image are extruded.
+    #[arg(short, long, default_value_t = 0)]
+    extrusion: usize,
+
+    /// Draw the extrusion into the padding around each image instead of reserving additional space for it.
+    #[arg(long)]
+    extrude_into_padding: bool,
+
+    /// Align the images to blocks of this size, as needed by block compression formats.
+    #[arg(long, default_value_t = 1)]
+    block_size: usize,
+
+    /// Pack identical images only once, letting their sprites share the same region.
+    #[arg(short, long)]
+    deduplicate: bool,
+
+    /// Trim the fully transparent margins of each image before packing it.
+    #[arg(long)]
+    trim: bool,
+
+    /// The format of the metadata.
+    #[arg(short, long, value_enum, default_value_t = Format::TexturePackerHash)]
+    format: Format,
+
+    /// The TinyTemplate template file used by the `template` format.
+    #[arg(short, long, required_if_eq("format", "template"))]
+    template: Option<PathBuf>,
+}
+
+/// The packers selectable with `--algorithm`.
+#[derive(Clone, Copy, ValueEnum)]
+enum Algorithm {
+    /// Packs the images into rows, sorted by height.
+    Height,
+}
+
+/// The overflow policies selectable with `--overflow`.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Overflow {
+    /// Fail if the images do not fit.
+    Error,
+    /// Open another page, written to its own image, for the images that do not fit.
+    NewPage,
+    /// Leave out the images that do not fit.
+    Partial,
+    /// Double the shorter side of the atlas until the images fit.
+    Grow,
+}
+
+impl From<Overflow> for OverflowPolicy {
+    fn from(overflow: Overflow) -> OverflowPolicy {
+        match overflow {
+            Overflow::Error => OverflowPolicy::Error,
+            Overflow::NewPage => OverflowPolicy::NewPage,
+            Overflow::Partial => OverflowPolicy::Partial,
+            Overflow::Grow => OverflowPolicy::Grow,
+        }
+    }
+}
+
+/// The metadata formats selectable with `--format`.
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// TexturePacker "JSON (hash)", as read by Phaser and PixiJS.
+    TexturePackerHash,
+    /// TexturePacker "JSON (array)", as read by Phaser and PixiJS.
+    TexturePackerArray,
+    /// The libGDX `.atlas` format.
+    Libgdx,
+    /// The Spine 4 `.atlas` format.
+    Spine,
+    /// Sprite metadata for a Unity import step.
+    Unity,
+    /// One Godot AtlasTexture `.tres` resource per image.
+    Godot,
+    /// The cocos2d plist format.
+    Cocos2d,
+    /// A CSS sprite sheet.
+    Css,
+    /// Aseprite sprite sheet JSON.
+    Aseprite,
+    /// Comma-separated values.
+    Csv,
+    /// Tab-separated values.
+    Tsv,
+    /// A user-supplied template, see `--template`.
+    Template,
+}
+
+/// Parses a size given as `WIDTHxHEIGHT`.
+fn parse_size(value: &str) -> Result<Size, String> {
+    let (width, height) = value
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected WIDTHxHEIGHT, got `{}`", value))?;
+    let width = width.trim().parse().map_err(|_| format!("invalid width `{}`", width))?;
+    let height = height.trim().parse().map_err(|_| format!("invalid height `{}`", height))?;
+    Ok(Size::new(width, height))
+}
+
+/// Returns the PNG files in the given directory, sorted by name.
+fn find_images(directory: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let is_png = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("png"));
+        if path.is_file() && is_png {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Writes the metadata of the layout in the requested format next to the image at the given path.
+fn write_metadata(args: &Args, layout: &AtlasLayout, output: &Path) -> Result<(), Box<dyn Error>> {
+    let image = output.file_name().and_then(|n| n.to_str()).unwrap_or("atlas.png");
+
+    let (extension, metadata) = match args.format {
+        Format::TexturePackerHash => ("json", layout.to_texture_packer_json(image, TexturePackerFormat::Hash)),
+        Format::TexturePackerArray => ("json", layout.to_texture_packer_json(image, TexturePackerFormat::Array)),
+        Format::Libgdx => ("atlas", layout.to_libgdx_atlas(image)),
+        Format::Spine => ("atlas", layout.to_spine_atlas(image)),
+        Format::Unity => ("json", layout.to_unity_json(image, (0.5, 0.5))),
+        Format::Cocos2d => ("plist", layout.to_cocos2d_plist(image)),
+        Format::Css => ("css", layout.to_css(image, "sprite")),
+        Format::Aseprite => ("json", layout.to_aseprite_json(image, 100, &[])),
+        Format::Csv => ("csv", layout.to_csv()),
+        Format::Tsv => ("tsv", layout.to_tsv()),
+        Format::Template => {
+            let template = fs::read_to_string(args.template.as_ref().expect("--template is required"))?;
+            ("txt", layout.to_template(&template, image)?)
+        }
+        Format::Godot => {
+            let directory = output.parent().unwrap_or(Path::new(""));
+            for (name, resource) in layout.to_godot_atlas_textures(&format!("res://{}", image)) {
+                fs::write(directory.join(name).with_extension("tres"), resource)?;
+            }
+            return Ok(());
+        }
+    };
+
+    fs::write(output.with_extension(extension), metadata)?;
+    Ok(())
+}
+
+/// Packs the images and writes the atlas as requested by the arguments.
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let paths = find_images(&args.input)?;
+    if paths.is_empty() {
+        return Err(format!("No PNG images found in {}", args.input.display()).into());
+    }
+
+    let mut builder = AtlasBuilder::new(RectanglePackerConfig {
+        max_size: args.max_size,
+        rectangle_padding: args.padding,
+        owned_padding: args.owned_padding,
+        border_padding: args.border_padding,
+        column_gutter: args.column_gutter,
+        row_gutter: args.row_gutter,
+        row_height_multiple: args.row_height_multiple,
+        fill_row_gaps: args.fill_row_gaps,
+        overflow: args.overflow.into(),
+        max_pages: args.max_pages,
+        ..Default::default()
+    });
+    builder
+        .extrusion(args.extrusion)
+        .extrude_into_padding(args.extrude_into_padding)
+        .block_size(args.block_size)
+        .deduplicate(args.deduplicate)
+        .trim(args.trim);
+    for path in &paths {
+        let name = path.file_stem().and_then(|s| s.to_str()).ok_or("Invalid file name")?;
+        builder.add_image(name, image::open(path)?.into_rgba8());
+    }
+
+    // Only new pages are written to images of their own, every other policy packs a single image.
+    let output = args.output.file_name().and_then(|n| n.to_str()).ok_or("Invalid output file name")?;
+    let template = match (args.overflow, &args.page_name) {
+        (Overflow::NewPage, Some(template)) => template.clone(),
+        (Overflow::NewPage, None) => {
+            let stem = args.output.file_stem().and_then(|s| s.to_str()).unwrap_or("atlas");
+            let extension = args.output.extension().and_then(|e| e.to_str()).unwrap_or("png");
+            format!("{}_{{n}}.{}", stem, extension)
+        }
+        _ => output.to_string(),
+    };
+    if args.overflow == Overflow::NewPage && !template.contains("{n}") {
+        return Err(format!("The page name `{}` must contain {{n}}", template).into());
+    }
+
+    let atlas = match args.algorithm {
+        Algorithm::Height => builder.build_pages::<HeightRectPacker>(&template)?,
+    };
+
+    let directory = args.output.parent().unwrap_or(Path::new(""));
+    for (image, page) in atlas.images.iter().zip(&atlas.pages.pages) {
+        let path = directory.join(&page.image);
+        image.save(&path)?;
+        write_metadata(args, &page.layout, &path)?;
+        println!(
+            "Packed {} images into {} ({}x{})",
+            page.layout.sprites.len(),
+            path.display(),
+            page.layout.size.width,
+            page.layout.size.height
+        );
+    }
+    if !atlas.leftovers.is_empty() {
+        eprintln!("warning: {} images did not fit: {}", atlas.leftovers.len(), atlas.leftovers.join(", "));
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt;
+
+/// The configuration of a one-dimensional bin packer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BinPackerConfig {
+    /// The capacity of every bin, e.g. the length of a piece of linear stock or the width of a row.
+    pub capacity: usize,
+}
+
+/// The place of an item in a bin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BinPlacement {
+    /// The index of the bin the item was placed in.
+    pub bin: usize,
+
+    /// The offset of the item from the start of the bin.
+    pub offset: usize,
+}
+
+/// The result of packing items into bins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinPackingResult {
+    /// The placement of every item, in the order of the lengths.
+    pub placements: Vec<BinPlacement>,
+
+    /// The used length of every bin.
+    pub bins: Vec<usize>,
+}
+
+impl BinPackingResult {
+    /// Returns the number of bins used.
+    pub fn bin_count(&self) -> usize {
+        self.bins.len()
+    }
+
+    /// Returns the indices of the items in the given bin, ordered by their offset.
+    pub fn items_in(&self, bin: usize) -> Vec<usize> {
+        let mut items: Vec<usize> = (0..self.placements.len()).filter(|&i| self.placements[i].bin == bin).collect();
+        items.sort_by_key(|&i| self.placements[i].offset);
+        items
+    }
+
+    /// Returns the ratio of the total used length to the total capacity of the used bins.
+    ///
+    /// Without any bins or capacity, the utilization is `0.0`.
+    pub fn utilization(&self, config: &BinPackerConfig) -> f64 {
+        match self.bins.len() * config.capacity {
+            0 => 0.0,
+            capacity => self.bins.iter().sum::<usize>() as f64 / capacity as f64,
+        }
+    }
+}
+
+/// The error returned if items could not be packed into bins.
+#[derive(Debug, Clone)]
+pub struct BinPackingError {
+    /// The error message.
+    pub message: String,
+
+    /// The result of the packing operation until the error occurred.
+    pub result: BinPackingResult,
+}
+
+impl fmt::Display for BinPackingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for BinPackingError {}
+
+/// A trait for packing items of given lengths into as few bins of a fixed capacity as possible.
+pub trait BinPacker {
+    /// Packs the items into bins.
+    ///
+    /// # Arguments
+    /// * `lengths` - The length of every item.
+    /// * `config` - The configuration of the packer.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - A `BinPackingResult` with the placement of every item, in the order of the lengths, and the used length of every bin.
+    ///   - A `BinPackingError` if an item is longer than the capacity of a bin.
+    fn pack(lengths: &[usize], config: &BinPackerConfig) -> Result<BinPackingResult, BinPackingError>;
+}
+
+/// Places the items from the longest to the shortest into the first bin they fit in.
+pub struct FirstFitDecreasing {}
+
+/// Places the items from the longest to the shortest into the bin they fill the most.
+pub struct BestFitDecreasing {}
+
+impl BinPacker for FirstFitDecreasing {
+    /// Packs the items with the first fit decreasing heuristic.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{BinPacker, BinPackerConfig, FirstFitDecreasing};
+    ///
+    /// let result = FirstFitDecreasing::pack(&[4, 8, 1, 4, 2, 1], &BinPackerConfig { capacity: 10 }).unwrap();
+    /// assert_eq!(result.bin_count(), 2);
+    /// assert_eq!(result.items_in(0), vec![1, 4]);
+    /// ```
+    fn pack(lengths: &[usize], config: &BinPackerConfig) -> Result<BinPackingResult, BinPackingError> {
+        pack_decreasing(lengths, config, |bins, length| {
+            bins.iter().position(|&used| used + length <= config.capacity)
+        })
+    }
+}
+
+impl BinPacker for BestFitDecreasing {
+    /// Packs the items with the best fit decreasing heuristic.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{BestFitDecreasing, BinPacker, BinPackerConfig};
+    ///
+    /// let result = BestFitDecreasing::pack(&[6, 5, 4, 3], &BinPackerConfig { capacity: 10 }).unwrap();
+    /// assert_eq!(result.bins, vec![10, 8]);
+    /// ```
+    fn pack(lengths: &[usize], config: &BinPackerConfig) -> Result<BinPackingResult, BinPackingError> {
+        pack_decreasing(lengths, config, |bins, length| {
+            (0..bins.len())
+                .filter(|&bin| bins[bin] + length <= config.capacity)
+                .max_by_key(|&bin| (bins[bin], Reverse(bin)))
+        })
+    }
+}
+
+/// Places the items from the longest to the shortest into the bin chosen by `choose`,
+/// or into a new bin if `choose` returns `None`.
+fn pack_decreasing<F>(lengths: &[usize], config: &BinPackerConfig, choose: F) -> Result<BinPackingResult, BinPackingError>
+where
+    F: Fn(&[usize], usize) -> Option<usize>,
+{
+    let mut result = BinPackingResult {
+        placements: vec![BinPlacement { bin: 0, offset: 0 }; lengths.len()],
+        bins: Vec::new(),
+    };
+
+    if let Some(index) = lengths.iter().position(|&length| length > config.capacity) {
+        return Err(BinPackingError {
+            message: format!("Item {} of length {} exceeds the capacity {}", index, lengths[index], config.capacity),
+            result,
+        });
+    }
+
+    let mut order: Vec<usize> = (0..lengths.len()).collect();
+    order.sort_by_key(|&i| Reverse(lengths[i]));
+
+    for index in order {
+        let length = lengths[index];
+        let bin = match choose(&result.bins, length) {
+            Some(bin) => bin,
+            None => {
+                result.bins.push(0);
+                result.bins.len() - 1
+            }
+        };
+        result.placements[index] = BinPlacement { bin, offset: result.bins[bin] };
+        result.bins[bin] += length;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_valid(lengths: &[usize], result: &BinPackingResult, config: &BinPackerConfig) {
+        for (bin, &used) in result.bins.iter().enumerate() {
+            assert!(used <= config.capacity);
+            let items = result.items_in(bin);
+            assert_eq!(items.iter().map(|&i| lengths[i]).sum::<usize>(), used);
+            let mut offset = 0;
+            for i in items {
+                assert_eq!(result.placements[i].offset, offset);
+                offset += lengths[i];
+            }
+        }
+    }
+
+    #[test]
+    fn first_fit_decreasing_works() {
+        let lengths = [5, 7, 5, 2, 4, 2, 5, 1, 6];
+        let config = BinPackerConfig { capacity: 10 };
+        let result = FirstFitDecreasing::pack(&lengths, &config).unwrap();
+
+        assert_valid(&lengths, &result, &config);
+        assert_eq!(result.bin_count(), 4);
+        assert_eq!(result.utilization(&config), 0.925);
+    }
+
+    #[test]
+    fn best_fit_decreasing_works() {
+        let lengths = [5, 7, 5, 2, 4, 2, 5, 1, 6];
+        let config = BinPackerConfig { capacity: 10 };
+        let result = BestFitDecreasing::pack(&lengths, &config).unwrap();
+
+        assert_valid(&lengths, &result, &config);
+        assert_eq!(result.bin_count(), 4);
+    }
+
+    #[test]
+    fn best_fit_prefers_fuller_bins() {
+        let config = BinPackerConfig { capacity: 20 };
+
+        // The item of length 2 fits into both bins, but fills the second one.
+        let result = BestFitDecreasing::pack(&[12, 9, 9, 2], &config).unwrap();
+        assert_eq!(result.placements[3], BinPlacement { bin: 1, offset: 18 });
+
+        let result = FirstFitDecreasing::pack(&[12, 9, 9, 2], &config).unwrap();
+        assert_eq!(result.placements[3], BinPlacement { bin: 0, offset: 12 });
+    }
+
+    #[test]
+    fn pack_handles_empty_inputs() {
+        let config = BinPackerConfig { capacity: 10 };
+        let result = FirstFitDecreasing::pack(&[], &config).unwrap();
+
+        assert_eq!(result.bin_count(), 0);
+        assert_eq!(result.utilization(&config), 0.0);
+    }
+
+    #[test]
+    fn pack_fails_for_too_long_items() {
+        let error = BestFitDecreasing::pack(&[4, 11], &BinPackerConfig { capacity: 10 }).unwrap_err();
+
+        assert_eq!(error.message, "Item 1 of length 11 exceeds the capacity 10");
+    }
+}
@@ -0,0 +1,602 @@
+use std::cmp::{max, Reverse};
+use std::error::Error;
+use std::fmt;
+
+/// Represents a 3D size with width, height and depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Size3 {
+    /// The extent along the x axis.
+    pub width: usize,
+
+    /// The extent along the y axis.
+    pub height: usize,
+
+    /// The extent along the z axis.
+    pub depth: usize,
+}
+
+impl Size3 {
+    /// Creates a new `Size3` instance with the given `width`, `height` and `depth`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::Size3;
+    ///
+    /// let size = Size3::new(10, 20, 30);
+    /// assert_eq!(size.volume(), 6000);
+    /// ```
+    pub fn new(width: usize, height: usize, depth: usize) -> Size3 {
+        Size3 { width, height, depth }
+    }
+
+    /// Returns the volume of the size.
+    pub fn volume(&self) -> usize {
+        self.width * self.height * self.depth
+    }
+}
+
+/// A cuboid in a 3D space, aligned to the axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Box3 {
+    /// The x coordinate of the corner closest to the origin.
+    pub x: usize,
+
+    /// The y coordinate of the corner closest to the origin.
+    pub y: usize,
+
+    /// The z coordinate of the corner closest to the origin.
+    pub z: usize,
+
+    /// The extent along the x axis.
+    pub width: usize,
+
+    /// The extent along the y axis.
+    pub height: usize,
+
+    /// The extent along the z axis.
+    pub depth: usize,
+}
+
+impl Box3 {
+    /// Creates a new box with its corner closest to the origin at `x`, `y` and `z`.
+    pub fn new(x: usize, y: usize, z: usize, width: usize, height: usize, depth: usize) -> Box3 {
+        Box3 { x, y, z, width, height, depth }
+    }
+
+    /// Creates a new box with its corner closest to the origin at `x`, `y` and `z` and the given size.
+    pub fn from_size(x: usize, y: usize, z: usize, size: &Size3) -> Box3 {
+        Box3::new(x, y, z, size.width, size.height, size.depth)
+    }
+
+    /// Returns `true` if the box overlaps with another box.
+    ///
+    /// Boxes that only touch each other do not overlap.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::Box3;
+    ///
+    /// let a = Box3::new(0, 0, 0, 4, 4, 4);
+    /// assert!(a.intersects(&Box3::new(2, 2, 2, 4, 4, 4)));
+    /// assert!(!a.intersects(&Box3::new(4, 0, 0, 4, 4, 4)));
+    /// ```
+    pub fn intersects(&self, other: &Box3) -> bool {
+        self.x < other.x + other.width
+            && self.x + self.width > other.x
+            && self.y < other.y + other.height
+            && self.y + self.height > other.y
+            && self.z < other.z + other.depth
+            && self.z + self.depth > other.z
+    }
+
+    /// Returns `true` if the box lies completely within a container of the given size at the origin.
+    pub fn fits_in(&self, container: &Size3) -> bool {
+        self.x + self.width <= container.width
+            && self.y + self.height <= container.height
+            && self.z + self.depth <= container.depth
+    }
+
+    /// Converts the box to its size.
+    pub fn to_size(&self) -> Size3 {
+        Size3::new(self.width, self.height, self.depth)
+    }
+}
+
+/// The configuration of a box packer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BoxPackerConfig {
+    /// The maximum size of the container.
+    ///
+    /// If `None`, the container dimensions will be dynamically determined to fit all the boxes.
+    /// If `Some`, the container will have the given dimensions, and an error will be returned if the boxes cannot be packed within those dimensions.
+    pub max_size: Option<Size3>,
+
+    /// The amount of padding to add around each box.
+    pub padding: usize,
+}
+
+/// The result of packing boxes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoxPackingResult {
+    /// The packed boxes, in the order of the sizes.
+    pub boxes: Vec<Box3>,
+
+    /// The size of the container.
+    pub size: Size3,
+}
+
+impl BoxPackingResult {
+    /// Returns the ratio of the total volume of the packed boxes to the volume of the container.
+    ///
+    /// A container without any volume has a volume ratio of `0.0`.
+    pub fn volume_ratio(&self) -> f64 {
+        match self.size.volume() {
+            0 => 0.0,
+            volume => self.boxes.iter().map(|b| b.to_size().volume()).sum::<usize>() as f64 / volume as f64,
+        }
+    }
+
+    /// Returns `true` if every box lies within the container and no two boxes overlap.
+    pub fn is_valid(&self) -> bool {
+        self.boxes.iter().enumerate().all(|(index, b)| {
+            b.fits_in(&self.size) && self.boxes[index + 1..].iter().all(|other| !b.intersects(other))
+        })
+    }
+}
+
+/// The error returned if boxes could not be packed.
+#[derive(Debug, Clone)]
+pub struct BoxPackingError {
+    /// The error message.
+    pub message: String,
+
+    /// The result of the packing operation until the error occurred.
+    pub result: BoxPackingResult,
+}
+
+impl fmt::Display for BoxPackingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for BoxPackingError {}
+
+/// A trait for packing boxes into a container volume.
+pub trait BoxPacker {
+    /// Packs a list of box sizes into a single container.
+    ///
+    /// # Arguments
+    /// * `sizes` - The sizes of the boxes to pack.
+    /// * `config` - The configuration of the packer.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - A `BoxPackingResult` with the packed boxes, in the order of the sizes, and the size of the container.
+    ///   - A `BoxPackingError` if the boxes do not fit into the max size.
+    fn pack(sizes: &[Size3], config: &BoxPackerConfig) -> Result<BoxPackingResult, BoxPackingError>;
+}
+
+/// A box packer that stacks layers of boxes, the three-dimensional counterpart of `HeightRectPacker`.
+///
+/// The boxes are sorted by height and placed next to each other along the x axis. A full row is followed by
+/// the next row along the z axis, and a full layer by the next layer on top of it, as high as its highest box.
+pub struct LayerBoxPacker {}
+
+impl BoxPacker for LayerBoxPacker {
+    /// Packs the boxes in layers.
+    ///
+    /// Without a max size, the footprint of the container is a square, as wide as the cube root of the
+    /// total volume of the boxes or the widest or deepest box, and the container is as high as the stacked layers.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{BoxPacker, BoxPackerConfig, LayerBoxPacker, Size3};
+    ///
+    /// let sizes = vec![Size3::new(4, 4, 4); 8];
+    /// let result = LayerBoxPacker::pack(&sizes, &BoxPackerConfig::default()).unwrap();
+    ///
+    /// assert_eq!(result.size, Size3::new(8, 8, 8));
+    /// assert!(result.is_valid());
+    /// ```
+    fn pack(sizes: &[Size3], config: &BoxPackerConfig) -> Result<BoxPackingResult, BoxPackingError> {
+        let padding = config.padding;
+        let padded: Vec<Size3> = sizes
+            .iter()
+            .map(|s| Size3::new(s.width + 2 * padding, s.height + 2 * padding, s.depth + 2 * padding))
+            .collect();
+
+        let error = |message: String, boxes: Vec<Box3>| BoxPackingError {
+            message,
+            result: BoxPackingResult { boxes, size: Size3::new(0, 0, 0) },
+        };
+
+        let max_size = match config.max_size {
+            Some(max_size) => max_size,
+            None => {
+                let volume: usize = padded.iter().map(Size3::volume).sum();
+                let side = cube_root_ceil(volume);
+                let width = padded.iter().map(|s| s.width).max().unwrap_or(0);
+                let depth = padded.iter().map(|s| s.depth).max().unwrap_or(0);
+                let side = max(side, max(width, depth));
+                Size3::new(side, usize::MAX, side)
+            }
+        };
+
+        if let Some(index) = padded.iter().position(|s| s.width > max_size.width || s.height > max_size.height || s.depth > max_size.depth) {
+            return Err(error(format!("Box {} of size {:?} does not fit in max size {:?}", index, sizes[index], max_size), Vec::new()));
+        }
+
+        // Place the highest boxes first, so that the boxes in each layer have similar heights.
+        let mut order: Vec<usize> = (0..sizes.len()).collect();
+        order.sort_by_key(|&i| (Reverse(padded[i].height), Reverse(padded[i].depth), Reverse(padded[i].width)));
+
+        let mut boxes = vec![Box3::new(0, 0, 0, 0, 0, 0); sizes.len()];
+        let (mut x, mut y, mut z) = (0, 0, 0);
+        let (mut row_depth, mut layer_height) = (0, 0);
+        let (mut used_width, mut used_depth) = (0, 0);
+
+        for &index in &order {
+            let size = padded[index];
+
+            // Start a new row if the box does not fit into the current one, and a new layer if the row does not fit into the current layer.
+            if x > 0 && x + size.width > max_size.width {
+                x = 0;
+                z += row_depth;
+                row_depth = 0;
+            }
+            if z > 0 && z + size.depth > max_size.depth {
+                x = 0;
+                z = 0;
+                y += layer_height;
+                row_depth = 0;
+                layer_height = 0;
+            }
+            if y + size.height > max_size.height {
+                return Err(error("Could not fit all boxes in max size".to_string(), boxes));
+            }
+
+            boxes[index] = Box3::from_size(x + padding, y + padding, z + padding, &sizes[index]);
+            x += size.width;
+            row_depth = max(row_depth, size.depth);
+            layer_height = max(layer_height, size.height);
+            used_width = max(used_width, x);
+            used_depth = max(used_depth, z + row_depth);
+        }
+
+        let size = match config.max_size {
+            Some(max_size) => max_size,
+            None => Size3::new(used_width, y + layer_height, used_depth),
+        };
+
+        Ok(BoxPackingResult { boxes, size })
+    }
+}
+
+/// The load-bearing properties of a box, used by the `StackingPacker`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BoxLoad {
+    /// The weight of the box.
+    pub weight: f64,
+
+    /// The maximum total weight that may rest on top of the box, or `None` if it is unlimited.
+    pub max_load: Option<f64>,
+
+    /// Whether the box must stay upright. Upright boxes may only be turned around the vertical axis.
+    pub this_side_up: bool,
+}
+
+/// A box packer for loading pallets, which stacks boxes with respect to their weight and support.
+///
+/// The boxes are placed from the heaviest to the lightest, each at the lowest, then frontmost, then leftmost
+/// position where it rests on the floor or on other boxes. A box is only placed on top of others if they support
+/// enough of its footprint and can bear its weight, which is shared among them in proportion to the area they support.
+/// Boxes may be turned to any side unless they must stay upright.
+pub struct StackingPacker {}
+
+impl StackingPacker {
+    /// The fraction of its footprint on which a box must be supported when packing with `pack`.
+    pub const DEFAULT_MIN_SUPPORT: f64 = 0.75;
+
+    /// Packs the boxes with respect to their loads.
+    ///
+    /// Without a max size, the footprint of the container is chosen like the one of the `LayerBoxPacker`,
+    /// and the container is as high as the stacked boxes.
+    ///
+    /// # Arguments
+    /// * `sizes` - The sizes of the boxes to pack.
+    /// * `loads` - The load-bearing properties of every box, in the order of the sizes.
+    /// * `min_support` - The fraction of its footprint, from `0.0` to `1.0`, on which a box must rest on the boxes below it.
+    /// * `config` - The configuration of the packer.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - A `BoxPackingResult` with the packed boxes, in the order of the sizes, and the size of the container.
+    ///   - A `BoxPackingError` if the boxes cannot be stacked into the max size.
+    ///
+    /// # Panics
+    /// Panics if the number of loads differs from the number of sizes.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{BoxLoad, BoxPackerConfig, Size3, StackingPacker};
+    ///
+    /// let sizes = vec![Size3::new(2, 2, 2); 3];
+    /// let fragile = BoxLoad { weight: 10.0, max_load: Some(0.0), this_side_up: true };
+    /// let sturdy = BoxLoad { weight: 5.0, ..Default::default() };
+    /// let config = BoxPackerConfig { max_size: Some(Size3::new(4, 4, 2)), padding: 0 };
+    /// let result = StackingPacker::pack_with_loads(&sizes, &[fragile, sturdy, sturdy], 1.0, &config).unwrap();
+    ///
+    /// // Nothing may be stacked on the fragile box, so the third box goes on top of the second one.
+    /// assert_eq!((result.boxes[2].x, result.boxes[2].y), (result.boxes[1].x, 2));
+    /// ```
+    pub fn pack_with_loads(sizes: &[Size3], loads: &[BoxLoad], min_support: f64, config: &BoxPackerConfig) -> Result<BoxPackingResult, BoxPackingError> {
+        assert_eq!(sizes.len(), loads.len(), "Expected one load per size");
+
+        let padding = config.padding;
+        let pad = |s: &Size3| Size3::new(s.width + 2 * padding, s.height + 2 * padding, s.depth + 2 * padding);
+        let error = |message: String, boxes: Vec<Box3>| BoxPackingError {
+            message,
+            result: BoxPackingResult { boxes, size: Size3::new(0, 0, 0) },
+        };
+
+        let max_size = match config.max_size {
+            Some(max_size) => max_size,
+            None => {
+                let volume: usize = sizes.iter().map(|s| pad(s).volume()).sum();
+                let side = cube_root_ceil(volume);
+                let widest = sizes.iter().map(|s| pad(s).width.max(pad(s).depth)).max().unwrap_or(0);
+                Size3::new(max(side, widest), usize::MAX, max(side, widest))
+            }
+        };
+
+        // Place the heaviest boxes first, so that they end up at the bottom.
+        let mut order: Vec<usize> = (0..sizes.len()).collect();
+        order.sort_by(|&a, &b| loads[b].weight.total_cmp(&loads[a].weight).then(sizes[b].volume().cmp(&sizes[a].volume())));
+
+        let mut boxes = vec![Box3::new(0, 0, 0, 0, 0, 0); sizes.len()];
+        // The padded boxes placed so far, with the index of their box.
+        let mut placed: Vec<(usize, Box3)> = Vec::with_capacity(sizes.len());
+        // The boxes every placed box rests on, with the share of its weight they bear.
+        let mut supporters: Vec<Vec<(usize, f64)>> = vec![Vec::new(); sizes.len()];
+        let mut borne = vec![0.0; sizes.len()];
+
+        for index in order {
+            // The corners of the footprints of the placed boxes are candidates for the corner of the next box.
+            let mut corners = vec![(0, 0)];
+            for (_, p) in &placed {
+                corners.extend([(p.x + p.width, p.z), (p.x, p.z + p.depth), (p.x + p.width, 0), (0, p.z + p.depth)]);
+            }
+
+            let mut best: Option<(Box3, Vec<(usize, f64)>)> = None;
+            for size in orientations(&sizes[index], loads[index].this_side_up).iter().map(pad) {
+                for &(x, z) in &corners {
+                    let footprint = Box3::new(x, 0, z, size.width, usize::MAX, size.depth);
+                    let y = placed.iter().filter(|(_, p)| p.intersects(&footprint)).map(|(_, p)| p.y + p.height).max().unwrap_or(0);
+                    let candidate = Box3::from_size(x, y, z, &size);
+                    if !candidate.fits_in(&max_size) || best.as_ref().is_some_and(|(b, _)| (b.y, b.z, b.x) <= (y, z, x)) {
+                        continue;
+                    }
+
+                    // The boxes whose tops touch the bottom of the candidate, with the area they support.
+                    let below = Box3::new(x, y.saturating_sub(1), z, size.width, 1, size.depth);
+                    let support: Vec<(usize, usize)> = placed
+                        .iter()
+                        .filter(|(_, p)| y > 0 && p.y + p.height == y && p.intersects(&below))
+                        .map(|&(i, p)| (i, overlap(p.x, p.width, x, size.width) * overlap(p.z, p.depth, z, size.depth)))
+                        .collect();
+                    let supported: usize = support.iter().map(|(_, area)| area).sum();
+                    let shares: Vec<(usize, f64)> = support.iter().map(|&(i, area)| (i, area as f64 / supported as f64)).collect();
+
+                    let footprint_area = size.width * size.depth;
+                    if y > 0 && (supported as f64) < min_support * footprint_area as f64 {
+                        continue;
+                    }
+                    if !can_bear(&shares, loads[index].weight, loads, &supporters, &borne) {
+                        continue;
+                    }
+                    best = Some((candidate, shares));
+                }
+            }
+
+            let Some((b, shares)) = best else {
+                return Err(error("Could not fit all boxes in max size".to_string(), boxes));
+            };
+            distribute(&shares, loads[index].weight, &supporters, &mut borne);
+            supporters[index] = shares;
+            placed.push((index, b));
+            boxes[index] = Box3::new(b.x + padding, b.y + padding, b.z + padding, b.width - 2 * padding, b.height - 2 * padding, b.depth - 2 * padding);
+        }
+
+        let size = match config.max_size {
+            Some(max_size) => max_size,
+            None => placed.iter().fold(Size3::new(0, 0, 0), |size, (_, p)| {
+                Size3::new(max(size.width, p.x + p.width), max(size.height, p.y + p.height), max(size.depth, p.z + p.depth))
+            }),
+        };
+
+        Ok(BoxPackingResult { boxes, size })
+    }
+}
+
+impl BoxPacker for StackingPacker {
+    /// Packs the boxes as weightless boxes that may be turned to any side,
+    /// each supported on `DEFAULT_MIN_SUPPORT` of its footprint.
+    fn pack(sizes: &[Size3], config: &BoxPackerConfig) -> Result<BoxPackingResult, BoxPackingError> {
+        Self::pack_with_loads(sizes, &vec![BoxLoad::default(); sizes.len()], Self::DEFAULT_MIN_SUPPORT, config)
+    }
+}
+
+/// Returns the distinct orientations of a box, only turning it around the vertical axis if it must stay upright.
+fn orientations(size: &Size3, this_side_up: bool) -> Vec<Size3> {
+    let (w, h, d) = (size.width, size.height, size.depth);
+    let mut orientations = match this_side_up {
+        true => vec![Size3::new(w, h, d), Size3::new(d, h, w)],
+        false => vec![Size3::new(w, h, d), Size3::new(d, h, w), Size3::new(h, w, d), Size3::new(d, w, h), Size3::new(w, d, h), Size3::new(h, d, w)],
+    };
+    let mut seen = Vec::new();
+    orientations.retain(|o| match seen.contains(o) {
+        true => false,
+        false => {
+            seen.push(*o);
+            true
+        }
+    });
+    orientations
+}
+
+/// Returns the length of the overlap of two intervals.
+fn overlap(a: usize, a_length: usize, b: usize, b_length: usize) -> usize {
+    (a + a_length).min(b + b_length).saturating_sub(a.max(b))
+}
+
+/// Returns `true` if the supporters, and the boxes below them, can bear the additional weight.
+fn can_bear(shares: &[(usize, f64)], weight: f64, loads: &[BoxLoad], supporters: &[Vec<(usize, f64)>], borne: &[f64]) -> bool {
+    let mut borne = borne.to_vec();
+    distribute(shares, weight, supporters, &mut borne);
+    (0..loads.len()).all(|i| loads[i].max_load.is_none_or(|max_load| borne[i] <= max_load + 1e-9))
+}
+
+/// Adds the weight to the boxes bearing it, passing it down to the boxes they rest on.
+fn distribute(shares: &[(usize, f64)], weight: f64, supporters: &[Vec<(usize, f64)>], borne: &mut [f64]) {
+    for &(i, share) in shares {
+        borne[i] += weight * share;
+        distribute(&supporters[i], weight * share, supporters, borne);
+    }
+}
+
+/// Returns the smallest integer whose cube is at least `value`, computed without floats to be exact on all platforms.
+fn cube_root_ceil(value: usize) -> usize {
+    let (mut low, mut high) = (0, 1usize << (usize::BITS / 3 + 1));
+    while low < high {
+        let middle = low + (high - low) / 2;
+        match middle.saturating_mul(middle).saturating_mul(middle) >= value {
+            true => high = middle,
+            false => low = middle + 1,
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_intersects_works() {
+        let a = Box3::new(0, 0, 0, 2, 2, 2);
+
+        assert!(a.intersects(&Box3::new(1, 1, 1, 2, 2, 2)));
+        assert!(!a.intersects(&Box3::new(0, 0, 2, 2, 2, 2)));
+        assert!(!a.intersects(&Box3::new(0, 2, 0, 2, 2, 2)));
+        assert!(a.fits_in(&Size3::new(2, 2, 2)));
+        assert!(!a.fits_in(&Size3::new(2, 1, 2)));
+    }
+
+    #[test]
+    fn pack_works() {
+        let sizes: Vec<Size3> = (1..30).map(|i| Size3::new(i % 4 + 1, i % 5 + 2, i % 3 + 1)).collect();
+        let result = LayerBoxPacker::pack(&sizes, &BoxPackerConfig::default()).unwrap();
+
+        assert_eq!(result.boxes.len(), sizes.len());
+        for (b, size) in result.boxes.iter().zip(&sizes) {
+            assert_eq!(b.to_size(), *size);
+        }
+        assert!(result.is_valid());
+        assert!(result.volume_ratio() > 0.0 && result.volume_ratio() <= 1.0);
+    }
+
+    #[test]
+    fn pack_with_padding_and_max_size_works() {
+        let sizes = vec![Size3::new(2, 2, 2); 8];
+        let config = BoxPackerConfig { max_size: Some(Size3::new(8, 8, 8)), padding: 1 };
+        let result = LayerBoxPacker::pack(&sizes, &config).unwrap();
+
+        assert_eq!(result.size, Size3::new(8, 8, 8));
+        assert!(result.is_valid());
+        for (index, b) in result.boxes.iter().enumerate() {
+            let padded = Box3::new(b.x - 1, b.y - 1, b.z - 1, 4, 4, 4);
+            assert!(result.boxes.iter().enumerate().all(|(other, o)| other == index || !padded.intersects(o)));
+        }
+    }
+
+    #[test]
+    fn pack_handles_empty_inputs() {
+        let result = LayerBoxPacker::pack(&[], &BoxPackerConfig::default()).unwrap();
+
+        assert!(result.boxes.is_empty());
+        assert_eq!(result.size, Size3::new(0, 0, 0));
+        assert_eq!(result.volume_ratio(), 0.0);
+    }
+
+    #[test]
+    fn stacking_packer_works() {
+        let sizes: Vec<Size3> = (1..30).map(|i| Size3::new(i % 4 + 1, i % 5 + 2, i % 3 + 1)).collect();
+        let result = StackingPacker::pack(&sizes, &BoxPackerConfig::default()).unwrap();
+
+        assert!(result.is_valid());
+        for (b, size) in result.boxes.iter().zip(&sizes) {
+            assert_eq!(b.to_size().volume(), size.volume());
+        }
+    }
+
+    #[test]
+    fn stacking_packer_respects_this_side_up() {
+        let sizes = [Size3::new(1, 4, 1)];
+        let config = BoxPackerConfig { max_size: Some(Size3::new(4, 1, 1)), padding: 0 };
+        let result = StackingPacker::pack(&sizes, &config).unwrap();
+
+        assert_eq!(result.boxes[0], Box3::new(0, 0, 0, 4, 1, 1));
+
+        let upright = BoxLoad { this_side_up: true, ..Default::default() };
+        assert!(StackingPacker::pack_with_loads(&sizes, &[upright], 1.0, &config).is_err());
+    }
+
+    #[test]
+    fn stacking_packer_requires_support() {
+        let sizes = [Size3::new(2, 2, 2), Size3::new(4, 1, 4)];
+        let loads = [
+            BoxLoad { weight: 2.0, this_side_up: true, ..Default::default() },
+            BoxLoad { weight: 1.0, this_side_up: true, ..Default::default() },
+        ];
+        let config = BoxPackerConfig { max_size: Some(Size3::new(4, 4, 4)), padding: 0 };
+
+        assert!(StackingPacker::pack_with_loads(&sizes, &loads, 0.5, &config).is_err());
+        let result = StackingPacker::pack_with_loads(&sizes, &loads, 0.25, &config).unwrap();
+        assert_eq!(result.boxes[1], Box3::new(0, 2, 0, 4, 1, 4));
+    }
+
+    #[test]
+    fn stacking_packer_passes_loads_down() {
+        let sizes = [Size3::new(2, 1, 2); 3];
+        let loads = [
+            BoxLoad { weight: 3.0, max_load: Some(2.0), this_side_up: true },
+            BoxLoad { weight: 2.0, this_side_up: true, ..Default::default() },
+            BoxLoad { weight: 1.0, this_side_up: true, ..Default::default() },
+        ];
+        let config = BoxPackerConfig { max_size: Some(Size3::new(2, 3, 2)), padding: 0 };
+
+        // The bottom box could bear the second one, but not the third one on top of it.
+        assert!(StackingPacker::pack_with_loads(&sizes, &loads, 1.0, &config).is_err());
+        let loads = [BoxLoad { max_load: Some(3.0), ..loads[0] }, loads[1], loads[2]];
+        assert!(StackingPacker::pack_with_loads(&sizes, &loads, 1.0, &config).unwrap().is_valid());
+    }
+
+    #[test]
+    fn pack_fails_if_boxes_do_not_fit() {
+        let config = BoxPackerConfig { max_size: Some(Size3::new(4, 4, 4)), padding: 0 };
+
+        assert!(LayerBoxPacker::pack(&[Size3::new(5, 1, 1)], &config).is_err());
+        assert!(LayerBoxPacker::pack(&[Size3::new(2, 2, 2); 9], &config).is_err());
+        assert!(LayerBoxPacker::pack(&[Size3::new(2, 2, 2); 8], &config).unwrap().is_valid());
+    }
+
+    #[test]
+    fn cube_root_ceil_works() {
+        assert_eq!(cube_root_ceil(0), 0);
+        assert_eq!(cube_root_ceil(1), 1);
+        assert_eq!(cube_root_ceil(27), 3);
+        assert_eq!(cube_root_ceil(28), 4);
+        assert_eq!(cube_root_ceil(1_000_000_001), 1001);
+    }
+}
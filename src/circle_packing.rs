@@ -0,0 +1,285 @@
+use std::error::Error;
+use std::fmt;
+
+/// The tolerance for floating point comparisons of distances.
+const EPSILON: f64 = 1e-9;
+
+/// A circle in a 2D space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    /// The x coordinate of the center.
+    pub x: f64,
+
+    /// The y coordinate of the center.
+    pub y: f64,
+
+    /// The radius.
+    pub radius: f64,
+}
+
+impl Circle {
+    /// Creates a new circle.
+    pub fn new(x: f64, y: f64, radius: f64) -> Circle {
+        Circle { x, y, radius }
+    }
+
+    /// Returns `true` if the circle overlaps with another circle.
+    ///
+    /// Circles that only touch each other do not overlap.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::Circle;
+    ///
+    /// let a = Circle::new(0.0, 0.0, 1.0);
+    /// assert!(a.intersects(&Circle::new(1.5, 0.0, 1.0)));
+    /// assert!(!a.intersects(&Circle::new(2.0, 0.0, 1.0)));
+    /// ```
+    pub fn intersects(&self, other: &Circle) -> bool {
+        self.distance(other) < self.radius + other.radius - EPSILON
+    }
+
+    /// Returns the distance between the centers of the circles.
+    fn distance(&self, other: &Circle) -> f64 {
+        // `hypot` is not correctly rounded on all platforms, unlike `sqrt`.
+        let (dx, dy) = (self.x - other.x, self.y - other.y);
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+/// The configuration of the circle packer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CirclePackerConfig {
+    /// The width of the container.
+    pub width: f64,
+
+    /// The height of the container.
+    ///
+    /// If `None`, the container is as high as needed to fit all circles.
+    /// If `Some`, an error is returned if the circles cannot be packed within that height.
+    pub height: Option<f64>,
+
+    /// The minimum distance between two circles.
+    pub spacing: f64,
+}
+
+/// The result of packing circles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CirclePackingResult {
+    /// The packed circles, in the order of the radii.
+    pub circles: Vec<Circle>,
+
+    /// The height of the container.
+    pub height: f64,
+}
+
+impl CirclePackingResult {
+    /// Returns the ratio of the total area of the circles to the area of the container.
+    ///
+    /// A container without any area has a packing ratio of `0.0`.
+    pub fn packing_ratio(&self, config: &CirclePackerConfig) -> f64 {
+        let area = config.width * self.height;
+        match area > 0.0 {
+            true => self.circles.iter().map(|c| std::f64::consts::PI * c.radius * c.radius).sum::<f64>() / area,
+            false => 0.0,
+        }
+    }
+}
+
+/// The error returned if circles could not be packed.
+#[derive(Debug, Clone)]
+pub struct CirclePackingError {
+    /// The error message.
+    pub message: String,
+}
+
+impl fmt::Display for CirclePackingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for CirclePackingError {}
+
+/// Packs circles into a rectangular container, greedily placing each circle as high up and then as far left as possible.
+///
+/// The circles are placed from the largest to the smallest. The candidate positions for a circle are
+/// the positions where it touches two of the top, left and right walls and the circles placed so far,
+/// of which the highest one not overlapping any placed circle is chosen, measured with y pointing down.
+/// This takes cubic time in the number of circles, so it is meant for layouts of up to a few hundred badges or tokens.
+///
+/// # Arguments
+/// * `radii` - The radius of every circle.
+/// * `config` - The configuration of the packer.
+///
+/// # Returns
+/// A `Result` containing either:
+///   - A `CirclePackingResult` with the packed circles, in the order of the radii, and the height of the container.
+///   - A `CirclePackingError` if a circle is wider than the container or the circles do not fit within its height.
+///
+/// # Examples
+/// ```
+/// use rpack::{pack_circles, CirclePackerConfig};
+///
+/// let config = CirclePackerConfig { width: 4.0, ..Default::default() };
+/// let result = pack_circles(&[1.0, 1.0, 1.0], &config).unwrap();
+///
+/// assert_eq!((result.circles[0].x, result.circles[0].y), (1.0, 1.0));
+/// assert_eq!((result.circles[1].x, result.circles[1].y), (3.0, 1.0));
+/// // The third circle rests in the gap between the first two.
+/// assert_eq!(result.circles[2].x, 2.0);
+/// assert!((result.height - (2.0 + 3.0f64.sqrt())).abs() < 1e-9);
+/// ```
+pub fn pack_circles(radii: &[f64], config: &CirclePackerConfig) -> Result<CirclePackingResult, CirclePackingError> {
+    if let Some(index) = radii.iter().position(|&r| !(r >= 0.0 && 2.0 * r <= config.width + EPSILON)) {
+        return Err(CirclePackingError {
+            message: format!("Circle {} with radius {} does not fit in width {}", index, radii[index], config.width),
+        });
+    }
+
+    let mut order: Vec<usize> = (0..radii.len()).collect();
+    order.sort_by(|&a, &b| radii[b].total_cmp(&radii[a]));
+
+    let mut placed: Vec<Circle> = Vec::with_capacity(radii.len());
+    let mut circles = vec![Circle::new(0.0, 0.0, 0.0); radii.len()];
+    for index in order {
+        let radius = radii[index];
+        let fits = |c: &Circle| {
+            c.x >= radius - EPSILON
+                && c.x <= config.width - radius + EPSILON
+                && c.y >= radius - EPSILON
+                && config.height.is_none_or(|height| c.y <= height - radius + EPSILON)
+                && placed.iter().all(|p| c.distance(p) >= radius + p.radius + config.spacing - EPSILON)
+        };
+
+        let circle = candidates(radius, &placed, config)
+            .into_iter()
+            .filter(fits)
+            .min_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+            .ok_or_else(|| CirclePackingError { message: "Could not fit all circles in the container".to_string() })?;
+
+        placed.push(circle);
+        circles[index] = circle;
+    }
+
+    let height = match config.height {
+        Some(height) => height,
+        None => circles.iter().map(|c| c.y + c.radius).fold(0.0, f64::max),
+    };
+
+    Ok(CirclePackingResult { circles, height })
+}
+
+/// Returns the positions where a circle with the given radius touches two of the walls and the placed circles.
+fn candidates(radius: f64, placed: &[Circle], config: &CirclePackerConfig) -> Vec<Circle> {
+    let left = radius;
+    let right = config.width - radius;
+    let top = radius;
+    let mut candidates = vec![Circle::new(left, top, radius), Circle::new(right, top, radius)];
+
+    // Below all placed circles along the left wall, which always fits without a max height.
+    let bottom = placed.iter().map(|p| p.y + p.radius).fold(0.0, f64::max);
+    candidates.push(Circle::new(left, bottom + config.spacing + radius, radius));
+
+    for (index, p) in placed.iter().enumerate() {
+        let distance = radius + p.radius + config.spacing;
+
+        // Touching a vertical wall and the placed circle.
+        for x in [left, right] {
+            let dy = (distance * distance - (x - p.x) * (x - p.x)).sqrt();
+            if dy.is_finite() {
+                candidates.push(Circle::new(x, p.y + dy, radius));
+                candidates.push(Circle::new(x, p.y - dy, radius));
+            }
+        }
+
+        // Touching the top wall and the placed circle.
+        let dx = (distance * distance - (top - p.y) * (top - p.y)).sqrt();
+        if dx.is_finite() {
+            candidates.push(Circle::new(p.x + dx, top, radius));
+            candidates.push(Circle::new(p.x - dx, top, radius));
+        }
+
+        // Touching two placed circles.
+        for q in &placed[index + 1..] {
+            let other_distance = radius + q.radius + config.spacing;
+            candidates.extend(intersections(p, distance, q, other_distance).map(|(x, y)| Circle::new(x, y, radius)));
+        }
+    }
+
+    candidates
+}
+
+/// Returns the intersections of the circles with the given radii around the centers of `a` and `b`.
+fn intersections(a: &Circle, ra: f64, b: &Circle, rb: f64) -> impl Iterator<Item = (f64, f64)> {
+    let d = a.distance(b);
+    let along = (d * d + ra * ra - rb * rb) / (2.0 * d);
+    let across = (ra * ra - along * along).sqrt();
+    let (ux, uy) = ((b.x - a.x) / d, (b.y - a.y) / d);
+    let (mx, my) = (a.x + along * ux, a.y + along * uy);
+
+    [(mx - across * uy, my + across * ux), (mx + across * uy, my - across * ux)]
+        .into_iter()
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_valid(result: &CirclePackingResult, config: &CirclePackerConfig) {
+        for (index, c) in result.circles.iter().enumerate() {
+            assert!(c.x >= c.radius - EPSILON && c.x <= config.width - c.radius + EPSILON);
+            assert!(c.y >= c.radius - EPSILON && c.y <= result.height - c.radius + EPSILON);
+            for other in &result.circles[index + 1..] {
+                assert!(c.distance(other) >= c.radius + other.radius + config.spacing - 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn pack_circles_works() {
+        let radii: Vec<f64> = (0..40).map(|i| 1.0 + (i % 5) as f64 * 0.5).collect();
+        let config = CirclePackerConfig { width: 20.0, height: None, spacing: 0.25 };
+        let result = pack_circles(&radii, &config).unwrap();
+
+        assert_eq!(result.circles.len(), radii.len());
+        for (circle, radius) in result.circles.iter().zip(&radii) {
+            assert_eq!(circle.radius, *radius);
+        }
+        assert_valid(&result, &config);
+        assert!(result.packing_ratio(&config) > 0.5);
+    }
+
+    #[test]
+    fn pack_circles_uses_hexagonal_gaps() {
+        let config = CirclePackerConfig { width: 4.0, height: None, spacing: 0.0 };
+        let result = pack_circles(&[1.0; 3], &config).unwrap();
+
+        // The third circle drops into the gap between the first two.
+        assert!((result.circles[2].x - 2.0).abs() < 1e-9);
+        assert!(result.height < 4.0);
+        assert_valid(&result, &config);
+    }
+
+    #[test]
+    fn pack_circles_handles_empty_inputs() {
+        let config = CirclePackerConfig { width: 5.0, ..Default::default() };
+        let result = pack_circles(&[], &config).unwrap();
+
+        assert!(result.circles.is_empty());
+        assert_eq!(result.height, 0.0);
+        assert_eq!(result.packing_ratio(&config), 0.0);
+    }
+
+    #[test]
+    fn pack_circles_fails_if_circles_do_not_fit() {
+        let config = CirclePackerConfig { width: 4.0, height: Some(4.0), spacing: 0.0 };
+
+        assert!(pack_circles(&[2.5], &config).is_err());
+        assert!(pack_circles(&[-1.0], &config).is_err());
+        assert!(pack_circles(&[1.0; 3], &config).is_ok());
+        // The greedy placement puts the third circle into the gap, leaving no room for a fourth one.
+        assert_eq!(pack_circles(&[1.0; 4], &config).unwrap_err().message, "Could not fit all circles in the container");
+    }
+}
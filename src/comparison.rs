@@ -0,0 +1,189 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use crate::{Size, RectanglePackingResult, RectanglePackingError, RectanglePackerConfig};
+
+/// A function that packs a list of rectangle sizes, such as `HeightRectPacker::pack`.
+pub type PackFn = fn(&[Size], &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError>;
+
+/// The outcome of running a single packer as part of a comparison.
+#[derive(Debug)]
+pub struct ComparisonEntry {
+    /// The name the packer was registered with.
+    pub name: String,
+
+    /// The result returned by the packer.
+    pub result: Result<RectanglePackingResult, RectanglePackingError>,
+
+    /// The wall-clock time the packer took.
+    pub runtime: Duration,
+}
+
+impl ComparisonEntry {
+    /// Returns the packing ratio of the result, or `None` if the packer failed.
+    pub fn packing_ratio(&self) -> Option<f64> {
+        self.result.as_ref().ok().map(|r| r.packing_ratio())
+    }
+
+    /// Returns the size of the container, or `None` if the packer failed.
+    pub fn size(&self) -> Option<Size> {
+        self.result.as_ref().ok().map(|r| r.size)
+    }
+
+    /// Returns the height of the container, or `None` if the packer failed.
+    pub fn height(&self) -> Option<usize> {
+        self.size().map(|s| s.height)
+    }
+}
+
+/// A report comparing several packers on the same input.
+#[derive(Debug)]
+pub struct ComparisonReport {
+    /// One entry per packer, in the order the packers were given.
+    pub entries: Vec<ComparisonEntry>,
+}
+
+impl ComparisonReport {
+    /// Returns the successful entry with the highest packing ratio.
+    ///
+    /// If several entries share the highest packing ratio, the first of them is returned.
+    pub fn best(&self) -> Option<&ComparisonEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.result.is_ok())
+            .fold(None, |best: Option<&ComparisonEntry>, entry| match best {
+                Some(best) if best.packing_ratio() >= entry.packing_ratio() => Some(best),
+                _ => Some(entry),
+            })
+    }
+
+    /// Returns the entry for the packer with the given name.
+    pub fn get(&self, name: &str) -> Option<&ComparisonEntry> {
+        self.entries.iter().find(|e| e.name == name)
+    }
+}
+
+impl fmt::Display for ComparisonReport {
+    /// Formats the report as a plain text table with one row per packer.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name_width = self.entries.iter().map(|e| e.name.len()).max().unwrap_or(0).max(4);
+        writeln!(f, "{:<name_width$}  {:>8}  {:>8}  {:>8}  {:>12}", "name", "ratio", "width", "height", "runtime")?;
+        for entry in &self.entries {
+            match &entry.result {
+                Ok(result) => writeln!(
+                    f,
+                    "{:<name_width$}  {:>8.4}  {:>8}  {:>8}  {:>12?}",
+                    entry.name,
+                    result.packing_ratio(),
+                    result.size.width,
+                    result.size.height,
+                    entry.runtime
+                )?,
+                Err(error) => writeln!(f, "{:<name_width$}  failed: {}", entry.name, error)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs several packers on the same input and reports how each of them performed.
+///
+/// With the `rayon` feature, the packers run in parallel on the rayon thread pool,
+/// so the runtimes of the entries may be affected by the other packers running at the same time.
+///
+/// # Arguments
+/// * `sizes` - A list of `Size` structs representing the dimensions of the rectangles to be packed.
+/// * `packers` - A list of packers to compare, each given by a name and its pack function.
+/// * `config` - The config every packer is run with.
+///
+/// # Returns
+/// A `ComparisonReport` with one entry per packer, in the order they were given.
+///
+/// # Examples
+/// ```
+/// use rpack::{compare, HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+///
+/// let sizes = vec![Size::new(10, 20), Size::new(30, 5), Size::new(8, 8)];
+/// let report = compare(&sizes, &[("height", HeightRectPacker::pack)], &RectanglePackerConfig::default());
+///
+/// let entry = report.get("height").unwrap();
+/// assert!(entry.packing_ratio().unwrap() > 0.0);
+/// println!("{}", report);
+/// ```
+pub fn compare(sizes: &[Size], packers: &[(&str, PackFn)], config: &RectanglePackerConfig) -> ComparisonReport {
+    #[cfg(feature = "rayon")]
+    let packers = packers.par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let packers = packers.iter();
+
+    let entries = packers
+        .map(|(name, pack)| {
+            let start = Instant::now();
+            let result = pack(sizes, config);
+            ComparisonEntry {
+                name: name.to_string(),
+                result,
+                runtime: start.elapsed(),
+            }
+        })
+        .collect();
+
+    ComparisonReport { entries }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeightRectPacker, Rectangle, RectanglePacker};
+
+    use super::*;
+
+    fn single_row(sizes: &[Size], _config: &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError> {
+        let mut x = 0;
+        let rectangles = sizes
+            .iter()
+            .map(|size| {
+                let rectangle = Rectangle::from_size(x, 0, size);
+                x += size.width;
+                rectangle
+            })
+            .collect();
+        let height = sizes.iter().map(|s| s.height).max().unwrap_or(0);
+        Ok(RectanglePackingResult { rectangles, size: Size::new(x, height), skipped: Vec::new() })
+    }
+
+    fn failing(_sizes: &[Size], _config: &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError> {
+        Err(RectanglePackingError {
+            message: "failed".to_string(),
+            config_error: None,
+            result: RectanglePackingResult { rectangles: Vec::new(), size: Size::new(0, 0), skipped: Vec::new() },
+        })
+    }
+
+    #[test]
+    fn compare_works() {
+        let sizes = vec![Size::new(10, 10), Size::new(10, 5)];
+        let report = compare(
+            &sizes,
+            &[("height", HeightRectPacker::pack), ("row", single_row), ("failing", failing)],
+            &RectanglePackerConfig::default(),
+        );
+
+        assert_eq!(report.entries.len(), 3);
+        assert_eq!(report.entries[0].name, "height");
+        assert_eq!(report.get("row").unwrap().size(), Some(Size::new(20, 10)));
+        assert_eq!(report.get("row").unwrap().packing_ratio(), Some(0.75));
+        assert!(report.get("failing").unwrap().packing_ratio().is_none());
+        assert!(report.get("missing").is_none());
+    }
+
+    #[test]
+    fn best_works() {
+        let sizes = vec![Size::new(10, 10), Size::new(10, 10)];
+        let report = compare(&sizes, &[("failing", failing), ("row", single_row)], &RectanglePackerConfig::default());
+        assert_eq!(report.best().unwrap().name, "row");
+
+        let report = compare(&sizes, &[("failing", failing)], &RectanglePackerConfig::default());
+        assert!(report.best().is_none());
+    }
+}
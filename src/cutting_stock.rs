@@ -0,0 +1,388 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Area, Rectangle, Size};
+
+/// The configuration of a cutting plan.
+#[derive(Debug, Clone, Copy)]
+pub struct CuttingStockConfig {
+    /// The size of every sheet of stock.
+    pub sheet: Size,
+
+    /// The width of material consumed by every cut, e.g. the width of the saw blade.
+    ///
+    /// Unlike padding, which surrounds every rectangle, the kerf is taken once per cut line.
+    pub kerf: usize,
+
+    /// Whether items without a grain may be rotated by 90 degrees.
+    pub allow_rotation: bool,
+
+    /// The direction of the grain of the sheets, e.g. of wood or fabric.
+    ///
+    /// If `Some`, items with a grain are rotated so that their grain runs along it.
+    /// If `None`, items with a grain are never rotated.
+    pub grain: Option<GrainAxis>,
+}
+
+impl CuttingStockConfig {
+    /// Creates a new configuration without rotation or grain.
+    pub fn new(sheet: Size, kerf: usize) -> CuttingStockConfig {
+        CuttingStockConfig { sheet, kerf, allow_rotation: false, grain: None }
+    }
+}
+
+/// The axis along which the grain of a sheet runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrainAxis {
+    /// The grain runs along the x axis.
+    Horizontal,
+
+    /// The grain runs along the y axis.
+    Vertical,
+}
+
+/// The grain of an item, which restricts how it may be rotated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Grain {
+    /// The item has no grain and may be rotated if the configuration allows it.
+    #[default]
+    None,
+
+    /// The grain runs along the width of the item.
+    AlongWidth,
+
+    /// The grain runs along the height of the item.
+    AlongHeight,
+}
+
+/// The direction of a cut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CutDirection {
+    /// A cut along the x axis, separating a piece above from a piece below.
+    Horizontal,
+
+    /// A cut along the y axis, separating a piece on the left from a piece on the right.
+    Vertical,
+}
+
+/// A straight cut through a piece of a sheet, from one edge of the piece to the opposite one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cut {
+    /// The index of the sheet the cut is made on.
+    pub sheet: usize,
+
+    /// The direction of the cut.
+    pub direction: CutDirection,
+
+    /// The region consumed by the kerf of the cut.
+    ///
+    /// A horizontal cut runs along its width and a vertical cut along its height.
+    pub kerf: Rectangle,
+}
+
+/// The placement of an item on a sheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SheetPlacement {
+    /// The index of the sheet the item is cut from.
+    pub sheet: usize,
+
+    /// The rectangle of the item on the sheet.
+    pub rectangle: Rectangle,
+
+    /// Whether the item was rotated by 90 degrees, swapping its width and height.
+    pub rotated: bool,
+}
+
+/// A plan for cutting items from sheets of stock with guillotine cuts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CuttingPlan {
+    /// The placement of every item, in the order of the sizes.
+    pub placements: Vec<SheetPlacement>,
+
+    /// The cuts in the order they can be made, each one through a piece left over by the cuts before it.
+    pub cuts: Vec<Cut>,
+
+    /// The number of sheets used.
+    pub sheet_count: usize,
+}
+
+impl CuttingPlan {
+    /// Returns the cuts made on the given sheet, in the order they can be made.
+    pub fn cuts_on(&self, sheet: usize) -> Vec<Cut> {
+        self.cuts.iter().filter(|cut| cut.sheet == sheet).copied().collect()
+    }
+}
+
+/// The error returned if a cutting plan could not be made.
+#[derive(Debug, Clone)]
+pub struct CuttingStockError {
+    /// The error message.
+    pub message: String,
+}
+
+impl fmt::Display for CuttingStockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for CuttingStockError {}
+
+/// Plans cutting items from as few sheets of stock as possible, using only guillotine cuts as made by a panel saw.
+///
+/// The items are placed from the largest to the smallest into the top-left corner of the smallest leftover piece
+/// they fit in, opening a new sheet if there is none. The piece is then cut twice, first along the axis that leaves
+/// the larger leftover piece spanning the whole piece, and each cut consumes the kerf. Items are only rotated
+/// if the configuration allows it.
+///
+/// # Arguments
+/// * `sizes` - The size of every item.
+/// * `config` - The configuration of the plan.
+///
+/// # Returns
+/// A `Result` containing either:
+///   - A `CuttingPlan` with the placement of every item, in the order of the sizes, and the sequence of cuts.
+///   - A `CuttingStockError` if an item is larger than a sheet.
+///
+/// # Examples
+/// ```
+/// use rpack::{plan_cuts, CuttingStockConfig, Rectangle, Size};
+///
+/// let config = CuttingStockConfig::new(Size::new(10, 4), 1);
+/// let plan = plan_cuts(&[Size::new(4, 4), Size::new(5, 4)], &config).unwrap();
+///
+/// // A single cut separates both items, consuming the column at x = 5.
+/// assert_eq!(plan.placements[1].rectangle, Rectangle::new(0, 0, 5, 4));
+/// assert_eq!(plan.placements[0].rectangle, Rectangle::new(6, 0, 4, 4));
+/// assert_eq!(plan.cuts.len(), 1);
+/// ```
+pub fn plan_cuts(sizes: &[Size], config: &CuttingStockConfig) -> Result<CuttingPlan, CuttingStockError> {
+    plan_cuts_with_grain(sizes, &vec![Grain::None; sizes.len()], config)
+}
+
+/// Plans cutting items with a grain from as few sheets of stock as possible, using only guillotine cuts.
+///
+/// Works like [`plan_cuts`], but items with a grain are never rotated freely, even if the configuration
+/// allows rotation. If the sheets have a grain, such items are placed so that their grain runs along it,
+/// rotating them if necessary. Otherwise, they are placed as given.
+///
+/// # Arguments
+/// * `sizes` - The size of every item.
+/// * `grains` - The grain of every item, in the order of the sizes.
+/// * `config` - The configuration of the plan.
+///
+/// # Returns
+/// A `Result` containing either:
+///   - A `CuttingPlan` with the placement of every item, in the order of the sizes, and the sequence of cuts.
+///   - A `CuttingStockError` if an item is larger than a sheet in every orientation it may be placed in.
+///
+/// # Panics
+/// Panics if the number of grains differs from the number of sizes.
+///
+/// # Examples
+/// ```
+/// use rpack::{plan_cuts_with_grain, CuttingStockConfig, Grain, GrainAxis, Size};
+///
+/// let config = CuttingStockConfig { grain: Some(GrainAxis::Vertical), ..CuttingStockConfig::new(Size::new(10, 10), 0) };
+/// let plan = plan_cuts_with_grain(&[Size::new(6, 2)], &[Grain::AlongWidth], &config).unwrap();
+///
+/// // The grain along the width of the item has to run along the vertical grain of the sheet.
+/// assert!(plan.placements[0].rotated);
+/// assert_eq!(plan.placements[0].rectangle.to_size(), Size::new(2, 6));
+/// ```
+pub fn plan_cuts_with_grain(sizes: &[Size], grains: &[Grain], config: &CuttingStockConfig) -> Result<CuttingPlan, CuttingStockError> {
+    assert_eq!(sizes.len(), grains.len(), "Expected one grain per size");
+
+    let orientations: Vec<Vec<(Size, bool)>> = sizes
+        .iter()
+        .zip(grains)
+        .map(|(&size, &grain)| {
+            orientations(size, grain, config)
+                .into_iter()
+                .filter(|(s, _)| s.width <= config.sheet.width && s.height <= config.sheet.height)
+                .collect()
+        })
+        .collect();
+
+    if let Some(index) = orientations.iter().position(|o| o.is_empty()) {
+        return Err(CuttingStockError {
+            message: format!(
+                "Item {} of size {}x{} does not fit on a sheet of size {}x{}",
+                index, sizes[index].width, sizes[index].height, config.sheet.width, config.sheet.height
+            ),
+        });
+    }
+
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|&a, &b| sizes[b].area().cmp(&sizes[a].area()).then(sizes[b].cmp(&sizes[a])));
+
+    let mut plan = CuttingPlan {
+        placements: vec![SheetPlacement { sheet: 0, rectangle: Rectangle::new(0, 0, 0, 0), rotated: false }; sizes.len()],
+        cuts: Vec::new(),
+        sheet_count: 0,
+    };
+
+    // The leftover pieces of all sheets, with the index of their sheet.
+    let mut pieces: Vec<(usize, Rectangle)> = Vec::new();
+    for index in order {
+        // The smallest piece the item fits in, preferring to keep the item unrotated.
+        let best = orientations[index]
+            .iter()
+            .flat_map(|&(size, rotated)| (0..pieces.len()).map(move |i| (i, size, rotated)))
+            .filter(|&(i, size, _)| pieces[i].1.width >= size.width && pieces[i].1.height >= size.height)
+            .min_by_key(|&(i, _, rotated)| (pieces[i].1.area(), rotated));
+        let (sheet, piece, size, rotated) = match best {
+            Some((i, size, rotated)) => {
+                let (sheet, piece) = pieces.swap_remove(i);
+                (sheet, piece, size, rotated)
+            }
+            None => {
+                plan.sheet_count += 1;
+                let (size, rotated) = orientations[index][0];
+                (plan.sheet_count - 1, Rectangle::from_size(0, 0, &config.sheet), size, rotated)
+            }
+        };
+
+        let (cuts, leftovers) = split(&piece, &size, config.kerf);
+        plan.cuts.extend(cuts.into_iter().map(|(direction, kerf)| Cut { sheet, direction, kerf }));
+        pieces.extend(leftovers.into_iter().map(|leftover| (sheet, leftover)));
+        plan.placements[index] = SheetPlacement { sheet, rectangle: Rectangle::from_size(piece.x, piece.y, &size), rotated };
+    }
+
+    Ok(plan)
+}
+
+/// Returns the orientations an item may be placed in, with whether it is rotated, unrotated first.
+fn orientations(size: Size, grain: Grain, config: &CuttingStockConfig) -> Vec<(Size, bool)> {
+    let unrotated = (size, false);
+    let rotated = (Size::new(size.height, size.width), true);
+    match (grain, config.grain) {
+        (Grain::None, _) if config.allow_rotation && size.width != size.height => vec![unrotated, rotated],
+        (Grain::None, _) | (_, None) => vec![unrotated],
+        (Grain::AlongWidth, Some(GrainAxis::Horizontal)) | (Grain::AlongHeight, Some(GrainAxis::Vertical)) => vec![unrotated],
+        _ => vec![rotated],
+    }
+}
+
+/// Cuts an item of the given size from the top-left corner of the piece.
+///
+/// Returns the cuts in the order they are made and the leftover pieces that are not empty.
+fn split(piece: &Rectangle, size: &Size, kerf: usize) -> (Vec<(CutDirection, Rectangle)>, Vec<Rectangle>) {
+    let leftover_width = piece.width - size.width;
+    let leftover_height = piece.height - size.height;
+
+    // Returns the kerf of a horizontal cut below the item, running for the given width, and the leftover below it.
+    let horizontal = |width: usize| {
+        let kerf = Rectangle::new(piece.x, piece.y + size.height, width, kerf.min(leftover_height));
+        (kerf, Rectangle::new(piece.x, kerf.y + kerf.height, width, leftover_height - kerf.height))
+    };
+
+    // Returns the kerf of a vertical cut right of the item, running for the given height, and the leftover right of it.
+    let vertical = |height: usize| {
+        let kerf = Rectangle::new(piece.x + size.width, piece.y, kerf.min(leftover_width), height);
+        (kerf, Rectangle::new(kerf.x + kerf.width, piece.y, leftover_width - kerf.width, height))
+    };
+
+    // The first cut runs through the whole piece, so making it along the larger leftover keeps that leftover whole.
+    let cuts = match leftover_height >= leftover_width {
+        true => [
+            (leftover_height > 0).then(|| (CutDirection::Horizontal, horizontal(piece.width))),
+            (leftover_width > 0).then(|| (CutDirection::Vertical, vertical(size.height))),
+        ],
+        false => [
+            (leftover_width > 0).then(|| (CutDirection::Vertical, vertical(piece.height))),
+            (leftover_height > 0).then(|| (CutDirection::Horizontal, horizontal(size.width))),
+        ],
+    };
+
+    let cuts: Vec<(CutDirection, (Rectangle, Rectangle))> = cuts.into_iter().flatten().collect();
+    let leftovers = cuts.iter().map(|(_, (_, leftover))| *leftover).filter(|l| l.width > 0 && l.height > 0).collect();
+    (cuts.into_iter().map(|(direction, (kerf, _))| (direction, kerf)).collect(), leftovers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_cuts_works() {
+        let config = CuttingStockConfig::new(Size::new(10, 10), 1);
+        let plan = plan_cuts(&[Size::new(4, 4); 4], &config).unwrap();
+
+        assert_eq!(plan.sheet_count, 1);
+        let rectangles: Vec<Rectangle> = plan.placements.iter().map(|p| p.rectangle).collect();
+        assert_eq!(
+            rectangles,
+            vec![Rectangle::new(0, 0, 4, 4), Rectangle::new(5, 0, 4, 4), Rectangle::new(0, 5, 4, 4), Rectangle::new(5, 5, 4, 4)]
+        );
+        assert_eq!(plan.cuts[0], Cut { sheet: 0, direction: CutDirection::Horizontal, kerf: Rectangle::new(0, 4, 10, 1) });
+        assert_eq!(plan.cuts[1], Cut { sheet: 0, direction: CutDirection::Vertical, kerf: Rectangle::new(4, 0, 1, 4) });
+        assert_eq!(plan.cuts.len(), 7);
+    }
+
+    #[test]
+    fn kerf_is_never_cut_into_items() {
+        let sizes: Vec<Size> = (0..30).map(|i| Size::new(3 + i % 7, 2 + i % 5)).collect();
+        let config = CuttingStockConfig::new(Size::new(20, 12), 2);
+        let plan = plan_cuts(&sizes, &config).unwrap();
+
+        let sheet = Rectangle::from_size(0, 0, &config.sheet);
+        for (i, a) in plan.placements.iter().enumerate() {
+            assert!(sheet.contains(&a.rectangle));
+            for b in &plan.placements[i + 1..] {
+                assert!(a.sheet != b.sheet || !a.rectangle.intersects(&b.rectangle));
+            }
+            for cut in plan.cuts_on(a.sheet) {
+                assert!(!cut.kerf.intersects(&a.rectangle));
+            }
+        }
+    }
+
+    #[test]
+    fn kerf_is_clamped_at_the_edge() {
+        let config = CuttingStockConfig::new(Size::new(5, 1), 3);
+        let plan = plan_cuts(&[Size::new(3, 1), Size::new(1, 1)], &config).unwrap();
+
+        // The kerf consumes the rest of the first sheet, so the second item is cut from a new one.
+        assert_eq!(plan.cuts[0], Cut { sheet: 0, direction: CutDirection::Vertical, kerf: Rectangle::new(3, 0, 2, 1) });
+        assert_eq!(plan.placements[1].sheet, 1);
+        assert_eq!(plan.sheet_count, 2);
+    }
+
+    #[test]
+    fn rotation_works() {
+        let config = CuttingStockConfig { allow_rotation: true, ..CuttingStockConfig::new(Size::new(10, 4), 0) };
+        let plan = plan_cuts(&[Size::new(10, 2), Size::new(2, 10)], &config).unwrap();
+
+        assert_eq!(plan.sheet_count, 1);
+        assert!(!plan.placements[0].rotated);
+        assert!(plan.placements[1].rotated);
+        assert_eq!(plan.placements[1].rectangle.to_size(), Size::new(10, 2));
+    }
+
+    #[test]
+    fn grain_restricts_rotation() {
+        let sizes = [Size::new(2, 10), Size::new(2, 10)];
+        let grains = [Grain::None, Grain::AlongHeight];
+        let config = CuttingStockConfig { allow_rotation: true, ..CuttingStockConfig::new(Size::new(10, 4), 0) };
+
+        // Without a grain of the sheet, the item with a grain is kept as given, which does not fit.
+        assert!(plan_cuts_with_grain(&sizes, &grains, &config).is_err());
+
+        let config = CuttingStockConfig { grain: Some(GrainAxis::Horizontal), ..config };
+        let plan = plan_cuts_with_grain(&sizes, &grains, &config).unwrap();
+        assert!(plan.placements.iter().all(|p| p.rotated));
+        assert_eq!(plan.sheet_count, 1);
+
+        let config = CuttingStockConfig { grain: Some(GrainAxis::Vertical), ..config };
+        assert!(plan_cuts_with_grain(&sizes, &grains, &config).is_err());
+    }
+
+    #[test]
+    fn plan_cuts_fails_for_too_large_items() {
+        let config = CuttingStockConfig::new(Size::new(10, 10), 1);
+        let error = plan_cuts(&[Size::new(4, 4), Size::new(4, 11)], &config).unwrap_err();
+
+        assert_eq!(error.message, "Item 1 of size 4x11 does not fit on a sheet of size 10x10");
+    }
+}
@@ -0,0 +1,180 @@
+//! Generators for synthetic packing instances.
+//!
+//! All generators are deterministic: the same arguments and seed always produce the same sizes,
+//! so that algorithms can be compared and benchmarked on reproducible workloads.
+
+use crate::Size;
+
+/// A SplitMix64 pseudo random number generator, which is small, fast and good enough for generating instances.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0.0..1.0`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a number in `min..=max`.
+    fn range(&mut self, min: usize, max: usize) -> usize {
+        min + (self.next_u64() % (max - min + 1) as u64) as usize
+    }
+}
+
+/// Generates sizes whose widths and heights are uniformly distributed between `min` and `max`, inclusively.
+///
+/// # Panics
+/// Panics if `min` is larger than `max` in either dimension.
+///
+/// # Examples
+/// ```
+/// use rpack::datasets;
+/// use rpack::Size;
+///
+/// let sizes = datasets::uniform(100, Size::new(4, 4), Size::new(32, 16), 42);
+/// assert_eq!(sizes.len(), 100);
+/// assert_eq!(sizes, datasets::uniform(100, Size::new(4, 4), Size::new(32, 16), 42));
+/// ```
+pub fn uniform(count: usize, min: Size, max: Size, seed: u64) -> Vec<Size> {
+    assert!(min.width <= max.width && min.height <= max.height, "min must not be larger than max");
+    let mut rng = Rng::new(seed);
+    (0..count)
+        .map(|_| Size::new(rng.range(min.width, max.width), rng.range(min.height, max.height)))
+        .collect()
+}
+
+/// Generates sizes between `min` and `max` that are mostly small with a few large ones,
+/// like the sprites and icons of a typical game.
+///
+/// The width and height are drawn independently, each with a cubed uniform distribution.
+///
+/// # Panics
+/// Panics if `min` is larger than `max` in either dimension.
+pub fn skewed(count: usize, min: Size, max: Size, seed: u64) -> Vec<Size> {
+    assert!(min.width <= max.width && min.height <= max.height, "min must not be larger than max");
+    let mut rng = Rng::new(seed);
+    // The cube is multiplied out, as the precision of `powi` differs between platforms.
+    let mut skewed = |min: usize, max: usize| {
+        let uniform = rng.next_f64();
+        min + ((max - min) as f64 * (uniform * uniform * uniform)).round() as usize
+    };
+    (0..count)
+        .map(|_| {
+            let width = skewed(min.width, max.width);
+            Size::new(width, skewed(min.height, max.height))
+        })
+        .collect()
+}
+
+/// Generates sizes by cutting a container into `count` pieces with random guillotine cuts.
+///
+/// The pieces fill the container exactly, so a perfect packing into a container of the given size is known to exist.
+/// Pieces smaller than `2 x 2` are not cut any further, so fewer pieces are returned if the container is too small.
+///
+/// # Examples
+/// ```
+/// use rpack::datasets;
+/// use rpack::Size;
+///
+/// let sizes = datasets::guillotine(Size::new(64, 48), 20, 7);
+/// assert_eq!(sizes.len(), 20);
+/// assert_eq!(sizes.iter().map(|s| s.area()).sum::<usize>(), 64 * 48);
+/// ```
+pub fn guillotine(container: Size, count: usize, seed: u64) -> Vec<Size> {
+    let mut rng = Rng::new(seed);
+    let mut pieces = vec![container];
+    while pieces.len() < count {
+        // Cut the largest piece that can still be cut, to keep the pieces of similar size.
+        let Some(index) = (0..pieces.len())
+            .filter(|&i| pieces[i].width >= 2 || pieces[i].height >= 2)
+            .max_by_key(|&i| (pieces[i].width * pieces[i].height, usize::MAX - i))
+        else {
+            break;
+        };
+
+        let piece = pieces[index];
+        let vertical = match (piece.width >= 2, piece.height >= 2) {
+            (true, true) => piece.width >= piece.height,
+            (width, _) => width,
+        };
+        if vertical {
+            let cut = rng.range(1, piece.width - 1);
+            pieces[index] = Size::new(cut, piece.height);
+            pieces.push(Size::new(piece.width - cut, piece.height));
+        } else {
+            let cut = rng.range(1, piece.height - 1);
+            pieces[index] = Size::new(piece.width, cut);
+            pieces.push(Size::new(piece.width, piece.height - cut));
+        }
+    }
+    pieces
+}
+
+/// Generates roughly square sizes, with sides between `min_side` and `max_side` and an aspect ratio of at most `1.25`.
+///
+/// # Panics
+/// Panics if `min_side` is larger than `max_side` or `0`.
+pub fn near_square(count: usize, min_side: usize, max_side: usize, seed: u64) -> Vec<Size> {
+    assert!(0 < min_side && min_side <= max_side, "min_side must be positive and not larger than max_side");
+    let mut rng = Rng::new(seed);
+    (0..count)
+        .map(|_| {
+            let width = rng.range(min_side, max_side);
+            let min_height = min_side.max((width as f64 / 1.25).ceil() as usize);
+            let max_height = max_side.min((width as f64 * 1.25).floor() as usize);
+            Size::new(width, rng.range(min_height, max_height))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uniform_stays_in_bounds() {
+        let sizes = uniform(500, Size::new(3, 5), Size::new(9, 5), 1);
+
+        assert!(sizes.iter().all(|s| (3..=9).contains(&s.width) && s.height == 5));
+        assert_ne!(sizes, uniform(500, Size::new(3, 5), Size::new(9, 5), 2));
+    }
+
+    #[test]
+    fn skewed_is_mostly_small() {
+        let sizes = skewed(1000, Size::new(1, 1), Size::new(100, 100), 3);
+        let small = sizes.iter().filter(|s| s.width <= 50).count();
+
+        assert!(sizes.iter().all(|s| s.width <= 100 && s.height <= 100));
+        assert!(small > 700);
+    }
+
+    #[test]
+    fn guillotine_fills_the_container() {
+        let sizes = guillotine(Size::new(3, 2), 100, 4);
+
+        assert_eq!(sizes.len(), 6);
+        assert!(sizes.iter().all(|s| s.width == 1 && s.height == 1));
+        assert_eq!(guillotine(Size::new(100, 80), 50, 5).iter().map(|s| s.area()).sum::<usize>(), 8000);
+    }
+
+    #[test]
+    fn near_square_is_near_square() {
+        let sizes = near_square(500, 8, 40, 6);
+
+        assert!(sizes.iter().all(|s| {
+            let (short, long) = (s.width.min(s.height) as f64, s.width.max(s.height) as f64);
+            long / short <= 1.25 && (8..=40).contains(&s.width) && (8..=40).contains(&s.height)
+        }));
+    }
+}
@@ -0,0 +1,162 @@
+use egui::{Color32, ColorImage, Context, Pos2, Rect, TextureHandle, TextureId, TextureOptions};
+use crate::{AllocationId, AtlasAllocator, Rectangle, Size};
+
+/// An egui texture that user images are packed into at runtime.
+///
+/// Images are placed with an `AtlasAllocator` and copied into a CPU-side copy of the texture.
+/// The texture is created on the first call to [`texture_id`](Self::texture_id),
+/// after which only the regions that changed are uploaded.
+///
+/// # Examples
+/// ```
+/// use egui::{Color32, ColorImage};
+/// use rpack::egui::EguiTextureAtlas;
+/// use rpack::Size;
+///
+/// let ctx = egui::Context::default();
+/// let mut atlas = EguiTextureAtlas::new("icons", Size::new(256, 256), 1);
+///
+/// let icon = atlas.add(&ColorImage::filled([16, 16], Color32::RED)).unwrap();
+/// let texture = atlas.texture_id(&ctx);
+/// let uv = atlas.uv(icon).unwrap();
+/// // Draw with e.g. `egui::Image::new((texture, egui::vec2(16.0, 16.0))).uv(uv)`.
+/// ```
+pub struct EguiTextureAtlas {
+    name: String,
+    allocator: AtlasAllocator,
+    image: ColorImage,
+    options: TextureOptions,
+    texture: Option<TextureHandle>,
+    changed: Vec<Rectangle>,
+}
+
+impl EguiTextureAtlas {
+    /// Creates an empty, transparent atlas.
+    ///
+    /// # Arguments
+    /// * `name` - The debug name of the egui texture.
+    /// * `size` - The size of the texture in pixels.
+    /// * `padding` - The amount of padding kept free around each image, so that images do not bleed into each other.
+    pub fn new<S: Into<String>>(name: S, size: Size, padding: usize) -> EguiTextureAtlas {
+        EguiTextureAtlas {
+            name: name.into(),
+            allocator: AtlasAllocator::new(size).with_padding(padding, 0),
+            image: ColorImage::filled([size.width, size.height], Color32::TRANSPARENT),
+            options: TextureOptions::LINEAR,
+            texture: None,
+            changed: Vec::new(),
+        }
+    }
+
+    /// Sets the options the texture is created and updated with.
+    pub fn with_options(mut self, options: TextureOptions) -> EguiTextureAtlas {
+        self.options = options;
+        self
+    }
+
+    /// Adds an image to the atlas.
+    ///
+    /// # Returns
+    /// The identifier of the image, or `None` if there is no free space large enough for it.
+    pub fn add(&mut self, image: &ColorImage) -> Option<AllocationId> {
+        let allocation = self.allocator.allocate(Size::new(image.width(), image.height()))?;
+        let r = allocation.rectangle;
+        for y in 0..r.height {
+            let source = &image.pixels[y * r.width..(y + 1) * r.width];
+            let start = (r.y + y) * self.image.width() + r.x;
+            self.image.pixels[start..start + r.width].copy_from_slice(source);
+        }
+        self.changed.push(r);
+        Some(allocation.id)
+    }
+
+    /// Removes an image from the atlas, making its space available to new images.
+    ///
+    /// # Returns
+    /// `true` if the image was part of the atlas.
+    pub fn remove(&mut self, id: AllocationId) -> bool {
+        let Some(r) = self.allocator.deallocate(id) else {
+            return false;
+        };
+        for y in r.y..r.y + r.height {
+            let start = y * self.image.width() + r.x;
+            self.image.pixels[start..start + r.width].fill(Color32::TRANSPARENT);
+        }
+        self.changed.push(r);
+        true
+    }
+
+    /// Returns the region of the image in pixels, or `None` if it is not part of the atlas.
+    pub fn rectangle(&self, id: AllocationId) -> Option<Rectangle> {
+        self.allocator.get(id)
+    }
+
+    /// Returns the normalized texture coordinates of the image, or `None` if it is not part of the atlas.
+    pub fn uv(&self, id: AllocationId) -> Option<Rect> {
+        let uv = self.allocator.get(id)?.uv_rect(&Size::new(self.image.width(), self.image.height()));
+        Some(Rect::from_min_max(Pos2::from(uv.min), Pos2::from(uv.max)))
+    }
+
+    /// Returns the id of the egui texture, creating the texture or uploading the changed regions first if necessary.
+    pub fn texture_id(&mut self, ctx: &Context) -> TextureId {
+        match &mut self.texture {
+            Some(texture) => {
+                for r in self.changed.drain(..) {
+                    let region = self.image.region_by_pixels([r.x, r.y], [r.width, r.height]);
+                    texture.set_partial([r.x, r.y], region, self.options);
+                }
+            }
+            None => {
+                self.texture = Some(ctx.load_texture(self.name.clone(), self.image.clone(), self.options));
+                self.changed.clear();
+            }
+        }
+        self.texture.as_ref().map(|t| t.id()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_works() {
+        let mut atlas = EguiTextureAtlas::new("atlas", Size::new(8, 4), 0);
+        let red = atlas.add(&ColorImage::filled([4, 4], Color32::RED)).unwrap();
+        let blue = atlas.add(&ColorImage::filled([4, 2], Color32::BLUE)).unwrap();
+
+        assert!(atlas.add(&ColorImage::filled([4, 4], Color32::GREEN)).is_none());
+        assert_eq!(atlas.uv(red), Some(Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(0.5, 1.0))));
+        assert_eq!(atlas.uv(blue), Some(Rect::from_min_max(Pos2::new(0.5, 0.0), Pos2::new(1.0, 0.5))));
+        assert_eq!(atlas.image.pixels[3 * 8 + 3], Color32::RED);
+        assert_eq!(atlas.image.pixels[4], Color32::BLUE);
+        assert_eq!(atlas.image.pixels[3 * 8 + 4], Color32::TRANSPARENT);
+    }
+
+    #[test]
+    fn remove_works() {
+        let mut atlas = EguiTextureAtlas::new("atlas", Size::new(4, 4), 0);
+        let red = atlas.add(&ColorImage::filled([4, 4], Color32::RED)).unwrap();
+
+        assert!(atlas.remove(red));
+        assert!(!atlas.remove(red));
+        assert!(atlas.uv(red).is_none());
+        assert!(atlas.image.pixels.iter().all(|&p| p == Color32::TRANSPARENT));
+        assert!(atlas.add(&ColorImage::filled([4, 4], Color32::BLUE)).is_some());
+    }
+
+    #[test]
+    fn texture_id_uploads_changes() {
+        let ctx = Context::default();
+        let mut atlas = EguiTextureAtlas::new("atlas", Size::new(16, 16), 1);
+        atlas.add(&ColorImage::filled([4, 4], Color32::RED));
+
+        let id = atlas.texture_id(&ctx);
+        assert!(atlas.changed.is_empty());
+
+        atlas.add(&ColorImage::filled([4, 4], Color32::BLUE));
+        assert_eq!(atlas.changed.len(), 1);
+        assert_eq!(atlas.texture_id(&ctx), id);
+        assert!(atlas.changed.is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::Write;
+use crate::export::json_string;
+use crate::{AtlasLayout, AtlasSprite, NineSlice};
+
+/// A named range of frames, exported as an entry of `frameTags`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsepriteFrameTag {
+    /// The name of the tag, usually the name of an animation.
+    pub name: String,
+
+    /// The index of the first frame of the tag.
+    pub from: usize,
+
+    /// The index of the last frame of the tag, inclusively.
+    pub to: usize,
+}
+
+impl AtlasLayout {
+    /// Exports the layout as sprite sheet JSON in the schema written by Aseprite with "Array" frames.
+    ///
+    /// Every sprite becomes a frame, in the order of the sprites, so the frame tags refer to the sprite indices.
+    /// Sprites are never rotated, so `rotated` is always `false`. The source size and the region
+    /// within the source image are written from the trim information of each sprite.
+    /// Every nine-slice sprite gets an entry in `slices`, named like the sprite, with a single key for its frame
+    /// whose bounds cover the source image and whose center is the stretched center of the nine-slice.
+    ///
+    /// # Arguments
+    /// * `image` - The file name of the sprite sheet image, referenced from the `meta` section.
+    /// * `duration` - The duration of every frame in milliseconds.
+    /// * `tags` - The frame tags, e.g. one per animation.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, Rectangle, RectanglePackingResult, Size};
+    /// use rpack::export::AsepriteFrameTag;
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 16, 16), Rectangle::new(16, 0, 16, 16)],
+    ///     size: Size::new(32, 16),
+    ///     skipped: Vec::new(),
+    /// };
+    /// let layout = AtlasLayout::from_result(&result, &["walk 0", "walk 1"]);
+    /// let tags = [AsepriteFrameTag { name: "walk".to_string(), from: 0, to: 1 }];
+    ///
+    /// let json = layout.to_aseprite_json("player.png", 100, &tags);
+    /// assert!(json.contains(r#"{"name": "walk", "from": 0, "to": 1, "direction": "forward"}"#));
+    /// ```
+    pub fn to_aseprite_json(&self, image: &str, duration: usize, tags: &[AsepriteFrameTag]) -> String {
+        let mut json = String::new();
+        json.push_str("{ \"frames\": [\n");
+        for (index, sprite) in self.sprites.iter().enumerate() {
+            let r = &sprite.rectangle;
+            json.push_str("  {\n");
+            writeln!(json, "   \"filename\": {},", json_string(&sprite.name)).unwrap();
+            writeln!(json, "   \"frame\": {{ \"x\": {}, \"y\": {}, \"w\": {}, \"h\": {} }},", r.x, r.y, r.width, r.height).unwrap();
+            json.push_str("   \"rotated\": false,\n");
+            let source = &sprite.source_rectangle;
+            writeln!(json, "   \"trimmed\": {},", sprite.is_trimmed()).unwrap();
+            writeln!(json, "   \"spriteSourceSize\": {{ \"x\": {}, \"y\": {}, \"w\": {}, \"h\": {} }},", source.x, source.y, source.width, source.height).unwrap();
+            writeln!(json, "   \"sourceSize\": {{ \"w\": {}, \"h\": {} }},", sprite.source_size.width, sprite.source_size.height).unwrap();
+            writeln!(json, "   \"duration\": {}", duration).unwrap();
+            json.push_str(if index + 1 < self.sprites.len() { "  },\n" } else { "  }\n" });
+        }
+        json.push_str(" ],\n");
+
+        json.push_str(" \"meta\": {\n");
+        json.push_str("  \"app\": \"rpack\",\n");
+        writeln!(json, "  \"version\": \"{}\",", env!("CARGO_PKG_VERSION")).unwrap();
+        writeln!(json, "  \"image\": {},", json_string(image)).unwrap();
+        json.push_str("  \"format\": \"RGBA8888\",\n");
+        writeln!(json, "  \"size\": {{ \"w\": {}, \"h\": {} }},", self.size.width, self.size.height).unwrap();
+        json.push_str("  \"scale\": \"1\",\n");
+        json.push_str("  \"frameTags\": [\n");
+        for (index, tag) in tags.iter().enumerate() {
+            write!(
+                json,
+                "   {{\"name\": {}, \"from\": {}, \"to\": {}, \"direction\": \"forward\"}}",
+                json_string(&tag.name), tag.from, tag.to
+            ).unwrap();
+            json.push_str(if index + 1 < tags.len() { ",\n" } else { "\n" });
+        }
+        json.push_str("  ],\n");
+        json.push_str("  \"layers\": [],\n");
+        json.push_str("  \"slices\": [");
+        let slices: Vec<(usize, &AtlasSprite, &NineSlice)> = self.sprites
+            .iter()
+            .enumerate()
+            .filter_map(|(index, sprite)| sprite.nine_slice.as_ref().map(|nine_slice| (index, sprite, nine_slice)))
+            .collect();
+        for (position, (index, sprite, nine_slice)) in slices.iter().enumerate() {
+            let size = &sprite.source_size;
+            let center = nine_slice.center(size);
+            write!(
+                json,
+                "\n   {{\"name\": {}, \"color\": \"#0000ffff\", \"keys\": [{{\"frame\": {}, \"bounds\": {{\"x\": 0, \"y\": 0, \"w\": {}, \"h\": {}}}, \
+                 \"center\": {{\"x\": {}, \"y\": {}, \"w\": {}, \"h\": {}}}}}]}}",
+                json_string(&sprite.name), index, size.width, size.height, center.x, center.y, center.width, center.height
+            ).unwrap();
+            json.push_str(if position + 1 < slices.len() { "," } else { "\n  " });
+        }
+        json.push_str("]\n");
+        json.push_str(" }\n}\n");
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NineSlice, Rectangle, RectanglePackingResult, Size};
+
+    use super::*;
+
+    #[test]
+    fn to_aseprite_json_works() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 4, 2), Rectangle::new(4, 0, 2, 2), Rectangle::new(0, 2, 2, 2)],
+            size: Size::new(6, 4),
+            skipped: Vec::new(),
+        };
+        let layout = AtlasLayout::from_result(&result, &["idle", "run 0", "run 1"]);
+        let tags = [
+            AsepriteFrameTag { name: "idle".to_string(), from: 0, to: 0 },
+            AsepriteFrameTag { name: "run".to_string(), from: 1, to: 2 },
+        ];
+        let json = layout.to_aseprite_json("sheet.png", 80, &tags);
+
+        assert!(json.starts_with("{ \"frames\": [\n  {\n   \"filename\": \"idle\",\n"));
+        assert!(json.contains("   \"frame\": { \"x\": 4, \"y\": 0, \"w\": 2, \"h\": 2 },\n"));
+        assert!(json.contains("   \"duration\": 80\n  }\n ],\n"));
+        assert!(json.contains(
+            "  \"frameTags\": [\n   {\"name\": \"idle\", \"from\": 0, \"to\": 0, \"direction\": \"forward\"},\n\
+             \x20  {\"name\": \"run\", \"from\": 1, \"to\": 2, \"direction\": \"forward\"}\n  ],\n"
+        ));
+    }
+
+    #[test]
+    fn nine_slices_are_exported_as_slices() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 4, 4), Rectangle::new(4, 0, 8, 6)],
+            size: Size::new(12, 6),
+            skipped: Vec::new(),
+        };
+        let mut layout = AtlasLayout::from_result(&result, &["icon", "panel"]);
+        assert!(layout.to_aseprite_json("sheet.png", 100, &[]).contains("  \"slices\": []\n"));
+
+        layout.sprites[1].nine_slice = Some(NineSlice::new(2, 1, 3, 2));
+        assert!(layout.to_aseprite_json("sheet.png", 100, &[]).contains(
+            "  \"slices\": [\n   {\"name\": \"panel\", \"color\": \"#0000ffff\", \"keys\": [{\"frame\": 1, \
+             \"bounds\": {\"x\": 0, \"y\": 0, \"w\": 8, \"h\": 6}, \"center\": {\"x\": 2, \"y\": 1, \"w\": 3, \"h\": 3}}]}\n  ]\n"
+        ));
+    }
+}
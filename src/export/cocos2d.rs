@@ -0,0 +1,129 @@
+use std::fmt::Write;
+use crate::export::xml_escape;
+use crate::AtlasLayout;
+
+impl AtlasLayout {
+    /// Exports the layout as a cocos2d sprite sheet in the plist format 3 written by TexturePacker.
+    ///
+    /// Sprites are never rotated, so `textureRotated` is always `false`. The offset of a trimmed sprite
+    /// is the distance from the center of its source image to the center of the trimmed region, with y pointing up.
+    ///
+    /// # Arguments
+    /// * `texture` - The file name of the atlas texture.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, Rectangle, RectanglePackingResult, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 16, 16)],
+    ///     size: Size::new(16, 16),
+    ///     skipped: Vec::new(),
+    /// };
+    /// let layout = AtlasLayout::from_result(&result, &["coin.png"]);
+    ///
+    /// let plist = layout.to_cocos2d_plist("atlas.png");
+    /// assert!(plist.contains("<string>{{0,0},{16,16}}</string>"));
+    /// ```
+    pub fn to_cocos2d_plist(&self, texture: &str) -> String {
+        let texture = xml_escape(texture);
+        let mut plist = String::new();
+        plist.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        plist.push_str("<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n");
+        plist.push_str("<plist version=\"1.0\">\n");
+        plist.push_str("    <dict>\n");
+        plist.push_str("        <key>frames</key>\n");
+        plist.push_str("        <dict>\n");
+
+        for sprite in &self.sprites {
+            let r = &sprite.rectangle;
+            writeln!(plist, "            <key>{}</key>", xml_escape(&sprite.name)).unwrap();
+            plist.push_str("            <dict>\n");
+            plist.push_str("                <key>aliases</key>\n");
+            plist.push_str("                <array/>\n");
+            let source = &sprite.source_rectangle;
+            let source_size = &sprite.source_size;
+            let offset_x = half((2 * source.x + source.width) as isize - source_size.width as isize);
+            let offset_y = half(source_size.height as isize - (2 * source.y + source.height) as isize);
+            plist.push_str("                <key>spriteOffset</key>\n");
+            writeln!(plist, "                <string>{{{},{}}}</string>", offset_x, offset_y).unwrap();
+            plist.push_str("                <key>spriteSize</key>\n");
+            writeln!(plist, "                <string>{{{},{}}}</string>", r.width, r.height).unwrap();
+            plist.push_str("                <key>spriteSourceSize</key>\n");
+            writeln!(plist, "                <string>{{{},{}}}</string>", source_size.width, source_size.height).unwrap();
+            plist.push_str("                <key>textureRect</key>\n");
+            writeln!(plist, "                <string>{{{{{},{}}},{{{},{}}}}}</string>", r.x, r.y, r.width, r.height).unwrap();
+            plist.push_str("                <key>textureRotated</key>\n");
+            plist.push_str("                <false/>\n");
+            plist.push_str("            </dict>\n");
+        }
+
+        plist.push_str("        </dict>\n");
+        plist.push_str("        <key>metadata</key>\n");
+        plist.push_str("        <dict>\n");
+        plist.push_str("            <key>format</key>\n");
+        plist.push_str("            <integer>3</integer>\n");
+        plist.push_str("            <key>pixelFormat</key>\n");
+        plist.push_str("            <string>RGBA8888</string>\n");
+        plist.push_str("            <key>premultiplyAlpha</key>\n");
+        plist.push_str("            <false/>\n");
+        plist.push_str("            <key>realTextureFileName</key>\n");
+        writeln!(plist, "            <string>{}</string>", texture).unwrap();
+        plist.push_str("            <key>size</key>\n");
+        writeln!(plist, "            <string>{{{},{}}}</string>", self.size.width, self.size.height).unwrap();
+        plist.push_str("            <key>textureFileName</key>\n");
+        writeln!(plist, "            <string>{}</string>", texture).unwrap();
+        plist.push_str("        </dict>\n");
+        plist.push_str("    </dict>\n");
+        plist.push_str("</plist>\n");
+        plist
+    }
+}
+
+/// Returns half of the given value, with one decimal if it is odd.
+fn half(value: isize) -> String {
+    match value % 2 {
+        0 => (value / 2).to_string(),
+        _ => format!("{:.1}", value as f64 / 2.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Rectangle, RectanglePackingResult, Size};
+    use crate::export::test_layout;
+
+    use super::*;
+
+    #[test]
+    fn to_cocos2d_plist_works() {
+        let layout = test_layout(["a&b.png", "c.png"]);
+        let plist = layout.to_cocos2d_plist("atlas.png");
+
+        assert!(plist.contains("            <key>a&amp;b.png</key>\n"));
+        assert!(plist.contains(
+            "                <key>spriteSize</key>\n                <string>{2,2}</string>\n\
+             \x20               <key>spriteSourceSize</key>\n                <string>{2,2}</string>\n\
+             \x20               <key>textureRect</key>\n                <string>{{4,0},{2,2}}</string>\n"
+        ));
+        assert!(plist.contains("            <key>size</key>\n            <string>{6,2}</string>\n"));
+        assert!(plist.contains("            <key>textureFileName</key>\n            <string>atlas.png</string>\n"));
+        assert!(plist.ends_with("</plist>\n"));
+    }
+
+    #[test]
+    fn trimmed_sprites_work() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 2, 2)],
+            size: Size::new(2, 2),
+            skipped: Vec::new(),
+        };
+        let mut layout = AtlasLayout::from_result(&result, &["a.png"]);
+        layout.sprites[0].source_rectangle = Rectangle::new(5, 1, 2, 2);
+        layout.sprites[0].source_size = Size::new(8, 5);
+        let plist = layout.to_cocos2d_plist("atlas.png");
+
+        assert!(plist.contains("                <key>spriteOffset</key>\n                <string>{2,0.5}</string>\n"));
+        assert!(plist.contains("                <key>spriteSourceSize</key>\n                <string>{8,5}</string>\n"));
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt::Write;
+use crate::AtlasLayout;
+
+impl AtlasLayout {
+    /// Exports the layout as a CSS sprite sheet.
+    ///
+    /// The class `prefix` sets the atlas image as background, and every sprite gets a class
+    /// `prefix-name` selecting its region with `width`, `height` and `background-position`.
+    /// Characters of the sprite names that are not allowed in class names are replaced by `-`,
+    /// see [`css_identifier`](Self::css_identifier).
+    ///
+    /// # Arguments
+    /// * `image_url` - The URL of the atlas image, as used in the `url()` of the stylesheet.
+    /// * `prefix` - The class name of the sprite sheet and the prefix of the class of every sprite.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, Rectangle, RectanglePackingResult, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 16, 16), Rectangle::new(16, 0, 16, 16)],
+    ///     size: Size::new(32, 16),
+    ///     skipped: Vec::new(),
+    /// };
+    /// let layout = AtlasLayout::from_result(&result, &["home.png", "search.png"]);
+    ///
+    /// let css = layout.to_css("icons.png", "icon");
+    /// assert!(css.contains(".icon-search-png {\n  width: 16px;\n  height: 16px;\n  background-position: -16px 0;\n}\n"));
+    /// ```
+    pub fn to_css(&self, image_url: &str, prefix: &str) -> String {
+        let mut css = String::new();
+        writeln!(css, ".{} {{", prefix).unwrap();
+        writeln!(css, "  display: inline-block;").unwrap();
+        writeln!(css, "  background-image: url(\"{}\");", image_url.replace('"', "\\\"")).unwrap();
+        writeln!(css, "  background-repeat: no-repeat;").unwrap();
+        writeln!(css, "  background-size: {}px {}px;", self.size.width, self.size.height).unwrap();
+        writeln!(css, "}}").unwrap();
+
+        for sprite in &self.sprites {
+            let r = &sprite.rectangle;
+            writeln!(css).unwrap();
+            writeln!(css, ".{}-{} {{", prefix, Self::css_identifier(&sprite.name)).unwrap();
+            writeln!(css, "  width: {}px;", r.width).unwrap();
+            writeln!(css, "  height: {}px;", r.height).unwrap();
+            writeln!(css, "  background-position: {} {};", offset(r.x), offset(r.y)).unwrap();
+            writeln!(css, "}}").unwrap();
+        }
+
+        css
+    }
+
+    /// Exports the regions of the sprites as SCSS variables.
+    ///
+    /// For every sprite, the variables `$prefix-name-x`, `$prefix-name-y`, `$prefix-name-width`
+    /// and `$prefix-name-height` are written, where the position is given as background offset.
+    /// The size of the whole sheet is written as `$prefix-width` and `$prefix-height`.
+    ///
+    /// # Arguments
+    /// * `prefix` - The prefix of every variable name.
+    pub fn to_scss_variables(&self, prefix: &str) -> String {
+        let mut scss = String::new();
+        writeln!(scss, "${}-width: {}px;", prefix, self.size.width).unwrap();
+        writeln!(scss, "${}-height: {}px;", prefix, self.size.height).unwrap();
+
+        for sprite in &self.sprites {
+            let r = &sprite.rectangle;
+            let name = format!("{}-{}", prefix, Self::css_identifier(&sprite.name));
+            writeln!(scss, "${}-x: {};", name, offset(r.x)).unwrap();
+            writeln!(scss, "${}-y: {};", name, offset(r.y)).unwrap();
+            writeln!(scss, "${}-width: {}px;", name, r.width).unwrap();
+            writeln!(scss, "${}-height: {}px;", name, r.height).unwrap();
+        }
+
+        scss
+    }
+
+    /// Returns the sprite name with every character other than ASCII letters, digits, `-` and `_` replaced by `-`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::AtlasLayout;
+    ///
+    /// assert_eq!(AtlasLayout::css_identifier("ui/home button.png"), "ui-home-button-png");
+    /// ```
+    pub fn css_identifier(name: &str) -> String {
+        name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+            .collect()
+    }
+}
+
+/// Returns the CSS background offset that moves the given position of the sheet to the origin.
+fn offset(position: usize) -> String {
+    match position {
+        0 => "0".to_string(),
+        _ => format!("-{}px", position),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::export::test_layout;
+
+    #[test]
+    fn to_css_works() {
+        assert_eq!(
+            test_layout(["a", "b.png"]).to_css("sheet.png", "sprite"),
+            ".sprite {\n  display: inline-block;\n  background-image: url(\"sheet.png\");\n  \
+             background-repeat: no-repeat;\n  background-size: 6px 2px;\n}\n\n\
+             .sprite-a {\n  width: 4px;\n  height: 2px;\n  background-position: 0 0;\n}\n\n\
+             .sprite-b-png {\n  width: 2px;\n  height: 2px;\n  background-position: -4px 0;\n}\n"
+        );
+    }
+
+    #[test]
+    fn to_scss_variables_works() {
+        let scss = test_layout(["a", "b.png"]).to_scss_variables("sprite");
+
+        assert!(scss.starts_with("$sprite-width: 6px;\n$sprite-height: 2px;\n"));
+        assert!(scss.contains("$sprite-b-png-x: -4px;\n$sprite-b-png-y: 0;\n$sprite-b-png-width: 2px;\n$sprite-b-png-height: 2px;\n"));
+    }
+}
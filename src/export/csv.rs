@@ -0,0 +1,130 @@
+use std::fmt::Write;
+use crate::{AtlasLayout, AtlasPages};
+
+/// The header row of the tabular formats.
+const COLUMNS: [&str; 7] = ["name", "page", "x", "y", "w", "h", "rotated"];
+
+impl AtlasLayout {
+    /// Exports the layout as comma-separated values with one row per sprite.
+    ///
+    /// The columns are `name`, `page`, `x`, `y`, `w`, `h` and `rotated`, preceded by a header row.
+    /// The atlas consists of a single page, so `page` is always `0`, and sprites are never rotated.
+    /// Names containing commas, quotes or line breaks are quoted as described in RFC 4180.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, Rectangle, RectanglePackingResult, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 16, 16)],
+    ///     size: Size::new(16, 16),
+    ///     skipped: Vec::new(),
+    /// };
+    /// let layout = AtlasLayout::from_result(&result, &["coin"]);
+    ///
+    /// assert_eq!(layout.to_csv(), "name,page,x,y,w,h,rotated\ncoin,0,0,0,16,16,false\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        self.to_delimited(',', csv_escape)
+    }
+
+    /// Exports the layout as tab-separated values with one row per sprite.
+    ///
+    /// The columns are the same as for [`to_csv`](Self::to_csv).
+    /// Tabs and line breaks in the names are replaced by spaces, as the format has no way to quote them.
+    pub fn to_tsv(&self) -> String {
+        self.to_delimited('\t', tsv_escape)
+    }
+
+    /// Writes the header and one row per sprite, separating the columns by `delimiter`.
+    fn to_delimited<F: Fn(&str) -> String>(&self, delimiter: char, escape: F) -> String {
+        let mut table = header(delimiter);
+        self.write_rows(&mut table, 0, delimiter, &escape);
+        table
+    }
+
+    /// Writes one row per sprite on the page with the given index.
+    fn write_rows<F: Fn(&str) -> String>(&self, table: &mut String, page: usize, delimiter: char, escape: &F) {
+        for sprite in &self.sprites {
+            let r = &sprite.rectangle;
+            let d = delimiter;
+            writeln!(table, "{}{d}{page}{d}{}{d}{}{d}{}{d}{}{d}false", escape(&sprite.name), r.x, r.y, r.width, r.height).unwrap();
+        }
+    }
+}
+
+impl AtlasPages {
+    /// Exports the sprites of all pages as comma-separated values, with the index of its page in the `page` column of every sprite.
+    ///
+    /// The columns and quoting are the same as for [`AtlasLayout::to_csv`].
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasPages, HeightRectPacker, OverflowPolicy, RectanglePackerConfig, Size};
+    ///
+    /// let items = [("a", Size::new(8, 8)), ("b", Size::new(8, 8))];
+    /// let config = RectanglePackerConfig { max_size: Some(Size::new(8, 8)), overflow: OverflowPolicy::NewPage, ..Default::default() };
+    /// let atlas = AtlasPages::pack::<HeightRectPacker, _>(&items, &config, "atlas_{n}.png").unwrap();
+    ///
+    /// assert_eq!(atlas.to_csv(), "name,page,x,y,w,h,rotated\na,0,0,0,8,8,false\nb,1,0,0,8,8,false\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        self.to_delimited(',', csv_escape)
+    }
+
+    /// Exports the sprites of all pages as tab-separated values, like [`to_csv`](Self::to_csv).
+    pub fn to_tsv(&self) -> String {
+        self.to_delimited('\t', tsv_escape)
+    }
+
+    /// Writes the header and one row per sprite of every page, separating the columns by `delimiter`.
+    fn to_delimited<F: Fn(&str) -> String>(&self, delimiter: char, escape: F) -> String {
+        let mut table = header(delimiter);
+        for page in &self.pages {
+            page.layout.write_rows(&mut table, page.index, delimiter, &escape);
+        }
+        table
+    }
+}
+
+/// Returns the header row, separating the columns by `delimiter`.
+fn header(delimiter: char) -> String {
+    let mut table = COLUMNS.join(&delimiter.to_string());
+    table.push('\n');
+    table
+}
+
+/// Quotes names containing commas, quotes or line breaks as described in RFC 4180.
+fn csv_escape(name: &str) -> String {
+    if name.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    } else {
+        name.to_string()
+    }
+}
+
+/// Replaces tabs and line breaks by spaces.
+fn tsv_escape(name: &str) -> String {
+    name.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::export::test_layout;
+
+    #[test]
+    fn to_csv_works() {
+        assert_eq!(
+            test_layout(["a, \"b\"", "c\td"]).to_csv(),
+            "name,page,x,y,w,h,rotated\n\"a, \"\"b\"\"\",0,0,0,4,2,false\nc\td,0,4,0,2,2,false\n"
+        );
+    }
+
+    #[test]
+    fn to_tsv_works() {
+        assert_eq!(
+            test_layout(["a, \"b\"", "c\td"]).to_tsv(),
+            "name\tpage\tx\ty\tw\th\trotated\na, \"b\"\t0\t0\t0\t4\t2\tfalse\nc d\t0\t4\t0\t2\t2\tfalse\n"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt::Write;
+use crate::export::json_string;
+use crate::AtlasLayout;
+
+impl AtlasLayout {
+    /// Exports every sprite of the layout as a Godot 4 `AtlasTexture` resource in the `.tres` text format.
+    ///
+    /// Each resource references the atlas image as an external `Texture2D` and selects the region of its sprite.
+    /// Trimmed sprites get a `margin` restoring the size of their source image.
+    ///
+    /// # Arguments
+    /// * `texture_path` - The Godot resource path of the atlas image, e.g. `res://sprites/atlas.png`.
+    ///
+    /// # Returns
+    /// One pair of sprite name and resource content per sprite, in the order of the sprites.
+    /// The resources are usually saved as `<name>.tres` next to the atlas image.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, Rectangle, RectanglePackingResult, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 16, 16)],
+    ///     size: Size::new(16, 16),
+    ///     skipped: Vec::new(),
+    /// };
+    /// let layout = AtlasLayout::from_result(&result, &["coin"]);
+    ///
+    /// let resources = layout.to_godot_atlas_textures("res://atlas.png");
+    /// assert_eq!(resources[0].0, "coin");
+    /// assert!(resources[0].1.contains("region = Rect2(0, 0, 16, 16)"));
+    /// ```
+    pub fn to_godot_atlas_textures(&self, texture_path: &str) -> Vec<(String, String)> {
+        self.sprites
+            .iter()
+            .map(|sprite| {
+                let r = &sprite.rectangle;
+                let mut resource = String::new();
+                writeln!(resource, "[gd_resource type=\"AtlasTexture\" load_steps=2 format=3]").unwrap();
+                writeln!(resource).unwrap();
+                writeln!(resource, "[ext_resource type=\"Texture2D\" path={} id=\"1\"]", json_string(texture_path)).unwrap();
+                writeln!(resource).unwrap();
+                writeln!(resource, "[resource]").unwrap();
+                writeln!(resource, "atlas = ExtResource(\"1\")").unwrap();
+                writeln!(resource, "region = Rect2({}, {}, {}, {})", r.x, r.y, r.width, r.height).unwrap();
+                if sprite.is_trimmed() {
+                    let source = &sprite.source_rectangle;
+                    let margin_x = sprite.source_size.width - source.width;
+                    let margin_y = sprite.source_size.height - source.height;
+                    writeln!(resource, "margin = Rect2({}, {}, {}, {})", source.x, source.y, margin_x, margin_y).unwrap();
+                }
+                (sprite.name.clone(), resource)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::export::test_layout;
+
+    #[test]
+    fn to_godot_atlas_textures_works() {
+        let layout = test_layout(["a", "b"]);
+        let resources = layout.to_godot_atlas_textures("res://atlas.png");
+
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[1].0, "b");
+        assert_eq!(
+            resources[1].1,
+            "[gd_resource type=\"AtlasTexture\" load_steps=2 format=3]\n\n\
+             [ext_resource type=\"Texture2D\" path=\"res://atlas.png\" id=\"1\"]\n\n\
+             [resource]\natlas = ExtResource(\"1\")\nregion = Rect2(4, 0, 2, 2)\n"
+        );
+    }
+}
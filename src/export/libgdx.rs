@@ -0,0 +1,114 @@
+use std::fmt::Write;
+use crate::{AtlasLayout, AtlasPages};
+
+impl AtlasLayout {
+    /// Exports the layout in the libGDX `.atlas` text format, as written by gdx-texturepacker.
+    ///
+    /// The atlas consists of a single page. Sprites are never rotated, so `rotate` is always `false`.
+    /// The original size and the offset of the trimmed region from the bottom left corner of the
+    /// source image are written from the trim information of each sprite, and the `split` of nine-slice sprites
+    /// from their insets.
+    /// The sprite names are written as they are, so they should not contain file extensions
+    /// if the regions are looked up by name in libGDX.
+    ///
+    /// # Arguments
+    /// * `image` - The file name of the atlas image of the page.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, Rectangle, RectanglePackingResult, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 16, 16)],
+    ///     size: Size::new(16, 16),
+    ///     skipped: Vec::new(),
+    /// };
+    /// let layout = AtlasLayout::from_result(&result, &["coin"]);
+    ///
+    /// let atlas = layout.to_libgdx_atlas("atlas.png");
+    /// assert!(atlas.contains("coin\n  rotate: false\n  xy: 0, 0\n"));
+    /// ```
+    pub fn to_libgdx_atlas(&self, image: &str) -> String {
+        let mut atlas = String::new();
+
+        // Every page starts with an empty line followed by the file name of its image.
+        writeln!(atlas).unwrap();
+        writeln!(atlas, "{}", image).unwrap();
+        writeln!(atlas, "size: {}, {}", self.size.width, self.size.height).unwrap();
+        writeln!(atlas, "format: RGBA8888").unwrap();
+        writeln!(atlas, "filter: Nearest, Nearest").unwrap();
+        writeln!(atlas, "repeat: none").unwrap();
+
+        for sprite in &self.sprites {
+            let r = &sprite.rectangle;
+            writeln!(atlas, "{}", sprite.name).unwrap();
+            writeln!(atlas, "  rotate: false").unwrap();
+            writeln!(atlas, "  xy: {}, {}", r.x, r.y).unwrap();
+            writeln!(atlas, "  size: {}, {}", r.width, r.height).unwrap();
+            if let Some(nine_slice) = &sprite.nine_slice {
+                writeln!(atlas, "  split: {}, {}, {}, {}", nine_slice.left, nine_slice.right, nine_slice.top, nine_slice.bottom).unwrap();
+            }
+            let source = &sprite.source_rectangle;
+            let source_size = &sprite.source_size;
+            writeln!(atlas, "  orig: {}, {}", source_size.width, source_size.height).unwrap();
+            writeln!(atlas, "  offset: {}, {}", source.x, source_size.height - source.y - source.height).unwrap();
+            writeln!(atlas, "  index: -1").unwrap();
+        }
+
+        atlas
+    }
+}
+
+impl AtlasPages {
+    /// Exports all pages into a single libGDX `.atlas` file, each page with the name of its image.
+    ///
+    /// Each page is written like by [`AtlasLayout::to_libgdx_atlas`].
+    pub fn to_libgdx_atlas(&self) -> String {
+        self.pages.iter().map(|page| page.layout.to_libgdx_atlas(&page.image)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeightRectPacker, NineSlice, OverflowPolicy, Rectangle, RectanglePackerConfig, RectanglePackingResult, Size};
+    use crate::export::test_layout;
+
+    use super::*;
+
+    #[test]
+    fn to_libgdx_atlas_works() {
+        let layout = test_layout(["a", "b"]);
+
+        assert_eq!(
+            layout.to_libgdx_atlas("atlas.png"),
+            "\natlas.png\nsize: 6, 2\nformat: RGBA8888\nfilter: Nearest, Nearest\nrepeat: none\n\
+             a\n  rotate: false\n  xy: 0, 0\n  size: 4, 2\n  orig: 4, 2\n  offset: 0, 0\n  index: -1\n\
+             b\n  rotate: false\n  xy: 4, 0\n  size: 2, 2\n  orig: 2, 2\n  offset: 0, 0\n  index: -1\n"
+        );
+    }
+
+    #[test]
+    fn nine_slices_have_splits() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 8, 6)],
+            size: Size::new(8, 6),
+            skipped: Vec::new(),
+        };
+        let mut layout = AtlasLayout::from_result(&result, &["panel"]);
+        layout.sprites[0].nine_slice = Some(NineSlice::new(1, 2, 3, 1));
+
+        assert!(layout.to_libgdx_atlas("atlas.png").contains("  size: 8, 6\n  split: 1, 3, 2, 1\n  orig: 8, 6\n"));
+    }
+
+    #[test]
+    fn pages_are_written_with_their_images() {
+        let config = RectanglePackerConfig { max_size: Some(Size::new(8, 8)), overflow: OverflowPolicy::NewPage, ..Default::default() };
+        let atlas = AtlasPages::pack::<HeightRectPacker, _>(&[("a", Size::new(8, 8)), ("b", Size::new(4, 4))], &config, "atlas_{n}.png").unwrap();
+        let text = atlas.to_libgdx_atlas();
+
+        assert!(text.starts_with("\natlas_0.png\nsize: 8, 8\n"));
+        assert!(text.contains("a\n  rotate: false\n  xy: 0, 0\n  size: 8, 8\n"));
+        assert!(text.contains("\natlas_1.png\nsize: 8, 8\n"));
+        assert!(text.find("atlas_1.png").unwrap() < text.find("b\n").unwrap());
+    }
+}
@@ -0,0 +1,82 @@
+//! Exporters writing the layout of an atlas in the metadata formats of common engines and tools.
+
+pub mod texture_packer;
+pub mod libgdx;
+pub mod unity;
+pub mod godot;
+pub mod cocos2d;
+pub mod spine;
+pub mod css;
+pub mod aseprite;
+pub mod csv;
+pub mod texture_array;
+#[cfg(feature = "template")]
+pub mod template;
+
+pub use texture_packer::TexturePackerFormat;
+pub use aseprite::AsepriteFrameTag;
+
+/// Returns the layout of a 6x2 atlas with two sprites of the given names,
+/// a 4x2 sprite at the origin and a 2x2 sprite to the right of it, for the tests of the exporters.
+#[cfg(test)]
+pub(crate) fn test_layout(names: [&str; 2]) -> crate::AtlasLayout {
+    let result = crate::RectanglePackingResult {
+        rectangles: vec![crate::Rectangle::new(0, 0, 4, 2), crate::Rectangle::new(4, 0, 2, 2)],
+        size: crate::Size::new(6, 2),
+        skipped: Vec::new(),
+    };
+    crate::AtlasLayout::from_result(&result, &names)
+}
+
+/// Returns the given string as a quoted JSON string literal.
+pub(crate) fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Returns the given string with the characters that are special in XML replaced by entities.
+pub(crate) fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_string_works() {
+        assert_eq!(json_string("player.png"), r#""player.png""#);
+        assert_eq!(json_string("a \"b\"\\c\n"), r#""a \"b\"\\c\n""#);
+        assert_eq!(json_string("\u{1}"), r#""\u0001""#);
+    }
+
+    #[test]
+    fn xml_escape_works() {
+        assert_eq!(xml_escape("player.png"), "player.png");
+        assert_eq!(xml_escape("<a & 'b'>\""), "&lt;a &amp; &apos;b&apos;&gt;&quot;");
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt::Write;
+use crate::AtlasLayout;
+
+impl AtlasLayout {
+    /// Exports the layout in the `.atlas` text format of Spine 4.
+    ///
+    /// The atlas consists of a single page. Each region is written with its `bounds`, and trimmed regions
+    /// with their `offsets` from the bottom left corner of the source image and its original size.
+    /// Nine-slice regions additionally get a `split` with their insets.
+    /// The field for rotation is left out since sprites are never rotated.
+    /// Spine 3 reads the same format as libGDX, see [`to_libgdx_atlas`](Self::to_libgdx_atlas).
+    ///
+    /// # Arguments
+    /// * `image` - The file name of the atlas image of the page.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, Rectangle, RectanglePackingResult, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 16, 16)],
+    ///     size: Size::new(16, 16),
+    ///     skipped: Vec::new(),
+    /// };
+    /// let layout = AtlasLayout::from_result(&result, &["head"]);
+    ///
+    /// let atlas = layout.to_spine_atlas("skeleton.png");
+    /// assert!(atlas.contains("head\n  bounds: 0, 0, 16, 16\n"));
+    /// ```
+    pub fn to_spine_atlas(&self, image: &str) -> String {
+        let mut atlas = String::new();
+        writeln!(atlas, "{}", image).unwrap();
+        writeln!(atlas, "size: {}, {}", self.size.width, self.size.height).unwrap();
+        writeln!(atlas, "format: RGBA8888").unwrap();
+        writeln!(atlas, "filter: Linear, Linear").unwrap();
+        writeln!(atlas, "repeat: none").unwrap();
+        writeln!(atlas, "pma: false").unwrap();
+
+        for sprite in &self.sprites {
+            let r = &sprite.rectangle;
+            writeln!(atlas, "{}", sprite.name).unwrap();
+            writeln!(atlas, "  bounds: {}, {}, {}, {}", r.x, r.y, r.width, r.height).unwrap();
+            if sprite.is_trimmed() {
+                let source = &sprite.source_rectangle;
+                let source_size = &sprite.source_size;
+                let bottom = source_size.height - source.y - source.height;
+                writeln!(atlas, "  offsets: {}, {}, {}, {}", source.x, bottom, source_size.width, source_size.height).unwrap();
+            }
+            if let Some(nine_slice) = &sprite.nine_slice {
+                writeln!(atlas, "  split: {}, {}, {}, {}", nine_slice.left, nine_slice.right, nine_slice.top, nine_slice.bottom).unwrap();
+            }
+        }
+
+        atlas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NineSlice, Rectangle, RectanglePackingResult, Size};
+    use crate::export::test_layout;
+
+    use super::*;
+
+    #[test]
+    fn to_spine_atlas_works() {
+        let layout = test_layout(["a", "b"]);
+
+        assert_eq!(
+            layout.to_spine_atlas("skeleton.png"),
+            "skeleton.png\nsize: 6, 2\nformat: RGBA8888\nfilter: Linear, Linear\nrepeat: none\npma: false\n\
+             a\n  bounds: 0, 0, 4, 2\nb\n  bounds: 4, 0, 2, 2\n"
+        );
+    }
+
+    #[test]
+    fn trimmed_and_nine_slice_regions_work() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 2, 2)],
+            size: Size::new(2, 2),
+            skipped: Vec::new(),
+        };
+        let mut layout = AtlasLayout::from_result(&result, &["a"]);
+        layout.sprites[0].source_rectangle = Rectangle::new(1, 1, 2, 2);
+        layout.sprites[0].source_size = Size::new(4, 5);
+        layout.sprites[0].nine_slice = Some(NineSlice::new(1, 0, 2, 3));
+
+        assert!(layout.to_spine_atlas("skeleton.png").ends_with("a\n  bounds: 0, 0, 2, 2\n  offsets: 1, 2, 4, 5\n  split: 1, 2, 0, 3\n"));
+    }
+}
@@ -0,0 +1,155 @@
+use serde::Serialize;
+use tinytemplate::{format_unescaped, TinyTemplate};
+use tinytemplate::error::Error;
+use crate::AtlasLayout;
+
+/// The fields of the page available to a template as `page`.
+#[derive(Serialize)]
+struct PageContext<'a> {
+    index: usize,
+    image: &'a str,
+    width: usize,
+    height: usize,
+}
+
+/// The fields of a sprite available to a template for every entry of `sprites`.
+#[derive(Serialize)]
+struct SpriteContext<'a> {
+    index: usize,
+    name: &'a str,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    rotated: bool,
+    trimmed: bool,
+    source_x: usize,
+    source_y: usize,
+    source_width: usize,
+    source_height: usize,
+    nine_slice: bool,
+    slice_left: usize,
+    slice_top: usize,
+    slice_right: usize,
+    slice_bottom: usize,
+}
+
+/// The root of the values available to a template.
+#[derive(Serialize)]
+struct Context<'a> {
+    page: PageContext<'a>,
+    sprites: Vec<SpriteContext<'a>>,
+}
+
+impl AtlasLayout {
+    /// Exports the layout with a user-supplied [TinyTemplate](https://docs.rs/tinytemplate) template.
+    ///
+    /// This covers formats that have no dedicated exporter. The template has access to
+    /// - `page`, with the fields `index`, `image`, `width` and `height`, and
+    /// - `sprites`, a list with the fields `index`, `name`, `x`, `y`, `width`, `height`, `rotated`, `trimmed`,
+    ///   `source_x`, `source_y`, `source_width` and `source_height` for every sprite. The source fields give the position
+    ///   of the trimmed region within the source image and the size of the source image.
+    ///   The fields `nine_slice`, `slice_left`, `slice_top`, `slice_right` and `slice_bottom` give the nine-slice insets,
+    ///   which are zero for sprites that are not nine-slices.
+    ///
+    /// The atlas consists of a single page with the index `0`, and sprites are never rotated.
+    /// Values are inserted as they are, without any escaping.
+    ///
+    /// # Arguments
+    /// * `template` - The template text.
+    /// * `image` - The file name of the atlas image, available as `page.image`.
+    ///
+    /// # Returns
+    /// A `Result` containing either the rendered template or the error raised while compiling or rendering it.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, Rectangle, RectanglePackingResult, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 16, 16), Rectangle::new(16, 0, 8, 8)],
+    ///     size: Size::new(24, 16),
+    ///     skipped: Vec::new(),
+    /// };
+    /// let layout = AtlasLayout::from_result(&result, &["coin", "gem"]);
+    ///
+    /// let template = "{page.image}\n{{ for sprite in sprites }}{sprite.name} {sprite.x} {sprite.y}\n{{ endfor }}";
+    /// let output = layout.to_template(template, "atlas.png").unwrap();
+    /// assert_eq!(output, "atlas.png\ncoin 0 0\ngem 16 0\n");
+    /// ```
+    pub fn to_template(&self, template: &str, image: &str) -> Result<String, Error> {
+        let mut engine = TinyTemplate::new();
+        engine.set_default_formatter(&format_unescaped);
+        engine.add_template("layout", template)?;
+
+        let context = Context {
+            page: PageContext {
+                index: 0,
+                image,
+                width: self.size.width,
+                height: self.size.height,
+            },
+            sprites: self.sprites
+                .iter()
+                .enumerate()
+                .map(|(index, sprite)| {
+                    let nine_slice = sprite.nine_slice.unwrap_or_default();
+                    SpriteContext {
+                        index,
+                        name: &sprite.name,
+                        x: sprite.rectangle.x,
+                        y: sprite.rectangle.y,
+                        width: sprite.rectangle.width,
+                        height: sprite.rectangle.height,
+                        rotated: false,
+                        trimmed: sprite.is_trimmed(),
+                        source_x: sprite.source_rectangle.x,
+                        source_y: sprite.source_rectangle.y,
+                        source_width: sprite.source_size.width,
+                        source_height: sprite.source_size.height,
+                        nine_slice: sprite.nine_slice.is_some(),
+                        slice_left: nine_slice.left,
+                        slice_top: nine_slice.top,
+                        slice_right: nine_slice.right,
+                        slice_bottom: nine_slice.bottom,
+                    }
+                })
+                .collect(),
+        };
+
+        engine.render("layout", &context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Rectangle, Size};
+    use crate::export::test_layout;
+
+    #[test]
+    fn to_template_works() {
+        let template = "{page.index} {page.image} {page.width}x{page.height}\n\
+                        {{ for sprite in sprites }}{sprite.index}:{sprite.name}:{sprite.x},{sprite.y},{sprite.width},{sprite.height},{sprite.rotated}\n{{ endfor }}";
+
+        assert_eq!(
+            test_layout(["a<b>", "c"]).to_template(template, "atlas.png").unwrap(),
+            "0 atlas.png 6x2\n0:a<b>:0,0,4,2,false\n1:c:4,0,2,2,false\n"
+        );
+    }
+
+    #[test]
+    fn trimmed_sprites_work() {
+        let mut layout = test_layout(["a<b>", "c"]);
+        layout.sprites[1].source_rectangle = Rectangle::new(1, 3, 2, 2);
+        layout.sprites[1].source_size = Size::new(4, 6);
+        let template = "{{ for sprite in sprites }}{sprite.trimmed}:{sprite.source_x},{sprite.source_y},{sprite.source_width},{sprite.source_height}\n{{ endfor }}";
+
+        assert_eq!(layout.to_template(template, "atlas.png").unwrap(), "false:0,0,4,2\ntrue:1,3,4,6\n");
+    }
+
+    #[test]
+    fn invalid_template_fails() {
+        assert!(test_layout(["a<b>", "c"]).to_template("{{ for sprite in sprites }}", "atlas.png").is_err());
+        assert!(test_layout(["a<b>", "c"]).to_template("{missing}", "atlas.png").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt::Write;
+use crate::export::json_string;
+use crate::{AtlasPages, Size};
+
+impl AtlasPages {
+    /// Exports the pages as the layers of a GPU texture array in a JSON format.
+    ///
+    /// The `layers` hold the image of every layer, in the order of the layers, and every frame holds the `layer`
+    /// it was placed on, its `frame` in pixels and its `uv` coordinates, which are the same on every layer.
+    /// The `meta` section holds the size shared by all layers and their number.
+    ///
+    /// # Panics
+    /// Panics if the pages differ in size, see `AtlasPages::pack_texture_array`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasPages, HeightRectPacker, OverflowPolicy, RectanglePackerConfig, Size};
+    ///
+    /// let items = [("grass", Size::new(32, 32)), ("stone", Size::new(32, 32))];
+    /// let config = RectanglePackerConfig { max_size: Some(Size::new(32, 32)), overflow: OverflowPolicy::NewPage, ..Default::default() };
+    /// let atlas = AtlasPages::pack_texture_array::<HeightRectPacker, _>(&items, &config, "tiles_{n}.png").unwrap();
+    ///
+    /// let json = atlas.to_texture_array_json();
+    /// assert!(json.contains(r#"{"layer": 1, "image": "tiles_1.png"}"#));
+    /// assert!(json.contains(r#""stone": {"layer": 1, "frame": {"x": 0, "y": 0, "w": 32, "h": 32}"#));
+    /// ```
+    pub fn to_texture_array_json(&self) -> String {
+        let size = match self.pages.is_empty() {
+            true => Size::new(0, 0),
+            false => self.layer_size().expect("The layers of a texture array must have the same size"),
+        };
+        let mut json = String::new();
+
+        json.push_str("{\"layers\": [\n");
+        for (index, page) in self.pages.iter().enumerate() {
+            write!(json, "\t{{\"layer\": {}, \"image\": {}}}", page.index, json_string(&page.image)).unwrap();
+            json.push_str(if index + 1 < self.pages.len() { ",\n" } else { "\n" });
+        }
+        json.push_str("],\n");
+
+        let frames: Vec<_> = self.pages.iter().flat_map(|page| page.layout.sprites.iter().map(move |sprite| (page, sprite))).collect();
+        json.push_str("\"frames\": {\n");
+        for (index, (page, sprite)) in frames.iter().enumerate() {
+            let r = &sprite.rectangle;
+            let uv = r.uv_rect(&size);
+            write!(
+                json,
+                "\t{}: {{\"layer\": {}, \"frame\": {{\"x\": {}, \"y\": {}, \"w\": {}, \"h\": {}}}, \"uv\": {{\"u0\": {}, \"v0\": {}, \"u1\": {}, \"v1\": {}}}}}",
+                json_string(&sprite.name), page.index, r.x, r.y, r.width, r.height, uv.min[0], uv.min[1], uv.max[0], uv.max[1]
+            )
+            .unwrap();
+            json.push_str(if index + 1 < frames.len() { ",\n" } else { "\n" });
+        }
+        json.push_str("},\n");
+
+        json.push_str("\"meta\": {\n");
+        json.push_str("\t\"app\": \"rpack\",\n");
+        writeln!(json, "\t\"version\": \"{}\",", env!("CARGO_PKG_VERSION")).unwrap();
+        json.push_str("\t\"format\": \"RGBA8888\",\n");
+        writeln!(json, "\t\"size\": {{\"w\": {}, \"h\": {}}},", size.width, size.height).unwrap();
+        writeln!(json, "\t\"layers\": {}", self.pages.len()).unwrap();
+        json.push_str("}\n}\n");
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AtlasLayout, AtlasPage, Rectangle, RectanglePackingResult};
+
+    use super::*;
+
+    fn page(index: usize, size: Size, names: &[&str]) -> AtlasPage {
+        let result = RectanglePackingResult {
+            rectangles: (0..names.len()).map(|i| Rectangle::new(i * 4, 0, 4, 2)).collect(),
+            size,
+            skipped: Vec::new(),
+        };
+        AtlasPage { index, image: format!("layer_{}.png", index), layout: AtlasLayout::from_result(&result, names) }
+    }
+
+    #[test]
+    fn to_texture_array_json_works() {
+        let atlas = AtlasPages { pages: vec![page(0, Size::new(8, 4), &["a", "b"]), page(1, Size::new(8, 4), &["c"])] };
+
+        assert_eq!(
+            atlas.to_texture_array_json(),
+            format!(
+                "{{\"layers\": [\n\
+                 \t{{\"layer\": 0, \"image\": \"layer_0.png\"}},\n\
+                 \t{{\"layer\": 1, \"image\": \"layer_1.png\"}}\n\
+                 ],\n\
+                 \"frames\": {{\n\
+                 \t\"a\": {{\"layer\": 0, \"frame\": {{\"x\": 0, \"y\": 0, \"w\": 4, \"h\": 2}}, \"uv\": {{\"u0\": 0, \"v0\": 0, \"u1\": 0.5, \"v1\": 0.5}}}},\n\
+                 \t\"b\": {{\"layer\": 0, \"frame\": {{\"x\": 4, \"y\": 0, \"w\": 4, \"h\": 2}}, \"uv\": {{\"u0\": 0.5, \"v0\": 0, \"u1\": 1, \"v1\": 0.5}}}},\n\
+                 \t\"c\": {{\"layer\": 1, \"frame\": {{\"x\": 0, \"y\": 0, \"w\": 4, \"h\": 2}}, \"uv\": {{\"u0\": 0, \"v0\": 0, \"u1\": 0.5, \"v1\": 0.5}}}}\n\
+                 }},\n\
+                 \"meta\": {{\n\
+                 \t\"app\": \"rpack\",\n\
+                 \t\"version\": \"{}\",\n\
+                 \t\"format\": \"RGBA8888\",\n\
+                 \t\"size\": {{\"w\": 8, \"h\": 4}},\n\
+                 \t\"layers\": 2\n\
+                 }}\n}}\n",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must have the same size")]
+    fn layers_of_different_sizes_are_rejected() {
+        let atlas = AtlasPages { pages: vec![page(0, Size::new(8, 4), &["a"]), page(1, Size::new(4, 4), &["b"])] };
+        atlas.to_texture_array_json();
+    }
+}
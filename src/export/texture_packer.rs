@@ -0,0 +1,137 @@
+use std::fmt::Write;
+use crate::export::json_string;
+use crate::{AtlasLayout, AtlasSprite};
+
+/// The flavour of the TexturePacker JSON format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TexturePackerFormat {
+    /// "JSON (hash)": the frames are an object keyed by the sprite names.
+    Hash,
+
+    /// "JSON (array)": the frames are an array, each frame carrying its name in a `filename` field.
+    Array,
+}
+
+impl AtlasLayout {
+    /// Exports the layout in the TexturePacker JSON format, as read by Phaser and PixiJS.
+    ///
+    /// Sprites are never rotated, so `rotated` is always `false`. The source size and the region
+    /// within the source image are written from the trim information of each sprite.
+    ///
+    /// # Arguments
+    /// * `image` - The file name of the atlas image, referenced from the `meta` section.
+    /// * `format` - Whether to write the frames as a hash or as an array.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, Rectangle, RectanglePackingResult, Size};
+    /// use rpack::export::TexturePackerFormat;
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 16, 16)],
+    ///     size: Size::new(16, 16),
+    ///     skipped: Vec::new(),
+    /// };
+    /// let layout = AtlasLayout::from_result(&result, &["coin.png"]);
+    ///
+    /// let json = layout.to_texture_packer_json("atlas.png", TexturePackerFormat::Hash);
+    /// assert!(json.contains(r#""coin.png": {"#));
+    /// ```
+    pub fn to_texture_packer_json(&self, image: &str, format: TexturePackerFormat) -> String {
+        let mut json = String::new();
+
+        match format {
+            TexturePackerFormat::Hash => {
+                json.push_str("{\"frames\": {\n");
+                for (index, sprite) in self.sprites.iter().enumerate() {
+                    writeln!(json, "\t{}: {{", json_string(&sprite.name)).unwrap();
+                    write_frame(&mut json, sprite);
+                    json.push_str(separator(index, self.sprites.len()));
+                }
+                json.push_str("},\n");
+            }
+            TexturePackerFormat::Array => {
+                json.push_str("{\"frames\": [\n");
+                for (index, sprite) in self.sprites.iter().enumerate() {
+                    json.push_str("\t{\n");
+                    writeln!(json, "\t\t\"filename\": {},", json_string(&sprite.name)).unwrap();
+                    write_frame(&mut json, sprite);
+                    json.push_str(separator(index, self.sprites.len()));
+                }
+                json.push_str("],\n");
+            }
+        }
+
+        json.push_str("\"meta\": {\n");
+        json.push_str("\t\"app\": \"rpack\",\n");
+        writeln!(json, "\t\"version\": \"{}\",", env!("CARGO_PKG_VERSION")).unwrap();
+        writeln!(json, "\t\"image\": {},", json_string(image)).unwrap();
+        json.push_str("\t\"format\": \"RGBA8888\",\n");
+        writeln!(json, "\t\"size\": {{\"w\": {}, \"h\": {}}},", self.size.width, self.size.height).unwrap();
+        json.push_str("\t\"scale\": \"1\"\n");
+        json.push_str("}\n}\n");
+        json
+    }
+}
+
+/// Writes the fields of a frame shared by the hash and the array format.
+fn write_frame(json: &mut String, sprite: &AtlasSprite) {
+    let r = &sprite.rectangle;
+    writeln!(json, "\t\t\"frame\": {{\"x\": {}, \"y\": {}, \"w\": {}, \"h\": {}}},", r.x, r.y, r.width, r.height).unwrap();
+    let source = &sprite.source_rectangle;
+    json.push_str("\t\t\"rotated\": false,\n");
+    writeln!(json, "\t\t\"trimmed\": {},", sprite.is_trimmed()).unwrap();
+    writeln!(json, "\t\t\"spriteSourceSize\": {{\"x\": {}, \"y\": {}, \"w\": {}, \"h\": {}}},", source.x, source.y, source.width, source.height).unwrap();
+    writeln!(json, "\t\t\"sourceSize\": {{\"w\": {}, \"h\": {}}}", sprite.source_size.width, sprite.source_size.height).unwrap();
+}
+
+/// Returns the closing brace of the frame at `index`, followed by a comma unless it is the last frame.
+fn separator(index: usize, len: usize) -> &'static str {
+    if index + 1 < len {
+        "\t},\n"
+    } else {
+        "\t}\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Rectangle, Size};
+    use crate::export::test_layout;
+
+    use super::*;
+
+    #[test]
+    fn hash_format_works() {
+        let json = test_layout(["a.png", "b.png"]).to_texture_packer_json("atlas.png", TexturePackerFormat::Hash);
+
+        assert!(json.starts_with("{\"frames\": {\n\t\"a.png\": {\n"));
+        assert!(json.contains("\t\t\"frame\": {\"x\": 4, \"y\": 0, \"w\": 2, \"h\": 2},\n"));
+        assert!(json.contains("\t\t\"sourceSize\": {\"w\": 4, \"h\": 2}\n\t},\n\t\"b.png\""));
+        assert!(json.contains("\t\"image\": \"atlas.png\",\n"));
+        assert!(json.contains("\t\"size\": {\"w\": 6, \"h\": 2},\n"));
+    }
+
+    #[test]
+    fn array_format_works() {
+        let json = test_layout(["a.png", "b.png"]).to_texture_packer_json("atlas.png", TexturePackerFormat::Array);
+
+        assert!(json.starts_with("{\"frames\": [\n\t{\n\t\t\"filename\": \"a.png\",\n"));
+        assert!(json.contains("\t}\n],\n\"meta\""));
+        assert!(json.ends_with("}\n}\n"));
+    }
+
+    #[test]
+    fn trimmed_sprites_work() {
+        let mut layout = test_layout(["a.png", "b.png"]);
+        layout.sprites[1].source_rectangle = Rectangle::new(3, 1, 2, 2);
+        layout.sprites[1].source_size = Size::new(6, 5);
+        let json = layout.to_texture_packer_json("atlas.png", TexturePackerFormat::Hash);
+
+        assert!(json.contains(
+            "\t\t\"trimmed\": true,\n\t\t\"spriteSourceSize\": {\"x\": 3, \"y\": 1, \"w\": 2, \"h\": 2},\n\
+             \t\t\"sourceSize\": {\"w\": 6, \"h\": 5}\n"
+        ));
+        assert!(json.contains("\t\t\"trimmed\": false,\n"));
+    }
+}
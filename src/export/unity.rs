@@ -0,0 +1,96 @@
+use std::fmt::Write;
+use crate::export::json_string;
+use crate::AtlasLayout;
+
+impl AtlasLayout {
+    /// Exports the layout as JSON sprite metadata for a Unity import step.
+    ///
+    /// Every sprite is written with its name, its rect and its pivot, matching the fields of Unity's `SpriteMetaData`.
+    /// Unity places the origin of a texture in its bottom-left corner, so the y coordinate of each rect is flipped.
+    /// Nine-slice sprites additionally get a `border` with their left, bottom, right and top insets as `x`, `y`, `z` and `w`.
+    ///
+    /// # Arguments
+    /// * `texture` - The file name of the atlas texture.
+    /// * `pivot` - The normalized pivot of every sprite, where `(0.5, 0.5)` is the center and `(0.0, 0.0)` the bottom-left corner.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{AtlasLayout, Rectangle, RectanglePackingResult, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 16, 16)],
+    ///     size: Size::new(16, 32),
+    ///     skipped: Vec::new(),
+    /// };
+    /// let layout = AtlasLayout::from_result(&result, &["coin"]);
+    ///
+    /// let json = layout.to_unity_json("atlas.png", (0.5, 0.5));
+    /// assert!(json.contains(r#""rect": {"x": 0, "y": 16, "width": 16, "height": 16}"#));
+    /// ```
+    pub fn to_unity_json(&self, texture: &str, pivot: (f64, f64)) -> String {
+        let mut json = String::new();
+        json.push_str("{\n");
+        writeln!(json, "\t\"texture\": {},", json_string(texture)).unwrap();
+        writeln!(json, "\t\"width\": {},", self.size.width).unwrap();
+        writeln!(json, "\t\"height\": {},", self.size.height).unwrap();
+        json.push_str("\t\"sprites\": [\n");
+
+        for (index, sprite) in self.sprites.iter().enumerate() {
+            let r = &sprite.rectangle;
+            let y = self.size.height - r.y - r.height;
+            write!(
+                json,
+                "\t\t{{\"name\": {}, \"rect\": {{\"x\": {}, \"y\": {}, \"width\": {}, \"height\": {}}}, \"pivot\": {{\"x\": {:?}, \"y\": {:?}}}",
+                json_string(&sprite.name), r.x, y, r.width, r.height, pivot.0, pivot.1
+            ).unwrap();
+            if let Some(n) = &sprite.nine_slice {
+                write!(json, ", \"border\": {{\"x\": {}, \"y\": {}, \"z\": {}, \"w\": {}}}", n.left, n.bottom, n.right, n.top).unwrap();
+            }
+            json.push('}');
+            json.push_str(if index + 1 < self.sprites.len() { ",\n" } else { "\n" });
+        }
+
+        json.push_str("\t]\n}\n");
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NineSlice, Rectangle, RectanglePackingResult, Size};
+
+    use super::*;
+
+    #[test]
+    fn to_unity_json_works() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 4, 2), Rectangle::new(4, 2, 2, 2)],
+            size: Size::new(6, 6),
+            skipped: Vec::new(),
+        };
+        let layout = AtlasLayout::from_result(&result, &["a", "b"]);
+
+        assert_eq!(
+            layout.to_unity_json("atlas.png", (0.0, 1.0)),
+            "{\n\t\"texture\": \"atlas.png\",\n\t\"width\": 6,\n\t\"height\": 6,\n\t\"sprites\": [\n\
+             \t\t{\"name\": \"a\", \"rect\": {\"x\": 0, \"y\": 4, \"width\": 4, \"height\": 2}, \"pivot\": {\"x\": 0.0, \"y\": 1.0}},\n\
+             \t\t{\"name\": \"b\", \"rect\": {\"x\": 4, \"y\": 2, \"width\": 2, \"height\": 2}, \"pivot\": {\"x\": 0.0, \"y\": 1.0}}\n\
+             \t]\n}\n"
+        );
+    }
+
+    #[test]
+    fn nine_slices_have_borders() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 8, 8)],
+            size: Size::new(8, 8),
+            skipped: Vec::new(),
+        };
+        let mut layout = AtlasLayout::from_result(&result, &["panel"]);
+        layout.sprites[0].nine_slice = Some(NineSlice::new(1, 2, 3, 4));
+
+        assert!(layout.to_unity_json("atlas.png", (0.5, 0.5)).contains(
+            r#""pivot": {"x": 0.5, "y": 0.5}, "border": {"x": 1, "y": 4, "z": 3, "w": 2}}"#
+        ));
+    }
+}
@@ -0,0 +1,580 @@
+//! A C interface to the rectangle packers, declared in `include/rpack.h`.
+//!
+//! The header is generated from this module with cbindgen, using the `cbindgen.toml` of the repository:
+//! `cbindgen --config cbindgen.toml --output include/rpack.h src/ffi.rs`.
+//! The crate is only built as a Rust library by default, so the library to link against is built with
+//! `cargo rustc --lib --release --features ffi --crate-type staticlib` (or `cdylib` for a shared library).
+//! The config is an opaque handle, so options can be added without breaking the ABI.
+
+use std::ffi::{c_char, c_int};
+use std::panic::catch_unwind;
+use std::slice;
+use crate::{
+    pack_pages, HeightRectPacker, ObjectiveWeights, OverflowPolicy, PageFill, RectanglePacker, RectanglePackerConfig,
+    Size, ZeroSizePolicy,
+};
+
+/// The rectangles were packed successfully.
+pub const RPACK_OK: c_int = 0;
+
+/// A required pointer argument was null.
+pub const RPACK_ERROR_NULL_POINTER: c_int = 1;
+
+/// The rectangles could not be packed, e.g. because they do not fit into the maximum size.
+pub const RPACK_ERROR_PACKING_FAILED: c_int = 2;
+
+/// The packer panicked.
+pub const RPACK_ERROR_PANIC: c_int = 3;
+
+/// An argument was not one of the values it can take, e.g. an unknown policy.
+pub const RPACK_ERROR_INVALID_ARGUMENT: c_int = 4;
+
+/// Sizes with a width or height of zero are placed like any other size.
+pub const RPACK_ZERO_SIZE_PLACE: c_int = 0;
+
+/// Packing fails if any size has a width or height of zero.
+pub const RPACK_ZERO_SIZE_REJECT: c_int = 1;
+
+/// Sizes with a width or height of zero are skipped and reported as such.
+pub const RPACK_ZERO_SIZE_SKIP: c_int = 2;
+
+/// `rpack_pack_pages` fails if the sizes do not fit into a single page.
+pub const RPACK_OVERFLOW_ERROR: c_int = 0;
+
+/// `rpack_pack_pages` opens new pages for the sizes that do not fit.
+pub const RPACK_OVERFLOW_NEW_PAGE: c_int = 1;
+
+/// `rpack_pack_pages` packs as many sizes as fit into a single page and leaves the others unplaced.
+pub const RPACK_OVERFLOW_PARTIAL: c_int = 2;
+
+/// `rpack_pack_pages` grows the single page beyond the maximum size.
+pub const RPACK_OVERFLOW_GROW: c_int = 3;
+
+/// New pages are filled one after the other.
+pub const RPACK_PAGE_FILL_SEQUENTIAL: c_int = 0;
+
+/// The sizes are distributed evenly over the pages.
+pub const RPACK_PAGE_FILL_BALANCED: c_int = 1;
+
+/// Every size is placed on the first page it fits on.
+pub const RPACK_PAGE_FILL_FIRST_FIT: c_int = 2;
+
+/// Every size is placed on the fullest page it fits on.
+pub const RPACK_PAGE_FILL_BEST_FIT: c_int = 3;
+
+/// Every size is placed on the emptiest page it fits on.
+pub const RPACK_PAGE_FILL_WORST_FIT: c_int = 4;
+
+/// The page written by `rpack_pack_pages` for sizes that were skipped or not placed.
+pub const RPACK_NOT_PLACED: usize = usize::MAX;
+
+/// The size of a rectangle or container.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpackSize {
+    pub width: usize,
+    pub height: usize,
+}
+
+/// A packed rectangle, given by its top-left corner and its size.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpackRect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// The configuration of the packers, an opaque handle to a `RectanglePackerConfig`.
+///
+/// Created with `rpack_config_new`, changed with the `rpack_config_set_*` functions and freed with `rpack_config_free`.
+#[derive(Debug, Default)]
+pub struct RpackConfig {
+    config: RectanglePackerConfig,
+}
+
+/// Creates a config with the default values of `RectanglePackerConfig`, to be freed with `rpack_config_free`.
+#[no_mangle]
+pub extern "C" fn rpack_config_new() -> *mut RpackConfig {
+    Box::into_raw(Box::default())
+}
+
+/// Frees a config created with `rpack_config_new`. Does nothing if `config` is null.
+///
+/// # Safety
+/// `config` must be null or a config created with `rpack_config_new` that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_free(config: *mut RpackConfig) {
+    if !config.is_null() {
+        drop(Box::from_raw(config));
+    }
+}
+
+/// Sets the maximum size of the container. A `width` or `height` of `0` means that the container size is determined dynamically.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_max_size(config: *mut RpackConfig, width: usize, height: usize) -> c_int {
+    update(config, |config| {
+        config.max_size = match (width, height) {
+            (0, _) | (_, 0) => None,
+            (width, height) => Some(Size::new(width, height)),
+        };
+        true
+    })
+}
+
+/// Sets the margin kept free around each rectangle, the `rectangle_padding` of the config.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_rectangle_padding(config: *mut RpackConfig, padding: usize) -> c_int {
+    update(config, |config| {
+        config.rectangle_padding = padding;
+        true
+    })
+}
+
+/// Sets the padding added to every side of each rectangle, which is part of the packed rectangles.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_owned_padding(config: *mut RpackConfig, padding: usize) -> c_int {
+    update(config, |config| {
+        config.owned_padding = padding;
+        true
+    })
+}
+
+/// Sets the padding kept free along the edges of the container.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_border_padding(config: *mut RpackConfig, padding: usize) -> c_int {
+    update(config, |config| {
+        config.border_padding = padding;
+        true
+    })
+}
+
+/// Sets the space added between the columns and between the rows of rectangles.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_gutters(config: *mut RpackConfig, column: usize, row: usize) -> c_int {
+    update(config, |config| {
+        config.column_gutter = column;
+        config.row_gutter = row;
+        true
+    })
+}
+
+/// Sets the multiple the height of every row is rounded up to. A `multiple` of `0` leaves the heights unrounded.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_row_height_multiple(config: *mut RpackConfig, multiple: usize) -> c_int {
+    update(config, |config| {
+        config.row_height_multiple = (multiple > 0).then_some(multiple);
+        true
+    })
+}
+
+/// Sets whether the space left below the shorter rectangles of every row is filled with smaller rectangles.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_fill_row_gaps(config: *mut RpackConfig, fill: bool) -> c_int {
+    update(config, |config| {
+        config.fill_row_gaps = fill;
+        true
+    })
+}
+
+/// Sets how sizes with a width or height of zero are treated, one of the `RPACK_ZERO_SIZE_*` values.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_zero_size_policy(config: *mut RpackConfig, policy: c_int) -> c_int {
+    update(config, |config| {
+        config.zero_size_policy = match policy {
+            RPACK_ZERO_SIZE_PLACE => ZeroSizePolicy::Place,
+            RPACK_ZERO_SIZE_REJECT => ZeroSizePolicy::Reject,
+            RPACK_ZERO_SIZE_SKIP => ZeroSizePolicy::Skip,
+            _ => return false,
+        };
+        true
+    })
+}
+
+/// Sets whether identical sizes are packed only once, sharing the same rectangle.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_deduplicate_sizes(config: *mut RpackConfig, deduplicate: bool) -> c_int {
+    update(config, |config| {
+        config.deduplicate_sizes = deduplicate;
+        true
+    })
+}
+
+/// Sets the weights the packers trade off the dimensions of the container with, if there is no maximum size.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_objective(
+    config: *mut RpackConfig,
+    height: f64,
+    squareness: f64,
+    perimeter: f64,
+    area: f64,
+) -> c_int {
+    update(config, |config| {
+        config.objective = Some(ObjectiveWeights { height, squareness, perimeter, area });
+        true
+    })
+}
+
+/// Sets the maximum number of bytes of temporary memory the packers may use. A `bytes` of `0` removes the limit.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_memory_budget(config: *mut RpackConfig, bytes: usize) -> c_int {
+    update(config, |config| {
+        config.memory_budget = (bytes > 0).then_some(bytes);
+        true
+    })
+}
+
+/// Sets what `rpack_pack_pages` does if the sizes do not fit into the maximum size, one of the `RPACK_OVERFLOW_*` values.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_overflow(config: *mut RpackConfig, overflow: c_int) -> c_int {
+    update(config, |config| {
+        config.overflow = match overflow {
+            RPACK_OVERFLOW_ERROR => OverflowPolicy::Error,
+            RPACK_OVERFLOW_NEW_PAGE => OverflowPolicy::NewPage,
+            RPACK_OVERFLOW_PARTIAL => OverflowPolicy::Partial,
+            RPACK_OVERFLOW_GROW => OverflowPolicy::Grow,
+            _ => return false,
+        };
+        true
+    })
+}
+
+/// Sets the maximum number of pages `rpack_pack_pages` may open. A `max_pages` of `0` removes the limit.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_max_pages(config: *mut RpackConfig, max_pages: usize) -> c_int {
+    update(config, |config| {
+        config.max_pages = (max_pages > 0).then_some(max_pages);
+        true
+    })
+}
+
+/// Sets how `rpack_pack_pages` distributes the sizes over the pages, one of the `RPACK_PAGE_FILL_*` values.
+///
+/// # Safety
+/// `config` must be null or a valid config created with `rpack_config_new`.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_config_set_page_fill(config: *mut RpackConfig, fill: c_int) -> c_int {
+    update(config, |config| {
+        config.page_fill = match fill {
+            RPACK_PAGE_FILL_SEQUENTIAL => PageFill::Sequential,
+            RPACK_PAGE_FILL_BALANCED => PageFill::Balanced,
+            RPACK_PAGE_FILL_FIRST_FIT => PageFill::FirstFit,
+            RPACK_PAGE_FILL_BEST_FIT => PageFill::BestFit,
+            RPACK_PAGE_FILL_WORST_FIT => PageFill::WorstFit,
+            _ => return false,
+        };
+        true
+    })
+}
+
+/// Packs `count` rectangle sizes with the `HeightRectPacker`.
+///
+/// On success, the packed rectangles are written to `out_rects` in the order of the sizes,
+/// the size of the container is written to `out_size` and `RPACK_OK` is returned. If `out_skipped` is not null,
+/// it receives whether each size was skipped by the zero size policy, in which case its rectangle is empty.
+/// Otherwise one of the `RPACK_ERROR_*` codes is returned and the outputs are left untouched.
+///
+/// # Safety
+/// `sizes`, `out_rects` and `out_skipped`, unless it is null, must point to arrays of at least `count` elements and
+/// `out_size` to a single `RpackSize`. If `config` is null, the default config is used; otherwise it must be a valid
+/// config created with `rpack_config_new`. If `count` is `0`, `sizes` and `out_rects` may be null.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_pack(
+    sizes: *const RpackSize,
+    count: usize,
+    config: *const RpackConfig,
+    out_rects: *mut RpackRect,
+    out_skipped: *mut bool,
+    out_size: *mut RpackSize,
+) -> c_int {
+    if out_size.is_null() || (count > 0 && (sizes.is_null() || out_rects.is_null())) {
+        return RPACK_ERROR_NULL_POINTER;
+    }
+
+    let sizes = read_sizes(sizes, count);
+    let default = RectanglePackerConfig::default();
+    let config = config.as_ref().map_or(&default, |config| &config.config);
+
+    let result = match catch_unwind(|| HeightRectPacker::pack(&sizes, config)) {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => return RPACK_ERROR_PACKING_FAILED,
+        Err(_) => return RPACK_ERROR_PANIC,
+    };
+
+    if count > 0 {
+        let out_rects = slice::from_raw_parts_mut(out_rects, count);
+        for (out, r) in out_rects.iter_mut().zip(&result.rectangles) {
+            *out = RpackRect { x: r.x, y: r.y, width: r.width, height: r.height };
+        }
+    }
+    write_skipped(out_skipped, count, &result.skipped);
+    *out_size = RpackSize { width: result.size.width, height: result.size.height };
+    RPACK_OK
+}
+
+/// Packs `count` rectangle sizes with the `HeightRectPacker` onto pages, applying the overflow policy,
+/// the maximum number of pages and the page fill of the config.
+///
+/// On success, the page of every size is written to `out_pages` and its rectangle on that page to `out_rects`,
+/// in the order of the sizes, and `RPACK_OK` is returned. Sizes that were skipped by the zero size policy or not
+/// placed get the page `RPACK_NOT_PLACED`. The size of every page is written to `out_page_sizes`,
+/// and the number of pages to `out_page_count`. Otherwise one of the `RPACK_ERROR_*` codes is returned
+/// and the outputs are left untouched.
+///
+/// # Safety
+/// `sizes`, `out_pages` and `out_rects` must point to arrays of at least `count` elements, `out_page_sizes` to an
+/// array of at least `count` elements, or one if `count` is `0`, and `out_page_count` to a single `size_t`.
+/// If `config` is null, the default config is used; otherwise it must be a valid config created with `rpack_config_new`.
+/// If `count` is `0`, `sizes`, `out_pages` and `out_rects` may be null.
+#[no_mangle]
+pub unsafe extern "C" fn rpack_pack_pages(
+    sizes: *const RpackSize,
+    count: usize,
+    config: *const RpackConfig,
+    out_pages: *mut usize,
+    out_rects: *mut RpackRect,
+    out_page_sizes: *mut RpackSize,
+    out_page_count: *mut usize,
+) -> c_int {
+    if out_page_sizes.is_null()
+        || out_page_count.is_null()
+        || (count > 0 && (sizes.is_null() || out_pages.is_null() || out_rects.is_null()))
+    {
+        return RPACK_ERROR_NULL_POINTER;
+    }
+
+    let sizes = read_sizes(sizes, count);
+    let default = RectanglePackerConfig::default();
+    let config = config.as_ref().map_or(&default, |config| &config.config);
+
+    let result = match catch_unwind(|| pack_pages::<HeightRectPacker>(&sizes, config)) {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => return RPACK_ERROR_PACKING_FAILED,
+        Err(_) => return RPACK_ERROR_PANIC,
+    };
+    // Every page holds at least one size, so the pages fit into the documented capacity.
+    if result.pages.len() > count.max(1) {
+        return RPACK_ERROR_PACKING_FAILED;
+    }
+
+    if count > 0 {
+        let out_pages = slice::from_raw_parts_mut(out_pages, count);
+        let out_rects = slice::from_raw_parts_mut(out_rects, count);
+        for ((page, rect), placement) in out_pages.iter_mut().zip(out_rects.iter_mut()).zip(&result.placements) {
+            (*page, *rect) = match placement {
+                Some(placement) => {
+                    let r = placement.rectangle;
+                    (placement.page, RpackRect { x: r.x, y: r.y, width: r.width, height: r.height })
+                }
+                None => (RPACK_NOT_PLACED, RpackRect { x: 0, y: 0, width: 0, height: 0 }),
+            };
+        }
+    }
+    let out_page_sizes = slice::from_raw_parts_mut(out_page_sizes, result.pages.len());
+    for (out, size) in out_page_sizes.iter_mut().zip(&result.pages) {
+        *out = RpackSize { width: size.width, height: size.height };
+    }
+    *out_page_count = result.pages.len();
+    RPACK_OK
+}
+
+/// Returns a static, null-terminated description of a status code returned by the functions of this interface.
+#[no_mangle]
+pub extern "C" fn rpack_status_message(status: c_int) -> *const c_char {
+    let message: &'static [u8] = match status {
+        RPACK_OK => b"ok\0",
+        RPACK_ERROR_NULL_POINTER => b"a required pointer argument was null\0",
+        RPACK_ERROR_PACKING_FAILED => b"the rectangles could not be packed\0",
+        RPACK_ERROR_PANIC => b"the packer panicked\0",
+        RPACK_ERROR_INVALID_ARGUMENT => b"an argument was not one of the values it can take\0",
+        _ => b"unknown status\0",
+    };
+    message.as_ptr() as *const c_char
+}
+
+/// Changes the config behind the handle, returning `RPACK_ERROR_INVALID_ARGUMENT` if `change` rejects its arguments.
+unsafe fn update<F: FnOnce(&mut RectanglePackerConfig) -> bool>(config: *mut RpackConfig, change: F) -> c_int {
+    match config.as_mut().map(|config| change(&mut config.config)) {
+        None => RPACK_ERROR_NULL_POINTER,
+        Some(true) => RPACK_OK,
+        Some(false) => RPACK_ERROR_INVALID_ARGUMENT,
+    }
+}
+
+/// Reads `count` sizes, which may be null if `count` is `0`.
+unsafe fn read_sizes(sizes: *const RpackSize, count: usize) -> Vec<Size> {
+    match count {
+        0 => Vec::new(),
+        _ => slice::from_raw_parts(sizes, count).iter().map(|s| Size::new(s.width, s.height)).collect(),
+    }
+}
+
+/// Writes whether each of the `count` sizes was skipped to `out_skipped`, unless it is null.
+unsafe fn write_skipped(out_skipped: *mut bool, count: usize, skipped: &[usize]) {
+    if count > 0 && !out_skipped.is_null() {
+        let out_skipped = slice::from_raw_parts_mut(out_skipped, count);
+        for (index, out) in out_skipped.iter_mut().enumerate() {
+            *out = skipped.binary_search(&index).is_ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+    use std::ptr;
+
+    use super::*;
+
+    const EMPTY: RpackRect = RpackRect { x: 0, y: 0, width: 0, height: 0 };
+
+    #[test]
+    fn rpack_pack_works() {
+        let sizes = [RpackSize { width: 10, height: 20 }, RpackSize { width: 30, height: 5 }];
+        let mut rects = [EMPTY; 2];
+        let mut size = RpackSize { width: 0, height: 0 };
+
+        let status = unsafe { rpack_pack(sizes.as_ptr(), sizes.len(), ptr::null(), rects.as_mut_ptr(), ptr::null_mut(), &mut size) };
+
+        assert_eq!(status, RPACK_OK);
+        assert_eq!((rects[0].width, rects[0].height), (10, 20));
+        assert_eq!((rects[1].width, rects[1].height), (30, 5));
+        assert!(size.width >= 30 && size.height >= 25);
+    }
+
+    #[test]
+    fn rpack_pack_reports_errors() {
+        let sizes = [RpackSize { width: 10, height: 20 }];
+        let mut rects = [EMPTY];
+        let mut size = RpackSize { width: 0, height: 0 };
+        let config = rpack_config_new();
+        unsafe {
+            assert_eq!(rpack_config_set_max_size(config, 5, 5), RPACK_OK);
+
+            let status = rpack_pack(sizes.as_ptr(), 1, config, rects.as_mut_ptr(), ptr::null_mut(), &mut size);
+            assert_eq!(status, RPACK_ERROR_PACKING_FAILED);
+
+            let status = rpack_pack(ptr::null(), 1, config, rects.as_mut_ptr(), ptr::null_mut(), &mut size);
+            assert_eq!(status, RPACK_ERROR_NULL_POINTER);
+
+            rpack_config_free(config);
+        }
+    }
+
+    #[test]
+    fn config_options_are_applied() {
+        let sizes = [RpackSize { width: 4, height: 4 }, RpackSize { width: 0, height: 3 }, RpackSize { width: 4, height: 4 }];
+        let mut rects = [EMPTY; 3];
+        let mut skipped = [false; 3];
+        let mut size = RpackSize { width: 0, height: 0 };
+        let config = rpack_config_new();
+        unsafe {
+            assert_eq!(rpack_config_set_max_size(config, 20, 10), RPACK_OK);
+            assert_eq!(rpack_config_set_owned_padding(config, 1), RPACK_OK);
+            assert_eq!(rpack_config_set_gutters(config, 2, 0), RPACK_OK);
+            assert_eq!(rpack_config_set_zero_size_policy(config, RPACK_ZERO_SIZE_SKIP), RPACK_OK);
+            assert_eq!(rpack_config_set_zero_size_policy(config, 7), RPACK_ERROR_INVALID_ARGUMENT);
+            assert_eq!(rpack_config_set_page_fill(ptr::null_mut(), RPACK_PAGE_FILL_BALANCED), RPACK_ERROR_NULL_POINTER);
+
+            let status = rpack_pack(sizes.as_ptr(), 3, config, rects.as_mut_ptr(), skipped.as_mut_ptr(), &mut size);
+            rpack_config_free(config);
+            assert_eq!(status, RPACK_OK);
+        }
+
+        assert_eq!(skipped, [false, true, false]);
+        assert_eq!(rects[0], RpackRect { x: 0, y: 0, width: 6, height: 6 });
+        assert_eq!(rects[2], RpackRect { x: 8, y: 0, width: 6, height: 6 });
+        assert_eq!(size, RpackSize { width: 20, height: 10 });
+    }
+
+    #[test]
+    fn rpack_pack_pages_works() {
+        let sizes = [RpackSize { width: 8, height: 8 }, RpackSize { width: 0, height: 2 }, RpackSize { width: 8, height: 8 }];
+        let mut pages = [0; 3];
+        let mut rects = [EMPTY; 3];
+        let mut page_sizes = [RpackSize { width: 0, height: 0 }; 3];
+        let mut page_count = 0;
+        let config = rpack_config_new();
+        unsafe {
+            rpack_config_set_max_size(config, 10, 10);
+            rpack_config_set_zero_size_policy(config, RPACK_ZERO_SIZE_SKIP);
+            assert_eq!(rpack_config_set_overflow(config, RPACK_OVERFLOW_NEW_PAGE), RPACK_OK);
+
+            let status = rpack_pack_pages(
+                sizes.as_ptr(),
+                3,
+                config,
+                pages.as_mut_ptr(),
+                rects.as_mut_ptr(),
+                page_sizes.as_mut_ptr(),
+                &mut page_count,
+            );
+            assert_eq!(status, RPACK_OK);
+
+            // With a limit of one page, the second page can not be opened.
+            rpack_config_set_max_pages(config, 1);
+            let status = rpack_pack_pages(
+                sizes.as_ptr(),
+                3,
+                config,
+                pages.as_mut_ptr(),
+                rects.as_mut_ptr(),
+                page_sizes.as_mut_ptr(),
+                &mut page_count,
+            );
+            assert_eq!(status, RPACK_ERROR_PACKING_FAILED);
+            rpack_config_free(config);
+        }
+
+        assert_eq!(pages, [0, RPACK_NOT_PLACED, 1]);
+        assert_eq!(rects[2], RpackRect { x: 0, y: 0, width: 8, height: 8 });
+        assert_eq!(page_count, 2);
+        assert_eq!(page_sizes[..2], [RpackSize { width: 10, height: 10 }; 2]);
+    }
+
+    #[test]
+    fn rpack_status_message_works() {
+        let message = unsafe { CStr::from_ptr(rpack_status_message(RPACK_ERROR_PACKING_FAILED)) };
+        assert_eq!(message.to_str().unwrap(), "the rectangles could not be packed");
+    }
+}
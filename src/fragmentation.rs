@@ -0,0 +1,215 @@
+use crate::free_rectangles::FreeRectangles;
+use crate::{Area, Rectangle, RectanglePackingResult, RectanglePackerConfig};
+
+/// A bucket of a `FragmentationHistogram`, counting the free rectangles whose area lies in `min_area..max_area`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistogramBucket {
+    /// The smallest area counted by this bucket.
+    pub min_area: usize,
+
+    /// The area up to which (exclusively) free rectangles are counted by this bucket.
+    pub max_area: usize,
+
+    /// The number of free rectangles in this bucket.
+    pub count: usize,
+}
+
+/// The decomposition of the unused area of a packing into maximal free rectangles.
+#[derive(Debug, Clone)]
+pub struct FragmentationHistogram {
+    /// All maximal free rectangles of the container.
+    ///
+    /// A free rectangle is maximal if it can not be extended in any direction without overlapping a packed
+    /// rectangle, its padding or the border padding. The free rectangles may overlap each other.
+    pub free_rectangles: Vec<Rectangle>,
+
+    /// The total free area of the container, excluding the border padding and the padding around each rectangle.
+    pub free_area: usize,
+
+    /// The number of free rectangles by area, in buckets of powers of two, starting with the bucket `1..2`.
+    pub buckets: Vec<HistogramBucket>,
+}
+
+impl FragmentationHistogram {
+    /// Returns the largest free rectangle.
+    pub fn largest_free_rectangle(&self) -> Option<Rectangle> {
+        self.free_rectangles.iter().copied().max_by_key(|r| r.area())
+    }
+
+    /// Returns how fragmented the free area is, as a value between `0.0` and `1.0`.
+    ///
+    /// A value of `0.0` means that the whole free area is available as a single rectangle,
+    /// while values close to `1.0` mean that the free area is scattered into many small pieces.
+    /// If there is no free area at all, `0.0` is returned.
+    pub fn fragmentation(&self) -> f64 {
+        match (self.largest_free_rectangle(), self.free_area) {
+            (Some(largest), free_area) if free_area > 0 => 1.0 - largest.area() as f64 / free_area as f64,
+            _ => 0.0,
+        }
+    }
+}
+
+impl RectanglePackingResult {
+    /// Decomposes the unused area of the container into maximal free rectangles and
+    /// returns a histogram of their sizes.
+    ///
+    /// The padding around each rectangle and the border padding are treated as occupied,
+    /// so a free rectangle of size `w x h` can take a new rectangle of size
+    /// `(w - 2 * rectangle_padding) x (h - 2 * rectangle_padding)`.
+    ///
+    /// # Arguments
+    /// * `config` - The config the result was packed with.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{Rectangle, RectanglePackingResult, RectanglePackerConfig, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 6, 4)],
+    ///     size: Size::new(8, 8),
+    ///     skipped: Vec::new(),
+    /// };
+    ///
+    /// let histogram = result.fragmentation_histogram(&RectanglePackerConfig::default());
+    /// assert_eq!(histogram.free_rectangles.len(), 2);
+    /// assert_eq!(histogram.free_area, 40);
+    /// assert_eq!(histogram.largest_free_rectangle(), Some(Rectangle::new(0, 4, 8, 4)));
+    /// ```
+    pub fn fragmentation_histogram(&self, config: &RectanglePackerConfig) -> FragmentationHistogram {
+        let border = config.border_padding;
+        let padding = config.rectangle_padding;
+        let inner = Rectangle::new(
+            border,
+            border,
+            self.size.width.saturating_sub(2 * border),
+            self.size.height.saturating_sub(2 * border),
+        );
+
+        let mut free = FreeRectangles::new(inner);
+        for rectangle in &self.rectangles {
+            free.occupy(&rectangle.expanded(padding));
+        }
+
+        // The paddings of neighbouring rectangles may overlap,
+        // so the occupied area is the area of the union of the padded rectangles.
+        let free_area = inner.area() - occupied_area(&self.rectangles, padding, &inner);
+
+        let free_rectangles: Vec<Rectangle> = free.iter().collect();
+        let mut buckets: Vec<HistogramBucket> = Vec::new();
+        for rectangle in &free_rectangles {
+            let bucket = rectangle.area().ilog2() as usize;
+            while buckets.len() <= bucket {
+                let min_area = 1 << buckets.len();
+                buckets.push(HistogramBucket { min_area, max_area: min_area * 2, count: 0 });
+            }
+            buckets[bucket].count += 1;
+        }
+
+        FragmentationHistogram {
+            free_rectangles,
+            free_area,
+            buckets,
+        }
+    }
+}
+
+/// Returns the area within `bounds` covered by the union of the padded rectangles.
+fn occupied_area(rectangles: &[Rectangle], padding: usize, bounds: &Rectangle) -> usize {
+    let clipped: Vec<Rectangle> = rectangles
+        .iter()
+        .map(|r| r.expanded(padding))
+        .filter(|r| r.intersects(bounds))
+        .map(|r| {
+            let x = r.x.max(bounds.x);
+            let y = r.y.max(bounds.y);
+            let right = (r.x + r.width).min(bounds.x + bounds.width);
+            let bottom = (r.y + r.height).min(bounds.y + bounds.height);
+            Rectangle::new(x, y, right - x, bottom - y)
+        })
+        .collect();
+
+    // Sweep over the distinct y coordinates and sum up the covered width of each band.
+    let mut ys: Vec<usize> = clipped.iter().flat_map(|r| [r.y, r.y + r.height]).collect();
+    ys.sort_unstable();
+    ys.dedup();
+
+    let mut area = 0;
+    for band in ys.windows(2) {
+        let mut spans: Vec<(usize, usize)> = clipped
+            .iter()
+            .filter(|r| r.y <= band[0] && r.y + r.height >= band[1])
+            .map(|r| (r.x, r.x + r.width))
+            .collect();
+        spans.sort_unstable();
+
+        let mut covered = 0;
+        let mut end = 0;
+        for (start, stop) in spans {
+            let start = start.max(end);
+            if stop > start {
+                covered += stop - start;
+                end = stop;
+            }
+        }
+        area += covered * (band[1] - band[0]);
+    }
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Size;
+
+    use super::*;
+
+    #[test]
+    fn histogram_works() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 4, 4), Rectangle::new(4, 0, 4, 2)],
+            size: Size::new(8, 8),
+            skipped: Vec::new(),
+        };
+        let histogram = result.fragmentation_histogram(&RectanglePackerConfig::default());
+
+        // (4, 2, 4, 6) and (0, 4, 8, 4)
+        assert_eq!(histogram.free_rectangles.len(), 2);
+        assert_eq!(histogram.free_area, 40);
+        assert_eq!(histogram.buckets.len(), 6);
+        assert_eq!(histogram.buckets[4], HistogramBucket { min_area: 16, max_area: 32, count: 1 });
+        assert_eq!(histogram.buckets[5], HistogramBucket { min_area: 32, max_area: 64, count: 1 });
+        assert!((histogram.fragmentation() - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn histogram_with_padding_works() {
+        let config = RectanglePackerConfig {
+            rectangle_padding: 1,
+            border_padding: 1,
+            ..Default::default()
+        };
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(2, 2, 2, 2), Rectangle::new(5, 2, 2, 2)],
+            size: Size::new(10, 10),
+            skipped: Vec::new(),
+        };
+        let histogram = result.fragmentation_histogram(&config);
+
+        // The paddings of both rectangles overlap in a 1 x 4 column.
+        assert_eq!(histogram.free_area, 64 - 28);
+        assert_eq!(histogram.largest_free_rectangle(), Some(Rectangle::new(1, 5, 8, 4)));
+    }
+
+    #[test]
+    fn full_container_is_not_fragmented() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 4, 4)],
+            size: Size::new(4, 4),
+            skipped: Vec::new(),
+        };
+        let histogram = result.fragmentation_histogram(&RectanglePackerConfig::default());
+
+        assert!(histogram.free_rectangles.is_empty());
+        assert!(histogram.buckets.is_empty());
+        assert_eq!(histogram.fragmentation(), 0.0);
+    }
+}
@@ -0,0 +1,578 @@
+use crate::Rectangle;
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+
+/// The number of free rectangles from which on candidates are scored in parallel.
+#[cfg(feature = "rayon")]
+const PARALLEL_SCORING_THRESHOLD: usize = 1024;
+
+/// The number of free rectangles a block holds before it is split in two.
+///
+/// A block never holds more than `BLOCK_CAPACITY + 1` rectangles, so the rectangles of a block
+/// can be selected with the bits of a `u64` mask.
+const BLOCK_CAPACITY: usize = 32;
+
+/// A group of free rectangles with similar x coordinates, together with bounds on their positions and sizes.
+///
+/// The bounds allow queries to skip whole blocks without looking at their rectangles.
+/// The coordinates of the rectangles are stored in separate arrays (SoA), and the rectangles of a block are tested
+/// at once by plain loops building a `u64` mask. There is no explicit SIMD, the loops are left to auto-vectorization.
+#[derive(Debug, Clone)]
+struct Block {
+    /// The smallest x coordinate of the rectangles inserted into this block. Blocks are ordered by it.
+    start: usize,
+    xs: Vec<usize>,
+    ys: Vec<usize>,
+    widths: Vec<usize>,
+    heights: Vec<usize>,
+    min_x: usize,
+    min_y: usize,
+    max_right: usize,
+    max_bottom: usize,
+    max_width: usize,
+    max_height: usize,
+}
+
+impl Block {
+    fn new() -> Block {
+        let mut block = Block {
+            start: 0,
+            xs: Vec::with_capacity(BLOCK_CAPACITY + 1),
+            ys: Vec::with_capacity(BLOCK_CAPACITY + 1),
+            widths: Vec::with_capacity(BLOCK_CAPACITY + 1),
+            heights: Vec::with_capacity(BLOCK_CAPACITY + 1),
+            min_x: 0,
+            min_y: 0,
+            max_right: 0,
+            max_bottom: 0,
+            max_width: 0,
+            max_height: 0,
+        };
+        block.clear();
+        block
+    }
+
+    fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    fn get(&self, i: usize) -> Rectangle {
+        Rectangle::new(self.xs[i], self.ys[i], self.widths[i], self.heights[i])
+    }
+
+    fn iter(&self) -> impl Iterator<Item = Rectangle> + '_ {
+        (0..self.len()).map(|i| self.get(i))
+    }
+
+    /// Adds a rectangle and extends the bounds to include it.
+    fn push(&mut self, r: &Rectangle) {
+        self.xs.push(r.x);
+        self.ys.push(r.y);
+        self.widths.push(r.width);
+        self.heights.push(r.height);
+        self.include(r);
+    }
+
+    /// Extends the bounds to include the given rectangle.
+    fn include(&mut self, r: &Rectangle) {
+        self.min_x = self.min_x.min(r.x);
+        self.min_y = self.min_y.min(r.y);
+        self.max_right = self.max_right.max(r.x + r.width);
+        self.max_bottom = self.max_bottom.max(r.y + r.height);
+        self.max_width = self.max_width.max(r.width);
+        self.max_height = self.max_height.max(r.height);
+    }
+
+    /// Removes the rectangles selected by the mask, in place, and recomputes the bounds.
+    fn remove(&mut self, mask: u64) {
+        let mut kept = 0;
+        for i in 0..self.len() {
+            if mask & (1 << i) == 0 {
+                self.xs[kept] = self.xs[i];
+                self.ys[kept] = self.ys[i];
+                self.widths[kept] = self.widths[i];
+                self.heights[kept] = self.heights[i];
+                kept += 1;
+            }
+        }
+        self.xs.truncate(kept);
+        self.ys.truncate(kept);
+        self.widths.truncate(kept);
+        self.heights.truncate(kept);
+
+        self.reset_bounds();
+        for i in 0..kept {
+            let r = self.get(i);
+            self.include(&r);
+        }
+    }
+
+    /// Removes all rectangles and resets the bounds.
+    fn clear(&mut self) {
+        self.xs.clear();
+        self.ys.clear();
+        self.widths.clear();
+        self.heights.clear();
+        self.reset_bounds();
+    }
+
+    /// Resets the bounds to those of an empty block.
+    fn reset_bounds(&mut self) {
+        self.min_x = usize::MAX;
+        self.min_y = usize::MAX;
+        self.max_right = 0;
+        self.max_bottom = 0;
+        self.max_width = 0;
+        self.max_height = 0;
+    }
+
+    /// Returns the mask of the rectangles for which `test(x, y, width, height)` holds.
+    #[inline(always)]
+    fn mask<F: Fn(usize, usize, usize, usize) -> bool>(&self, test: F) -> u64 {
+        self.xs
+            .iter()
+            .zip(&self.ys)
+            .zip(&self.widths)
+            .zip(&self.heights)
+            .enumerate()
+            .fold(0, |mask, (i, (((&x, &y), &w), &h))| mask | ((test(x, y, w, h) as u64) << i))
+    }
+
+    /// Returns the mask of the rectangles intersecting the given rectangle.
+    fn intersecting(&self, r: &Rectangle) -> u64 {
+        let (right, bottom) = (r.x + r.width, r.y + r.height);
+        self.mask(|x, y, w, h| (x < right) & (x + w > r.x) & (y < bottom) & (y + h > r.y))
+    }
+
+    /// Returns the mask of the rectangles containing the given rectangle.
+    fn containing(&self, r: &Rectangle) -> u64 {
+        let (right, bottom) = (r.x + r.width, r.y + r.height);
+        self.mask(|x, y, w, h| (x <= r.x) & (y <= r.y) & (x + w >= right) & (y + h >= bottom))
+    }
+
+    /// Returns the mask of the rectangles contained in the given rectangle.
+    fn contained_in(&self, r: &Rectangle) -> u64 {
+        let (right, bottom) = (r.x + r.width, r.y + r.height);
+        self.mask(|x, y, w, h| (x >= r.x) & (y >= r.y) & (x + w <= right) & (y + h <= bottom))
+    }
+
+    /// Returns the mask of the rectangles that are at least as large as the given size.
+    fn fitting(&self, width: usize, height: usize) -> u64 {
+        self.mask(|_, _, w, h| (w >= width) & (h >= height))
+    }
+
+    /// Returns the rectangles selected by the mask.
+    fn selected(&self, mask: u64) -> impl Iterator<Item = Rectangle> + '_ {
+        let mut mask = mask;
+        std::iter::from_fn(move || {
+            if mask == 0 {
+                return None;
+            }
+            let i = mask.trailing_zeros() as usize;
+            mask &= mask - 1;
+            Some(self.get(i))
+        })
+    }
+
+    /// Returns `false` if no rectangle of this block can intersect the given rectangle.
+    fn may_intersect(&self, r: &Rectangle) -> bool {
+        self.min_x < r.x + r.width && self.max_right > r.x && self.min_y < r.y + r.height && self.max_bottom > r.y
+    }
+
+    /// Returns `false` if no rectangle of this block can contain the given rectangle.
+    fn may_contain(&self, r: &Rectangle) -> bool {
+        self.min_x <= r.x
+            && self.min_y <= r.y
+            && self.max_right >= r.x + r.width
+            && self.max_bottom >= r.y + r.height
+            && self.max_width >= r.width
+            && self.max_height >= r.height
+    }
+
+    /// Returns `false` if no rectangle of this block is large enough for a rectangle of the given size.
+    fn may_fit(&self, width: usize, height: usize) -> bool {
+        self.max_width >= width && self.max_height >= height
+    }
+}
+
+/// The free space of a container, described by the list of all maximal free rectangles.
+///
+/// A free rectangle is maximal if it can not be extended in any direction without
+/// overlapping an occupied region. The maximal free rectangles may overlap each other.
+///
+/// The rectangles are kept in a blocked layout: blocks ordered by their x coordinates, each with the bounds
+/// of its rectangles. Queries still visit every block, but skip the rectangles of blocks whose bounds can not match,
+/// so they remain linear in the number of blocks.
+/// Emptied blocks and the buffers used while splitting are kept and reused, so that occupying regions
+/// and resetting the free space do not allocate once the buffers have grown large enough.
+#[derive(Debug, Clone)]
+pub(crate) struct FreeRectangles {
+    blocks: Vec<Block>,
+    len: usize,
+    spare: Vec<Block>,
+    split: Vec<Rectangle>,
+    maximal: Vec<Rectangle>,
+    scratch: Vec<Rectangle>,
+}
+
+impl FreeRectangles {
+    /// Creates the free space of an empty container covering `bounds`.
+    pub fn new(bounds: Rectangle) -> FreeRectangles {
+        let mut free = FreeRectangles {
+            blocks: Vec::new(),
+            len: 0,
+            spare: Vec::new(),
+            split: Vec::new(),
+            maximal: Vec::new(),
+            scratch: Vec::new(),
+        };
+        free.reset(bounds);
+        free
+    }
+
+    /// Resets the free space to that of an empty container covering `bounds`, reusing the allocated blocks.
+    pub fn reset(&mut self, bounds: Rectangle) {
+        for mut block in self.blocks.drain(..) {
+            block.clear();
+            self.spare.push(block);
+        }
+        self.len = 0;
+        if bounds.width > 0 && bounds.height > 0 {
+            self.insert(bounds);
+        }
+    }
+
+    /// Returns the number of maximal free rectangles.
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the maximal free rectangles, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = Rectangle> + '_ {
+        self.blocks.iter().flat_map(|b| b.iter())
+    }
+
+    /// Returns the free rectangle that fits a rectangle of the given size best.
+    ///
+    /// Best short side fit: the free rectangle leaving the smallest leftover along either side is chosen.
+    /// Ties are broken by the larger leftover and then by position and size.
+    pub fn best_fit(&self, width: usize, height: usize) -> Option<Rectangle> {
+        // The free rectangles are only ordered by the blocks they are stored in,
+        // so the key includes the whole rectangle to make the choice deterministic.
+        let key = |free: &Rectangle| {
+            let leftover_x = free.width - width;
+            let leftover_y = free.height - height;
+            (leftover_x.min(leftover_y), leftover_x.max(leftover_y), free.y, free.x, free.width, free.height)
+        };
+
+        // With many free rectangles, scoring them dominates the runtime, so score chunks of them in parallel.
+        #[cfg(feature = "rayon")]
+        if self.len >= PARALLEL_SCORING_THRESHOLD {
+            return self.par_fitting(width, height).min_by_key(key);
+        }
+
+        self.fitting(width, height).min_by_key(key)
+    }
+
+    /// Returns the maximal free rectangles that are at least as large as the given size, in no particular order.
+    pub fn fitting(&self, width: usize, height: usize) -> impl Iterator<Item = Rectangle> + '_ {
+        self.blocks
+            .iter()
+            .filter(move |b| b.may_fit(width, height))
+            .flat_map(move |b| b.selected(b.fitting(width, height)))
+    }
+
+    /// Returns the maximal free rectangles that are at least as large as the given size as a parallel iterator.
+    #[cfg(feature = "rayon")]
+    pub fn par_fitting(&self, width: usize, height: usize) -> impl ParallelIterator<Item = Rectangle> + '_ {
+        use rayon::prelude::*;
+
+        self.blocks
+            .par_iter()
+            .filter(move |b| b.may_fit(width, height))
+            .flat_map_iter(move |b| b.selected(b.fitting(width, height)))
+    }
+
+    /// Marks the given region as occupied.
+    ///
+    /// Every free rectangle intersecting the region is split into up to four maximal
+    /// rectangles around it, and the split rectangles contained in other free rectangles are dropped.
+    pub fn occupy(&mut self, used: &Rectangle) {
+        if used.width == 0 || used.height == 0 {
+            return;
+        }
+
+        let mut split = std::mem::take(&mut self.split);
+        split.clear();
+        for block in self.blocks.iter_mut().filter(|b| b.may_intersect(used)) {
+            let intersecting = block.intersecting(used);
+            if intersecting == 0 {
+                continue;
+            }
+
+            for free in block.selected(intersecting) {
+                // Left of the used region
+                if used.x > free.x {
+                    split.push(Rectangle::new(free.x, free.y, used.x - free.x, free.height));
+                }
+                // Right of the used region
+                if used.x + used.width < free.x + free.width {
+                    let x = used.x + used.width;
+                    split.push(Rectangle::new(x, free.y, free.x + free.width - x, free.height));
+                }
+                // Above the used region
+                if used.y > free.y {
+                    split.push(Rectangle::new(free.x, free.y, free.width, used.y - free.y));
+                }
+                // Below the used region
+                if used.y + used.height < free.y + free.height {
+                    let y = used.y + used.height;
+                    split.push(Rectangle::new(free.x, y, free.width, free.y + free.height - y));
+                }
+            }
+            self.len -= intersecting.count_ones() as usize;
+            block.remove(intersecting);
+        }
+        self.remove_empty_blocks();
+
+        // The remaining free rectangles were not contained in each other before, and every split rectangle
+        // lies within a removed one, so the remaining ones can not be contained in a split rectangle either.
+        // Only the split rectangles have to be checked.
+        let mut maximal = std::mem::take(&mut self.maximal);
+        maximal.clear();
+        maximal.extend(split.iter().enumerate().filter_map(|(i, r)| {
+            let in_split = split
+                .iter()
+                .enumerate()
+                .any(|(j, other)| j != i && other.contains(r) && (other != r || j < i));
+            (!in_split && !self.contains(r)).then_some(*r)
+        }));
+
+        for r in &maximal {
+            self.insert(*r);
+        }
+        self.split = split;
+        self.maximal = maximal;
+    }
+
+    /// Marks the given region, which must not intersect any free rectangle, as free again,
+    /// merging it with the adjacent free rectangles.
+    ///
+    /// Unlike rebuilding the free space from the occupied regions, this takes time proportional to the number
+    /// of free rectangles, and keeps the list short. In exchange, the free rectangles are not necessarily maximal
+    /// anymore: the region is only merged with free rectangles sharing a whole edge with it, so a rectangle
+    /// fitting into the free space may not fit into any single free rectangle.
+    pub fn release(&mut self, region: &Rectangle) {
+        if region.width == 0 || region.height == 0 {
+            return;
+        }
+
+        let mut merged = *region;
+        // Merging may allow further merges, e.g. with a free rectangle that was only as wide as the merged one.
+        loop {
+            let Some(other) = self.iter().find(|other| Self::mergeable(&merged, other)) else {
+                break;
+            };
+            let x = merged.x.min(other.x);
+            let y = merged.y.min(other.y);
+            merged = Rectangle::new(
+                x,
+                y,
+                (merged.x + merged.width).max(other.x + other.width) - x,
+                (merged.y + merged.height).max(other.y + other.height) - y,
+            );
+            self.remove_contained(&merged);
+        }
+        self.insert(merged);
+    }
+
+    /// Returns `true` if the union of the two rectangles is a rectangle, because they share a whole edge or overlap along it.
+    fn mergeable(a: &Rectangle, b: &Rectangle) -> bool {
+        let columns = a.x == b.x && a.width == b.width && a.y <= b.y + b.height && b.y <= a.y + a.height;
+        let rows = a.y == b.y && a.height == b.height && a.x <= b.x + b.width && b.x <= a.x + a.width;
+        columns || rows
+    }
+
+    /// Keeps only the `max_len` free rectangles with the largest areas, to bound the memory of the free space.
+    ///
+    /// The dropped rectangles are treated as occupied, so their space can not be used until the free space is reset.
+    /// Ties are broken by position and size, so that the kept rectangles do not depend on the order of the list.
+    pub fn retain_largest(&mut self, max_len: usize) {
+        if self.len <= max_len {
+            return;
+        }
+
+        // Inserting may split blocks using the scratch buffer, so the rectangles are collected into another buffer.
+        let mut rectangles = std::mem::take(&mut self.maximal);
+        rectangles.clear();
+        rectangles.extend(self.iter());
+        rectangles.sort_unstable_by_key(|r| (std::cmp::Reverse(r.width * r.height), r.x, r.y, r.width, r.height));
+        rectangles.truncate(max_len);
+
+        for mut block in self.blocks.drain(..) {
+            block.clear();
+            self.spare.push(block);
+        }
+        self.len = 0;
+        // Insert by x coordinate, so that blocks are split like when the rectangles are added one by one.
+        rectangles.sort_unstable_by_key(|r| (r.x, r.y, r.width, r.height));
+        for &r in &rectangles {
+            self.insert(r);
+        }
+        self.maximal = rectangles;
+    }
+
+    /// Removes the free rectangles contained in the given rectangle.
+    fn remove_contained(&mut self, r: &Rectangle) {
+        for block in self.blocks.iter_mut().filter(|b| b.may_intersect(r)) {
+            let contained = block.contained_in(r);
+            self.len -= contained.count_ones() as usize;
+            block.remove(contained);
+        }
+        self.remove_empty_blocks();
+    }
+
+    /// Returns `true` if the given rectangle is contained in one of the free rectangles.
+    fn contains(&self, r: &Rectangle) -> bool {
+        self.blocks
+            .iter()
+            .filter(|b| b.may_contain(r))
+            .any(|b| b.containing(r) != 0)
+    }
+
+    /// Adds a free rectangle to the block covering its x coordinate, splitting the block if it gets too large.
+    fn insert(&mut self, r: Rectangle) {
+        self.len += 1;
+        if self.blocks.is_empty() {
+            let mut block = self.spare.pop().unwrap_or_else(Block::new);
+            block.start = 0;
+            block.push(&r);
+            self.blocks.push(block);
+            return;
+        }
+
+        let index = self.blocks.partition_point(|b| b.start <= r.x).saturating_sub(1);
+        self.blocks[index].push(&r);
+
+        if self.blocks[index].len() > BLOCK_CAPACITY {
+            let mut upper = self.spare.pop().unwrap_or_else(Block::new);
+            let block = &mut self.blocks[index];
+
+            let mut rectangles = std::mem::take(&mut self.scratch);
+            rectangles.clear();
+            rectangles.extend(block.iter());
+            rectangles.sort_unstable_by_key(|r| (r.x, r.y, r.width, r.height));
+
+            let (lower_half, upper_half) = rectangles.split_at(BLOCK_CAPACITY / 2);
+            block.clear();
+            for r in lower_half {
+                block.push(r);
+            }
+            upper.start = upper_half[0].x.max(block.start);
+            for r in upper_half {
+                upper.push(r);
+            }
+
+            self.blocks.insert(index + 1, upper);
+            self.scratch = rectangles;
+        }
+    }
+
+    /// Removes the blocks without rectangles, keeping the first block starting at `0`.
+    fn remove_empty_blocks(&mut self) {
+        self.spare.extend(self.blocks.extract_if(.., |b| b.len() == 0));
+        if let Some(first) = self.blocks.first_mut() {
+            first.start = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(free: &FreeRectangles) -> Vec<(usize, usize, usize, usize)> {
+        let mut rectangles: Vec<_> = free.iter().map(|r| (r.x, r.y, r.width, r.height)).collect();
+        rectangles.sort();
+        rectangles
+    }
+
+    #[test]
+    fn occupy_corner_works() {
+        let mut free = FreeRectangles::new(Rectangle::new(0, 0, 10, 10));
+        free.occupy(&Rectangle::new(0, 0, 4, 3));
+
+        assert_eq!(sorted(&free), vec![(0, 3, 10, 7), (4, 0, 6, 10)]);
+    }
+
+    #[test]
+    fn occupy_center_works() {
+        let mut free = FreeRectangles::new(Rectangle::new(0, 0, 10, 10));
+        free.occupy(&Rectangle::new(4, 4, 2, 2));
+
+        assert_eq!(sorted(&free), vec![(0, 0, 4, 10), (0, 0, 10, 4), (0, 6, 10, 4), (6, 0, 4, 10)]);
+    }
+
+    #[test]
+    fn occupy_everything_works() {
+        let mut free = FreeRectangles::new(Rectangle::new(0, 0, 10, 10));
+        free.occupy(&Rectangle::new(0, 0, 10, 5));
+        free.occupy(&Rectangle::new(0, 5, 10, 5));
+
+        assert!(free.iter().next().is_none());
+        assert_eq!(free.len(), 0);
+    }
+
+    #[test]
+    fn many_free_rectangles_stay_maximal() {
+        let mut free = FreeRectangles::new(Rectangle::new(0, 0, 200, 200));
+        for i in 0..40 {
+            free.occupy(&Rectangle::new((i * 37) % 190, (i * 53) % 190, 3 + i % 5, 2 + i % 7));
+        }
+
+        let rectangles: Vec<Rectangle> = free.iter().collect();
+        assert!(free.blocks.len() > 1);
+        assert_eq!(rectangles.len(), free.len());
+        for (i, a) in rectangles.iter().enumerate() {
+            for (j, b) in rectangles.iter().enumerate() {
+                assert!(i == j || !a.contains(b), "{:?} contains {:?}", a, b);
+            }
+        }
+        assert!(free.fitting(30, 30).all(|r| r.width >= 30 && r.height >= 30));
+        assert_eq!(free.fitting(1, 1).count(), free.len());
+    }
+
+    #[test]
+    fn retain_largest_keeps_the_largest_rectangles() {
+        let mut free = FreeRectangles::new(Rectangle::new(0, 0, 100, 100));
+        free.occupy(&Rectangle::new(10, 10, 10, 10));
+        free.occupy(&Rectangle::new(60, 60, 30, 30));
+        let mut expected = sorted(&free);
+        expected.sort_by_key(|&(x, y, w, h)| (std::cmp::Reverse(w * h), x, y, w, h));
+        expected.truncate(3);
+        expected.sort();
+
+        free.retain_largest(3);
+
+        assert_eq!(free.len(), 3);
+        assert_eq!(sorted(&free), expected);
+        free.retain_largest(5);
+        assert_eq!(free.len(), 3);
+    }
+
+    #[test]
+    fn release_merges_adjacent_rectangles() {
+        let mut free = FreeRectangles::new(Rectangle::new(0, 0, 10, 10));
+        free.occupy(&Rectangle::new(0, 0, 10, 4));
+        free.occupy(&Rectangle::new(0, 4, 5, 6));
+        assert_eq!(sorted(&free), vec![(5, 4, 5, 6)]);
+
+        free.release(&Rectangle::new(0, 4, 5, 6));
+        assert_eq!(sorted(&free), vec![(0, 4, 10, 6)]);
+
+        free.release(&Rectangle::new(0, 0, 10, 4));
+        assert_eq!(sorted(&free), vec![(0, 0, 10, 10)]);
+        assert_eq!(free.len(), 1);
+    }
+}
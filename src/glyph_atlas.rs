@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use crate::{AllocationId, AtlasAllocator, Rectangle, Size};
+
+/// The coverage bitmap of a rasterized glyph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlyphBitmap {
+    /// The width of the bitmap in pixels.
+    pub width: usize,
+
+    /// The height of the bitmap in pixels.
+    pub height: usize,
+
+    /// The horizontal offset of the left edge of the bitmap from the glyph origin.
+    pub left: i32,
+
+    /// The vertical offset of the top edge of the bitmap from the glyph origin, with y pointing down.
+    pub top: i32,
+
+    /// The coverage of each pixel, row by row, from `0` (empty) to `255` (fully covered).
+    pub coverage: Vec<u8>,
+}
+
+/// A glyph stored in a `GlyphAtlas`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedGlyph {
+    /// The region of the atlas containing the bitmap of the glyph.
+    pub rectangle: Rectangle,
+
+    /// The horizontal offset of the left edge of the bitmap from the glyph origin.
+    pub left: i32,
+
+    /// The vertical offset of the top edge of the bitmap from the glyph origin, with y pointing down.
+    pub top: i32,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    id: AllocationId,
+    glyph: CachedGlyph,
+    last_used: u64,
+}
+
+/// A cache of rasterized glyphs packed into a single-channel coverage texture.
+///
+/// Glyphs are placed with an `AtlasAllocator`. When a new glyph does not fit,
+/// the least recently used glyphs are evicted until it does.
+/// The keys identify the glyphs, e.g. a tuple of font, glyph id and scale.
+///
+/// # Examples
+/// ```
+/// use rpack::glyph_atlas::{GlyphAtlas, GlyphBitmap};
+/// use rpack::Size;
+///
+/// let mut atlas = GlyphAtlas::new(Size::new(64, 64), 1);
+/// let glyph = atlas
+///     .get_or_insert_with('a', || GlyphBitmap { width: 8, height: 10, left: 0, top: -10, coverage: vec![255; 80] })
+///     .unwrap();
+///
+/// assert_eq!(glyph.rectangle.width, 8);
+/// assert_eq!(atlas.pixels()[glyph.rectangle.y * 64 + glyph.rectangle.x], 255);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GlyphAtlas<K> {
+    allocator: AtlasAllocator,
+    padding: usize,
+    pixels: Vec<u8>,
+    glyphs: HashMap<K, Entry>,
+    clock: u64,
+    changed: Vec<Rectangle>,
+}
+
+impl<K: Hash + Eq + Clone> GlyphAtlas<K> {
+    /// Creates an empty atlas.
+    ///
+    /// # Arguments
+    /// * `size` - The size of the texture in pixels.
+    /// * `padding` - The amount of padding kept free around each glyph, so that glyphs do not bleed into each other.
+    pub fn new(size: Size, padding: usize) -> GlyphAtlas<K> {
+        GlyphAtlas {
+            allocator: AtlasAllocator::new(size).with_padding(padding, 0),
+            padding,
+            pixels: vec![0; size.width * size.height],
+            glyphs: HashMap::new(),
+            clock: 0,
+            changed: Vec::new(),
+        }
+    }
+
+    /// Returns the size of the texture.
+    pub fn size(&self) -> Size {
+        self.allocator.size()
+    }
+
+    /// Returns the coverage of each pixel of the texture, row by row.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns the number of cached glyphs.
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Returns `true` if no glyphs are cached.
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+
+    /// Returns `true` if the glyph is cached, without marking it as used.
+    pub fn contains(&self, key: &K) -> bool {
+        self.glyphs.contains_key(key)
+    }
+
+    /// Returns the cached glyph and marks it as used, or `None` if it is not cached.
+    pub fn get(&mut self, key: &K) -> Option<CachedGlyph> {
+        self.clock += 1;
+        let entry = self.glyphs.get_mut(key)?;
+        entry.last_used = self.clock;
+        Some(entry.glyph)
+    }
+
+    /// Returns the cached glyph, rasterizing and inserting it first if it is not cached.
+    ///
+    /// # Returns
+    /// The cached glyph, or `None` if the glyph is larger than the atlas.
+    pub fn get_or_insert_with<F: FnOnce() -> GlyphBitmap>(&mut self, key: K, rasterize: F) -> Option<CachedGlyph> {
+        match self.get(&key) {
+            Some(glyph) => Some(glyph),
+            None => self.insert(key, rasterize()),
+        }
+    }
+
+    /// Inserts a glyph, replacing any glyph with the same key and evicting
+    /// the least recently used glyphs while there is not enough free space.
+    ///
+    /// # Returns
+    /// The cached glyph, or `None` if the glyph is larger than the atlas, in which case no other glyphs are evicted.
+    ///
+    /// # Panics
+    /// Panics if the length of the coverage does not match the size of the bitmap.
+    pub fn insert(&mut self, key: K, bitmap: GlyphBitmap) -> Option<CachedGlyph> {
+        assert_eq!(bitmap.coverage.len(), bitmap.width * bitmap.height, "coverage does not match the bitmap size");
+        self.remove(&key);
+
+        // A glyph that does not fit into the empty atlas with its padding would evict all glyphs in vain.
+        let size = Size::new(bitmap.width, bitmap.height);
+        let bounds = self.size();
+        if size.width + 2 * self.padding > bounds.width || size.height + 2 * self.padding > bounds.height {
+            return None;
+        }
+        let allocation = loop {
+            if let Some(allocation) = self.allocator.allocate(size) {
+                break allocation;
+            }
+            let lru = self.glyphs.iter().min_by_key(|(_, entry)| entry.last_used).map(|(key, _)| key.clone())?;
+            self.remove(&lru);
+        };
+
+        let r = allocation.rectangle;
+        let width = self.size().width;
+        for y in 0..r.height {
+            let start = (r.y + y) * width + r.x;
+            self.pixels[start..start + r.width].copy_from_slice(&bitmap.coverage[y * r.width..(y + 1) * r.width]);
+        }
+        self.changed.push(r);
+
+        self.clock += 1;
+        let glyph = CachedGlyph { rectangle: r, left: bitmap.left, top: bitmap.top };
+        self.glyphs.insert(key, Entry { id: allocation.id, glyph, last_used: self.clock });
+        Some(glyph)
+    }
+
+    /// Removes a glyph from the atlas.
+    ///
+    /// # Returns
+    /// `true` if the glyph was cached.
+    pub fn remove(&mut self, key: &K) -> bool {
+        let Some(entry) = self.glyphs.remove(key) else {
+            return false;
+        };
+        self.allocator.deallocate(entry.id);
+
+        let r = entry.glyph.rectangle;
+        let width = self.size().width;
+        for y in r.y..r.y + r.height {
+            self.pixels[y * width + r.x..y * width + r.x + r.width].fill(0);
+        }
+        self.changed.push(r);
+        true
+    }
+
+    /// Removes all glyphs.
+    pub fn clear(&mut self) {
+        self.allocator.clear();
+        self.glyphs.clear();
+        self.pixels.fill(0);
+        let size = self.size();
+        self.changed = vec![Rectangle::from_size(0, 0, &size)];
+    }
+
+    /// Returns the regions of the texture that changed since the last call, e.g. to upload them to the GPU.
+    pub fn take_changed(&mut self) -> Vec<Rectangle> {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+#[cfg(feature = "ab_glyph")]
+impl GlyphBitmap {
+    /// Rasterizes a glyph with ab_glyph.
+    ///
+    /// The offsets of the bitmap are relative to the position of the glyph.
+    /// Glyphs without an outline, like spaces, result in an empty bitmap.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ab_glyph::{Font, FontRef};
+    /// use rpack::glyph_atlas::{GlyphAtlas, GlyphBitmap};
+    /// use rpack::Size;
+    ///
+    /// let data = std::fs::read("font.ttf").unwrap();
+    /// let font = FontRef::try_from_slice(&data).unwrap();
+    /// let mut atlas = GlyphAtlas::new(Size::new(512, 512), 1);
+    ///
+    /// let id = font.glyph_id('a');
+    /// let glyph = atlas.get_or_insert_with((id, 24), || GlyphBitmap::from_ab_glyph(&font, id.with_scale(24.0)));
+    /// ```
+    pub fn from_ab_glyph<F: ab_glyph::Font>(font: &F, glyph: ab_glyph::Glyph) -> GlyphBitmap {
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            return GlyphBitmap { width: 0, height: 0, left: 0, top: 0, coverage: Vec::new() };
+        };
+
+        let bounds = outlined.px_bounds();
+        let width = bounds.width() as usize;
+        let height = bounds.height() as usize;
+        let mut coverage = vec![0; width * height];
+        outlined.draw(|x, y, c| {
+            let (x, y) = (x as usize, y as usize);
+            if x < width && y < height {
+                coverage[y * width + x] = (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+        });
+
+        GlyphBitmap { width, height, left: bounds.min.x as i32, top: bounds.min.y as i32, coverage }
+    }
+}
+
+#[cfg(feature = "fontdue")]
+impl GlyphBitmap {
+    /// Rasterizes the glyph with the given index with fontdue.
+    ///
+    /// # Arguments
+    /// * `font` - The font containing the glyph.
+    /// * `index` - The index of the glyph, see `Font::lookup_glyph_index`.
+    /// * `px` - The font size in pixels per em.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use fontdue::{Font, FontSettings};
+    /// use rpack::glyph_atlas::{GlyphAtlas, GlyphBitmap};
+    /// use rpack::Size;
+    ///
+    /// let data = std::fs::read("font.ttf").unwrap();
+    /// let font = Font::from_bytes(data, FontSettings::default()).unwrap();
+    /// let mut atlas = GlyphAtlas::new(Size::new(512, 512), 1);
+    ///
+    /// let index = font.lookup_glyph_index('a');
+    /// let glyph = atlas.get_or_insert_with((index, 24), || GlyphBitmap::from_fontdue(&font, index, 24.0));
+    /// ```
+    pub fn from_fontdue(font: &fontdue::Font, index: u16, px: f32) -> GlyphBitmap {
+        let (metrics, coverage) = font.rasterize_indexed(index, px);
+        GlyphBitmap {
+            width: metrics.width,
+            height: metrics.height,
+            left: metrics.xmin,
+            top: -(metrics.ymin + metrics.height as i32),
+            coverage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitmap(width: usize, height: usize, value: u8) -> GlyphBitmap {
+        GlyphBitmap { width, height, left: 0, top: 0, coverage: vec![value; width * height] }
+    }
+
+    #[test]
+    fn insert_works() {
+        let mut atlas = GlyphAtlas::new(Size::new(8, 8), 0);
+        let a = atlas.insert('a', bitmap(4, 8, 10)).unwrap();
+        let b = atlas.insert('b', bitmap(4, 4, 20)).unwrap();
+
+        assert!(!a.rectangle.intersects(&b.rectangle));
+        assert_eq!(atlas.pixels()[a.rectangle.y * 8 + a.rectangle.x], 10);
+        assert_eq!(atlas.pixels()[b.rectangle.y * 8 + b.rectangle.x], 20);
+        assert_eq!(atlas.take_changed(), vec![a.rectangle, b.rectangle]);
+        assert!(atlas.take_changed().is_empty());
+        assert!(atlas.insert('c', bitmap(9, 1, 0)).is_none());
+    }
+
+    #[test]
+    fn get_or_insert_with_rasterizes_once() {
+        let mut atlas = GlyphAtlas::new(Size::new(8, 8), 1);
+        let mut count = 0;
+        for _ in 0..3 {
+            atlas.get_or_insert_with(1, || {
+                count += 1;
+                bitmap(2, 2, 255)
+            });
+        }
+
+        assert_eq!(count, 1);
+        assert_eq!(atlas.len(), 1);
+    }
+
+    #[test]
+    fn least_recently_used_glyphs_are_evicted() {
+        let mut atlas = GlyphAtlas::new(Size::new(8, 4), 0);
+        atlas.insert('a', bitmap(4, 4, 10));
+        atlas.insert('b', bitmap(4, 4, 20));
+        atlas.get(&'a');
+
+        let c = atlas.insert('c', bitmap(4, 4, 30)).unwrap();
+
+        assert!(atlas.contains(&'a'));
+        assert!(!atlas.contains(&'b'));
+        assert_eq!(atlas.pixels()[c.rectangle.x], 30);
+    }
+
+    #[test]
+    fn glyphs_larger_than_the_atlas_evict_nothing() {
+        let mut atlas = GlyphAtlas::new(Size::new(16, 8), 1);
+        atlas.insert('a', bitmap(4, 4, 10));
+        atlas.insert('b', bitmap(2, 2, 20));
+
+        assert!(atlas.insert('c', bitmap(15, 2, 30)).is_none());
+        assert!(atlas.contains(&'a'));
+        assert!(atlas.contains(&'b'));
+    }
+
+    #[test]
+    fn remove_clears_pixels() {
+        let mut atlas = GlyphAtlas::new(Size::new(4, 4), 0);
+        atlas.insert('a', bitmap(4, 4, 10));
+
+        assert!(atlas.remove(&'a'));
+        assert!(!atlas.remove(&'a'));
+        assert!(atlas.is_empty());
+        assert!(atlas.pixels().iter().all(|&p| p == 0));
+    }
+}
@@ -0,0 +1,170 @@
+//! Golden layout fixtures, which catch changes to the layouts produced by a packer.
+//!
+//! A packing result is written in a canonical text form, one line for the container and one line per rectangle:
+//!
+//! ```text
+//! size 24x16
+//! 0 0,0 16x16
+//! 1 16,0 8x8
+//! 2 skipped
+//! ```
+//!
+//! `assert_golden` compares a result against a fixture in this form. A fixture that does not exist yet is written,
+//! and all fixtures are rewritten if the environment variable `RPACK_UPDATE_GOLDEN` is set,
+//! so intended changes to layouts are accepted deliberately by reviewing the changes to the fixtures.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::RectanglePackingResult;
+
+/// The environment variable that makes `assert_golden` rewrite the fixtures instead of comparing against them.
+pub const UPDATE_VARIABLE: &str = "RPACK_UPDATE_GOLDEN";
+
+impl RectanglePackingResult {
+    /// Returns the packing in the canonical text form of golden fixtures.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{Rectangle, RectanglePackingResult, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 16, 16), Rectangle::new(16, 0, 8, 8)],
+    ///     size: Size::new(24, 16),
+    ///     skipped: Vec::new(),
+    /// };
+    ///
+    /// assert_eq!(result.to_golden(), "size 24x16\n0 0,0 16x16\n1 16,0 8x8\n");
+    /// ```
+    pub fn to_golden(&self) -> String {
+        let mut text = format!("size {}x{}\n", self.size.width, self.size.height);
+        for (index, r) in self.rectangles.iter().enumerate() {
+            match self.skipped.binary_search(&index) {
+                Ok(_) => writeln!(text, "{} skipped", index),
+                Err(_) => writeln!(text, "{} {},{} {}x{}", index, r.x, r.y, r.width, r.height),
+            }
+            .unwrap();
+        }
+        text
+    }
+}
+
+/// Asserts that the packing matches the golden fixture at the given path.
+///
+/// If the fixture does not exist or the environment variable `RPACK_UPDATE_GOLDEN` is set,
+/// the fixture is written instead, creating its directory if necessary.
+///
+/// # Arguments
+/// * `result` - The packing to compare.
+/// * `path` - The path of the fixture, usually below the `CARGO_MANIFEST_DIR` of the crate under test.
+///
+/// # Panics
+/// Panics if the packing does not match the fixture, listing the lines that differ,
+/// or if the fixture can not be read or written.
+pub fn assert_golden<P: AsRef<Path>>(result: &RectanglePackingResult, path: P) {
+    if let Err(message) = compare(result, path.as_ref(), std::env::var_os(UPDATE_VARIABLE).is_some()) {
+        panic!("{}", message);
+    }
+}
+
+/// Compares the packing against the fixture at the given path, or writes the fixture if `update` is set
+/// or it does not exist, returning the reason for a mismatch as an error.
+fn compare(result: &RectanglePackingResult, path: &Path, update: bool) -> Result<(), String> {
+    let actual = result.to_golden();
+
+    if update || !path.exists() {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(|e| format!("Could not create {}: {}", directory.display(), e))?;
+        }
+        return fs::write(path, &actual).map_err(|e| format!("Could not write {}: {}", path.display(), e));
+    }
+
+    let expected = fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    match diff(&expected, &actual) {
+        None => Ok(()),
+        Some(diff) => Err(format!(
+            "Packing does not match the golden fixture {}:\n{}\nSet {} to accept the new layout.",
+            path.display(),
+            diff,
+            UPDATE_VARIABLE
+        )),
+    }
+}
+
+/// Returns the lines that differ between the expected and the actual text, or `None` if they are equal.
+///
+/// Line endings are ignored, so fixtures checked out with CRLF line endings still match.
+fn diff(expected: &str, actual: &str) -> Option<String> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    if expected == actual {
+        return None;
+    }
+
+    let mut diff = String::new();
+    for line in 0..expected.len().max(actual.len()) {
+        match (expected.get(line), actual.get(line)) {
+            (Some(e), Some(a)) if e == a => {}
+            (e, a) => {
+                writeln!(diff, "line {}:", line + 1).unwrap();
+                if let Some(e) = e {
+                    writeln!(diff, "- {}", e).unwrap();
+                }
+                if let Some(a) = a {
+                    writeln!(diff, "+ {}", a).unwrap();
+                }
+            }
+        }
+    }
+    Some(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeightRectPacker, Rectangle, RectanglePacker, RectanglePackerConfig, Size, ZeroSizePolicy};
+
+    use super::*;
+
+    fn fixture(name: &str) -> String {
+        format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    #[test]
+    fn height_rect_packer_matches_fixture() {
+        let mut sizes: Vec<Size> = (0..24).map(|i| Size::new(i * 7 % 13 + 2, i * 5 % 11 + 2)).collect();
+        sizes[5] = Size::new(0, 4);
+        let config = RectanglePackerConfig {
+            rectangle_padding: 1,
+            border_padding: 2,
+            zero_size_policy: ZeroSizePolicy::Skip,
+            ..Default::default()
+        };
+
+        assert_golden(&HeightRectPacker::pack(&sizes, &config).unwrap(), fixture("height_rect_packer.txt"));
+    }
+
+    #[test]
+    fn diff_lists_changed_lines() {
+        assert_eq!(diff("size 4x4\r\n0 0,0 4x4\r\n", "size 4x4\n0 0,0 4x4\n"), None);
+        assert_eq!(
+            diff("size 4x4\n0 0,0 4x4\n", "size 4x8\n0 0,0 4x4\n1 0,4 4x4\n").unwrap(),
+            "line 1:\n- size 4x4\n+ size 4x8\nline 3:\n+ 1 0,4 4x4\n"
+        );
+    }
+
+    #[test]
+    fn compare_fails_for_changed_layouts() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 1, 1)],
+            size: Size::new(1, 1),
+            skipped: Vec::new(),
+        };
+        let path = std::env::temp_dir().join("rpack_golden_compare_fails_for_changed_layouts.txt");
+        fs::write(&path, "size 4x4\n0 0,0 4x4\n").unwrap();
+        let message = compare(&result, &path, false).unwrap_err();
+
+        assert!(message.starts_with("Packing does not match the golden fixture"));
+        assert!(message.contains("+ size 1x1"));
+    }
+}
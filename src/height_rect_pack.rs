@@ -1,102 +1,790 @@
 use std::cmp::max;
+use std::mem::size_of;
 
-use crate::{RectanglePacker, Size, RectanglePackingError, RectanglePackingResult, Rectangle, RectanglePackerConfig};
+use crate::search::{candidate_widths, square_root_ceil, SWEEP_MEMORY};
+use crate::{Candidate, ObjectiveWeights, PackingObserver, PlacementDecision, PackingScratch, RectanglePacker, Size, RectanglePackingError, RectanglePackingResult, Rectangle, RectanglePackerConfig};
 
 /// A rectangle packer that packs rectangles by height.
 pub struct HeightRectPacker {}
 
 impl RectanglePacker for HeightRectPacker {
-    fn pack(sizes: &Vec<Size>, config: &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError> {
+    fn pack(sizes: &[Size], config: &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError> {
+        Self::pack_with_scratch(sizes, config, &mut PackingScratch::new())
+    }
+
+    fn pack_with_scratch(
+        sizes: &[Size],
+        config: &RectanglePackerConfig,
+        scratch: &mut PackingScratch,
+    ) -> Result<RectanglePackingResult, RectanglePackingError> {
+        Self::pack_into(sizes, config, scratch, &mut ())
+    }
+
+    fn pack_observed(
+        sizes: &[Size],
+        config: &RectanglePackerConfig,
+        observer: &mut dyn PackingObserver,
+    ) -> Result<RectanglePackingResult, RectanglePackingError> {
+        Self::pack_into(sizes, config, &mut PackingScratch::new(), observer)
+    }
+}
+
+impl HeightRectPacker {
+    /// Packs the sizes using the buffers of `scratch`, notifying the observer of every event.
+    fn pack_into(
+        sizes: &[Size],
+        config: &RectanglePackerConfig,
+        scratch: &mut PackingScratch,
+        observer: &mut dyn PackingObserver,
+    ) -> Result<RectanglePackingResult, RectanglePackingError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("pack", packer = "HeightRectPacker", sizes = sizes.len()).entered();
+
+        config.validate().map_err(RectanglePackingError::from)?;
+
+        // The owned padding is part of the rectangles, so the packer places the padded sizes.
+        let padded;
+        let sizes = match config.owned_padding {
+            0 => sizes,
+            _ => {
+                padded = config.padded_sizes(sizes);
+                &padded
+            }
+        };
+
+        let skipped = config.zero_size_policy.skipped(sizes)?;
+        // The order of the sizes to place is the only buffer growing with the number of sizes,
+        // besides the gaps of the rows, of which every placed rectangle leaves at most two.
+        let gap_memory = match config.fill_row_gaps {
+            true => 2 * size_of::<Rectangle>(),
+            false => 0,
+        };
+        let order_memory = (sizes.len() - skipped.len()) * (size_of::<usize>() + gap_memory);
+        config.check_memory(order_memory).map_err(RectanglePackingError::from)?;
+        let mut rectangles = vec![Rectangle::new(0, 0, 0, 0); sizes.len()];
+
+        // Sort the indices of the sizes to place by height in descending order,
+        // so that the rectangles can be reported in the order of the input.
+        let order = &mut scratch.order;
+        {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("sort", skipped = skipped.len()).entered();
+
+            order.clear();
+            order.extend((0..sizes.len()).filter(|i| skipped.binary_search(i).is_err()));
+            // Equal sizes are ordered by their indices, as the order of equal elements of unstable sorts is unspecified.
+            order.sort_unstable_by(|&a, &b| sizes[b].cmp(&sizes[a]).then(a.cmp(&b)));
+            if config.deduplicate_sizes {
+                order.dedup_by(|a, b| sizes[*a] == sizes[*b]);
+            }
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(count = order.len(), "sorted sizes");
+        }
+
+        // Without any rectangles, the container consists only of the border padding.
+        if order.is_empty() {
+            let border = 2 * config.border_padding;
+            let size = config.max_size.unwrap_or(Size::new(border, border));
+            observer.on_open_page(0, size);
+            for &index in &skipped {
+                observer.on_reject(index, &sizes[index]);
+            }
+            return Ok(RectanglePackingResult { rectangles, size, skipped });
+        }
+
         let max_size = match config.max_size {
             Some(max_size) => max_size,
             None => {
-                // If no max size was specified, set the width of the container rectangle
-                // to the average number of rectangles per row times the average width of a rectangle.
-                // This is necessary because otherwise the algorithm would pack all the rectangles into a single row.
-                // As the height of the container rectangle is not restricted, 
+                // If no max size was specified, choose the width of the rows, as the algorithm would otherwise
+                // pack all the rectangles into a single row. The width is the one whose packing has the lowest cost
+                // by the objective weights, which by default favor small containers.
+                // As the height of the container rectangle is not restricted,
                 // the algorithm will always be able to fit all rectangles.
+                // If the widths to sweep exceed the memory budget, the width of a square container is used instead.
+                let weights = config.objective.unwrap_or(DEFAULT_OBJECTIVE);
+                let sweep = config.check_memory(order_memory + SWEEP_MEMORY).is_ok();
+                let row_width = best_row_width(sizes, order, config, &weights, sweep);
 
-                // Get the max width of all the rectangles
-                let max_width = sizes.iter().map(|s| s.width).max().unwrap() + 2 * config.rectangle_padding;
-
-                // Get the average width of all the rectangles
-                let total_width: usize = sizes.iter().map(|s| s.width).sum();
-
-                // Get the average number of rectangles per row
-                let average_num_rectangles_per_row = (sizes.len() as f64 / total_width as f64).sqrt() as usize + 1;
-
-                // Set the width to fit at least the largest rectangle in each row or the average number of rectangles per row
-                let row_width = max(
-                    (total_width / sizes.len()) * average_num_rectangles_per_row, 
-                    max_width) + 2 * config.border_padding;
-                    
                 Size::new(row_width, usize::MAX)
             },
         };
 
-        // Check that all sizes can fit in the max size
-        Self::check_sizes(sizes, config)?;
-        let mut rectangles = Vec::new();
+        // Check that all sizes to place can fit in the max size
+        match skipped.is_empty() {
+            true => Self::check_sizes(sizes, config)?,
+            false => Self::check_sizes(&order.iter().map(|&i| sizes[i]).collect::<Vec<_>>(), config)?,
+        }
 
-        // Sort the sizes by height in descending order
-        let mut sizes = sizes.clone();
-        sizes.sort_unstable();
+        observer.on_open_page(0, max_size);
+        for &index in &skipped {
+            observer.on_reject(index, &sizes[index]);
+        }
+
+        // The x and y positions for the left corner of the first rectangle in a row
+        let start = config.border_padding + config.rectangle_padding;
 
         // The current x and y positions for the left corner of the next rectangle
-        let mut x: usize = config.border_padding + config.rectangle_padding;
-        let mut y: usize = config.border_padding + config.rectangle_padding;
+        let mut x: usize = start;
+        let mut y: usize = start;
+
+        // The largest height of the current row, rounded up to the row height multiple
+        let mut largest_height: usize = 0;
+
+        // The largest width of any row, including the padding of the last rectangle
+        let mut largest_width: usize = 0;
+
+        // The used part of the container reported to the observer last
+        let mut grown = Size::new(0, 0);
+
+        #[cfg(feature = "tracing")]
+        let place_span = tracing::debug_span!("place", count = order.len()).entered();
+
+        // The number of rows started, reported to tracing
+        #[cfg(feature = "tracing")]
+        let mut rows = 1;
+
+        // Whether to report the candidates of every placement to the observer
+        let explain = observer.wants_decisions();
+
+        // The space left below the shorter rectangles of the rows, if it is filled
+        let mut gaps = RowGaps::new(max_size.height, config);
+
+        for &index in order.iter() {
+            if observer.is_cancelled() {
+                return Err(RectanglePackingError::cancelled());
+            }
+            let size = sizes[index];
+
+            // Fill the space left in the rows first, as it is part of the container already.
+            if let Some(gap) = gaps.find(size) {
+                let height = gaps.gaps[gap].height;
+                rectangles[index] = gaps.fill(gap, size, config);
+                if explain {
+                    observer.on_decision(&PlacementDecision {
+                        index,
+                        chosen: Some(0),
+                        candidates: vec![candidate(rectangles[index], height, None)],
+                        reason: "the rectangle fits into the space left below a shorter rectangle of a row".to_string(),
+                    });
+                }
+                observer.on_place(index, &rectangles[index]);
+                continue;
+            }
 
-        // The largest height of the current row
-        let mut largets_height: usize = 0;
+            let fits_row = x == start || x + size.width + config.rectangle_padding <= max_size.width - config.border_padding;
+
+            // The candidates considered for the rectangle, if the observer asked for them
+            let mut candidates = Vec::new();
+            if explain {
+                let rejection = match fits_row {
+                    true => None,
+                    false => Some("exceeds the width of the container".to_string()),
+                };
+                candidates.push(candidate(Rectangle::from_size(x, y, &size), largest_height, rejection));
+            }
 
-        for size in sizes {
-            
             // If adding the next rectangle would exceed the max width, move to the next row.
-            // To do this, reset the x position to 0 and increment the y position by the 
+            // To do this, reset the x position to the start of the row and increment the y position by the 
             // largest height of any rectangle in the current row.
-            if x + size.width + config.rectangle_padding > max_size.width - config.border_padding {
-                x = 0;
-                y += size.height + 2 * config.rectangle_padding;
-                largets_height = 0;
+            if !fits_row {
+                x = start;
+                y += largest_height + 2 * config.rectangle_padding + config.row_gutter;
+                largest_height = 0;
+
+                #[cfg(feature = "tracing")]
+                {
+                    rows += 1;
+                }
+
+                if explain {
+                    candidates.push(candidate(Rectangle::from_size(x, y, &size), 0, None));
+                }
+            }
+
+            let fits_height = y + size.height + config.rectangle_padding <= max_size.height - config.border_padding;
+            if explain {
+                let reason = match (fits_height, fits_row) {
+                    (false, _) => "the rectangle does not fit below the last row",
+                    (true, true) => "the rectangle fits next to the previous one in the current row",
+                    (true, false) => "the rectangle does not fit into the current row, so a new row is started",
+                };
+                if !fits_height {
+                    candidates.last_mut().unwrap().rejection = Some("exceeds the height of the container".to_string());
+                }
+                observer.on_decision(&PlacementDecision {
+                    index,
+                    chosen: fits_height.then(|| candidates.len() - 1),
+                    candidates,
+                    reason: reason.to_string(),
+                });
             }
 
             // If adding the next rectangle would exceed the max height, return an error.
-            if y + size.height + config.rectangle_padding > max_size.height - config.border_padding {
+            if !fits_height {
+                observer.on_reject(index, &size);
+
+                #[cfg(feature = "tracing")]
+                tracing::debug!(index, width = size.width, height = size.height, "rectangle does not fit");
+
                 return Err(RectanglePackingError {
-                    message: format!("Could not fit all rectangles in max size"),
+                    message: "Could not fit all rectangles in max size".to_string(),
+                    config_error: None,
                     result: RectanglePackingResult {
                         rectangles: Vec::new(),
-                        size: Size::new(x, y),
+                        size: Size::new(largest_width, y),
+                        skipped: Vec::new(),
                     },
                 });
             }
 
+            // Add the rectangle to the list of packed rectangles.
+            rectangles[index] = Rectangle::from_size(x, y, &size);
+            observer.on_place(index, &rectangles[index]);
+
             // Update the x position in order to place the next rectangle to the right of the current one.
-            x += size.width + 2 * config.rectangle_padding;
+            x += size.width + 2 * config.rectangle_padding + config.column_gutter;
 
-            // Update the largest height of the current row if necessary.
-            if size.height > largets_height {
-                largets_height = size.height;
+            // Update the height of the current row and the largest width if necessary.
+            largest_height = max(largest_height, row_height(size.height, config));
+            largest_width = max(largest_width, x - config.rectangle_padding - config.column_gutter);
+            if config.fill_row_gaps {
+                gaps.open(&rectangles[index], y + largest_height, config);
             }
 
-            // Add the rectangle to the list of packed rectangles.
-            rectangles.push(Rectangle::from_size(x, y, &size));
+            let used = Size::new(
+                largest_width + config.border_padding,
+                y + largest_height + config.rectangle_padding + config.border_padding,
+            );
+            if used != grown {
+                grown = used;
+                observer.on_grow(used);
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::debug!(rows, width = largest_width, height = y + largest_height, "placed rectangles");
+            drop(place_span);
+        }
+
+        // Give the duplicates the rectangles of the sizes that were placed in their stead.
+        if config.deduplicate_sizes {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("deduplicate", duplicates = sizes.len() - skipped.len() - order.len()).entered();
+
+            for index in (0..sizes.len()).filter(|i| skipped.binary_search(i).is_err()) {
+                let placed = order.binary_search_by(|&other| sizes[index].cmp(&sizes[other])).unwrap();
+                if order[placed] != index {
+                    rectangles[index] = rectangles[order[placed]];
+                    observer.on_place(index, &rectangles[index]);
+                }
+            }
         }
 
+        // If a max size was specified, the container has exactly that size.
+        // Otherwise it is as large as the packed rectangles plus their padding and the border.
+        let size = match config.max_size {
+            Some(max_size) => max_size,
+            None => Size::new(
+                largest_width + config.border_padding,
+                y + largest_height + config.rectangle_padding + config.border_padding,
+            ),
+        };
+
         Ok(RectanglePackingResult {
-            rectangles: rectangles,
-            size: Size::new(x, y + largets_height),
+            rectangles,
+            size,
+            skipped,
         })
     }
 }
 
+/// The objective weights of the height rect packer if the config has none: the area of the container,
+/// with a small penalty for containers far from a square, as a single row often wastes the least area.
+const DEFAULT_OBJECTIVE: ObjectiveWeights = ObjectiveWeights { squareness: 0.1, area: 1.0, ..ObjectiveWeights::NONE };
+
+/// Returns the row width, including the border padding, whose packing has the lowest cost by the given weights.
+///
+/// The widths are swept from the widest rectangle to all rectangles in a single row, see `search::candidate_widths`.
+/// Ties are broken by the smaller width. Without `sweep`, the width of a square of the area of the rectangles is returned.
+fn best_row_width(sizes: &[Size], order: &[usize], config: &RectanglePackerConfig, weights: &ObjectiveWeights, sweep: bool) -> usize {
+    let padding = 2 * config.rectangle_padding;
+    let border = 2 * config.border_padding;
+    let area: usize = order.iter().map(|&i| (sizes[i].width + padding) * (sizes[i].height + padding)).sum();
+    let narrowest = order.iter().map(|&i| sizes[i].width + padding).max().unwrap() + border;
+    let gutters = (order.len() - 1) * config.column_gutter;
+    let widest = order.iter().map(|&i| sizes[i].width + padding).sum::<usize>() + gutters + border;
+    if !sweep {
+        return (square_root_ceil(area) + border).clamp(narrowest, widest);
+    }
+
+    candidate_widths(narrowest, widest, area, border)
+        .into_iter()
+        .map(|width| (weights.cost(container_size(sizes, order, config, width), area), width))
+        .min_by(|(a, w), (b, v)| a.total_cmp(b).then(w.cmp(v)))
+        .map_or(narrowest, |(_, width)| width)
+}
+
+/// Returns the size of the container when packing the sizes in the given order into rows of the given width,
+/// like `HeightRectPacker::pack_into` without a max size.
+fn container_size(sizes: &[Size], order: &[usize], config: &RectanglePackerConfig, row_width: usize) -> Size {
+    let start = config.border_padding + config.rectangle_padding;
+    let (mut x, mut y) = (start, start);
+    let (mut largest_height, mut largest_width) = (0, 0);
+    let mut gaps = RowGaps::new(config.max_size.map_or(usize::MAX, |max_size| max_size.height), config);
+    for &index in order {
+        let size = sizes[index];
+        if let Some(gap) = gaps.find(size) {
+            gaps.fill(gap, size, config);
+            continue;
+        }
+        if x > start && x + size.width + config.rectangle_padding > row_width - config.border_padding {
+            x = start;
+            y += largest_height + 2 * config.rectangle_padding + config.row_gutter;
+            largest_height = 0;
+        }
+        let placed = Rectangle::from_size(x, y, &size);
+        x += size.width + 2 * config.rectangle_padding + config.column_gutter;
+        largest_height = max(largest_height, row_height(size.height, config));
+        largest_width = max(largest_width, x - config.rectangle_padding - config.column_gutter);
+        if config.fill_row_gaps {
+            gaps.open(&placed, y + largest_height, config);
+        }
+    }
+    Size::new(
+        largest_width + config.border_padding,
+        y + largest_height + config.rectangle_padding + config.border_padding,
+    )
+}
+
+/// Returns the height of a row holding a rectangle of the given height, rounded up to the `row_height_multiple` of the config.
+fn row_height(height: usize, config: &RectanglePackerConfig) -> usize {
+    match config.row_height_multiple {
+        Some(multiple) => height.next_multiple_of(multiple),
+        None => height,
+    }
+}
+
+/// The space left below the shorter rectangles of the rows, which later and smaller rectangles are placed into
+/// if the `fill_row_gaps` of the config is set.
+struct RowGaps {
+    /// The free areas a rectangle can be placed at the top left corner of, excluding the margins around them.
+    gaps: Vec<Rectangle>,
+
+    /// The bottom no gap reaches below, as rows rounded up to the `row_height_multiple` may end below the container.
+    limit: usize,
+}
+
+impl RowGaps {
+    /// Creates the gaps of rows in a container of the given max height.
+    fn new(max_height: usize, config: &RectanglePackerConfig) -> Self {
+        let limit = max_height.saturating_sub(config.border_padding + config.rectangle_padding);
+        Self { gaps: Vec::new(), limit }
+    }
+
+    /// Adds the space between the placed rectangle and the given bottom of its row, if any.
+    /// The space ends at the bottom of the container, if the row reaches below it.
+    fn open(&mut self, placed: &Rectangle, bottom: usize, config: &RectanglePackerConfig) {
+        let bottom = bottom.min(self.limit);
+        let y = placed.y + placed.height + 2 * config.rectangle_padding + config.row_gutter;
+        if y < bottom {
+            self.gaps.push(Rectangle::new(placed.x, y, placed.width, bottom - y));
+        }
+    }
+
+    /// Returns the index of the smallest gap the size fits into, preferring the earlier of equally large gaps.
+    fn find(&self, size: Size) -> Option<usize> {
+        self.gaps
+            .iter()
+            .enumerate()
+            .filter(|(_, gap)| size.width <= gap.width && size.height <= gap.height)
+            .min_by_key(|(index, gap)| (gap.width * gap.height, *index))
+            .map(|(index, _)| index)
+    }
+
+    /// Places the size at the top left corner of the gap with the given index, and replaces the gap by the space
+    /// left to the right of the placed rectangle and below it.
+    ///
+    /// # Returns
+    /// The rectangle the size was placed at.
+    fn fill(&mut self, index: usize, size: Size, config: &RectanglePackerConfig) -> Rectangle {
+        let gap = self.gaps.remove(index);
+        let placed = Rectangle::from_size(gap.x, gap.y, &size);
+        let right = size.width + 2 * config.rectangle_padding + config.column_gutter;
+        if right < gap.width {
+            self.gaps.push(Rectangle::new(gap.x + right, gap.y, gap.width - right, gap.height));
+        }
+        self.open(&placed, gap.y + gap.height, config);
+        placed
+    }
+}
+
+/// Returns a candidate of the height rect packer, scored by its position and by the height
+/// of the row left empty below it.
+fn candidate(rectangle: Rectangle, row_height: usize, rejection: Option<String>) -> Candidate {
+    Candidate {
+        rectangle,
+        scores: vec![
+            ("x", rectangle.x as f64),
+            ("y", rectangle.y as f64),
+            ("row_waste", row_height.saturating_sub(rectangle.height) as f64),
+        ],
+        rejection,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::{ConfigError, ZeroSizePolicy};
 
     use super::*;
 
     #[test]
-    fn test_pack() {
-        // TODO add tests
+    fn pack_works() {
+        let sizes = vec![Size::new(10, 20), Size::new(30, 5), Size::new(8, 8), Size::new(12, 20)];
+        let result = HeightRectPacker::pack(&sizes, &RectanglePackerConfig::default()).unwrap();
+
+        assert_eq!(result.rectangles.len(), sizes.len());
+        for (rectangle, size) in result.rectangles.iter().zip(&sizes) {
+            assert_eq!(rectangle.to_size(), *size);
+        }
+        assert!(result.validate(&RectanglePackerConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn pack_with_padding_works() {
+        let sizes: Vec<Size> = (1..40).map(|i| Size::new(i % 7 + 3, i % 5 + 2)).collect();
+        let config = RectanglePackerConfig {
+            rectangle_padding: 2,
+            border_padding: 3,
+            ..Default::default()
+        };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        assert!(result.validate(&config).is_ok());
+    }
+
+    #[test]
+    fn pack_with_max_size_works() {
+        let sizes = vec![Size::new(10, 10); 4];
+        let config = RectanglePackerConfig {
+            max_size: Some(Size::new(24, 24)),
+            rectangle_padding: 1,
+            ..Default::default()
+        };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        assert_eq!(result.size, Size::new(24, 24));
+        assert!(result.validate(&config).is_ok());
+    }
+
+    #[test]
+    fn pack_with_scratch_works() {
+        let mut scratch = PackingScratch::new();
+        let large: Vec<Size> = (1..40).map(|i| Size::new(i % 7 + 3, i % 5 + 2)).collect();
+        let small = vec![Size::new(10, 20), Size::new(30, 5)];
+
+        HeightRectPacker::pack_with_scratch(&large, &RectanglePackerConfig::default(), &mut scratch).unwrap();
+        let result = HeightRectPacker::pack_with_scratch(&small, &RectanglePackerConfig::default(), &mut scratch).unwrap();
+
+        assert_eq!(result.rectangles, HeightRectPacker::pack(&small, &RectanglePackerConfig::default()).unwrap().rectangles);
+    }
+
+    #[test]
+    fn pack_handles_degenerate_inputs() {
+        let config = RectanglePackerConfig { rectangle_padding: 1, border_padding: 2, ..Default::default() };
+
+        let empty = HeightRectPacker::pack(&[], &config).unwrap();
+        assert!(empty.rectangles.is_empty());
+        assert_eq!(empty.size, Size::new(4, 4));
+        assert_eq!(empty.packing_ratio(), 0.0);
+
+        let with_max_size = RectanglePackerConfig { max_size: Some(Size::new(8, 8)), ..Default::default() };
+        assert_eq!(HeightRectPacker::pack(&[], &with_max_size).unwrap().size, Size::new(8, 8));
+
+        let single = HeightRectPacker::pack(&[Size::new(5, 3)], &config).unwrap();
+        assert_eq!(single.rectangles, vec![Rectangle::new(3, 3, 5, 3)]);
+        assert_eq!(single.size, Size::new(11, 9));
+
+        let zero_width = HeightRectPacker::pack(&[Size::new(0, 3), Size::new(0, 2)], &config).unwrap();
+        assert_eq!(zero_width.rectangles.len(), 2);
+        assert!(zero_width.validate(&config).is_ok());
+    }
+
+    #[test]
+    fn owned_padding_is_part_of_the_rectangles() {
+        let sizes = vec![Size::new(8, 4), Size::new(0, 3), Size::new(4, 4)];
+        let config = RectanglePackerConfig { rectangle_padding: 1, owned_padding: 2, ..Default::default() };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        assert_eq!(result.rectangles[0].to_size(), Size::new(12, 8));
+        assert_eq!(result.rectangles[1].to_size(), Size::new(0, 3));
+        assert_eq!(result.rectangles[2].to_size(), Size::new(8, 8));
+        assert_eq!(result.validate(&config), Ok(()));
+
+        // The margin separates the padded rectangles like any other rectangles.
+        let margin_only = RectanglePackerConfig { rectangle_padding: 1, ..Default::default() };
+        assert_eq!(HeightRectPacker::pack(&config.padded_sizes(&sizes), &margin_only).unwrap().rectangles, result.rectangles);
+
+        let too_small = RectanglePackerConfig { max_size: Some(Size::new(8, 8)), owned_padding: 4, ..Default::default() };
+        assert!(HeightRectPacker::pack(&sizes, &too_small).is_err());
+    }
+
+    #[test]
+    fn gutters_separate_columns_and_rows() {
+        let sizes = vec![Size::new(4, 4); 4];
+        let config = RectanglePackerConfig { max_size: Some(Size::new(10, 20)), column_gutter: 2, row_gutter: 1, ..Default::default() };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        assert_eq!(
+            result.rectangles,
+            vec![Rectangle::new(0, 0, 4, 4), Rectangle::new(6, 0, 4, 4), Rectangle::new(0, 5, 4, 4), Rectangle::new(6, 5, 4, 4)]
+        );
+
+        // Without a max size, the gutters after the last column and row are not part of the container.
+        let unbounded = RectanglePackerConfig { rectangle_padding: 1, column_gutter: 3, row_gutter: 2, ..Default::default() };
+        let result = HeightRectPacker::pack(&sizes, &unbounded).unwrap();
+        assert_eq!(result.validate(&unbounded), Ok(()));
+        let right = result.rectangles.iter().map(|r| r.x + r.width).max().unwrap();
+        let bottom = result.rectangles.iter().map(|r| r.y + r.height).max().unwrap();
+        assert_eq!(result.size, Size::new(right + 1, bottom + 1));
+        for a in &result.rectangles {
+            for b in result.rectangles.iter().filter(|b| b.y == a.y && b.x > a.x) {
+                assert!(b.x >= a.x + a.width + 2 + 3);
+            }
+        }
+    }
+
+    #[test]
+    fn row_heights_are_rounded_to_the_multiple() {
+        let sizes = vec![Size::new(6, 5), Size::new(6, 3), Size::new(6, 9)];
+        let config = RectanglePackerConfig { max_size: Some(Size::new(6, 40)), row_height_multiple: Some(8), ..Default::default() };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        assert_eq!(result.validate(&config), Ok(()));
+        assert_eq!(result.rectangles, vec![Rectangle::new(0, 16, 6, 5), Rectangle::new(0, 24, 6, 3), Rectangle::new(0, 0, 6, 9)]);
+
+        // Without a max size, the container ends at the rounded height of the last row.
+        let unbounded = RectanglePackerConfig { row_height_multiple: Some(8), ..Default::default() };
+        let result = HeightRectPacker::pack(&sizes, &unbounded).unwrap();
+        assert!(result.rectangles.iter().all(|r| r.y % 8 == 0));
+        assert_eq!(result.size.height % 8, 0);
+
+        let zero = RectanglePackerConfig { row_height_multiple: Some(0), ..Default::default() };
+        assert_eq!(HeightRectPacker::pack(&sizes, &zero).unwrap_err().config_error, Some(ConfigError::ZeroRowHeightMultiple));
+    }
+
+    #[test]
+    fn row_gaps_are_filled_with_smaller_rectangles() {
+        let sizes = vec![Size::new(10, 10), Size::new(10, 4), Size::new(4, 4), Size::new(4, 4)];
+        let config = RectanglePackerConfig { max_size: Some(Size::new(20, 20)), fill_row_gaps: true, ..Default::default() };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        assert_eq!(result.validate(&config), Ok(()));
+        assert_eq!(
+            result.rectangles,
+            vec![Rectangle::new(0, 0, 10, 10), Rectangle::new(10, 0, 10, 4), Rectangle::new(10, 4, 4, 4), Rectangle::new(14, 4, 4, 4)]
+        );
+
+        // Filling the gaps below the tall rectangles packs the sizes into a smaller container, keeping the margins and gutters.
+        let sizes: Vec<_> = (0..40).map(|i| if i % 8 == 0 { Size::new(6, 30) } else { Size::new(3 + i * 7 % 11, 2 + i * 5 % 13) }).collect();
+        let rows = RectanglePackerConfig { rectangle_padding: 1, column_gutter: 2, row_gutter: 1, ..Default::default() };
+        let filled = RectanglePackerConfig { rectangle_padding: 1, column_gutter: 2, row_gutter: 1, fill_row_gaps: true, ..Default::default() };
+        let without = HeightRectPacker::pack(&sizes, &rows).unwrap();
+        let with = HeightRectPacker::pack(&sizes, &filled).unwrap();
+        assert_eq!(with.validate(&filled), Ok(()));
+        assert!(with.size.width * with.size.height < without.size.width * without.size.height);
+    }
+
+    #[test]
+    fn row_gaps_end_at_the_bottom_of_the_container() {
+        // The last row is rounded up to a height of 4, reaching below the container.
+        let sizes = vec![Size::new(10, 3), Size::new(10, 2), Size::new(5, 1), Size::new(5, 1), Size::new(5, 1)];
+        let config = |height| RectanglePackerConfig {
+            max_size: Some(Size::new(10, height)),
+            row_height_multiple: Some(4),
+            fill_row_gaps: true,
+            ..Default::default()
+        };
+
+        let result = HeightRectPacker::pack(&sizes, &config(7)).unwrap();
+        assert_eq!(result.validate(&config(7)), Ok(()));
+        assert_eq!(result.rectangles[4], Rectangle::new(0, 6, 5, 1));
+
+        // Without room below the last row, the rectangle is not placed in the part of the row outside the container.
+        assert!(HeightRectPacker::pack(&sizes, &config(6)).is_err());
+    }
+
+    #[test]
+    fn pack_applies_zero_size_policy() {
+        let sizes = vec![Size::new(4, 4), Size::new(0, 3), Size::new(5, 0)];
+
+        let placed = HeightRectPacker::pack(&sizes, &RectanglePackerConfig::default()).unwrap();
+        assert!(placed.skipped.is_empty());
+        assert_eq!(placed.rectangles[1].to_size(), Size::new(0, 3));
+
+        let config = RectanglePackerConfig { zero_size_policy: ZeroSizePolicy::Reject, ..Default::default() };
+        assert_eq!(HeightRectPacker::pack(&sizes, &config).unwrap_err().message, "Rectangle 1 has a size of zero: Size { width: 0, height: 3 }");
+
+        let config = RectanglePackerConfig {
+            max_size: Some(Size::new(4, 4)),
+            zero_size_policy: ZeroSizePolicy::Skip,
+            ..Default::default()
+        };
+        let skipped = HeightRectPacker::pack(&sizes, &config).unwrap();
+        assert_eq!(skipped.skipped, vec![1, 2]);
+        assert_eq!(skipped.rectangles, vec![Rectangle::new(0, 0, 4, 4), Rectangle::new(0, 0, 0, 0), Rectangle::new(0, 0, 0, 0)]);
+        assert!(skipped.validate(&config).is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn pack_deduplicates_sizes() {
+        let sizes = vec![Size::new(8, 8), Size::new(4, 8), Size::new(8, 8), Size::new(4, 8), Size::new(8, 8)];
+        let config = RectanglePackerConfig { rectangle_padding: 1, deduplicate_sizes: true, ..Default::default() };
+
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        assert_eq!(result.rectangles[0], result.rectangles[2]);
+        assert_eq!(result.rectangles[0], result.rectangles[4]);
+        assert_eq!(result.rectangles[1], result.rectangles[3]);
+        assert_ne!(result.rectangles[0], result.rectangles[1]);
+        assert!(result.validate(&config).is_ok());
+        assert!(result.validate(&RectanglePackerConfig { rectangle_padding: 1, ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn pack_fails_for_invalid_configs() {
+        let config = RectanglePackerConfig {
+            max_size: Some(Size::new(4, 4)),
+            border_padding: 3,
+            ..Default::default()
+        };
+
+        let error = HeightRectPacker::pack(&[Size::new(1, 1)], &config).unwrap_err();
+        assert_eq!(error.message, "Padding of 3 on each side leaves no room within max size Size { width: 4, height: 4 }");
+    }
+
+    #[test]
+    fn pack_fails_if_rectangles_do_not_fit() {
+        let sizes = vec![Size::new(10, 10); 5];
+        let config = RectanglePackerConfig {
+            max_size: Some(Size::new(20, 20)),
+            ..Default::default()
+        };
+
+        assert!(HeightRectPacker::pack(&sizes, &config).is_err());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn pack_emits_spans_for_its_phases() {
+        use std::sync::{Arc, Mutex};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        // Records the names of all spans created.
+        struct SpanNames(Arc<Mutex<Vec<&'static str>>>);
+
+        impl Subscriber for SpanNames {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut names = self.0.lock().unwrap();
+                names.push(span.metadata().name());
+                Id::from_u64(names.len() as u64)
+            }
+
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let config = RectanglePackerConfig { deduplicate_sizes: true, ..Default::default() };
+        tracing::subscriber::with_default(SpanNames(names.clone()), || {
+            HeightRectPacker::pack(&[Size::new(4, 4), Size::new(4, 4)], &config).unwrap();
+        });
+
+        assert_eq!(*names.lock().unwrap(), vec!["pack", "sort", "place", "deduplicate"]);
+    }
+
+    #[test]
+    fn pack_follows_objective_weights() {
+        let sizes: Vec<Size> = (0..40).map(|i| Size::new(i % 7 + 2, i % 5 + 2)).collect();
+        let pack = |height, squareness, perimeter| {
+            let config = RectanglePackerConfig {
+                rectangle_padding: 1,
+                objective: Some(ObjectiveWeights { height, squareness, perimeter, area: 0.0 }),
+                ..Default::default()
+            };
+            let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+            assert!(result.validate(&config).is_ok());
+            result.size
+        };
+
+        let flat = pack(1.0, 0.0, 0.0);
+        let square = pack(0.0, 1.0, 0.0);
+        let small = pack(0.0, 0.0, 1.0);
+
+        // Only minimizing the height packs all rectangles into a single row.
+        assert_eq!(flat.height, 8);
+        assert!(square.width.abs_diff(square.height) < flat.width.abs_diff(flat.height));
+        assert!(small.width + small.height <= square.width + square.height);
+    }
+
+    #[test]
+    fn pack_minimizes_area_by_default() {
+        let sizes: Vec<Size> = (0..40).map(|i| Size::new(i % 7 + 2, i % 5 + 2)).collect();
+        let config = RectanglePackerConfig { rectangle_padding: 1, ..Default::default() };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+        assert!(result.validate(&config).is_ok());
+
+        let area = |size: Size| size.width * size.height;
+        let mut order: Vec<usize> = (0..sizes.len()).collect();
+        order.sort_unstable_by(|&a, &b| sizes[b].cmp(&sizes[a]).then(a.cmp(&b)));
+        for width in (9..=100).step_by(7) {
+            let size = container_size(&sizes, &order, &config, width);
+            assert!(area(result.size) as f64 <= area(size) as f64 * 1.1);
+        }
+
+        // Tiles of the same height are not packed into a single row, although it wastes no area.
+        let tiles = HeightRectPacker::pack(&vec![Size::new(16, 16); 100], &RectanglePackerConfig::default()).unwrap();
+        assert_eq!(tiles.size, Size::new(160, 160));
+    }
+
+    #[test]
+    fn pack_places_equal_sizes_in_the_order_of_their_indices() {
+        let sizes: Vec<Size> = (0..64).map(|i| Size::new(4 + i % 2, 4)).collect();
+        let config = RectanglePackerConfig { max_size: Some(Size::new(40, 40)), ..Default::default() };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        for (a, b) in (1..64).step_by(2).zip((3..64).step_by(2)).chain((0..64).step_by(2).zip((2..64).step_by(2))) {
+            let (a, b) = (result.rectangles[a], result.rectangles[b]);
+            assert!((a.y, a.x) < (b.y, b.x));
+        }
+    }
+
+    #[test]
+    fn pack_respects_the_memory_budget() {
+        let sizes: Vec<Size> = (0..100).map(|i| Size::new(i % 9 + 1, i % 4 + 1)).collect();
+        let order_memory = sizes.len() * size_of::<usize>();
+        let config = |memory_budget| RectanglePackerConfig { memory_budget, ..Default::default() };
+
+        // Without room for the width sweep, the rows are as wide as a square of the area of the rectangles.
+        let degraded = HeightRectPacker::pack(&sizes, &config(Some(order_memory))).unwrap();
+        let area: usize = sizes.iter().map(|s| s.width * s.height).sum();
+        assert_eq!(degraded.size.width, square_root_ceil(area).max(9));
+        assert!(degraded.validate(&config(Some(order_memory))).is_ok());
+
+        let swept = HeightRectPacker::pack(&sizes, &config(Some(order_memory + SWEEP_MEMORY))).unwrap();
+        assert_eq!(swept.rectangles, HeightRectPacker::pack(&sizes, &config(None)).unwrap().rectangles);
+
+        let error = HeightRectPacker::pack(&sizes, &config(Some(order_memory - 1))).unwrap_err();
+        assert_eq!(error.config_error, Some(ConfigError::MemoryBudgetExceeded { required: order_memory, budget: order_memory - 1 }));
+    }
+}
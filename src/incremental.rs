@@ -0,0 +1,519 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use crate::free_rectangles::FreeRectangles;
+use crate::{Rectangle, RectanglePacker, RectanglePackerConfig, RectanglePackingError, RectanglePackingResult, Size};
+
+/// The error returned by `RectanglePackingResult::append` if some of the new rectangles did not fit.
+#[derive(Debug)]
+pub struct AppendError {
+    /// The indices of the new sizes that did not fit, in increasing order.
+    pub unplaced: Vec<usize>,
+
+    /// The result with all new rectangles that did fit appended, and the ones that did not fit skipped.
+    pub result: RectanglePackingResult,
+}
+
+impl fmt::Display for AppendError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Could not fit the new rectangles {:?} into the free space", self.unplaced)
+    }
+}
+
+impl Error for AppendError {}
+
+impl RectanglePackingResult {
+    /// Changes the sizes of some rectangles, moving as few rectangles as possible.
+    ///
+    /// All other rectangles keep their positions. A changed rectangle keeps its position as well if it still
+    /// fits there without overlapping the other rectangles, their padding or the border. Otherwise it is
+    /// placed into the free space left by the other rectangles, starting with the highest ones.
+    /// Without a maximum size in the config, the container grows in height if the free space is not sufficient,
+    /// while its width stays the same, so rectangles wider than the container can not be placed.
+    /// The zero size policy of the config applies to the new sizes, so skipped rectangles changed to a size
+    /// other than zero are placed, and rectangles changed to a size of zero may be skipped.
+    ///
+    /// # Arguments
+    /// * `changes` - The indices of the rectangles to change, with their new sizes.
+    /// * `config` - The config the result was packed with.
+    ///
+    /// # Returns
+    /// A `Result` containing either the updated result or a `RectanglePackingError` if a changed rectangle
+    /// does not fit into the maximum size or the zero size policy rejects a new size. The result of the error is the unchanged result.
+    ///
+    /// # Panics
+    /// Panics if an index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+    ///
+    /// let sizes = vec![Size::new(10, 10); 8];
+    /// let config = RectanglePackerConfig { max_size: Some(Size::new(64, 64)), rectangle_padding: 1, ..Default::default() };
+    /// let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+    ///
+    /// let repacked = result.repack(&[(3, Size::new(20, 12))], &config).unwrap();
+    ///
+    /// assert_eq!(repacked.rectangles[3].to_size(), Size::new(20, 12));
+    /// assert_eq!(repacked.rectangles[0], result.rectangles[0]);
+    /// assert!(repacked.validate(&config).is_ok());
+    /// ```
+    pub fn repack(&self, changes: &[(usize, Size)], config: &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError> {
+        let padding = config.rectangle_padding;
+        let border = config.border_padding;
+        let changed: BTreeMap<usize, Size> = changes.iter().map(|&(index, size)| (index, config.padded_size(size))).collect();
+        let mut rectangles = self.rectangles.clone();
+
+        // Apply the zero size policy to the new sizes. Skipped rectangles are empty and do not occupy any space.
+        let sizes: Vec<Size> = changed.values().copied().collect();
+        let newly_skipped = config.zero_size_policy.skipped(&sizes).map_err(|error| RectanglePackingError {
+            message: error.message,
+            config_error: error.config_error,
+            result: RectanglePackingResult { rectangles: self.rectangles.clone(), size: self.size, skipped: self.skipped.clone() },
+        })?;
+        let indices: Vec<usize> = changed.keys().copied().collect();
+        let mut skipped: Vec<usize> = self.skipped.iter().copied().filter(|index| !changed.contains_key(index)).collect();
+        skipped.extend(newly_skipped.iter().map(|&i| indices[i]));
+        skipped.sort_unstable();
+        for &index in &skipped {
+            rectangles[index] = Rectangle::new(0, 0, 0, 0);
+        }
+        let placed = |index: usize| skipped.binary_search(&index).is_err();
+
+        // Resize the changed rectangles in place, in the order of their indices, and remember the ones that
+        // do not fit there anymore. Changed rectangles that come later are checked against the resized ones instead.
+        let inner = Rectangle::new(
+            border,
+            border,
+            self.size.width.saturating_sub(2 * border),
+            self.size.height.saturating_sub(2 * border),
+        );
+        let mut moved = Vec::new();
+        for (&index, &size) in changed.iter().filter(|(&index, _)| placed(index)) {
+            // Rectangles that were skipped before have no position to resize them at.
+            if self.skipped.binary_search(&index).is_ok() {
+                moved.push(index);
+                continue;
+            }
+            let resized = Rectangle::from_size(rectangles[index].x, rectangles[index].y, &size).expanded(padding);
+            let collides = rectangles.iter().enumerate().any(|(other, r)| {
+                other != index && placed(other) && !moved.contains(&other) && r.expanded(padding).intersects(&resized)
+                    && !(changed.contains_key(&other) && other > index)
+            });
+            if !inner.contains(&resized) || collides {
+                moved.push(index);
+            } else {
+                rectangles[index] = Rectangle::from_size(rectangles[index].x, rectangles[index].y, &size);
+            }
+        }
+
+        if moved.is_empty() {
+            return Ok(RectanglePackingResult { rectangles, size: self.size, skipped });
+        }
+        moved.sort_unstable_by(|&a, &b| changed[&b].cmp(&changed[&a]).then(a.cmp(&b)));
+
+        // Without a maximum size, leave room below the container to stack all moved rectangles.
+        let extra_height: usize = match config.max_size {
+            Some(_) => 0,
+            None => moved.iter().map(|i| changed[i].height + 2 * padding).sum(),
+        };
+        let bounds = Rectangle::new(inner.x, inner.y, inner.width, inner.height + extra_height);
+        let mut free = FreeRectangles::new(bounds);
+        for (index, r) in rectangles.iter().enumerate() {
+            if placed(index) && !moved.contains(&index) {
+                free.occupy(&r.expanded(padding));
+            }
+        }
+
+        for &index in &moved {
+            let size = changed[&index];
+            let Some(target) = free.best_fit(size.width + 2 * padding, size.height + 2 * padding) else {
+                return Err(RectanglePackingError {
+                    message: format!("Could not fit rectangle {} with size {:?} in max size", index, size),
+                    config_error: None,
+                    result: RectanglePackingResult { rectangles: self.rectangles.clone(), size: self.size, skipped: self.skipped.clone() },
+                });
+            };
+            let placed = Rectangle::from_size(target.x + padding, target.y + padding, &size);
+            free.occupy(&placed.expanded(padding));
+            rectangles[index] = placed;
+        }
+
+        let bottom = rectangles.iter().map(|r| r.y + r.height + padding + border).max().unwrap_or(0);
+        let size = match config.max_size {
+            Some(_) => self.size,
+            None => Size::new(self.size.width, self.size.height.max(bottom)),
+        };
+        Ok(RectanglePackingResult { rectangles, size, skipped })
+    }
+
+    /// Inserts a rectangle of the given size into the free space of the container, without moving any other rectangle.
+    ///
+    /// The rectangle is placed into the free space left by the other rectangles, their padding and the border,
+    /// and appended to the rectangles of the result. The container keeps its size.
+    ///
+    /// # Arguments
+    /// * `size` - The size of the rectangle to insert.
+    /// * `config` - The config the result was packed with.
+    ///
+    /// # Returns
+    /// The inserted rectangle, or `None` if there is no free space large enough for it, leaving the result unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+    ///
+    /// let config = RectanglePackerConfig { max_size: Some(Size::new(32, 32)), ..Default::default() };
+    /// let mut result = HeightRectPacker::pack(&[Size::new(16, 16)], &config).unwrap();
+    ///
+    /// let inserted = result.try_insert(Size::new(8, 8), &config).unwrap();
+    /// assert_eq!(result.rectangles[1], inserted);
+    /// assert!(result.try_insert(Size::new(32, 32), &config).is_none());
+    /// ```
+    pub fn try_insert(&mut self, size: Size, config: &RectanglePackerConfig) -> Option<Rectangle> {
+        let size = config.padded_size(size);
+        let padding = config.rectangle_padding;
+        let border = config.border_padding;
+        let inner = Rectangle::new(
+            border,
+            border,
+            self.size.width.saturating_sub(2 * border),
+            self.size.height.saturating_sub(2 * border),
+        );
+
+        let mut free = FreeRectangles::new(inner);
+        for (index, r) in self.rectangles.iter().enumerate() {
+            if self.skipped.binary_search(&index).is_err() {
+                free.occupy(&r.expanded(padding));
+            }
+        }
+
+        let target = free.best_fit(size.width + 2 * padding, size.height + 2 * padding)?;
+        let inserted = Rectangle::from_size(target.x + padding, target.y + padding, &size);
+        self.rectangles.push(inserted);
+        Some(inserted)
+    }
+
+    /// Appends rectangles of the given sizes by placing them into the free space of the container,
+    /// without moving any of the existing rectangles or growing the container.
+    ///
+    /// The new rectangles are placed the largest ones first, into the free space left by the existing rectangles,
+    /// their padding and the border, and their indices follow the existing ones. Zero sizes skipped by the zero size
+    /// policy of the config are skipped, and zero sizes the policy rejects are reported as not fitting.
+    ///
+    /// # Arguments
+    /// * `sizes` - The sizes of the new rectangles.
+    /// * `config` - The config the result was packed with.
+    ///
+    /// # Returns
+    /// A `Result` containing either the result with the new rectangles appended, or an `AppendError`
+    /// listing the new sizes that did not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+    ///
+    /// let config = RectanglePackerConfig { max_size: Some(Size::new(32, 32)), ..Default::default() };
+    /// let result = HeightRectPacker::pack(&[Size::new(16, 16)], &config).unwrap();
+    ///
+    /// let appended = result.append(&[Size::new(16, 16), Size::new(8, 8)], &config).unwrap();
+    /// assert_eq!(appended.rectangles[0], result.rectangles[0]);
+    /// assert_eq!(appended.rectangles.len(), 3);
+    ///
+    /// let error = result.append(&[Size::new(8, 8), Size::new(32, 20)], &config).unwrap_err();
+    /// assert_eq!(error.unplaced, vec![1]);
+    /// ```
+    pub fn append(&self, sizes: &[Size], config: &RectanglePackerConfig) -> Result<RectanglePackingResult, AppendError> {
+        let sizes = &config.padded_sizes(sizes);
+        let padding = config.rectangle_padding;
+        let border = config.border_padding;
+        let inner = Rectangle::new(
+            border,
+            border,
+            self.size.width.saturating_sub(2 * border),
+            self.size.height.saturating_sub(2 * border),
+        );
+
+        let mut free = FreeRectangles::new(inner);
+        for (index, r) in self.rectangles.iter().enumerate() {
+            if self.skipped.binary_search(&index).is_err() {
+                free.occupy(&r.expanded(padding));
+            }
+        }
+
+        let zero = |i: &usize| sizes[*i].width == 0 || sizes[*i].height == 0;
+        let (skipped, mut unplaced) = match config.zero_size_policy.skipped(sizes) {
+            Ok(skipped) => (skipped, Vec::new()),
+            Err(_) => (Vec::new(), (0..sizes.len()).filter(zero).collect()),
+        };
+        let mut order: Vec<usize> = (0..sizes.len())
+            .filter(|i| skipped.binary_search(i).is_err() && unplaced.binary_search(i).is_err())
+            .collect();
+        order.sort_unstable_by(|&a, &b| sizes[b].cmp(&sizes[a]).then(a.cmp(&b)));
+
+        let mut rectangles = vec![Rectangle::new(0, 0, 0, 0); sizes.len()];
+        for index in order {
+            let size = sizes[index];
+            match free.best_fit(size.width + 2 * padding, size.height + 2 * padding) {
+                Some(target) => {
+                    let placed = Rectangle::from_size(target.x + padding, target.y + padding, &size);
+                    free.occupy(&placed.expanded(padding));
+                    rectangles[index] = placed;
+                }
+                None => unplaced.push(index),
+            }
+        }
+        unplaced.sort_unstable();
+
+        let offset = self.rectangles.len();
+        let mut result_skipped = self.skipped.clone();
+        result_skipped.extend(skipped.iter().chain(&unplaced).map(|i| i + offset));
+        result_skipped.sort_unstable();
+        let result = RectanglePackingResult {
+            rectangles: self.rectangles.iter().copied().chain(rectangles).collect(),
+            size: self.size,
+            skipped: result_skipped,
+        };
+
+        match unplaced.is_empty() {
+            true => Ok(result),
+            false => Err(AppendError { unplaced, result }),
+        }
+    }
+}
+
+/// Packs the sizes starting from a previous packing, so that rectangles with unchanged sizes keep their positions
+/// when possible, e.g. to avoid re-uploading textures and changing texture coordinates between runs of an asset bake.
+///
+/// The sizes are matched to the rectangles of the previous packing by their indices. A rectangle whose size did not change
+/// keeps its position if it lies within the container and does not overlap the rectangles kept before it, with their
+/// padding. All other sizes are placed into the free space left by the kept rectangles, the largest ones first.
+/// Without a maximum size in the config, the container keeps the size of the previous packing and grows in height
+/// if the free space is not sufficient. If a size does not fit at all, the sizes are packed from scratch with the packer `P`.
+///
+/// # Arguments
+/// * `sizes` - The sizes of the rectangles to pack.
+/// * `config` - The config to pack the rectangles with.
+/// * `previous` - The previous packing, usually packed with the same config.
+///
+/// # Returns
+/// A `Result` containing either:
+///   - The `RectanglePackingResult` of the packing.
+///   - A `RectanglePackingError` if the config is invalid, the zero size policy rejects a size,
+///     or packing from scratch failed.
+///
+/// # Examples
+/// ```
+/// use rpack::{pack_warm, HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+///
+/// let config = RectanglePackerConfig { max_size: Some(Size::new(64, 64)), ..Default::default() };
+/// let previous = HeightRectPacker::pack(&[Size::new(16, 16), Size::new(8, 8)], &config).unwrap();
+///
+/// let sizes = vec![Size::new(16, 16), Size::new(12, 12), Size::new(32, 8)];
+/// let result = pack_warm::<HeightRectPacker>(&sizes, &config, &previous).unwrap();
+///
+/// assert_eq!(result.rectangles[0], previous.rectangles[0]);
+/// assert!(result.validate(&config).is_ok());
+/// ```
+pub fn pack_warm<P: RectanglePacker>(
+    sizes: &[Size],
+    config: &RectanglePackerConfig,
+    previous: &RectanglePackingResult,
+) -> Result<RectanglePackingResult, RectanglePackingError> {
+    // Leave reporting invalid configs to the packer.
+    if config.validate().is_err() {
+        return P::pack(sizes, config);
+    }
+    let skipped = config.zero_size_policy.skipped(sizes)?;
+    let padding = config.rectangle_padding;
+    let border = config.border_padding;
+    let original = sizes;
+    let sizes = &config.padded_sizes(sizes);
+
+    let size = config.max_size.unwrap_or(previous.size);
+    let inner = Rectangle::new(border, border, size.width.saturating_sub(2 * border), size.height.saturating_sub(2 * border));
+
+    // Without a maximum size, leave room below the container to stack all sizes that are not kept.
+    let extra_height: usize = match config.max_size {
+        Some(_) => 0,
+        None => sizes.iter().map(|s| s.height + 2 * padding).sum(),
+    };
+    let mut free = FreeRectangles::new(Rectangle::new(inner.x, inner.y, inner.width, inner.height + extra_height));
+    let mut rectangles = vec![Rectangle::new(0, 0, 0, 0); sizes.len()];
+
+    let mut moved = Vec::new();
+    for index in (0..sizes.len()).filter(|i| skipped.binary_search(i).is_err()) {
+        let kept = previous.rectangles.get(index).filter(|r| {
+            previous.skipped.binary_search(&index).is_err() && r.to_size() == sizes[index] && {
+                let padded = r.expanded(padding);
+                inner.contains(&padded) && free.iter().any(|f| f.contains(&padded))
+            }
+        });
+        match kept {
+            Some(r) => {
+                free.occupy(&r.expanded(padding));
+                rectangles[index] = *r;
+            }
+            None => moved.push(index),
+        }
+    }
+
+    moved.sort_unstable_by(|&a, &b| sizes[b].cmp(&sizes[a]).then(a.cmp(&b)));
+    for &index in &moved {
+        let size = sizes[index];
+        let Some(target) = free.best_fit(size.width + 2 * padding, size.height + 2 * padding) else {
+            return P::pack(original, config);
+        };
+        let placed = Rectangle::from_size(target.x + padding, target.y + padding, &size);
+        free.occupy(&placed.expanded(padding));
+        rectangles[index] = placed;
+    }
+
+    let bottom = rectangles.iter().map(|r| r.y + r.height + padding + border).max().unwrap_or(0);
+    let size = match config.max_size {
+        Some(max_size) => max_size,
+        None => Size::new(size.width, size.height.max(bottom)),
+    };
+    Ok(RectanglePackingResult { rectangles, size, skipped })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeightRectPacker, ZeroSizePolicy};
+
+    use super::*;
+
+    #[test]
+    fn shrunk_rectangles_stay_in_place() {
+        let sizes = vec![Size::new(10, 10); 6];
+        let result = HeightRectPacker::pack(&sizes, &RectanglePackerConfig::default()).unwrap();
+
+        let repacked = result.repack(&[(2, Size::new(4, 4))], &RectanglePackerConfig::default()).unwrap();
+
+        assert_eq!(repacked.rectangles[2], Rectangle::from_size(result.rectangles[2].x, result.rectangles[2].y, &Size::new(4, 4)));
+        assert_eq!(repacked.size, result.size);
+    }
+
+    #[test]
+    fn grown_rectangles_are_moved() {
+        let sizes: Vec<Size> = (0..20).map(|i| Size::new(i % 5 + 4, i % 3 + 4)).collect();
+        let config = RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        let repacked = result.repack(&[(4, Size::new(result.size.width - 4, 9)), (7, Size::new(8, 12))], &config).unwrap();
+
+        assert_eq!(repacked.rectangles[4].to_size(), Size::new(result.size.width - 4, 9));
+        assert_eq!(repacked.rectangles[7].to_size(), Size::new(8, 12));
+        for index in (0..sizes.len()).filter(|&i| i != 4 && i != 7) {
+            assert_eq!(repacked.rectangles[index], result.rectangles[index]);
+        }
+        assert_eq!(repacked.size.width, result.size.width);
+        assert_eq!(repacked.validate(&config), Ok(()));
+    }
+
+    #[test]
+    fn repack_applies_zero_size_policy() {
+        let config = RectanglePackerConfig { rectangle_padding: 1, zero_size_policy: ZeroSizePolicy::Skip, ..Default::default() };
+        let result = HeightRectPacker::pack(&[Size::new(4, 4), Size::new(0, 3)], &config).unwrap();
+        assert_eq!(result.skipped, vec![1]);
+
+        let repacked = result.repack(&[(1, Size::new(3, 3))], &config).unwrap();
+        assert!(repacked.skipped.is_empty());
+        assert_eq!(repacked.rectangles[1].to_size(), Size::new(3, 3));
+        assert_eq!(repacked.validate(&config), Ok(()));
+
+        let emptied = repacked.repack(&[(0, Size::new(4, 0))], &config).unwrap();
+        assert_eq!(emptied.skipped, vec![0]);
+        assert_eq!(emptied.rectangles[0], Rectangle::new(0, 0, 0, 0));
+
+        let rejecting = RectanglePackerConfig { zero_size_policy: ZeroSizePolicy::Reject, ..Default::default() };
+        assert!(repacked.repack(&[(0, Size::new(4, 0))], &rejecting).is_err());
+    }
+
+    #[test]
+    fn try_insert_works() {
+        let sizes: Vec<Size> = (0..12).map(|i| Size::new(i % 4 + 3, i % 3 + 3)).collect();
+        let config = RectanglePackerConfig { max_size: Some(Size::new(40, 40)), rectangle_padding: 1, border_padding: 2, ..Default::default() };
+        let mut result = HeightRectPacker::pack(&sizes, &config).unwrap();
+        let original = result.rectangles.clone();
+
+        for _ in 0..5 {
+            assert!(result.try_insert(Size::new(5, 5), &config).is_some());
+        }
+
+        assert_eq!(result.rectangles[..sizes.len()], original[..]);
+        assert_eq!(result.rectangles.len(), sizes.len() + 5);
+        assert_eq!(result.validate(&config), Ok(()));
+    }
+
+    #[test]
+    fn try_insert_fails_without_free_space() {
+        let config = RectanglePackerConfig { max_size: Some(Size::new(20, 10)), ..Default::default() };
+        let mut result = HeightRectPacker::pack(&[Size::new(10, 10), Size::new(10, 10)], &config).unwrap();
+
+        assert_eq!(result.try_insert(Size::new(1, 1), &config), None);
+        assert_eq!(result.rectangles.len(), 2);
+    }
+
+    #[test]
+    fn repack_fails_if_rectangles_do_not_fit() {
+        let sizes = vec![Size::new(10, 10); 4];
+        let config = RectanglePackerConfig { max_size: Some(Size::new(20, 20)), ..Default::default() };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        assert!(result.repack(&[(0, Size::new(15, 10))], &config).is_err());
+    }
+
+    #[test]
+    fn pack_warm_keeps_unchanged_rectangles() {
+        let sizes: Vec<Size> = (0..20).map(|i| Size::new(i % 5 + 4, i % 3 + 4)).collect();
+        let config = RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() };
+        let previous = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        let mut changed = sizes.clone();
+        changed[3] = Size::new(9, 9);
+        changed.remove(10);
+        changed.push(Size::new(5, 5));
+        let result = pack_warm::<HeightRectPacker>(&changed, &config, &previous).unwrap();
+
+        assert_eq!(result.validate(&config), Ok(()));
+        assert_eq!(result.size.width, previous.size.width);
+        for index in (0..10).filter(|&i| i != 3) {
+            assert_eq!(result.rectangles[index], previous.rectangles[index]);
+        }
+        assert_eq!(result.rectangles[3].to_size(), Size::new(9, 9));
+    }
+
+    #[test]
+    fn pack_warm_packs_from_scratch_if_sizes_do_not_fit() {
+        let config = RectanglePackerConfig::default();
+        let previous = HeightRectPacker::pack(&[Size::new(8, 8)], &config).unwrap();
+
+        let sizes = vec![Size::new(8, 8), Size::new(16, 4)];
+        let result = pack_warm::<HeightRectPacker>(&sizes, &config, &previous).unwrap();
+
+        assert_eq!(result.rectangles, HeightRectPacker::pack(&sizes, &config).unwrap().rectangles);
+    }
+
+    #[test]
+    fn append_never_moves_existing_rectangles() {
+        let sizes: Vec<Size> = (0..12).map(|i| Size::new(i % 5 + 4, i % 3 + 4)).collect();
+        let config = RectanglePackerConfig {
+            max_size: Some(Size::new(40, 40)),
+            rectangle_padding: 1,
+            zero_size_policy: ZeroSizePolicy::Skip,
+            ..Default::default()
+        };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+        let error = result.append(&[Size::new(6, 6), Size::new(0, 3), Size::new(40, 40), Size::new(3, 3)], &config).unwrap_err();
+        assert_eq!(error.unplaced, vec![2]);
+        assert_eq!(error.result.rectangles[..12], result.rectangles[..]);
+        assert_eq!(error.result.skipped, vec![13, 14]);
+        assert_eq!(error.result.rectangles[12].to_size(), Size::new(6, 6));
+        assert_eq!(error.result.validate(&config), Ok(()));
+
+        let config = RectanglePackerConfig { zero_size_policy: ZeroSizePolicy::Reject, ..config };
+        assert_eq!(result.append(&[Size::new(0, 3), Size::new(3, 3)], &config).unwrap_err().unplaced, vec![0]);
+    }
+}
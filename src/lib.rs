@@ -2,12 +2,98 @@
 pub mod rectangle;
 pub mod size;
 pub mod rectangle_packer;
+pub mod observer;
 pub mod area;
 pub mod height_rect_pack;
+pub mod box_packing;
+pub mod bin_packing;
+pub mod circle_packing;
+pub mod subpixel;
+pub mod mask_packing;
+pub mod cutting_stock;
+pub mod treemap;
+pub mod validation;
+pub mod comparison;
+pub mod portfolio;
+pub mod batch;
+pub mod pages;
+pub mod fragmentation;
+pub mod incremental;
+pub mod datasets;
+mod free_rectangles;
+pub mod allocator;
+pub mod streaming;
+pub mod atlas_layout;
+pub mod atlas_pages;
+pub mod uv;
+pub mod export;
+pub mod ascii;
+pub mod steps;
+pub mod search;
+#[cfg(any(feature = "svg", feature = "image"))]
+mod render;
+#[cfg(feature = "svg")]
+pub mod svg;
+#[cfg(feature = "image")]
+pub mod png;
+#[cfg(feature = "image")]
+pub mod atlas;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "bevy")]
+pub mod bevy;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "egui")]
+pub mod egui;
+#[cfg(any(feature = "ab_glyph", feature = "fontdue"))]
+pub mod glyph_atlas;
+#[cfg(feature = "wgpu")]
+pub mod wgpu;
+#[cfg(feature = "sdl2")]
+pub mod sdl2;
+#[cfg(feature = "benchmarks")]
+pub mod benchmarks;
+#[cfg(feature = "problem")]
+pub mod problem;
+#[cfg(feature = "nesting")]
+pub mod nesting;
+#[cfg(any(feature = "proptest", feature = "arbitrary"))]
+pub mod strategies;
+#[cfg(feature = "golden")]
+pub mod golden;
+#[cfg(feature = "tokio")]
+pub mod tokio;
 
 // Re-exports
 pub use rectangle::Rectangle;
 pub use size::Size;
 pub use area::Area;
-pub use crate::rectangle_packer::{RectanglePacker, RectanglePackingResult, RectanglePackingError, RectanglePackerConfig};
-pub use height_rect_pack::HeightRectPacker;
\ No newline at end of file
+pub use crate::rectangle_packer::{RectanglePacker, RectanglePackingResult, RectanglePackingError, RectanglePackerConfig, PackingScratch, ZeroSizePolicy, OverflowPolicy, PageFill, ObjectiveWeights, PackedItem, PackedItems};
+pub use observer::{Candidate, DecisionLog, PackingEvent, PackingObserver, PackingTrace, PlacementDecision};
+pub use steps::{pack_steps, PackingStep, PackingSteps};
+pub use search::{best_strip_width, pareto_front, smallest_container, ContainerShape};
+pub use height_rect_pack::HeightRectPacker;
+pub use bin_packing::{BestFitDecreasing, BinPacker, BinPackerConfig, BinPackingError, BinPackingResult, BinPlacement, FirstFitDecreasing};
+pub use circle_packing::{pack_circles, Circle, CirclePackerConfig, CirclePackingError, CirclePackingResult};
+pub use subpixel::{pack_subpixel, RectangleF32, SizeF32, SubpixelPackerConfig, SubpixelPackingError, SubpixelPackingResult};
+pub use mask_packing::{pack_masks, Mask, MaskPackerConfig, MaskPackingError, MaskPackingResult};
+pub use cutting_stock::{plan_cuts, plan_cuts_with_grain, Cut, CutDirection, CuttingPlan, CuttingStockConfig, CuttingStockError, Grain, GrainAxis, SheetPlacement};
+pub use treemap::squarify;
+pub use box_packing::{Box3, BoxLoad, BoxPacker, BoxPackerConfig, BoxPackingError, BoxPackingResult, LayerBoxPacker, Size3, StackingPacker};
+pub use validation::{ConfigError, ValidationError};
+pub use comparison::{compare, ComparisonEntry, ComparisonReport, PackFn};
+pub use portfolio::{Portfolio, PortfolioResult};
+pub use batch::{pack_batch, PackJob};
+pub use pages::{pack_pages, PagePlacement, PagedPackingResult, PagingError, TilePlacement};
+pub use fragmentation::{FragmentationHistogram, HistogramBucket};
+pub use atlas_layout::{AtlasLayout, AtlasSprite, NineSlice};
+pub use atlas_pages::{page_name, AtlasPage, AtlasPages, DEFAULT_PAGE_TEMPLATE};
+pub use uv::UvRect;
+pub use allocator::{Allocation, AllocationId, AllocatorState, AtlasAllocator, EvictionPlan, PackerState};
+pub use streaming::StreamingPacker;
+pub use incremental::{pack_warm, AppendError};
+#[cfg(feature = "image")]
+pub use atlas::{Atlas, AtlasBuilder, PagedAtlas};
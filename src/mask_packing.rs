@@ -0,0 +1,262 @@
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "image")]
+use image::RgbaImage;
+
+use crate::{Rectangle, Size};
+
+/// The occupancy of an item on a pixel grid, e.g. the opaque pixels of a sprite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    /// The width of the mask.
+    pub width: usize,
+
+    /// The height of the mask.
+    pub height: usize,
+
+    /// Whether each pixel is occupied, row by row.
+    pub bits: Vec<bool>,
+}
+
+impl Mask {
+    /// Creates a new mask of the given size without any occupied pixels.
+    pub fn new(width: usize, height: usize) -> Mask {
+        Mask { width, height, bits: vec![false; width * height] }
+    }
+
+    /// Creates a new mask of the given size, occupying the pixels for which `f` returns `true`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::Mask;
+    ///
+    /// let triangle = Mask::from_fn(3, 3, |x, y| x <= y);
+    /// assert_eq!(triangle.count(), 6);
+    /// ```
+    pub fn from_fn<F>(width: usize, height: usize, f: F) -> Mask
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        Mask { width, height, bits: (0..width * height).map(|i| f(i % width.max(1), i / width.max(1))).collect() }
+    }
+
+    /// Creates a new mask occupying the pixels of the image with an alpha above the threshold.
+    ///
+    /// # Arguments
+    /// * `image` - The image, e.g. a sprite.
+    /// * `threshold` - The highest alpha of a pixel that is considered empty, usually `0`.
+    #[cfg(feature = "image")]
+    pub fn from_alpha(image: &RgbaImage, threshold: u8) -> Mask {
+        Mask::from_fn(image.width() as usize, image.height() as usize, |x, y| image.get_pixel(x as u32, y as u32)[3] > threshold)
+    }
+
+    /// Returns `true` if the pixel is occupied.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.bits[y * self.width + x]
+    }
+
+    /// Sets whether the pixel is occupied.
+    pub fn set(&mut self, x: usize, y: usize, occupied: bool) {
+        self.bits[y * self.width + x] = occupied;
+    }
+
+    /// Returns the number of occupied pixels.
+    pub fn count(&self) -> usize {
+        self.bits.iter().filter(|&&bit| bit).count()
+    }
+
+    /// Returns the coordinates of the occupied pixels.
+    fn occupied(&self) -> Vec<(usize, usize)> {
+        (0..self.bits.len()).filter(|&i| self.bits[i]).map(|i| (i % self.width, i / self.width)).collect()
+    }
+}
+
+/// The configuration of the mask packer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaskPackerConfig {
+    /// The width of the container.
+    pub width: usize,
+
+    /// The height of the container.
+    ///
+    /// If `None`, the container is as high as needed to fit all masks.
+    /// If `Some`, an error is returned if the masks cannot be packed within that height.
+    pub height: Option<usize>,
+
+    /// The number of empty pixels kept between the occupied pixels of two masks, along both axes and diagonally.
+    pub padding: usize,
+}
+
+/// The result of packing masks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaskPackingResult {
+    /// The rectangle every mask was placed at, in the order of the masks.
+    ///
+    /// The rectangles of masks can overlap, but their occupied pixels never do.
+    pub rectangles: Vec<Rectangle>,
+
+    /// The size of the smallest container holding all rectangles.
+    pub size: Size,
+}
+
+/// The error returned if masks could not be packed.
+#[derive(Debug, Clone)]
+pub struct MaskPackingError {
+    /// The error message.
+    pub message: String,
+}
+
+impl fmt::Display for MaskPackingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for MaskPackingError {}
+
+/// Packs masks into a container, placing each mask at the first position from the top-left where its occupied pixels
+/// do not collide with those of the masks placed so far.
+///
+/// Only the occupied pixels are tested for collisions, so concave masks, like the opaque pixels of sprites, interlock
+/// and share the empty parts of their rectangles. The masks are placed from the one with the most occupied pixels to the
+/// one with the fewest. Each position is tested pixel by pixel, so the time taken grows with the area of the container
+/// times the occupied pixels of the masks.
+///
+/// # Arguments
+/// * `masks` - The masks to pack.
+/// * `config` - The configuration of the packer.
+///
+/// # Returns
+/// A `Result` containing either:
+///   - A `MaskPackingResult` with the rectangle of every mask, in the order of the masks, and the size of the container.
+///   - A `MaskPackingError` if a mask is wider than the container or the masks do not fit within its height.
+///
+/// # Examples
+/// ```
+/// use rpack::{pack_masks, Mask, MaskPackerConfig, Rectangle};
+///
+/// let lower = Mask::from_fn(4, 4, |x, y| x <= y);
+/// let upper = Mask::from_fn(4, 4, |x, y| x > y);
+/// let result = pack_masks(&[lower, upper], &MaskPackerConfig { width: 4, ..Default::default() }).unwrap();
+///
+/// // The triangles interlock into a single square.
+/// assert_eq!(result.rectangles, vec![Rectangle::new(0, 0, 4, 4); 2]);
+/// ```
+pub fn pack_masks(masks: &[Mask], config: &MaskPackerConfig) -> Result<MaskPackingResult, MaskPackingError> {
+    if let Some(index) = masks.iter().position(|mask| mask.width > config.width) {
+        return Err(MaskPackingError {
+            message: format!("Mask {} of width {} does not fit in width {}", index, masks[index].width, config.width),
+        });
+    }
+
+    // Without a height, stacking all masks on top of each other always fits.
+    let height = config.height.unwrap_or_else(|| masks.iter().map(|mask| mask.height + config.padding).sum());
+    let mut grid = Mask::new(config.width, height);
+
+    let mut order: Vec<usize> = (0..masks.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(masks[i].count()));
+
+    let mut rectangles = vec![Rectangle::new(0, 0, 0, 0); masks.len()];
+    for index in order {
+        let mask = &masks[index];
+        let occupied = mask.occupied();
+        let collides = |x: usize, y: usize| occupied.iter().any(|&(px, py)| grid.get(x + px, y + py));
+
+        let (x, y) = (0..(height + 1).saturating_sub(mask.height))
+            .flat_map(|y| (0..=config.width - mask.width).map(move |x| (x, y)))
+            .find(|&(x, y)| !collides(x, y))
+            .ok_or_else(|| MaskPackingError { message: "Could not fit all masks in the container".to_string() })?;
+
+        // Occupy the pixels of the mask grown by the padding, so later masks keep their distance.
+        for &(px, py) in &occupied {
+            let (gx, gy) = (x + px, y + py);
+            for oy in gy.saturating_sub(config.padding)..(gy + config.padding + 1).min(height) {
+                for ox in gx.saturating_sub(config.padding)..(gx + config.padding + 1).min(config.width) {
+                    grid.set(ox, oy, true);
+                }
+            }
+        }
+        rectangles[index] = Rectangle::new(x, y, mask.width, mask.height);
+    }
+
+    let size = rectangles.iter().fold(Size::new(0, 0), |size, r| {
+        Size::new(size.width.max(r.x + r.width), size.height.max(r.y + r.height))
+    });
+
+    Ok(MaskPackingResult { rectangles, size })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_masks_works() {
+        let masks: Vec<Mask> = (0..20)
+            .map(|i| {
+                let s = 2 + i % 5;
+                match i % 2 {
+                    0 => Mask::from_fn(s, s, |x, y| x == 0 || y == s - 1),
+                    _ => Mask::from_fn(s, s, |x, y| (x as isize - y as isize).abs() <= 1),
+                }
+            })
+            .collect();
+        let config = MaskPackerConfig { width: 16, height: None, padding: 1 };
+        let result = pack_masks(&masks, &config).unwrap();
+
+        assert!(result.size.width <= 16);
+        let mut owners = vec![None; result.size.width * result.size.height];
+        for (index, (mask, r)) in masks.iter().zip(&result.rectangles).enumerate() {
+            for (x, y) in mask.occupied() {
+                let (gx, gy) = (r.x + x, r.y + y);
+                // No other mask may occupy a pixel within the padding.
+                for oy in gy.saturating_sub(1)..(gy + 2).min(result.size.height) {
+                    for ox in gx.saturating_sub(1)..(gx + 2).min(result.size.width) {
+                        let owner = owners[oy * result.size.width + ox];
+                        assert!(owner.is_none() || owner == Some(index));
+                    }
+                }
+                owners[gy * result.size.width + gx] = Some(index);
+            }
+        }
+    }
+
+    #[test]
+    fn masks_interlock() {
+        let l = Mask::from_fn(3, 3, |x, y| x == 0 || y == 2);
+        let corner = Mask::from_fn(2, 2, |_, _| true);
+        let config = MaskPackerConfig { width: 3, height: Some(3), padding: 0 };
+        let result = pack_masks(&[l, corner], &config).unwrap();
+
+        assert_eq!(result.rectangles, vec![Rectangle::new(0, 0, 3, 3), Rectangle::new(1, 0, 2, 2)]);
+        assert_eq!(result.size, Size::new(3, 3));
+    }
+
+    #[test]
+    fn padding_keeps_masks_apart() {
+        let masks = vec![Mask::from_fn(2, 2, |_, _| true); 2];
+        let result = pack_masks(&masks, &MaskPackerConfig { width: 5, height: None, padding: 1 }).unwrap();
+
+        assert_eq!(result.rectangles[1], Rectangle::new(3, 0, 2, 2));
+    }
+
+    #[test]
+    fn pack_masks_fails_if_masks_do_not_fit() {
+        let config = MaskPackerConfig { width: 2, height: Some(2), padding: 0 };
+        let masks = vec![Mask::from_fn(2, 1, |_, _| true); 3];
+
+        assert!(pack_masks(&[Mask::new(3, 1)], &config).is_err());
+        assert!(pack_masks(&masks[..2], &config).is_ok());
+        assert_eq!(pack_masks(&masks, &config).unwrap_err().message, "Could not fit all masks in the container");
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn from_alpha_works() {
+        let image = RgbaImage::from_fn(2, 2, |x, y| image::Rgba([0, 0, 0, (x * 100 + y * 10) as u8]));
+        let mask = Mask::from_alpha(&image, 10);
+
+        assert_eq!(mask.bits, vec![false, true, false, true]);
+    }
+}
@@ -0,0 +1,367 @@
+use std::error::Error;
+use std::fmt;
+
+/// The tolerance for floating point comparisons of coordinates.
+const EPSILON: f64 = 1e-9;
+
+/// A simple polygon, given by its vertices in order.
+///
+/// The vertices may be given clockwise or counter-clockwise, and the polygon is implicitly closed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    /// The vertices of the polygon.
+    pub vertices: Vec<[f64; 2]>,
+}
+
+impl Polygon {
+    /// Creates a new polygon from its vertices.
+    pub fn new(vertices: Vec<[f64; 2]>) -> Polygon {
+        Polygon { vertices }
+    }
+
+    /// Creates a new axis-aligned rectangle with its top-left corner at the origin.
+    pub fn rectangle(width: f64, height: f64) -> Polygon {
+        Polygon::new(vec![[0.0, 0.0], [width, 0.0], [width, height], [0.0, height]])
+    }
+
+    /// Returns the area of the polygon.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::nesting::Polygon;
+    ///
+    /// let triangle = Polygon::new(vec![[0.0, 0.0], [4.0, 0.0], [0.0, 3.0]]);
+    /// assert_eq!(triangle.area(), 6.0);
+    /// ```
+    pub fn area(&self) -> f64 {
+        let n = self.vertices.len();
+        let twice_area: f64 = (0..n)
+            .map(|i| {
+                let (a, b) = (self.vertices[i], self.vertices[(i + 1) % n]);
+                a[0] * b[1] - b[0] * a[1]
+            })
+            .sum();
+        twice_area.abs() / 2.0
+    }
+
+    /// Returns the top-left and bottom-right corners of the bounding box of the polygon.
+    ///
+    /// An empty polygon has an empty bounding box at the origin.
+    pub fn bounds(&self) -> ([f64; 2], [f64; 2]) {
+        if self.vertices.is_empty() {
+            return ([0.0, 0.0], [0.0, 0.0]);
+        }
+        self.vertices.iter().fold(([f64::MAX, f64::MAX], [f64::MIN, f64::MIN]), |(min, max), v| {
+            ([min[0].min(v[0]), min[1].min(v[1])], [max[0].max(v[0]), max[1].max(v[1])])
+        })
+    }
+
+    /// Returns the polygon moved by the given offset.
+    pub fn translate(&self, offset: [f64; 2]) -> Polygon {
+        Polygon::new(self.vertices.iter().map(|v| [v[0] + offset[0], v[1] + offset[1]]).collect())
+    }
+
+    /// Returns the convex hull of the polygon, with its vertices in counter-clockwise order
+    /// in a coordinate system with the y axis pointing up.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::nesting::Polygon;
+    ///
+    /// let arrow = Polygon::new(vec![[0.0, 0.0], [2.0, 1.0], [4.0, 0.0], [2.0, 4.0]]);
+    /// assert_eq!(arrow.convex_hull().vertices, vec![[0.0, 0.0], [4.0, 0.0], [2.0, 4.0]]);
+    /// ```
+    pub fn convex_hull(&self) -> Polygon {
+        let mut points = self.vertices.clone();
+        points.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+        points.dedup();
+        if points.len() < 3 {
+            return Polygon::new(points);
+        }
+
+        // Andrew's monotone chain, building the lower and the upper hull.
+        let mut hull: Vec<[f64; 2]> = Vec::with_capacity(points.len() * 2);
+        for pass in 0..2 {
+            let start = hull.len();
+            for &point in &points {
+                while hull.len() >= start + 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= EPSILON {
+                    hull.pop();
+                }
+                hull.push(point);
+            }
+            hull.pop();
+            if pass == 0 {
+                points.reverse();
+            }
+        }
+        Polygon::new(hull)
+    }
+
+    /// Returns `true` if the point lies strictly inside the convex polygon, which must be counter-clockwise.
+    fn strictly_contains(&self, point: [f64; 2]) -> bool {
+        let n = self.vertices.len();
+        n >= 3 && (0..n).all(|i| cross(self.vertices[i], self.vertices[(i + 1) % n], point) > EPSILON)
+    }
+}
+
+/// Returns the no-fit polygon of two convex polygons, the offsets by which `moving` overlaps `fixed`.
+///
+/// Moving `moving` by an offset strictly inside the no-fit polygon makes it overlap `fixed`,
+/// and moving it by an offset on its boundary makes them touch. Concave polygons are replaced by their
+/// convex hull, so the result is conservative for them: offsets outside of it never overlap, but
+/// offsets at which the polygons would interlock are inside of it.
+///
+/// # Examples
+/// ```
+/// use rpack::nesting::{no_fit_polygon, Polygon};
+///
+/// let nfp = no_fit_polygon(&Polygon::rectangle(2.0, 2.0), &Polygon::rectangle(1.0, 1.0));
+/// assert_eq!(nfp.bounds(), ([-1.0, -1.0], [2.0, 2.0]));
+/// ```
+pub fn no_fit_polygon(fixed: &Polygon, moving: &Polygon) -> Polygon {
+    let fixed = fixed.convex_hull();
+    let moving = moving.convex_hull();
+    let sums = fixed
+        .vertices
+        .iter()
+        .flat_map(|a| moving.vertices.iter().map(move |b| [a[0] - b[0], a[1] - b[1]]))
+        .collect();
+    Polygon::new(sums).convex_hull()
+}
+
+/// The configuration of the nesting.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NestingConfig {
+    /// The width of the sheet.
+    pub width: f64,
+
+    /// The height of the sheet.
+    ///
+    /// If `None`, the sheet is as high as needed to fit all parts.
+    /// If `Some`, an error is returned if the parts cannot be nested within that height.
+    pub height: Option<f64>,
+}
+
+/// The result of nesting parts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NestingResult {
+    /// The offset by which every part is moved, in the order of the parts.
+    pub offsets: Vec<[f64; 2]>,
+
+    /// The height of the sheet.
+    pub height: f64,
+}
+
+impl NestingResult {
+    /// Returns the parts moved to their place on the sheet.
+    pub fn place(&self, parts: &[Polygon]) -> Vec<Polygon> {
+        parts.iter().zip(&self.offsets).map(|(part, &offset)| part.translate(offset)).collect()
+    }
+}
+
+/// The error returned if parts could not be nested.
+#[derive(Debug, Clone)]
+pub struct NestingError {
+    /// The error message.
+    pub message: String,
+}
+
+impl fmt::Display for NestingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for NestingError {}
+
+/// Nests polygonal parts on a sheet, greedily moving each part as far up and then as far left as possible.
+///
+/// The parts are placed from the largest to the smallest. The candidate offsets for a part are the corners of the
+/// region in which it stays on the sheet, the vertices of its no-fit polygons with the parts placed so far and the
+/// intersections of their edges, of which the one closest to the top-left corner outside of all no-fit polygons is chosen.
+/// As the no-fit polygons are computed with [`no_fit_polygon`], concave parts are kept apart by their convex hulls
+/// and are never interlocked. Parts are not rotated.
+///
+/// # Arguments
+/// * `parts` - The parts to nest.
+/// * `config` - The configuration of the nesting.
+///
+/// # Returns
+/// A `Result` containing either:
+///   - A `NestingResult` with the offset of every part, in the order of the parts, and the height of the sheet.
+///   - A `NestingError` if a part has no area, is wider than the sheet or the parts do not fit within its height.
+///
+/// # Examples
+/// ```
+/// use rpack::nesting::{nest, NestingConfig, Polygon};
+///
+/// let triangle = Polygon::new(vec![[0.0, 0.0], [2.0, 0.0], [0.0, 2.0]]);
+/// let flipped = Polygon::new(vec![[2.0, 0.0], [2.0, 2.0], [0.0, 2.0]]);
+/// let result = nest(&[triangle, flipped], &NestingConfig { width: 2.0, height: None }).unwrap();
+///
+/// // The triangles fit together into a square.
+/// assert_eq!(result.offsets, vec![[0.0, 0.0], [0.0, 0.0]]);
+/// assert_eq!(result.height, 2.0);
+/// ```
+pub fn nest(parts: &[Polygon], config: &NestingConfig) -> Result<NestingResult, NestingError> {
+    if let Some(index) = parts.iter().position(|part| part.area() <= EPSILON) {
+        return Err(NestingError { message: format!("Part {} does not have a positive area", index) });
+    }
+
+    let bounds: Vec<([f64; 2], [f64; 2])> = parts.iter().map(Polygon::bounds).collect();
+    if let Some(index) = bounds.iter().position(|(min, max)| max[0] - min[0] > config.width + EPSILON) {
+        return Err(NestingError { message: format!("Part {} does not fit in width {}", index, config.width) });
+    }
+
+    // Without a height, stacking all parts on top of each other always fits.
+    let height = config.height.unwrap_or_else(|| bounds.iter().map(|(min, max)| max[1] - min[1]).sum());
+
+    let mut order: Vec<usize> = (0..parts.len()).collect();
+    order.sort_by(|&a, &b| parts[b].area().total_cmp(&parts[a].area()));
+
+    let mut offsets = vec![[0.0, 0.0]; parts.len()];
+    let mut placed: Vec<usize> = Vec::with_capacity(parts.len());
+    for index in order {
+        let (min, max) = bounds[index];
+        let (left, right) = (-min[0], config.width - max[0]);
+        let (top, bottom) = (-min[1], height - max[1]);
+        if bottom < top - EPSILON {
+            return Err(NestingError { message: "Could not fit all parts on the sheet".to_string() });
+        }
+
+        let nfps: Vec<Polygon> = placed
+            .iter()
+            .map(|&other| no_fit_polygon(&parts[other].translate(offsets[other]), &parts[index]))
+            .collect();
+
+        let fit = Polygon::new(vec![[left, top], [right, top], [right, bottom], [left, bottom]]);
+        let mut candidates = fit.vertices.clone();
+        let edges: Vec<([f64; 2], [f64; 2])> = nfps.iter().chain(std::iter::once(&fit)).flat_map(edges).collect();
+        for (i, &(a, b)) in edges.iter().enumerate() {
+            candidates.push(a);
+            candidates.extend(edges[i + 1..].iter().filter_map(|&(c, d)| intersection(a, b, c, d)));
+        }
+
+        let offset = candidates
+            .into_iter()
+            .filter(|c| c[0] >= left - EPSILON && c[0] <= right + EPSILON && c[1] >= top - EPSILON && c[1] <= bottom + EPSILON)
+            .filter(|&c| nfps.iter().all(|nfp| !nfp.strictly_contains(c)))
+            .min_by(|a, b| a[1].total_cmp(&b[1]).then(a[0].total_cmp(&b[0])))
+            .ok_or_else(|| NestingError { message: "Could not fit all parts on the sheet".to_string() })?;
+
+        offsets[index] = offset;
+        placed.push(index);
+    }
+
+    let height = match config.height {
+        Some(height) => height,
+        None => bounds.iter().zip(&offsets).map(|((_, max), offset)| max[1] + offset[1]).fold(0.0, f64::max),
+    };
+
+    Ok(NestingResult { offsets, height })
+}
+
+/// Returns the cross product of `b - a` and `c - a`, which is positive if `c` lies to the left of the line from `a` to `b`.
+fn cross(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Returns the edges of the polygon.
+fn edges(polygon: &Polygon) -> Vec<([f64; 2], [f64; 2])> {
+    let n = polygon.vertices.len();
+    (0..n).map(|i| (polygon.vertices[i], polygon.vertices[(i + 1) % n])).collect()
+}
+
+/// Returns the intersection of the segments from `a` to `b` and from `c` to `d`, if they are not parallel.
+fn intersection(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> Option<[f64; 2]> {
+    let denominator = (b[0] - a[0]) * (d[1] - c[1]) - (b[1] - a[1]) * (d[0] - c[0]);
+    if denominator.abs() <= EPSILON {
+        return None;
+    }
+    let s = cross(c, d, a) / -denominator;
+    let t = cross(a, b, c) / denominator;
+    match (-EPSILON..=1.0 + EPSILON).contains(&s) && (-EPSILON..=1.0 + EPSILON).contains(&t) {
+        true => Some([a[0] + s * (b[0] - a[0]), a[1] + s * (b[1] - a[1])]),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns `true` if the convex polygons overlap, using the separating axis theorem.
+    fn overlap(a: &Polygon, b: &Polygon) -> bool {
+        let project = |polygon: &Polygon, axis: [f64; 2]| {
+            polygon.vertices.iter().map(|v| v[0] * axis[0] + v[1] * axis[1]).fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p), hi.max(p)))
+        };
+        edges(a).into_iter().chain(edges(b)).all(|(p, q)| {
+            let axis = [q[1] - p[1], p[0] - q[0]];
+            let ((a_lo, a_hi), (b_lo, b_hi)) = (project(a, axis), project(b, axis));
+            a_hi > b_lo + 1e-6 && b_hi > a_lo + 1e-6
+        })
+    }
+
+    #[test]
+    fn convex_hull_works() {
+        let notched = Polygon::new(vec![[0.0, 0.0], [0.5, 0.0], [1.0, 0.0], [1.0, 1.0], [0.5, 0.5], [0.0, 1.0]]);
+
+        assert_eq!(notched.convex_hull().vertices, vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+        assert_eq!(notched.area(), 0.75);
+    }
+
+    #[test]
+    fn no_fit_polygon_works() {
+        let fixed = Polygon::rectangle(2.0, 1.0);
+        let nfp = no_fit_polygon(&fixed, &Polygon::rectangle(1.0, 1.0));
+
+        assert_eq!(nfp.vertices, vec![[-1.0, -1.0], [2.0, -1.0], [2.0, 1.0], [-1.0, 1.0]]);
+        assert!(nfp.strictly_contains([0.5, 0.5]));
+        assert!(!nfp.strictly_contains([2.0, 0.0]));
+    }
+
+    #[test]
+    fn nest_works() {
+        let parts: Vec<Polygon> = (0..12)
+            .map(|i| {
+                let s = 1.0 + (i % 4) as f64 * 0.5;
+                match i % 3 {
+                    0 => Polygon::rectangle(s, s / 2.0),
+                    1 => Polygon::new(vec![[0.0, 0.0], [s, 0.0], [s / 2.0, s]]),
+                    _ => Polygon::new(vec![[s / 2.0, 0.0], [s, s / 2.0], [s / 2.0, s], [0.0, s / 2.0]]),
+                }
+            })
+            .collect();
+        let config = NestingConfig { width: 5.0, height: None };
+        let result = nest(&parts, &config).unwrap();
+        let placed = result.place(&parts);
+
+        for (i, part) in placed.iter().enumerate() {
+            let (min, max) = part.bounds();
+            assert!(min[0] >= -1e-6 && min[1] >= -1e-6 && max[0] <= 5.0 + 1e-6 && max[1] <= result.height + 1e-6);
+            for other in &placed[i + 1..] {
+                assert!(!overlap(&part.convex_hull(), &other.convex_hull()));
+            }
+        }
+    }
+
+    #[test]
+    fn nest_fills_rows() {
+        let parts = vec![Polygon::rectangle(1.0, 1.0); 4];
+        let result = nest(&parts, &NestingConfig { width: 2.0, height: None }).unwrap();
+
+        assert_eq!(result.offsets, vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]]);
+        assert_eq!(result.height, 2.0);
+    }
+
+    #[test]
+    fn nest_fails_if_parts_do_not_fit() {
+        let config = NestingConfig { width: 2.0, height: Some(1.0) };
+
+        assert!(nest(&[Polygon::rectangle(3.0, 1.0)], &config).is_err());
+        assert!(nest(&[Polygon::new(vec![[0.0, 0.0], [1.0, 1.0]])], &config).is_err());
+        let squares = vec![Polygon::rectangle(1.0, 1.0); 3];
+        assert!(nest(&squares[..2], &config).is_ok());
+        assert_eq!(nest(&squares, &config).unwrap_err().message, "Could not fit all parts on the sheet");
+    }
+}
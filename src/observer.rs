@@ -0,0 +1,315 @@
+use std::fmt;
+
+use crate::{Rectangle, Size};
+
+/// An observer notified of the events of a packing run, see `RectanglePacker::pack_observed`.
+///
+/// All methods do nothing by default, so an observer only implements the events it is interested in.
+pub trait PackingObserver {
+    /// Called when a page is opened to place rectangles into.
+    ///
+    /// # Arguments
+    /// * `page` - The index of the page, starting at `0`.
+    /// * `size` - The maximum size of the page. Without a maximum size in the config, its height is `usize::MAX`.
+    fn on_open_page(&mut self, page: usize, size: Size) {
+        let _ = (page, size);
+    }
+
+    /// Called when the rectangle of a size was placed.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the size.
+    /// * `rectangle` - The rectangle the size was placed at.
+    fn on_place(&mut self, index: usize, rectangle: &Rectangle) {
+        let _ = (index, rectangle);
+    }
+
+    /// Called when a size was not placed, either because it is skipped by the zero size policy or because it did not fit.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the size.
+    /// * `size` - The size that was not placed.
+    fn on_reject(&mut self, index: usize, size: &Size) {
+        let _ = (index, size);
+    }
+
+    /// Called when the used part of the container grew, e.g. because a new row was started.
+    ///
+    /// # Arguments
+    /// * `size` - The size of the used part of the container, including the padding and the border.
+    fn on_grow(&mut self, size: Size) {
+        let _ = size;
+    }
+
+    /// Returns `true` if the packer should report its placement decisions with `on_decision`.
+    ///
+    /// Collecting the candidates of every decision takes time and memory, so packers only do so if asked.
+    fn wants_decisions(&self) -> bool {
+        false
+    }
+
+    /// Called before a size is placed or rejected, with the candidate positions the packer considered,
+    /// if `wants_decisions` returns `true`.
+    fn on_decision(&mut self, decision: &PlacementDecision) {
+        let _ = decision;
+    }
+
+    /// Returns `true` if the packing run should stop as soon as possible.
+    ///
+    /// Packers that report events while packing check this between placements,
+    /// the others before they start. A cancelled packing fails with `RectanglePackingError::cancelled`.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// An observer ignoring all events.
+impl PackingObserver for () {}
+
+/// An event of a packing run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackingEvent {
+    /// A page was opened, see `PackingObserver::on_open_page`.
+    OpenPage { page: usize, size: Size },
+
+    /// A size was placed, see `PackingObserver::on_place`.
+    Place { index: usize, rectangle: Rectangle },
+
+    /// A size was not placed, see `PackingObserver::on_reject`.
+    Reject { index: usize, size: Size },
+
+    /// The used part of the container grew, see `PackingObserver::on_grow`.
+    Grow { size: Size },
+}
+
+/// A position a packer considered for a size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    /// The rectangle the size would be placed at.
+    pub rectangle: Rectangle,
+
+    /// The scores of the candidate by the heuristics of the packer, by name. Lower scores are better.
+    pub scores: Vec<(&'static str, f64)>,
+
+    /// Why the candidate can not be used, or `None` if it can.
+    pub rejection: Option<String>,
+}
+
+/// The candidates a packer considered for a size, and which one it chose.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacementDecision {
+    /// The index of the size.
+    pub index: usize,
+
+    /// The candidates, in the order they were considered.
+    pub candidates: Vec<Candidate>,
+
+    /// The index of the chosen candidate, or `None` if the size was rejected.
+    pub chosen: Option<usize>,
+
+    /// Why the chosen candidate won, or why the size was rejected.
+    pub reason: String,
+}
+
+impl fmt::Display for PlacementDecision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Size {}: {}", self.index, self.reason)?;
+        for (position, candidate) in self.candidates.iter().enumerate() {
+            let r = candidate.rectangle;
+            let marker = match self.chosen == Some(position) { true => '*', false => ' ' };
+            write!(f, "{} {},{} {}x{}", marker, r.x, r.y, r.width, r.height)?;
+            for (name, score) in &candidate.scores {
+                write!(f, " {}={}", name, score)?;
+            }
+            if let Some(rejection) = &candidate.rejection {
+                write!(f, " ({})", rejection)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// An observer recording the placement decisions of a packer, to explain why a layout looks the way it does.
+///
+/// # Examples
+/// ```
+/// use rpack::{DecisionLog, HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+///
+/// let mut log = DecisionLog::new();
+/// let config = RectanglePackerConfig { max_size: Some(Size::new(8, 8)), ..Default::default() };
+/// HeightRectPacker::pack_observed(&[Size::new(6, 4), Size::new(4, 4)], &config, &mut log).unwrap();
+///
+/// // The second size did not fit next to the first one, so a new row was started.
+/// let decision = log.decision(1).unwrap();
+/// assert_eq!(decision.candidates.len(), 2);
+/// assert_eq!(decision.chosen, Some(1));
+/// println!("{}", decision);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecisionLog {
+    /// The recorded decisions, in the order they were made.
+    pub decisions: Vec<PlacementDecision>,
+}
+
+impl DecisionLog {
+    /// Creates an empty log.
+    pub fn new() -> DecisionLog {
+        DecisionLog::default()
+    }
+
+    /// Returns the last decision made for the size with the given index.
+    pub fn decision(&self, index: usize) -> Option<&PlacementDecision> {
+        self.decisions.iter().rev().find(|decision| decision.index == index)
+    }
+}
+
+impl PackingObserver for DecisionLog {
+    fn wants_decisions(&self) -> bool {
+        true
+    }
+
+    fn on_decision(&mut self, decision: &PlacementDecision) {
+        self.decisions.push(decision.clone());
+    }
+}
+
+/// An observer recording all events of a packing run in order, e.g. to replay or visualize it.
+///
+/// # Examples
+/// ```
+/// use rpack::{HeightRectPacker, PackingEvent, PackingTrace, RectanglePacker, RectanglePackerConfig, Size};
+///
+/// let mut trace = PackingTrace::new();
+/// let sizes = vec![Size::new(8, 8), Size::new(4, 4)];
+/// HeightRectPacker::pack_observed(&sizes, &RectanglePackerConfig::default(), &mut trace).unwrap();
+///
+/// let placed: Vec<usize> = trace.events.iter().filter_map(|event| match event {
+///     PackingEvent::Place { index, .. } => Some(*index),
+///     _ => None,
+/// }).collect();
+/// assert_eq!(placed, vec![0, 1]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackingTrace {
+    /// The recorded events, in the order they occurred.
+    pub events: Vec<PackingEvent>,
+}
+
+impl PackingTrace {
+    /// Creates an empty trace.
+    pub fn new() -> PackingTrace {
+        PackingTrace::default()
+    }
+}
+
+impl PackingObserver for PackingTrace {
+    fn on_open_page(&mut self, page: usize, size: Size) {
+        self.events.push(PackingEvent::OpenPage { page, size });
+    }
+
+    fn on_place(&mut self, index: usize, rectangle: &Rectangle) {
+        self.events.push(PackingEvent::Place { index, rectangle: *rectangle });
+    }
+
+    fn on_reject(&mut self, index: usize, size: &Size) {
+        self.events.push(PackingEvent::Reject { index, size: *size });
+    }
+
+    fn on_grow(&mut self, size: Size) {
+        self.events.push(PackingEvent::Grow { size });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, RectanglePackingError, ZeroSizePolicy};
+
+    use super::*;
+
+    #[test]
+    fn trace_records_placements_in_packing_order() {
+        let sizes = vec![Size::new(2, 2), Size::new(6, 6), Size::new(4, 4)];
+        let mut trace = PackingTrace::new();
+        let result = HeightRectPacker::pack_observed(&sizes, &RectanglePackerConfig::default(), &mut trace).unwrap();
+
+        let placed: Vec<PackingEvent> = trace.events.iter().copied().filter(|e| matches!(e, PackingEvent::Place { .. })).collect();
+        assert_eq!(placed, vec![
+            PackingEvent::Place { index: 1, rectangle: result.rectangles[1] },
+            PackingEvent::Place { index: 2, rectangle: result.rectangles[2] },
+            PackingEvent::Place { index: 0, rectangle: result.rectangles[0] },
+        ]);
+        assert!(matches!(trace.events[0], PackingEvent::OpenPage { page: 0, .. }));
+    }
+
+    #[test]
+    fn trace_records_growth_and_rejections() {
+        let sizes = vec![Size::new(4, 4), Size::new(0, 3), Size::new(4, 4), Size::new(4, 4)];
+        let config = RectanglePackerConfig {
+            max_size: Some(Size::new(8, 8)),
+            zero_size_policy: ZeroSizePolicy::Skip,
+            ..Default::default()
+        };
+        let mut trace = PackingTrace::new();
+        HeightRectPacker::pack_observed(&sizes, &config, &mut trace).unwrap();
+
+        assert_eq!(trace.events[0], PackingEvent::OpenPage { page: 0, size: Size::new(8, 8) });
+        assert!(trace.events.contains(&PackingEvent::Reject { index: 1, size: Size::new(0, 3) }));
+        assert!(trace.events.contains(&PackingEvent::Grow { size: Size::new(8, 4) }));
+    }
+
+    #[test]
+    fn trace_records_rectangle_that_does_not_fit() {
+        let sizes = vec![Size::new(4, 4); 3];
+        let config = RectanglePackerConfig { max_size: Some(Size::new(8, 4)), ..Default::default() };
+        let mut trace = PackingTrace::new();
+
+        assert!(HeightRectPacker::pack_observed(&sizes, &config, &mut trace).is_err());
+        assert_eq!(trace.events.last(), Some(&PackingEvent::Reject { index: 2, size: Size::new(4, 4) }));
+    }
+
+    #[test]
+    fn decision_log_explains_placements() {
+        let sizes = vec![Size::new(4, 4), Size::new(4, 2), Size::new(4, 4)];
+        let config = RectanglePackerConfig { max_size: Some(Size::new(8, 5)), ..Default::default() };
+        let mut log = DecisionLog::new();
+
+        assert!(HeightRectPacker::pack_observed(&sizes, &config, &mut log).is_err());
+
+        let second = log.decision(2).unwrap();
+        assert_eq!(second.chosen, Some(0));
+        assert_eq!(second.candidates[0].rectangle, Rectangle::new(4, 0, 4, 4));
+
+        let rejected = log.decision(1).unwrap();
+        assert_eq!(rejected.chosen, None);
+        assert_eq!(rejected.candidates.len(), 2);
+        assert_eq!(rejected.candidates[1].rejection.as_deref(), Some("exceeds the height of the container"));
+        assert_eq!(
+            rejected.to_string(),
+            "Size 1: the rectangle does not fit below the last row\n  \
+             8,0 4x2 x=8 y=0 row_waste=2 (exceeds the width of the container)\n  \
+             0,4 4x2 x=0 y=4 row_waste=0 (exceeds the height of the container)\n"
+        );
+    }
+
+    /// An observer cancelling the packing after the given number of placements.
+    struct CancelAfter(usize);
+
+    impl PackingObserver for CancelAfter {
+        fn on_place(&mut self, _index: usize, _rectangle: &Rectangle) {
+            self.0 = self.0.saturating_sub(1);
+        }
+
+        fn is_cancelled(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    #[test]
+    fn cancelled_packings_stop_between_placements() {
+        let sizes = vec![Size::new(4, 4); 3];
+        let error = HeightRectPacker::pack_observed(&sizes, &RectanglePackerConfig::default(), &mut CancelAfter(2)).unwrap_err();
+
+        assert_eq!(error.message, RectanglePackingError::CANCELLED);
+    }
+}
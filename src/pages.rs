@@ -0,0 +1,715 @@
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::search::with_max_size;
+use crate::{OverflowPolicy, PageFill, Rectangle, RectanglePacker, RectanglePackerConfig, RectanglePackingError, RectanglePackingResult, Size};
+
+/// The size of a page and the rectangle of every size placed on it, by the index of the size.
+type Page = (Size, Vec<(usize, Rectangle)>);
+
+/// The page and rectangle of a packed size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PagePlacement {
+    /// The index of the page the rectangle was placed on.
+    pub page: usize,
+
+    /// The rectangle on the page.
+    pub rectangle: Rectangle,
+}
+
+/// A tile of a size split by `pack_pages` because it does not fit into an empty page,
+/// see `RectanglePackerConfig::split_oversized`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TilePlacement {
+    /// The index of the split size.
+    pub index: usize,
+
+    /// The part of the size shown by the tile, relative to the top left corner of the size.
+    pub source: Rectangle,
+
+    /// The page and rectangle of the tile.
+    pub placement: PagePlacement,
+}
+
+/// The result of packing rectangles onto pages with `pack_pages`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PagedPackingResult {
+    /// The size of every page.
+    pub pages: Vec<Size>,
+
+    /// The placement of every size, in the order of the sizes, or `None` if it was skipped, left over or split into tiles.
+    pub placements: Vec<Option<PagePlacement>>,
+
+    /// The tiles of the sizes split because they do not fit into an empty page, in the order of the sizes,
+    /// and of the rows and columns of the tiles of every size.
+    pub tiles: Vec<TilePlacement>,
+
+    /// The indices of the sizes that did not fit with `OverflowPolicy::Partial`, in ascending order.
+    pub leftovers: Vec<usize>,
+
+    /// The indices of the sizes skipped by the `ZeroSizePolicy` of the config, in ascending order.
+    pub skipped: Vec<usize>,
+}
+
+impl PagedPackingResult {
+    /// Returns the number of pages.
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Returns the indices of the sizes placed on the given page, in ascending order.
+    /// Tiles of split sizes are not included, see `tiles_on`.
+    pub fn items_on(&self, page: usize) -> Vec<usize> {
+        (0..self.placements.len()).filter(|&i| self.placements[i].is_some_and(|p| p.page == page)).collect()
+    }
+
+    /// Returns the tiles placed on the given page.
+    pub fn tiles_on(&self, page: usize) -> Vec<TilePlacement> {
+        self.tiles.iter().filter(|tile| tile.placement.page == page).copied().collect()
+    }
+
+    /// Returns the tiles of the size with the given index, which are empty unless the size was split.
+    pub fn tiles_of(&self, index: usize) -> Vec<TilePlacement> {
+        self.tiles.iter().filter(|tile| tile.index == index).copied().collect()
+    }
+
+    /// Shrinks every page to the bounds of its rectangles, keeping the padding around them and the border,
+    /// e.g. so that the last page, which is usually mostly empty, does not waste memory at the full max size.
+    ///
+    /// # Arguments
+    /// * `config` - The config the result was packed with.
+    /// * `power_of_two` - Whether to round the width and height of every page up to a power of two.
+    ///   A page is never grown beyond its size, so a page not shrunk below a max size that is not a power of two keeps that size.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{pack_pages, HeightRectPacker, OverflowPolicy, RectanglePackerConfig, Size};
+    ///
+    /// let config = RectanglePackerConfig { max_size: Some(Size::new(64, 64)), overflow: OverflowPolicy::NewPage, ..Default::default() };
+    /// let mut result = pack_pages::<HeightRectPacker>(&[Size::new(64, 64), Size::new(20, 10)], &config).unwrap();
+    ///
+    /// result.shrink_to_fit(&config, true);
+    /// assert_eq!(result.pages, vec![Size::new(64, 64), Size::new(32, 16)]);
+    /// result.shrink_to_fit(&config, false);
+    /// assert_eq!(result.pages, vec![Size::new(64, 64), Size::new(20, 10)]);
+    /// ```
+    pub fn shrink_to_fit(&mut self, config: &RectanglePackerConfig, power_of_two: bool) {
+        let padding = config.rectangle_padding;
+        let border = config.border_padding;
+        for (page, size) in self.pages.iter_mut().enumerate() {
+            let (right, bottom) = self
+                .placements
+                .iter()
+                .flatten()
+                .chain(self.tiles.iter().map(|tile| &tile.placement))
+                .filter(|placement| placement.page == page)
+                .map(|placement| placement.rectangle)
+                .fold((0, 0), |(right, bottom), r| (right.max(r.x + r.width + padding), bottom.max(r.y + r.height + padding)));
+            let shrink = |extent: usize, limit: usize| {
+                let extent = extent.max(border) + border;
+                match power_of_two {
+                    true => extent.next_power_of_two().min(limit),
+                    false => extent.min(limit),
+                }
+            };
+            *size = Size::new(shrink(right, size.width), shrink(bottom, size.height));
+        }
+    }
+
+    /// Grows every page to the largest width and the largest height of all pages, so that the pages can be
+    /// the layers of a GPU texture array, with the page of every placement as its layer index.
+    ///
+    /// # Returns
+    /// The size shared by all pages, or an empty size if there are no pages.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{pack_pages, HeightRectPacker, OverflowPolicy, RectanglePackerConfig, Size};
+    ///
+    /// let config = RectanglePackerConfig { max_size: Some(Size::new(64, 64)), overflow: OverflowPolicy::NewPage, ..Default::default() };
+    /// let mut result = pack_pages::<HeightRectPacker>(&[Size::new(64, 32), Size::new(40, 48)], &config).unwrap();
+    /// result.shrink_to_fit(&config, false);
+    ///
+    /// assert_eq!(result.make_uniform(), Size::new(64, 48));
+    /// assert_eq!(result.pages, vec![Size::new(64, 48); 2]);
+    /// ```
+    pub fn make_uniform(&mut self) -> Size {
+        let size = self.pages.iter().fold(Size::new(0, 0), |a, b| Size::new(a.width.max(b.width), a.height.max(b.height)));
+        self.pages.fill(size);
+        size
+    }
+
+    /// Creates the result from the size of every page and the rectangles placed on it.
+    ///
+    /// The sizes are followed by the tiles, which are given with the index of their size and
+    /// the part of the size they show.
+    fn from_pages(
+        sizes: &[Size],
+        tiles: &[(usize, Rectangle)],
+        pages: Vec<Page>,
+        leftovers: Vec<usize>,
+        skipped: Vec<usize>,
+    ) -> PagedPackingResult {
+        let mut placements = vec![None; sizes.len() + tiles.len()];
+        for (page, (_, rectangles)) in pages.iter().enumerate() {
+            for &(index, rectangle) in rectangles {
+                placements[index] = Some(PagePlacement { page, rectangle });
+            }
+        }
+        let tiles = placements
+            .split_off(sizes.len())
+            .into_iter()
+            .zip(tiles)
+            .map(|(placement, &(index, source))| TilePlacement { index, source, placement: placement.unwrap() })
+            .collect();
+        PagedPackingResult { pages: pages.into_iter().map(|(size, _)| size).collect(), placements, tiles, leftovers, skipped }
+    }
+}
+
+/// The error returned by `pack_pages`.
+#[derive(Debug)]
+pub enum PagingError {
+    /// The packer failed, e.g. because the config is not valid or a size does not fit into an empty page.
+    Packing(RectanglePackingError),
+
+    /// The sizes need more pages than the `max_pages` of the config allow.
+    TooManyPages {
+        /// The number of pages the sizes need.
+        required: usize,
+        /// The maximum number of pages of the config.
+        max_pages: usize,
+    },
+}
+
+impl fmt::Display for PagingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PagingError::Packing(error) => write!(f, "{}", error),
+            PagingError::TooManyPages { required, max_pages } => {
+                write!(f, "The rectangles need {} pages, but at most {} are allowed", required, max_pages)
+            }
+        }
+    }
+}
+
+impl Error for PagingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PagingError::Packing(error) => Some(error),
+            PagingError::TooManyPages { .. } => None,
+        }
+    }
+}
+
+impl From<RectanglePackingError> for PagingError {
+    fn from(error: RectanglePackingError) -> PagingError {
+        PagingError::Packing(error)
+    }
+}
+
+impl From<RectanglePackingResult> for PagedPackingResult {
+    /// Converts the result of a packer into a result with a single page.
+    fn from(result: RectanglePackingResult) -> PagedPackingResult {
+        let placements = (0..result.rectangles.len())
+            .map(|index| match result.skipped.binary_search(&index) {
+                Ok(_) => None,
+                Err(_) => Some(PagePlacement { page: 0, rectangle: result.rectangles[index] }),
+            })
+            .collect();
+        PagedPackingResult { pages: vec![result.size], placements, tiles: Vec::new(), leftovers: Vec::new(), skipped: result.skipped }
+    }
+}
+
+/// Packs the sizes with the packer `P`, applying the `OverflowPolicy` of the config if they do not fit into its max size.
+///
+/// With `OverflowPolicy::NewPage` and `OverflowPolicy::Partial`, the sizes are added to a page from the highest
+/// to the lowest one, and the longest run of them that `P` can pack into the max size fills the page.
+/// Sizes that do not fit into an empty page on their own never fit, and are left over with `OverflowPolicy::Partial`.
+/// With `PageFill::Balanced`, the sizes are then distributed over the same number of pages by their padded areas,
+/// always adding the next largest one to the emptiest page, and another page is added until `P` packs every page.
+/// With `PageFill::FirstFit`, `PageFill::BestFit` and `PageFill::WorstFit`, every size is instead added
+/// to the first, the fullest or the emptiest open page that `P` can still pack, and opens a new page if there is none.
+/// With `split_oversized`, sizes that do not fit into an empty page are split into tiles of at most the max size
+/// without the padding and border, which are packed like the other sizes and reported as `TilePlacement`s.
+///
+/// With the `rayon` feature, the pages and the candidates of the search for the sizes of a page are packed
+/// in parallel on the current rayon thread pool. The result is the same as without it.
+///
+/// # Arguments
+/// * `sizes` - The sizes of the rectangles to pack.
+/// * `config` - The configuration of the packer.
+///
+/// # Returns
+/// A `Result` containing either:
+///   - A `PagedPackingResult` with the pages and the placement of every size.
+///   - A `PagingError` if the config is not valid, the rectangles do not fit and the policy does not allow it,
+///     or they need more than `max_pages` pages.
+///
+/// # Examples
+/// ```
+/// use rpack::{pack_pages, HeightRectPacker, OverflowPolicy, PagingError, RectanglePackerConfig, Size};
+///
+/// let sizes = vec![Size::new(32, 32); 6];
+/// let config = |overflow| RectanglePackerConfig { max_size: Some(Size::new(64, 64)), overflow, ..Default::default() };
+///
+/// assert!(pack_pages::<HeightRectPacker>(&sizes, &config(OverflowPolicy::Error)).is_err());
+/// assert_eq!(pack_pages::<HeightRectPacker>(&sizes, &config(OverflowPolicy::NewPage)).unwrap().page_count(), 2);
+/// assert_eq!(pack_pages::<HeightRectPacker>(&sizes, &config(OverflowPolicy::Partial)).unwrap().leftovers, vec![4, 5]);
+/// assert_eq!(pack_pages::<HeightRectPacker>(&sizes, &config(OverflowPolicy::Grow)).unwrap().pages, vec![Size::new(128, 64)]);
+///
+/// let limited = RectanglePackerConfig { max_pages: Some(1), ..config(OverflowPolicy::NewPage) };
+/// assert!(matches!(pack_pages::<HeightRectPacker>(&sizes, &limited), Err(PagingError::TooManyPages { required: 2, max_pages: 1 })));
+/// ```
+pub fn pack_pages<P: RectanglePacker>(sizes: &[Size], config: &RectanglePackerConfig) -> Result<PagedPackingResult, PagingError> {
+    let max_size = match (config.max_size, config.overflow) {
+        (Some(max_size), OverflowPolicy::NewPage | OverflowPolicy::Partial | OverflowPolicy::Grow) => max_size,
+        _ => return Ok(P::pack(sizes, config)?.into()),
+    };
+    config.validate().map_err(RectanglePackingError::from)?;
+
+    if config.overflow == OverflowPolicy::Grow {
+        return Ok(grow::<P>(sizes, config, max_size)?);
+    }
+
+    let skipped = config.zero_size_policy.skipped(sizes)?;
+    let margin = 2 * (config.border_padding + config.rectangle_padding);
+    let fits = |size: &Size| {
+        let size = config.padded_size(*size);
+        size.width + margin <= max_size.width && size.height + margin <= max_size.height
+    };
+
+    let (mut remaining, mut leftovers): (Vec<usize>, Vec<usize>) =
+        (0..sizes.len()).filter(|i| skipped.binary_search(i).is_err()).partition(|&i| fits(&sizes[i]));
+    let tiles: Vec<(usize, Rectangle)> = match (config.split_oversized, config.overflow) {
+        (true, OverflowPolicy::NewPage) => {
+            let owned = 2 * config.owned_padding;
+            let tile = Size::new(max_size.width - margin - owned, max_size.height - margin - owned);
+            leftovers.drain(..).flat_map(|index| split(index, sizes[index], tile)).collect()
+        }
+        _ => Vec::new(),
+    };
+    let count = sizes.len();
+    let sizes = &[sizes.to_vec(), tiles.iter().map(|(_, source)| source.to_size()).collect()].concat();
+    remaining.extend(count..sizes.len());
+    if let (OverflowPolicy::NewPage, Some(&index)) = (config.overflow, leftovers.first()) {
+        return Err(error_result(format!("Rectangle {} with size {:?} does not fit into an empty page", index, sizes[index])).into());
+    }
+    remaining.sort_by(|&a, &b| sizes[b].cmp(&sizes[a]).then(a.cmp(&b)));
+
+    let order = remaining.clone();
+    let mut pages = Vec::new();
+    let fit = matches!(config.page_fill, PageFill::FirstFit | PageFill::BestFit | PageFill::WorstFit);
+    if config.overflow == OverflowPolicy::NewPage && fit {
+        pages = fit_pages::<P>(sizes, &order, config)?;
+        remaining.clear();
+    }
+    while !remaining.is_empty() && (config.overflow == OverflowPolicy::NewPage || pages.is_empty()) {
+        let (count, page) = fill_page::<P>(sizes, &remaining, config)?;
+        pages.push(page);
+        remaining.drain(..count);
+    }
+    if config.overflow == OverflowPolicy::NewPage && config.page_fill == PageFill::Balanced && pages.len() > 1 {
+        pages = balance::<P>(sizes, &order, pages.len(), config)?;
+    }
+    if let Some(max_pages) = config.max_pages.filter(|&max_pages| pages.len() > max_pages) {
+        return Err(PagingError::TooManyPages { required: pages.len(), max_pages });
+    }
+    leftovers.extend(remaining);
+    leftovers.sort_unstable();
+
+    Ok(PagedPackingResult::from_pages(&sizes[..count], &tiles, pages, leftovers, skipped))
+}
+
+/// Packs the longest run of the given sizes that fits into a single page, found by bisection.
+///
+/// # Returns
+/// The number of sizes on the page, together with the size of the page and the rectangle of every size on it.
+fn fill_page<P: RectanglePacker>(
+    sizes: &[Size],
+    order: &[usize],
+    config: &RectanglePackerConfig,
+) -> Result<(usize, Page), RectanglePackingError> {
+    let pack = |count: usize| pack_page::<P>(sizes, &order[..count], config);
+
+    // Every size fits into an empty page on its own, so the first one always fits.
+    let mut best = (1, pack(1)?);
+    let (mut low, mut high) = (2, order.len());
+    while low <= high {
+        let count = low + (high - low) / 2;
+
+        #[cfg(not(feature = "rayon"))]
+        bisect(&mut best, (&mut low, &mut high), count, pack(count));
+
+        // Pack the counts the bisection probes next for either outcome in parallel, and drop the one it does not.
+        #[cfg(feature = "rayon")]
+        {
+            let probe = |low: usize, high: usize| (low <= high).then(|| low + (high - low) / 2).map(|count| (count, pack(count)));
+            let (result, (smaller, larger)) = rayon::join(|| pack(count), || rayon::join(|| probe(low, count - 1), || probe(count + 1, high)));
+            let next = match result.is_ok() {
+                true => larger,
+                false => smaller,
+            };
+            bisect(&mut best, (&mut low, &mut high), count, result);
+            if let Some((count, result)) = next {
+                bisect(&mut best, (&mut low, &mut high), count, result);
+            }
+        }
+    }
+
+    Ok(best)
+}
+
+/// Narrows the range of counts searched by `fill_page` to the counts above `count` if its page was packed,
+/// and to the ones below it otherwise.
+fn bisect(best: &mut (usize, Page), (low, high): (&mut usize, &mut usize), count: usize, result: Result<Page, RectanglePackingError>) {
+    match result {
+        Ok(page) => {
+            *best = (count, page);
+            *low = count + 1;
+        }
+        Err(_) => *high = count - 1,
+    }
+}
+
+/// Distributes the sizes over the given number of pages so that the padded areas of the pages are about equal,
+/// adding a page until the packer `P` packs every page.
+fn balance<P: RectanglePacker>(
+    sizes: &[Size],
+    order: &[usize],
+    mut count: usize,
+    config: &RectanglePackerConfig,
+) -> Result<Vec<Page>, RectanglePackingError> {
+    let area = |i: usize| config.padded_area(sizes[i]);
+    let mut by_area = order.to_vec();
+    by_area.sort_by(|&a, &b| area(b).cmp(&area(a)).then(a.cmp(&b)));
+
+    loop {
+        let mut bins = vec![(0, Vec::new()); count];
+        for &index in &by_area {
+            let emptiest = (0..count).min_by_key(|&bin| (bins[bin].0, bin)).unwrap();
+            bins[emptiest].0 += area(index);
+            bins[emptiest].1.push(index);
+        }
+        #[cfg(feature = "rayon")]
+        let bins = bins.into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let bins = bins.into_iter();
+
+        let pages: Result<Vec<Page>, RectanglePackingError> = bins
+            .map(|(_, mut items)| {
+                items.sort_by(|&a, &b| sizes[b].cmp(&sizes[a]).then(a.cmp(&b)));
+                pack_page::<P>(sizes, &items, config)
+            })
+            .collect();
+
+        match pages {
+            Ok(pages) => return Ok(pages),
+            // With a page for every size, every page fits, as every size fits into an empty page on its own.
+            Err(error) if count >= order.len() => return Err(error),
+            Err(_) => count += 1,
+        }
+    }
+}
+
+/// Adds every size in the given order to an open page chosen by the `PageFill` of the config,
+/// opening a new page if the packer `P` can not pack it into any of them.
+fn fit_pages<P: RectanglePacker>(
+    sizes: &[Size],
+    order: &[usize],
+    config: &RectanglePackerConfig,
+) -> Result<Vec<Page>, RectanglePackingError> {
+    // The open pages with the padded area of their sizes.
+    let mut bins: Vec<(Page, usize)> = Vec::new();
+    for &index in order {
+        let mut candidates: Vec<usize> = (0..bins.len()).collect();
+        match config.page_fill {
+            PageFill::BestFit => candidates.sort_by_key(|&bin| std::cmp::Reverse(bins[bin].1)),
+            PageFill::WorstFit => candidates.sort_by_key(|&bin| bins[bin].1),
+            _ => {}
+        }
+
+        let area = config.padded_area(sizes[index]);
+        let pack = |bin: usize| {
+            let mut items: Vec<usize> = bins[bin].0 .1.iter().map(|&(i, _)| i).collect();
+            items.push(index);
+            pack_page::<P>(sizes, &items, config).ok().map(|page| (bin, page))
+        };
+        #[cfg(feature = "rayon")]
+        let page = candidates.into_par_iter().find_map_first(pack);
+        #[cfg(not(feature = "rayon"))]
+        let page = candidates.into_iter().find_map(pack);
+        match page {
+            Some((bin, page)) => bins[bin] = (page, bins[bin].1 + area),
+            // Every size fits into an empty page on its own.
+            None => bins.push((pack_page::<P>(sizes, &[index], config)?, area)),
+        }
+    }
+
+    Ok(bins.into_iter().map(|(page, _)| page).collect())
+}
+
+/// Packs the sizes with the given indices into a single page.
+fn pack_page<P: RectanglePacker>(sizes: &[Size], items: &[usize], config: &RectanglePackerConfig) -> Result<Page, RectanglePackingError> {
+    let result = P::pack(&items.iter().map(|&i| sizes[i]).collect::<Vec<_>>(), config)?;
+    Ok((result.size, items.iter().copied().zip(result.rectangles).collect()))
+}
+
+/// Splits a size into tiles of at most the given size, row by row.
+///
+/// # Returns
+/// The index of the size together with the part of the size shown by every tile.
+fn split(index: usize, size: Size, tile: Size) -> Vec<(usize, Rectangle)> {
+    let mut tiles = Vec::new();
+    for y in (0..size.height).step_by(tile.height) {
+        for x in (0..size.width).step_by(tile.width) {
+            tiles.push((index, Rectangle::new(x, y, tile.width.min(size.width - x), tile.height.min(size.height - y))));
+        }
+    }
+    tiles
+}
+
+/// Packs the sizes into a single page, doubling the shorter side of the page until they fit.
+fn grow<P: RectanglePacker>(sizes: &[Size], config: &RectanglePackerConfig, max_size: Size) -> Result<PagedPackingResult, RectanglePackingError> {
+    let mut size = max_size;
+    loop {
+        let error = match P::pack(sizes, &with_max_size(config, size)) {
+            Ok(result) => return Ok(result.into()),
+            Err(error) => error,
+        };
+        let grown = match size.width <= size.height {
+            true => size.width.checked_mul(2).map(|width| Size::new(width, size.height)),
+            false => size.height.checked_mul(2).map(|height| Size::new(size.width, height)),
+        };
+        size = grown.ok_or(error)?;
+    }
+}
+
+/// Returns an error with the given message and an empty result.
+fn error_result(message: String) -> RectanglePackingError {
+    RectanglePackingError {
+        message,
+        config_error: None,
+        result: RectanglePackingResult { rectangles: Vec::new(), size: Size::new(0, 0), skipped: Vec::new() },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ConfigError, HeightRectPacker, ZeroSizePolicy};
+
+    use super::*;
+
+    fn config(overflow: OverflowPolicy) -> RectanglePackerConfig {
+        RectanglePackerConfig { max_size: Some(Size::new(40, 40)), rectangle_padding: 1, overflow, ..Default::default() }
+    }
+
+    /// Validates every page as a packing result of its own.
+    fn validate(result: &PagedPackingResult, config: &RectanglePackerConfig) {
+        for (page, &size) in result.pages.iter().enumerate() {
+            let rectangles = result.items_on(page).iter().map(|&i| result.placements[i].unwrap().rectangle).collect();
+            let page = RectanglePackingResult { rectangles, size, skipped: Vec::new() };
+            assert_eq!(page.validate(config), Ok(()));
+        }
+    }
+
+    #[test]
+    fn new_pages_are_opened_for_overflowing_sizes() {
+        let sizes: Vec<Size> = (0..60).map(|i| Size::new(i % 9 + 2, i % 5 + 2)).collect();
+        let config = config(OverflowPolicy::NewPage);
+        let result = pack_pages::<HeightRectPacker>(&sizes, &config).unwrap();
+
+        assert!(result.page_count() > 1);
+        assert!(result.pages.iter().all(|&size| size == Size::new(40, 40)));
+        assert!(result.placements.iter().all(|p| p.is_some()));
+        assert!(result.leftovers.is_empty());
+        validate(&result, &config);
+
+        let oversized = pack_pages::<HeightRectPacker>(&[Size::new(4, 4), Size::new(50, 4)], &config).unwrap_err();
+        assert!(oversized.to_string().contains("Rectangle 1"));
+    }
+
+    #[test]
+    fn balanced_pages_are_filled_evenly() {
+        let sizes: Vec<Size> = (0..40).map(|i| Size::new(i % 9 + 2, i % 5 + 2)).collect();
+        let area = |result: &PagedPackingResult, page| -> usize {
+            result.items_on(page).iter().map(|&i| sizes[i].width * sizes[i].height).sum()
+        };
+        let spread = |result: &PagedPackingResult| {
+            let areas: Vec<usize> = (0..result.page_count()).map(|page| area(result, page)).collect();
+            areas.iter().max().unwrap() - areas.iter().min().unwrap()
+        };
+
+        let sequential = pack_pages::<HeightRectPacker>(&sizes, &config(OverflowPolicy::NewPage)).unwrap();
+        let config = RectanglePackerConfig { page_fill: PageFill::Balanced, ..config(OverflowPolicy::NewPage) };
+        let balanced = pack_pages::<HeightRectPacker>(&sizes, &config).unwrap();
+
+        assert!(balanced.page_count() >= sequential.page_count());
+        assert!(spread(&balanced) < spread(&sequential));
+        assert!(balanced.placements.iter().all(|p| p.is_some()));
+        validate(&balanced, &config);
+    }
+
+    #[test]
+    fn fit_heuristics_choose_the_page_of_every_size() {
+        let sizes: Vec<Size> = (0..60).map(|i| Size::new(i * 7 % 13 + 2, i * 5 % 11 + 2)).collect();
+        let pack = |page_fill| {
+            let config = RectanglePackerConfig { page_fill, ..config(OverflowPolicy::NewPage) };
+            let result = pack_pages::<HeightRectPacker>(&sizes, &config).unwrap();
+            assert!(result.placements.iter().all(|p| p.is_some()));
+            validate(&result, &config);
+            result.page_count()
+        };
+        assert!(pack(PageFill::FirstFit) <= pack(PageFill::Sequential));
+        assert!(pack(PageFill::BestFit) <= pack(PageFill::Sequential));
+        pack(PageFill::WorstFit);
+
+        // Full-width rows of the heights 6, 5, 4, 3 and 2 on pages of height 10.
+        let rows: Vec<Size> = [6, 5, 4, 3, 2].iter().map(|&height| Size::new(10, height)).collect();
+        let pages = |page_fill| {
+            let config = RectanglePackerConfig { max_size: Some(Size::new(10, 10)), overflow: OverflowPolicy::NewPage, page_fill, ..Default::default() };
+            let result = pack_pages::<HeightRectPacker>(&rows, &config).unwrap();
+            validate(&result, &config);
+            result.placements.iter().map(|p| p.unwrap().page).collect::<Vec<_>>()
+        };
+        assert_eq!(pages(PageFill::FirstFit), vec![0, 1, 0, 1, 1]);
+        assert_eq!(pages(PageFill::BestFit), vec![0, 1, 0, 1, 1]);
+        assert_eq!(pages(PageFill::WorstFit), vec![0, 1, 1, 0, 2]);
+    }
+
+    #[test]
+    fn oversized_sizes_are_split_into_tiles() {
+        let sizes = vec![Size::new(10, 10), Size::new(90, 50), Size::new(0, 0)];
+        let tiled = RectanglePackerConfig { split_oversized: true, zero_size_policy: ZeroSizePolicy::Skip, ..config(OverflowPolicy::NewPage) };
+        let result = pack_pages::<HeightRectPacker>(&sizes, &tiled).unwrap();
+
+        assert!(result.placements[0].is_some());
+        assert_eq!(result.placements[1], None);
+        assert_eq!(result.skipped, vec![2]);
+        assert!(result.tiles_of(0).is_empty());
+
+        // The tiles are at most 38 by 38, the max size without the padding on both sides.
+        let tiles = result.tiles_of(1);
+        let sources: Vec<Rectangle> = tiles.iter().map(|tile| tile.source).collect();
+        assert_eq!(
+            sources,
+            vec![
+                Rectangle::new(0, 0, 38, 38),
+                Rectangle::new(38, 0, 38, 38),
+                Rectangle::new(76, 0, 14, 38),
+                Rectangle::new(0, 38, 38, 12),
+                Rectangle::new(38, 38, 38, 12),
+                Rectangle::new(76, 38, 14, 12),
+            ]
+        );
+        for tile in &tiles {
+            assert_eq!(tile.placement.rectangle.to_size(), tile.source.to_size());
+        }
+        for page in 0..result.page_count() {
+            let mut rectangles: Vec<Rectangle> = result.items_on(page).iter().map(|&i| result.placements[i].unwrap().rectangle).collect();
+            rectangles.extend(result.tiles_on(page).iter().map(|tile| tile.placement.rectangle));
+            let page = RectanglePackingResult { rectangles, size: result.pages[page], skipped: Vec::new() };
+            assert_eq!(page.validate(&tiled), Ok(()));
+        }
+
+        let partial = RectanglePackerConfig { split_oversized: true, ..config(OverflowPolicy::Partial) };
+        let result = pack_pages::<HeightRectPacker>(&sizes[..2], &partial).unwrap();
+        assert_eq!(result.leftovers, vec![1]);
+        assert!(result.tiles.is_empty());
+    }
+
+    #[test]
+    fn the_number_of_pages_is_limited() {
+        let sizes = vec![Size::new(18, 18); 9];
+        let limited = |overflow, max_pages| RectanglePackerConfig { max_pages: Some(max_pages), ..config(overflow) };
+
+        assert_eq!(pack_pages::<HeightRectPacker>(&sizes, &limited(OverflowPolicy::NewPage, 3)).unwrap().page_count(), 3);
+        match pack_pages::<HeightRectPacker>(&sizes, &limited(OverflowPolicy::NewPage, 2)) {
+            Err(PagingError::TooManyPages { required, max_pages }) => assert_eq!((required, max_pages), (3, 2)),
+            other => panic!("Expected too many pages, got {:?}", other),
+        }
+        // Partial packings never need more than a single page.
+        let partial = pack_pages::<HeightRectPacker>(&sizes, &limited(OverflowPolicy::Partial, 1)).unwrap();
+        assert_eq!(partial.leftovers.len(), 5);
+    }
+
+    #[test]
+    fn invalid_configs_keep_their_config_error() {
+        let sizes = vec![Size::new(4, 4); 3];
+        let expected = ConfigError::PaddingExceedsMaxSize { max_size: Size::new(40, 40), padding: 21 };
+
+        // `Error` is validated by the packer, every other policy by `pack_pages` itself.
+        for overflow in [OverflowPolicy::Error, OverflowPolicy::NewPage, OverflowPolicy::Partial, OverflowPolicy::Grow] {
+            let invalid = RectanglePackerConfig { border_padding: 20, ..config(overflow) };
+            match pack_pages::<HeightRectPacker>(&sizes, &invalid) {
+                Err(PagingError::Packing(error)) => {
+                    assert_eq!(error.message, expected.to_string());
+                    assert_eq!(error.config_error, Some(expected.clone()), "{:?}", overflow);
+                }
+                other => panic!("Expected a config error for {:?}, got {:?}", overflow, other),
+            }
+        }
+    }
+
+    #[test]
+    fn partial_packings_return_leftovers() {
+        let sizes: Vec<Size> = (0..60).map(|i| Size::new(i % 9 + 2, i % 5 + 2)).chain([Size::new(50, 1)]).collect();
+        let config = config(OverflowPolicy::Partial);
+        let result = pack_pages::<HeightRectPacker>(&sizes, &config).unwrap();
+
+        assert_eq!(result.page_count(), 1);
+        assert!(result.leftovers.contains(&60));
+        for (index, placement) in result.placements.iter().enumerate() {
+            assert_eq!(placement.is_none(), result.leftovers.binary_search(&index).is_ok());
+        }
+        validate(&result, &config);
+    }
+
+    #[test]
+    fn grown_containers_fit_all_sizes() {
+        let sizes = vec![Size::new(30, 30); 5];
+        let config = config(OverflowPolicy::Grow);
+        let result = pack_pages::<HeightRectPacker>(&sizes, &config).unwrap();
+
+        assert_eq!(result.pages, vec![Size::new(160, 80)]);
+        validate(&result, &config);
+    }
+
+    #[test]
+    fn shrunk_pages_keep_their_rectangles() {
+        let sizes: Vec<Size> = (0..30).map(|i| Size::new(i % 9 + 2, i % 5 + 2)).collect();
+        let config = RectanglePackerConfig { border_padding: 2, ..config(OverflowPolicy::NewPage) };
+        let mut result = pack_pages::<HeightRectPacker>(&sizes, &config).unwrap();
+        let full = result.clone();
+
+        result.shrink_to_fit(&config, false);
+        assert_eq!(result.placements, full.placements);
+        assert!(result.pages.last().unwrap().height < 40);
+        validate(&result, &config);
+
+        let mut rounded = full.clone();
+        rounded.shrink_to_fit(&config, true);
+        for (rounded, shrunk) in rounded.pages.iter().zip(&result.pages) {
+            assert!(rounded.width >= shrunk.width && rounded.width <= 40);
+            assert!(rounded.height.is_power_of_two() || rounded.height == 40);
+        }
+        validate(&rounded, &config);
+    }
+
+    #[test]
+    fn pack_pages_matches_the_packer_if_everything_fits() {
+        let sizes = vec![Size::new(8, 8), Size::new(0, 3), Size::new(4, 4)];
+        for overflow in [OverflowPolicy::Error, OverflowPolicy::NewPage, OverflowPolicy::Partial, OverflowPolicy::Grow] {
+            let config = RectanglePackerConfig { zero_size_policy: ZeroSizePolicy::Skip, ..config(overflow) };
+            let result = pack_pages::<HeightRectPacker>(&sizes, &config).unwrap();
+            let expected = HeightRectPacker::pack(&sizes, &config).unwrap();
+
+            assert_eq!(result.pages, vec![expected.size]);
+            assert_eq!(result.skipped, vec![1]);
+            assert_eq!(result.placements[0].unwrap().rectangle, expected.rectangles[0]);
+            assert_eq!(result.placements[1], None);
+        }
+    }
+}
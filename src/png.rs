@@ -0,0 +1,182 @@
+use std::path::Path;
+use image::{ImageResult, Rgba, RgbaImage};
+use crate::render::rectangle_hue;
+use crate::{Rectangle, RectanglePackingResult};
+
+/// The background color of the container.
+const BACKGROUND: Rgba<u8> = Rgba([244, 244, 244, 255]);
+
+/// The color of the rectangle outlines and the index labels.
+const FOREGROUND: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+/// A 3 x 5 pixel font for the digits `0` to `9`, one row per entry with the leftmost pixel in the highest bit.
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+impl RectanglePackingResult {
+    /// Renders the packing as an RGBA image.
+    ///
+    /// Every packed rectangle is filled with a distinct color and outlined in black.
+    /// If `labels` is `true`, each rectangle is labeled with its index, as long as the label fits into it.
+    /// One pixel corresponds to one unit of the packing.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+    ///
+    /// let sizes = vec![Size::new(10, 20), Size::new(30, 5)];
+    /// let result = HeightRectPacker::pack(&sizes, &RectanglePackerConfig::default()).unwrap();
+    ///
+    /// let image = result.to_image(true);
+    /// assert_eq!(image.dimensions(), (result.size.width as u32, result.size.height as u32));
+    /// ```
+    pub fn to_image(&self, labels: bool) -> RgbaImage {
+        let mut image = RgbaImage::from_pixel(self.size.width as u32, self.size.height as u32, BACKGROUND);
+
+        for (index, rectangle) in self.rectangles.iter().enumerate() {
+            let color = hsl_to_rgba(rectangle_hue(index), 0.6, 0.7);
+            fill(&mut image, rectangle, color);
+            outline(&mut image, rectangle);
+            if labels {
+                label(&mut image, rectangle, index);
+            }
+        }
+
+        image
+    }
+
+    /// Renders the packing as described in [`to_image`](Self::to_image) and saves it as a PNG file.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the PNG file to write.
+    /// * `labels` - Whether to label each rectangle with its index.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P, labels: bool) -> ImageResult<()> {
+        self.to_image(labels).save_with_format(path, image::ImageFormat::Png)
+    }
+}
+
+/// Fills the given rectangle, clipped to the image.
+fn fill(image: &mut RgbaImage, rectangle: &Rectangle, color: Rgba<u8>) {
+    let right = (rectangle.x + rectangle.width).min(image.width() as usize);
+    let bottom = (rectangle.y + rectangle.height).min(image.height() as usize);
+    for y in rectangle.y..bottom {
+        for x in rectangle.x..right {
+            image.put_pixel(x as u32, y as u32, color);
+        }
+    }
+}
+
+/// Draws the one pixel wide outline along the inside of the given rectangle.
+fn outline(image: &mut RgbaImage, rectangle: &Rectangle) {
+    if rectangle.width == 0 || rectangle.height == 0 {
+        return;
+    }
+    let right = rectangle.x + rectangle.width - 1;
+    let bottom = rectangle.y + rectangle.height - 1;
+    fill(image, &Rectangle::new(rectangle.x, rectangle.y, rectangle.width, 1), FOREGROUND);
+    fill(image, &Rectangle::new(rectangle.x, bottom, rectangle.width, 1), FOREGROUND);
+    fill(image, &Rectangle::new(rectangle.x, rectangle.y, 1, rectangle.height), FOREGROUND);
+    fill(image, &Rectangle::new(right, rectangle.y, 1, rectangle.height), FOREGROUND);
+}
+
+/// Draws the index centered into the given rectangle, scaled up as far as it fits.
+///
+/// Nothing is drawn if the label does not fit into the rectangle at all.
+fn label(image: &mut RgbaImage, rectangle: &Rectangle, index: usize) {
+    let digits: Vec<usize> = index.to_string().bytes().map(|b| (b - b'0') as usize).collect();
+
+    // Each digit is 3 pixels wide with one pixel of spacing, and one pixel is kept free to the outline.
+    let text_width = 4 * digits.len() - 1;
+    let scale = ((rectangle.width.saturating_sub(4)) / text_width)
+        .min(rectangle.height.saturating_sub(4) / 5)
+        .min(4);
+    if scale == 0 {
+        return;
+    }
+
+    let left = rectangle.x + (rectangle.width - text_width * scale) / 2;
+    let top = rectangle.y + (rectangle.height - 5 * scale) / 2;
+    for (position, &digit) in digits.iter().enumerate() {
+        for (row, bits) in DIGITS[digit].iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) != 0 {
+                    let x = left + (4 * position + column) * scale;
+                    let y = top + row * scale;
+                    fill(image, &Rectangle::new(x, y, scale, scale), FOREGROUND);
+                }
+            }
+        }
+    }
+}
+
+/// Converts a color given by its hue in degrees, saturation and lightness to an opaque RGBA color.
+fn hsl_to_rgba(hue: usize, saturation: f64, lightness: f64) -> Rgba<u8> {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue as f64 / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as usize {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+    Rgba([channel(r), channel(g), channel(b), 255])
+}
+
+#[cfg(test)]
+mod tests {
+    use image::GenericImageView;
+    use crate::Size;
+
+    use super::*;
+
+    fn result() -> RectanglePackingResult {
+        RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 20, 20), Rectangle::new(20, 0, 4, 4)],
+            size: Size::new(30, 20),
+            skipped: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn to_image_works() {
+        let image = result().to_image(false);
+
+        assert_eq!(image.dimensions(), (30, 20));
+        assert_eq!(*image.get_pixel(0, 0), FOREGROUND);
+        assert_eq!(*image.get_pixel(10, 10), hsl_to_rgba(0, 0.6, 0.7));
+        assert_eq!(*image.get_pixel(21, 1), hsl_to_rgba(137, 0.6, 0.7));
+        assert_eq!(*image.get_pixel(29, 19), BACKGROUND);
+    }
+
+    #[test]
+    fn labels_are_drawn_if_they_fit() {
+        let unlabeled = result().to_image(false);
+        let labeled = result().to_image(true);
+
+        // The first rectangle is large enough for its label, the second one is not.
+        assert_ne!(unlabeled.view(0, 0, 20, 20).to_image(), labeled.view(0, 0, 20, 20).to_image());
+        assert_eq!(unlabeled.view(20, 0, 4, 4).to_image(), labeled.view(20, 0, 4, 4).to_image());
+    }
+
+    #[test]
+    fn hsl_to_rgba_works() {
+        assert_eq!(hsl_to_rgba(0, 1.0, 0.5), Rgba([255, 0, 0, 255]));
+        assert_eq!(hsl_to_rgba(120, 1.0, 0.5), Rgba([0, 255, 0, 255]));
+        assert_eq!(hsl_to_rgba(240, 1.0, 0.5), Rgba([0, 0, 255, 255]));
+    }
+}
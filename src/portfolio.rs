@@ -0,0 +1,223 @@
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{RectanglePacker, RectanglePackerConfig, RectanglePackingError, RectanglePackingResult, Size};
+
+/// A solver of a portfolio, such as a packer or a search with particular settings.
+type Solver = Arc<dyn Fn(&[Size], &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError> + Send + Sync>;
+
+/// The result of racing the solvers of a `Portfolio`.
+#[derive(Debug)]
+pub struct PortfolioResult {
+    /// The name of the solver that produced the result.
+    pub name: String,
+
+    /// The packing of the solver.
+    pub result: RectanglePackingResult,
+
+    /// The wall-clock time the solver took.
+    pub runtime: Duration,
+
+    /// Whether the packing meets the target packing ratio of the portfolio.
+    /// If `false`, no solver met it before the deadline or before all solvers finished.
+    pub met_target: bool,
+}
+
+/// Races several solvers on their own threads with a shared deadline, to get a good packing with a predictable latency,
+/// e.g. in interactive tools.
+///
+/// The first packing that meets the target packing ratio is returned as soon as it is found. Otherwise, the packing
+/// with the highest packing ratio among the solvers finished by the deadline is returned.
+///
+/// Solvers can not be interrupted, so solvers still running when `solve` returns finish in the background,
+/// and their results are dropped. A solver that panics counts as a solver that does not finish.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use rpack::{best_strip_width, HeightRectPacker, ObjectiveWeights, Portfolio, RectanglePackerConfig, Size};
+///
+/// let portfolio = Portfolio::new(Duration::from_millis(100))
+///     .with_packer::<HeightRectPacker>("height")
+///     .with_solver("smallest area", |sizes, config| {
+///         best_strip_width::<HeightRectPacker>(sizes, config, &ObjectiveWeights { area: 1.0, ..ObjectiveWeights::NONE })
+///     })
+///     .with_target_ratio(0.9);
+///
+/// let sizes = vec![Size::new(10, 20), Size::new(30, 5), Size::new(8, 8)];
+/// let result = portfolio.solve(&sizes, &RectanglePackerConfig::default()).unwrap();
+/// assert!(result.result.packing_ratio() > 0.0);
+/// ```
+pub struct Portfolio {
+    solvers: Vec<(String, Solver)>,
+    deadline: Duration,
+    target_ratio: Option<f64>,
+}
+
+impl Portfolio {
+    /// Creates a portfolio without solvers, which waits at most `deadline` for the solvers in `solve`.
+    pub fn new(deadline: Duration) -> Portfolio {
+        Portfolio { solvers: Vec::new(), deadline, target_ratio: None }
+    }
+
+    /// Adds a solver with the given name, e.g. a search with particular settings or a seed.
+    pub fn with_solver<F>(mut self, name: &str, solver: F) -> Portfolio
+    where
+        F: Fn(&[Size], &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError> + Send + Sync + 'static,
+    {
+        self.solvers.push((name.to_string(), Arc::new(solver)));
+        self
+    }
+
+    /// Adds the packer `P` as a solver with the given name.
+    pub fn with_packer<P: RectanglePacker + 'static>(self, name: &str) -> Portfolio {
+        self.with_solver(name, P::pack)
+    }
+
+    /// Sets the packing ratio at which `solve` returns a packing without waiting for the other solvers.
+    ///
+    /// Without a target, `solve` waits for all solvers until the deadline.
+    pub fn with_target_ratio(mut self, target_ratio: f64) -> Portfolio {
+        self.target_ratio = Some(target_ratio);
+        self
+    }
+
+    /// Runs all solvers on the sizes, each on its own thread, and returns the first packing meeting the target ratio,
+    /// or the best packing found once all solvers finished or the deadline passed.
+    ///
+    /// # Arguments
+    /// * `sizes` - The sizes of the rectangles to pack.
+    /// * `config` - The config every solver is run with.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - The `PortfolioResult` of the chosen packing. Of packings with the same packing ratio, the first one found is chosen.
+    ///   - A `RectanglePackingError` if no solver succeeded by the deadline. If a solver failed, its error is returned.
+    pub fn solve(&self, sizes: &[Size], config: &RectanglePackerConfig) -> Result<PortfolioResult, RectanglePackingError> {
+        let deadline = Instant::now() + self.deadline;
+        let sizes: Arc<[Size]> = sizes.into();
+        let (sender, receiver) = mpsc::channel();
+
+        for (index, (_, solver)) in self.solvers.iter().enumerate() {
+            let (solver, sizes, sender) = (Arc::clone(solver), Arc::clone(&sizes), sender.clone());
+            let config = config.clone();
+            thread::spawn(move || {
+                let start = Instant::now();
+                let result = solver(&sizes, &config);
+                // The portfolio may have returned already, which drops the receiver.
+                let _ = sender.send((index, result, start.elapsed()));
+            });
+        }
+        drop(sender);
+
+        let mut best: Option<PortfolioResult> = None;
+        let mut error = None;
+        // Receiving fails once the deadline passed or all solvers finished.
+        while let Ok((index, result, runtime)) = receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            match result {
+                Ok(result) => {
+                    let ratio = result.packing_ratio();
+                    let met_target = self.target_ratio.is_some_and(|target| ratio >= target);
+                    if met_target || best.as_ref().is_none_or(|best| ratio > best.result.packing_ratio()) {
+                        best = Some(PortfolioResult { name: self.solvers[index].0.clone(), result, runtime, met_target });
+                    }
+                    if met_target {
+                        break;
+                    }
+                }
+                Err(e) => error = error.or(Some(e)),
+            }
+        }
+
+        best.ok_or_else(|| error.unwrap_or_else(|| RectanglePackingError {
+            message: format!("No solver of the portfolio finished within {:?}", self.deadline),
+            config_error: None,
+            result: RectanglePackingResult { rectangles: Vec::new(), size: Size::new(0, 0), skipped: Vec::new() },
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeightRectPacker, Rectangle};
+
+    use super::*;
+
+    fn single_row(sizes: &[Size], _config: &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError> {
+        let mut x = 0;
+        let rectangles = sizes
+            .iter()
+            .map(|size| {
+                let rectangle = Rectangle::from_size(x, 0, size);
+                x += size.width;
+                rectangle
+            })
+            .collect();
+        let height = sizes.iter().map(|s| s.height).max().unwrap_or(0);
+        Ok(RectanglePackingResult { rectangles, size: Size::new(x, height), skipped: Vec::new() })
+    }
+
+    fn sleeping(duration: Duration) -> impl Fn(&[Size], &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError> {
+        move |sizes, config| {
+            thread::sleep(duration);
+            single_row(sizes, config)
+        }
+    }
+
+    #[test]
+    fn solve_returns_the_best_result() {
+        let sizes = vec![Size::new(8, 8), Size::new(4, 4), Size::new(4, 4), Size::new(4, 4), Size::new(4, 4)];
+        let portfolio = Portfolio::new(Duration::from_secs(10))
+            .with_solver("single row", single_row)
+            .with_packer::<HeightRectPacker>("height");
+        let result = portfolio.solve(&sizes, &RectanglePackerConfig::default()).unwrap();
+
+        assert_eq!(result.name, "height");
+        assert!(!result.met_target);
+        assert!(result.result.packing_ratio() > single_row(&sizes, &RectanglePackerConfig::default()).unwrap().packing_ratio());
+    }
+
+    #[test]
+    fn solve_stops_at_the_target_ratio() {
+        let sizes = vec![Size::new(4, 4); 4];
+        let portfolio = Portfolio::new(Duration::from_secs(10))
+            .with_solver("slow", sleeping(Duration::from_secs(5)))
+            .with_solver("fast", single_row)
+            .with_target_ratio(1.0);
+        let start = Instant::now();
+        let result = portfolio.solve(&sizes, &RectanglePackerConfig::default()).unwrap();
+
+        assert_eq!(result.name, "fast");
+        assert!(result.met_target);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn solve_returns_at_the_deadline() {
+        let sizes = vec![Size::new(4, 4); 4];
+        let start = Instant::now();
+
+        let portfolio = Portfolio::new(Duration::from_millis(50)).with_solver("slow", sleeping(Duration::from_secs(5)));
+        assert!(portfolio.solve(&sizes, &RectanglePackerConfig::default()).is_err());
+
+        let portfolio = Portfolio::new(Duration::from_millis(200))
+            .with_solver("slow", sleeping(Duration::from_secs(5)))
+            .with_solver("fast", single_row)
+            .with_target_ratio(2.0);
+        assert_eq!(portfolio.solve(&sizes, &RectanglePackerConfig::default()).unwrap().name, "fast");
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn solve_returns_errors_of_failed_solvers() {
+        let config = RectanglePackerConfig { max_size: Some(Size::new(4, 4)), ..Default::default() };
+        let error = Portfolio::new(Duration::from_secs(10))
+            .with_packer::<HeightRectPacker>("height")
+            .solve(&[Size::new(8, 8)], &config)
+            .unwrap_err();
+
+        assert!(!error.message.contains("No solver"));
+    }
+}
@@ -0,0 +1,443 @@
+//! A file format capturing packing problems, so they can be attached to bug reports and replayed in tests.
+//!
+//! A problem consists of the sizes to pack and the config to pack them with, and is stored as JSON or TOML:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "config": { "max_size": { "width": 64, "height": 64 }, "rectangle_padding": 1, "border_padding": 0 },
+//!   "sizes": [{ "width": 16, "height": 8 }, { "width": 4, "height": 4 }]
+//! }
+//! ```
+//!
+//! The `zero_size_policy` is one of `"place"`, `"reject"` and `"skip"`, and the `objective` holds the
+//! `height`, `squareness`, `perimeter` and `area` weights of `ObjectiveWeights`. The `memory_budget` is a number of bytes,
+//! the `overflow` is one of `"error"`, `"new_page"`, `"partial"` and `"grow"`, `max_pages` is a number of pages
+//! and the `page_fill` is one of `"sequential"`, `"balanced"`, `"first_fit"`, `"best_fit"` and `"worst_fit"`.
+//! `split_oversized` and `fill_row_gaps` are booleans, and `owned_padding`, `column_gutter`, `row_gutter`
+//! and `row_height_multiple` are numbers of pixels like the paddings.
+//! All fields of the config may be omitted, and default to the values of `RectanglePackerConfig::default()`.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::{RectanglePacker, RectanglePackerConfig, RectanglePackingError, RectanglePackingResult, Size, ZeroSizePolicy, ObjectiveWeights, OverflowPolicy, PageFill};
+
+/// The version of the format written by this crate.
+pub const VERSION: u32 = 1;
+
+/// A packing problem: the sizes to pack and the config to pack them with.
+pub struct PackingProblem {
+    /// The sizes of the rectangles to pack.
+    pub sizes: Vec<Size>,
+
+    /// The config to pack the rectangles with.
+    pub config: RectanglePackerConfig,
+}
+
+impl fmt::Debug for PackingProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PackingProblem")
+            .field("sizes", &self.sizes)
+            .field("max_size", &self.config.max_size)
+            .field("rectangle_padding", &self.config.rectangle_padding)
+            .field("owned_padding", &self.config.owned_padding)
+            .field("border_padding", &self.config.border_padding)
+            .field("column_gutter", &self.config.column_gutter)
+            .field("row_gutter", &self.config.row_gutter)
+            .field("row_height_multiple", &self.config.row_height_multiple)
+            .field("fill_row_gaps", &self.config.fill_row_gaps)
+            .field("zero_size_policy", &self.config.zero_size_policy)
+            .field("deduplicate_sizes", &self.config.deduplicate_sizes)
+            .field("objective", &self.config.objective)
+            .field("memory_budget", &self.config.memory_budget)
+            .field("overflow", &self.config.overflow)
+            .field("max_pages", &self.config.max_pages)
+            .field("page_fill", &self.config.page_fill)
+            .field("split_oversized", &self.config.split_oversized)
+            .finish()
+    }
+}
+
+/// An error raised while loading or saving a problem.
+#[derive(Debug)]
+pub enum ProblemError {
+    /// The file could not be read or written.
+    Io(io::Error),
+
+    /// The file is not valid JSON or does not match the format.
+    Json(serde_json::Error),
+
+    /// The file is not valid TOML or does not match the format.
+    Toml(String),
+
+    /// The file was written by a newer version of the format.
+    UnsupportedVersion(u32),
+
+    /// The file extension is neither `json` nor `toml`.
+    UnknownFormat,
+}
+
+impl fmt::Display for ProblemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProblemError::Io(error) => write!(f, "{}", error),
+            ProblemError::Json(error) => write!(f, "invalid JSON problem: {}", error),
+            ProblemError::Toml(message) => write!(f, "invalid TOML problem: {}", message),
+            ProblemError::UnsupportedVersion(version) => {
+                write!(f, "unsupported problem version {}, expected at most {}", version, VERSION)
+            }
+            ProblemError::UnknownFormat => write!(f, "unknown problem format, expected a .json or .toml file"),
+        }
+    }
+}
+
+impl Error for ProblemError {}
+
+#[derive(Serialize, Deserialize)]
+struct SizeDef {
+    width: usize,
+    height: usize,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum ZeroSizePolicyDef {
+    #[default]
+    Place,
+    Reject,
+    Skip,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum OverflowPolicyDef {
+    #[default]
+    Error,
+    NewPage,
+    Partial,
+    Grow,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum PageFillDef {
+    #[default]
+    Sequential,
+    Balanced,
+    FirstFit,
+    BestFit,
+    WorstFit,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct ConfigDef {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_size: Option<SizeDef>,
+    #[serde(default)]
+    rectangle_padding: usize,
+    #[serde(default)]
+    owned_padding: usize,
+    #[serde(default)]
+    border_padding: usize,
+    #[serde(default)]
+    column_gutter: usize,
+    #[serde(default)]
+    row_gutter: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    row_height_multiple: Option<usize>,
+    #[serde(default)]
+    fill_row_gaps: bool,
+    #[serde(default)]
+    zero_size_policy: ZeroSizePolicyDef,
+    #[serde(default)]
+    deduplicate_sizes: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    objective: Option<ObjectiveDef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    memory_budget: Option<usize>,
+    #[serde(default)]
+    overflow: OverflowPolicyDef,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_pages: Option<usize>,
+    #[serde(default)]
+    page_fill: PageFillDef,
+    #[serde(default)]
+    split_oversized: bool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct ObjectiveDef {
+    #[serde(default)]
+    height: f64,
+    #[serde(default)]
+    squareness: f64,
+    #[serde(default)]
+    perimeter: f64,
+    #[serde(default)]
+    area: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProblemDef {
+    version: u32,
+    #[serde(default)]
+    config: ConfigDef,
+    sizes: Vec<SizeDef>,
+}
+
+impl PackingProblem {
+    /// Creates a problem from the sizes and the config to pack them with.
+    pub fn new(sizes: &[Size], config: &RectanglePackerConfig) -> PackingProblem {
+        PackingProblem {
+            sizes: sizes.to_vec(),
+            config: config.clone(),
+        }
+    }
+
+    /// Packs the sizes of the problem with the given packer.
+    pub fn pack<P: RectanglePacker>(&self) -> Result<RectanglePackingResult, RectanglePackingError> {
+        P::pack(&self.sizes, &self.config)
+    }
+
+    /// Parses a problem from JSON.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::problem::PackingProblem;
+    /// use rpack::{HeightRectPacker, Size};
+    ///
+    /// let problem = PackingProblem::from_json(r#"{ "version": 1, "sizes": [{ "width": 4, "height": 2 }] }"#).unwrap();
+    /// assert_eq!(problem.sizes, vec![Size::new(4, 2)]);
+    /// assert!(problem.pack::<HeightRectPacker>().is_ok());
+    /// ```
+    pub fn from_json(json: &str) -> Result<PackingProblem, ProblemError> {
+        PackingProblem::from_def(serde_json::from_str(json).map_err(ProblemError::Json)?)
+    }
+
+    /// Writes the problem as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_def()).expect("problems are always serializable")
+    }
+
+    /// Parses a problem from TOML, with the sizes as an array of tables:
+    ///
+    /// ```toml
+    /// version = 1
+    ///
+    /// [config]
+    /// rectangle_padding = 1
+    ///
+    /// [[sizes]]
+    /// width = 16
+    /// height = 8
+    /// ```
+    pub fn from_toml(text: &str) -> Result<PackingProblem, ProblemError> {
+        PackingProblem::from_def(toml::from_str(text).map_err(|error| ProblemError::Toml(error.to_string()))?)
+    }
+
+    /// Writes the problem as TOML.
+    pub fn to_toml(&self) -> String {
+        toml::to_string(&self.to_def()).expect("problems are always serializable")
+    }
+
+    /// Loads a problem from a `.json` or `.toml` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<PackingProblem, ProblemError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(ProblemError::Io)?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => PackingProblem::from_json(&text),
+            Some("toml") => PackingProblem::from_toml(&text),
+            _ => Err(ProblemError::UnknownFormat),
+        }
+    }
+
+    /// Saves the problem to a `.json` or `.toml` file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ProblemError> {
+        let path = path.as_ref();
+        let text = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => self.to_json(),
+            Some("toml") => self.to_toml(),
+            _ => return Err(ProblemError::UnknownFormat),
+        };
+        fs::write(path, text).map_err(ProblemError::Io)
+    }
+
+    fn from_def(def: ProblemDef) -> Result<PackingProblem, ProblemError> {
+        if def.version > VERSION {
+            return Err(ProblemError::UnsupportedVersion(def.version));
+        }
+        Ok(PackingProblem {
+            sizes: def.sizes.into_iter().map(|s| Size::new(s.width, s.height)).collect(),
+            config: RectanglePackerConfig {
+                max_size: def.config.max_size.map(|s| Size::new(s.width, s.height)),
+                rectangle_padding: def.config.rectangle_padding,
+                owned_padding: def.config.owned_padding,
+                border_padding: def.config.border_padding,
+                column_gutter: def.config.column_gutter,
+                row_gutter: def.config.row_gutter,
+                row_height_multiple: def.config.row_height_multiple,
+                fill_row_gaps: def.config.fill_row_gaps,
+                zero_size_policy: match def.config.zero_size_policy {
+                    ZeroSizePolicyDef::Place => ZeroSizePolicy::Place,
+                    ZeroSizePolicyDef::Reject => ZeroSizePolicy::Reject,
+                    ZeroSizePolicyDef::Skip => ZeroSizePolicy::Skip,
+                },
+                deduplicate_sizes: def.config.deduplicate_sizes,
+                objective: def.config.objective.map(|o| ObjectiveWeights {
+                    height: o.height,
+                    squareness: o.squareness,
+                    perimeter: o.perimeter,
+                    area: o.area,
+                }),
+                memory_budget: def.config.memory_budget,
+                overflow: match def.config.overflow {
+                    OverflowPolicyDef::Error => OverflowPolicy::Error,
+                    OverflowPolicyDef::NewPage => OverflowPolicy::NewPage,
+                    OverflowPolicyDef::Partial => OverflowPolicy::Partial,
+                    OverflowPolicyDef::Grow => OverflowPolicy::Grow,
+                },
+                max_pages: def.config.max_pages,
+                page_fill: match def.config.page_fill {
+                    PageFillDef::Sequential => PageFill::Sequential,
+                    PageFillDef::Balanced => PageFill::Balanced,
+                    PageFillDef::FirstFit => PageFill::FirstFit,
+                    PageFillDef::BestFit => PageFill::BestFit,
+                    PageFillDef::WorstFit => PageFill::WorstFit,
+                },
+                split_oversized: def.config.split_oversized,
+            },
+        })
+    }
+
+    fn to_def(&self) -> ProblemDef {
+        ProblemDef {
+            version: VERSION,
+            config: ConfigDef {
+                max_size: self.config.max_size.map(|s| SizeDef { width: s.width, height: s.height }),
+                rectangle_padding: self.config.rectangle_padding,
+                owned_padding: self.config.owned_padding,
+                border_padding: self.config.border_padding,
+                column_gutter: self.config.column_gutter,
+                row_gutter: self.config.row_gutter,
+                row_height_multiple: self.config.row_height_multiple,
+                fill_row_gaps: self.config.fill_row_gaps,
+                zero_size_policy: match self.config.zero_size_policy {
+                    ZeroSizePolicy::Place => ZeroSizePolicyDef::Place,
+                    ZeroSizePolicy::Reject => ZeroSizePolicyDef::Reject,
+                    ZeroSizePolicy::Skip => ZeroSizePolicyDef::Skip,
+                },
+                deduplicate_sizes: self.config.deduplicate_sizes,
+                objective: self.config.objective.map(|o| ObjectiveDef {
+                    height: o.height,
+                    squareness: o.squareness,
+                    perimeter: o.perimeter,
+                    area: o.area,
+                }),
+                memory_budget: self.config.memory_budget,
+                overflow: match self.config.overflow {
+                    OverflowPolicy::Error => OverflowPolicyDef::Error,
+                    OverflowPolicy::NewPage => OverflowPolicyDef::NewPage,
+                    OverflowPolicy::Partial => OverflowPolicyDef::Partial,
+                    OverflowPolicy::Grow => OverflowPolicyDef::Grow,
+                },
+                max_pages: self.config.max_pages,
+                page_fill: match self.config.page_fill {
+                    PageFill::Sequential => PageFillDef::Sequential,
+                    PageFill::Balanced => PageFillDef::Balanced,
+                    PageFill::FirstFit => PageFillDef::FirstFit,
+                    PageFill::BestFit => PageFillDef::BestFit,
+                    PageFill::WorstFit => PageFillDef::WorstFit,
+                },
+                split_oversized: self.config.split_oversized,
+            },
+            sizes: self.sizes.iter().map(|s| SizeDef { width: s.width, height: s.height }).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem() -> PackingProblem {
+        let config = RectanglePackerConfig {
+            max_size: Some(Size::new(64, 32)),
+            rectangle_padding: 1,
+            owned_padding: 1,
+            border_padding: 2,
+            column_gutter: 3,
+            row_gutter: 1,
+            row_height_multiple: Some(8),
+            fill_row_gaps: true,
+            zero_size_policy: ZeroSizePolicy::Skip,
+            deduplicate_sizes: true,
+            objective: Some(ObjectiveWeights { height: 1.0, squareness: 0.5, perimeter: 0.0, area: 0.25 }),
+            memory_budget: Some(1 << 20),
+            overflow: OverflowPolicy::NewPage,
+            max_pages: Some(4),
+            page_fill: PageFill::Balanced,
+            split_oversized: true,
+        };
+        PackingProblem::new(&[Size::new(16, 8), Size::new(4, 4)], &config)
+    }
+
+    fn assert_same(a: &PackingProblem, b: &PackingProblem) {
+        assert_eq!(a.sizes, b.sizes);
+        assert_eq!(a.config.max_size, b.config.max_size);
+        assert_eq!(a.config.rectangle_padding, b.config.rectangle_padding);
+        assert_eq!(a.config.owned_padding, b.config.owned_padding);
+        assert_eq!(a.config.border_padding, b.config.border_padding);
+        assert_eq!(a.config.column_gutter, b.config.column_gutter);
+        assert_eq!(a.config.row_gutter, b.config.row_gutter);
+        assert_eq!(a.config.row_height_multiple, b.config.row_height_multiple);
+        assert_eq!(a.config.fill_row_gaps, b.config.fill_row_gaps);
+        assert_eq!(a.config.zero_size_policy, b.config.zero_size_policy);
+        assert_eq!(a.config.deduplicate_sizes, b.config.deduplicate_sizes);
+        assert_eq!(a.config.objective, b.config.objective);
+        assert_eq!(a.config.memory_budget, b.config.memory_budget);
+        assert_eq!(a.config.overflow, b.config.overflow);
+        assert_eq!(a.config.max_pages, b.config.max_pages);
+        assert_eq!(a.config.page_fill, b.config.page_fill);
+        assert_eq!(a.config.split_oversized, b.config.split_oversized);
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let problem = problem();
+
+        assert_same(&PackingProblem::from_json(&problem.to_json()).unwrap(), &problem);
+    }
+
+    #[test]
+    fn toml_round_trips() {
+        let problem = problem();
+        let toml = problem.to_toml();
+
+        assert!(toml.contains("[[sizes]]"));
+        assert_same(&PackingProblem::from_toml(&toml).unwrap(), &problem);
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let error = PackingProblem::from_json(r#"{ "version": 2, "sizes": [] }"#).unwrap_err();
+
+        assert!(matches!(error, ProblemError::UnsupportedVersion(2)));
+    }
+
+    #[test]
+    fn save_and_load_work() {
+        let problem = problem();
+        let path = std::env::temp_dir().join(format!("rpack-problem-{}.toml", std::process::id()));
+
+        problem.save(&path).unwrap();
+        let loaded = PackingProblem::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_same(&loaded.unwrap(), &problem);
+        assert!(matches!(problem.save("problem.yaml"), Err(ProblemError::UnknownFormat)));
+    }
+}
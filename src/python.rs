@@ -0,0 +1,61 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use crate::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+
+/// A packed rectangle as seen from Python: `(x, y, width, height)`.
+type PyRectangle = (usize, usize, usize, usize);
+
+/// Packs rectangles with the `HeightRectPacker`.
+///
+/// Called from Python as `rpack.pack(sizes, rectangle_padding=0, border_padding=0, max_size=None)`,
+/// where `sizes` and `max_size` are `(width, height)` tuples and `rectangle_padding` is the margin around each rectangle.
+/// Returns the size of the container and the packed rectangles as `(x, y, width, height)` tuples,
+/// in the same order as the sizes. Raises a `ValueError` if the rectangles can not be packed.
+#[pyfunction]
+#[pyo3(signature = (sizes, rectangle_padding = 0, border_padding = 0, max_size = None))]
+fn pack(
+    sizes: Vec<(usize, usize)>,
+    rectangle_padding: usize,
+    border_padding: usize,
+    max_size: Option<(usize, usize)>,
+) -> PyResult<((usize, usize), Vec<PyRectangle>)> {
+    let sizes: Vec<Size> = sizes.iter().map(|&(width, height)| Size::new(width, height)).collect();
+    let config = RectanglePackerConfig {
+        max_size: max_size.map(|(width, height)| Size::new(width, height)),
+        rectangle_padding,
+        border_padding,
+        ..Default::default()
+    };
+
+    let result = HeightRectPacker::pack(&sizes, &config).map_err(|e| PyValueError::new_err(e.message))?;
+    Ok((
+        (result.size.width, result.size.height),
+        result.rectangles.iter().map(|r| (r.x, r.y, r.width, r.height)).collect(),
+    ))
+}
+
+/// The `rpack` Python module.
+#[pymodule]
+fn rpack(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(pack, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_works() {
+        let (size, rectangles) = pack(vec![(10, 20), (30, 5)], 1, 1, None).unwrap();
+
+        assert_eq!((rectangles[0].2, rectangles[0].3), (10, 20));
+        assert_eq!((rectangles[1].2, rectangles[1].3), (30, 5));
+        assert!(size.0 >= 34 && size.1 >= 24);
+    }
+
+    #[test]
+    fn pack_fails_if_rectangles_do_not_fit() {
+        assert!(pack(vec![(10, 20)], 0, 0, Some((5, 5))).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Size, Area};
+use crate::{Size, Area, UvRect};
 
 /// A rectangle in a 2D space.
 #[derive(Debug, Clone, Copy)]
@@ -111,6 +111,34 @@ impl Rectangle {
     pub fn to_size(&self) -> Size {
         Size::new(self.width, self.height)
     }
+
+    /// Returns the rectangle grown by `padding` on each side, clamped at the origin.
+    pub(crate) fn expanded(&self, padding: usize) -> Rectangle {
+        let x = self.x.saturating_sub(padding);
+        let y = self.y.saturating_sub(padding);
+        Rectangle::new(x, y, self.x + self.width + padding - x, self.y + self.height + padding - y)
+    }
+
+    /// Returns the normalized texture coordinates of the rectangle within a container of the given size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rpack::{Rectangle, Size};
+    /// 
+    /// let uv = Rectangle::new(16, 0, 16, 32).uv_rect(&Size::new(64, 32));
+    /// assert_eq!(uv.min, [0.25, 0.0]);
+    /// assert_eq!(uv.max, [0.5, 1.0]);
+    /// ```
+    pub fn uv_rect(&self, container: &Size) -> UvRect {
+        UvRect::new(self, container)
+    }
+
+    /// Returns the normalized texture coordinates of the rectangle within a container of the given size,
+    /// with every side moved inwards by `inset` texels. See [`UvRect::with_inset`].
+    pub fn uv_rect_with_inset(&self, container: &Size, inset: f32) -> UvRect {
+        UvRect::with_inset(self, container, inset)
+    }
 }
 
 impl Area for Rectangle {
@@ -1,34 +1,217 @@
 use std::error::Error;
+use std::cmp::max;
 use std::fmt;
-use crate::{Size, Area, Rectangle};
+use crate::{Size, Area, Rectangle, UvRect, PackingObserver, ConfigError};
 
 pub struct RectanglePackingResult {
-    /// The list of rectangles that were packed.
+    /// The list of rectangles that were packed, in the same order as the sizes they were packed from.
     pub rectangles: Vec<Rectangle>,
     
     /// The size of the packed rectangle.
     pub size: Size,
+
+    /// The indices of the sizes that were not packed, in ascending order.
+    ///
+    /// Only sizes with a width or height of zero are skipped, if the config uses `ZeroSizePolicy::Skip`.
+    /// Their rectangles are empty rectangles at the origin.
+    pub skipped: Vec<usize>,
 }
 
 impl RectanglePackingResult {
     /// Returns the packing ratio of the result.
     /// 
     /// The packing ratio is the ratio of the total area of the packed rectangles to the total area of the container rectangle.
+    /// A container without any area has a packing ratio of `0.0`.
     pub fn packing_ratio(&self) -> f64 {
         let total_area = self.size.area();
+        if total_area == 0 {
+            return 0.0;
+        }
         let total_rect_area: usize = self.rectangles.iter().map(|r| r.area()).sum();
         total_rect_area as f64 / total_area as f64
     }
+
+    /// Returns the packing ratio of the result, counting padding as used space.
+    ///
+    /// In addition to the area of the packed rectangles, the padding around each rectangle
+    /// and the border padding of the container are counted as used area.
+    /// This reflects how much of an atlas is actually available for further rectangles.
+    /// A container without any area has a padded packing ratio of `0.0`.
+    ///
+    /// # Arguments
+    /// * `config` - The config the result was packed with.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{Rectangle, RectanglePackingResult, RectanglePackerConfig, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(2, 2, 4, 4)],
+    ///     size: Size::new(8, 8),
+    ///     skipped: Vec::new(),
+    /// };
+    /// let config = RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() };
+    ///
+    /// assert_eq!(result.packing_ratio(), 0.25);
+    /// assert_eq!(result.padded_packing_ratio(&config), 1.0);
+    /// ```
+    pub fn padded_packing_ratio(&self, config: &RectanglePackerConfig) -> f64 {
+        let total_area = self.size.area();
+        if total_area == 0 {
+            return 0.0;
+        }
+        let padding = 2 * config.rectangle_padding;
+        let padded_rect_area: usize = self.rectangles
+            .iter()
+            .map(|r| (r.width + padding) * (r.height + padding))
+            .sum();
+        let inner_area = self.size.width.saturating_sub(2 * config.border_padding)
+            * self.size.height.saturating_sub(2 * config.border_padding);
+        let border_area = total_area - inner_area;
+        (padded_rect_area + border_area) as f64 / total_area as f64
+    }
+
+    /// Returns the ratio of a theoretical lower bound of the container area to the actual container area.
+    ///
+    /// The lower bound keeps the width of the container and assumes the padded rectangles
+    /// could be packed without any gaps, while still requiring the container to be at least as
+    /// high as the highest padded rectangle.
+    /// A value of `1.0` means that no packing of these rectangles into a container of the same
+    /// width can be lower, while smaller values show how much room for improvement is left.
+    /// A container without any area has a normalized packing ratio of `1.0`.
+    ///
+    /// # Arguments
+    /// * `config` - The config the result was packed with.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{Rectangle, RectanglePackingResult, RectanglePackerConfig, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 4, 4), Rectangle::new(0, 4, 4, 4)],
+    ///     size: Size::new(8, 8),
+    ///     skipped: Vec::new(),
+    /// };
+    ///
+    /// // Both rectangles would fit next to each other in a container of height 4.
+    /// assert_eq!(result.normalized_packing_ratio(&Default::default()), 0.5);
+    /// ```
+    pub fn normalized_packing_ratio(&self, config: &RectanglePackerConfig) -> f64 {
+        if self.size.area() == 0 {
+            return 1.0;
+        }
+        let padding = 2 * config.rectangle_padding;
+        let inner_width = self.size.width.saturating_sub(2 * config.border_padding);
+        let padded_rect_area: usize = self.rectangles
+            .iter()
+            .map(|r| (r.width + padding) * (r.height + padding))
+            .sum();
+        let max_height = self.rectangles.iter().map(|r| r.height + padding).max().unwrap_or(0);
+
+        let lower_bound_height = match inner_width {
+            0 => max_height,
+            _ => max(padded_rect_area.div_ceil(inner_width), max_height),
+        } + 2 * config.border_padding;
+
+        (self.size.width * lower_bound_height) as f64 / self.size.area() as f64
+    }
+
+    /// Returns the normalized texture coordinates of every packed rectangle within the container,
+    /// or `None` for the rectangles skipped by the zero size policy.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{Rectangle, RectanglePackingResult, Size};
+    ///
+    /// let result = RectanglePackingResult {
+    ///     rectangles: vec![Rectangle::new(0, 0, 2, 4), Rectangle::new(2, 0, 2, 2)],
+    ///     size: Size::new(4, 4),
+    ///     skipped: Vec::new(),
+    /// };
+    ///
+    /// let uvs = result.uv_rects();
+    /// let uv = uvs[1].unwrap();
+    /// assert_eq!(uv.min, [0.5, 0.0]);
+    /// assert_eq!(uv.max, [1.0, 0.5]);
+    /// ```
+    pub fn uv_rects(&self) -> Vec<Option<UvRect>> {
+        self.uv_rects_with_inset(0.0)
+    }
+
+    /// Returns the normalized texture coordinates of every packed rectangle within the container,
+    /// with every side moved inwards by `inset` texels, e.g. [`UvRect::HALF_TEXEL`]. See [`UvRect::with_inset`].
+    /// The rectangles skipped by the zero size policy have no texture coordinates.
+    pub fn uv_rects_with_inset(&self, inset: f32) -> Vec<Option<UvRect>> {
+        self.rectangles
+            .iter()
+            .enumerate()
+            .map(|(index, r)| match self.skipped.binary_search(&index) {
+                Ok(_) => None,
+                Err(_) => Some(r.uv_rect_with_inset(&self.size, inset)),
+            })
+            .collect()
+    }
+}
+
+/// An item packed by `RectanglePacker::pack_items`: its data and the rectangle it was placed in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackedItem<T> {
+    /// The rectangle the item was placed in.
+    pub rectangle: Rectangle,
+
+    /// The data of the item.
+    pub data: T,
+}
+
+/// The result of `RectanglePacker::pack_items`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackedItems<T> {
+    /// The packed items, in the order of the input.
+    pub items: Vec<PackedItem<T>>,
+
+    /// The size of the packed rectangle.
+    pub size: Size,
+
+    /// The indices of the items that were not packed, in ascending order. See `RectanglePackingResult::skipped`.
+    pub skipped: Vec<usize>,
 }
 
 pub struct RectanglePackingError {
     /// The error message.
     pub message: String,
 
+    /// The reason why the config can not be packed with, if the packing failed because of the config.
+    pub config_error: Option<ConfigError>,
+
     /// The result of the packing operation until the error occurred.
     pub result: RectanglePackingResult,
 }
 
+impl RectanglePackingError {
+    /// The message of the error of a packing run stopped by `PackingObserver::is_cancelled`.
+    pub const CANCELLED: &'static str = "Packing was cancelled";
+
+    /// Returns the error of a packing run stopped by `PackingObserver::is_cancelled`, with an empty result.
+    pub fn cancelled() -> Self {
+        RectanglePackingError {
+            message: Self::CANCELLED.to_string(),
+            config_error: None,
+            result: RectanglePackingResult { rectangles: Vec::new(), size: Size::new(0, 0), skipped: Vec::new() },
+        }
+    }
+}
+
+impl From<ConfigError> for RectanglePackingError {
+    /// Returns the error for a config the sizes can not be packed with, with an empty result.
+    fn from(error: ConfigError) -> Self {
+        RectanglePackingError {
+            message: error.to_string(),
+            config_error: Some(error),
+            result: RectanglePackingResult { rectangles: Vec::new(), size: Size::new(0, 0), skipped: Vec::new() },
+        }
+    }
+}
+
 impl fmt::Display for RectanglePackingError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.message)
@@ -39,6 +222,7 @@ impl fmt::Debug for RectanglePackingError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("RectanglePackingError")
             .field("message", &self.message)
+            .field("config_error", &self.config_error)
             .field("result", &self.result)
             .finish()
     }
@@ -49,12 +233,161 @@ impl fmt::Debug for RectanglePackingResult {
         f.debug_struct("RectanglePackingResult")
             .field("rectangles", &self.rectangles)
             .field("size", &self.size)
+            .field("skipped", &self.skipped)
             .finish()
     }
 }
 
-impl Error for RectanglePackingError {}
+impl Error for RectanglePackingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.config_error.as_ref().map(|error| error as &(dyn Error + 'static))
+    }
+}
+
+/// How packers treat sizes with a width or height of zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ZeroSizePolicy {
+    /// Zero sizes are placed like any other size, as rectangles without area.
+    /// They can serve as markers, but do not cover any pixels, e.g. when computing texture coordinates.
+    #[default]
+    Place,
+
+    /// Packing fails with an error if any size has a width or height of zero.
+    Reject,
+
+    /// Zero sizes are not placed, and their indices are listed in `RectanglePackingResult::skipped`.
+    Skip,
+}
+
+/// What `pack_pages` does if the rectangles do not fit into the max size of the config.
+///
+/// Without a max size, all rectangles always fit into a single container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Packing fails with an error, like the packers do.
+    #[default]
+    Error,
+
+    /// The rectangles that do not fit are packed into further pages of the max size.
+    /// Packing fails if a single rectangle does not fit into an empty page.
+    NewPage,
+
+    /// The rectangles that fit into a single page are packed, and the others are returned as leftovers.
+    Partial,
+
+    /// The container is grown until all rectangles fit, by repeatedly doubling its shorter side, or its width if both are equal.
+    Grow,
+}
+
+/// How `pack_pages` distributes the rectangles over several pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PageFill {
+    /// Every page is filled with as many rectangles as fit before the next page is opened,
+    /// which leaves the last page mostly empty.
+    #[default]
+    Sequential,
+
+    /// The rectangles are distributed so that all pages are about equally full, e.g. for streaming textures
+    /// of similar memory cost. Balancing never needs fewer pages than `Sequential`, and may need more.
+    Balanced,
+
+    /// Every rectangle, from the largest to the smallest, is added to the first open page it fits into.
+    FirstFit,
+
+    /// Every rectangle, from the largest to the smallest, is added to the fullest open page it fits into,
+    /// which tends to need the fewest pages.
+    BestFit,
+
+    /// Every rectangle, from the largest to the smallest, is added to the emptiest open page it fits into,
+    /// which spreads the rectangles over the open pages.
+    WorstFit,
+}
+
+/// Weights of the objectives a packer minimizes when choosing the size of the container.
+///
+/// Each objective is measured relative to a square container holding the area of all padded rectangles,
+/// so the weights of different objectives are comparable:
+/// - `height`: The height of the container, e.g. to pack into strips of a fixed width.
+/// - `squareness`: How far the aspect ratio of the container is from a square, as the longer side
+///   divided by the shorter side minus one, e.g. for textures that are sampled along both axes.
+/// - `perimeter`: The sum of the width and the height of the container.
+/// - `area`: The area of the container, which includes the space left empty by the packer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObjectiveWeights {
+    /// The weight of the height of the container.
+    pub height: f64,
+
+    /// The weight of the deviation of the container from a square.
+    pub squareness: f64,
 
+    /// The weight of the perimeter of the container.
+    pub perimeter: f64,
+
+    /// The weight of the area of the container.
+    pub area: f64,
+}
+
+impl ObjectiveWeights {
+    /// Weights of zero for all objectives, to set only some of them with the struct update syntax.
+    pub const NONE: ObjectiveWeights = ObjectiveWeights { height: 0.0, squareness: 0.0, perimeter: 0.0, area: 0.0 };
+
+    /// Returns the weighted cost of a container of the given size. Lower costs are better.
+    ///
+    /// # Arguments
+    /// * `size` - The size of the container.
+    /// * `area` - The area of all padded rectangles, which the objectives are measured relative to.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{ObjectiveWeights, Size};
+    ///
+    /// let square = ObjectiveWeights { squareness: 1.0, ..ObjectiveWeights::NONE };
+    /// assert!(square.cost(Size::new(8, 8), 64) < square.cost(Size::new(16, 4), 64));
+    /// ```
+    pub fn cost(&self, size: Size, area: usize) -> f64 {
+        let side = (area.max(1) as f64).sqrt();
+        let (long, short) = (size.width.max(size.height) as f64, size.width.min(size.height).max(1) as f64);
+        self.height * size.height as f64 / side
+            + self.squareness * (long / short - 1.0)
+            + self.perimeter * (size.width + size.height) as f64 / (2.0 * side)
+            + self.area * (size.width * size.height) as f64 / (side * side)
+    }
+}
+
+impl Default for ObjectiveWeights {
+    /// Weights minimizing the perimeter of the container only, which favors containers that are both small and square.
+    fn default() -> Self {
+        ObjectiveWeights { perimeter: 1.0, ..ObjectiveWeights::NONE }
+    }
+}
+
+impl ZeroSizePolicy {
+    /// Returns the indices of the sizes with a width or height of zero that are not packed under this policy,
+    /// or an error if the policy rejects them.
+    ///
+    /// Packers call this before packing, and leave the skipped sizes out.
+    pub fn skipped(&self, sizes: &[Size]) -> Result<Vec<usize>, RectanglePackingError> {
+        let mut zero = (0..sizes.len()).filter(|&i| sizes[i].width == 0 || sizes[i].height == 0);
+        match self {
+            ZeroSizePolicy::Place => Ok(Vec::new()),
+            ZeroSizePolicy::Skip => Ok(zero.collect()),
+            ZeroSizePolicy::Reject => match zero.next() {
+                None => Ok(Vec::new()),
+                Some(index) => Err(RectanglePackingError {
+                    message: format!("Rectangle {} has a size of zero: {:?}", index, sizes[index]),
+                    config_error: None,
+                    result: RectanglePackingResult {
+                        rectangles: Vec::new(),
+                        size: Size::new(0, 0),
+                        skipped: Vec::new(),
+                    },
+                }),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct RectanglePackerConfig {
     /// The maximum size of the container rectangle.
     /// 
@@ -62,18 +395,109 @@ pub struct RectanglePackerConfig {
     /// If `Some`, the container will have the given dimensions, and an error will be returned if the rectangles cannot be packed within those dimensions.
     pub max_size: Option<Size>,
 
-    /// The amount of padding to add around each rectangle.
+    /// The margin to leave around each rectangle.
+    ///
+    /// Despite its name, `rectangle_padding` is this margin and not padding of the rectangles.
+    /// The margin is a pure gap on every side of a rectangle that is not part of it, so neighbouring rectangles
+    /// are at least twice the margin apart. Use `owned_padding` for space that belongs to the rectangles.
     pub rectangle_padding: usize,
 
+    /// The padding owned by each rectangle, added to its size on every side.
+    ///
+    /// Unlike the margin of `rectangle_padding`, the padding is part of the rectangles reported by the packers,
+    /// e.g. for sprites whose edges are extruded or that are sampled with a border. Sizes with a width or height
+    /// of zero are not padded, see `RectanglePackerConfig::padded_size`.
+    pub owned_padding: usize,
+
     /// The amount of padding to add around the container rectangle.
     pub border_padding: usize,
+
+    /// The space added between neighbouring columns of rectangles on top of their margins,
+    /// e.g. for print layouts that are cut apart along the columns.
+    ///
+    /// Only the shelf packers, such as `HeightRectPacker`, place rectangles in columns and rows.
+    /// Other packers ignore the gutters.
+    pub column_gutter: usize,
+
+    /// The space added between neighbouring rows of rectangles on top of their margins, see `column_gutter`.
+    pub row_gutter: usize,
+
+    /// The multiple the height of every row of the `HeightRectPacker` is rounded up to, so that the rows
+    /// align to a grid, e.g. for tile-based renderers or to insert items of a fixed height into the rows later.
+    ///
+    /// Only the heights of the rows are rounded, not their margins or gutters. If `None`, every row is
+    /// as high as its highest rectangle.
+    pub row_height_multiple: Option<usize>,
+
+    /// Whether the `HeightRectPacker` fills the space left below the shorter rectangles of every row
+    /// with later, smaller rectangles, instead of leaving it empty.
+    ///
+    /// This packs noticeably denser when the heights of the rectangles vary, at the cost of
+    /// rectangles no longer being placed in the order of their heights.
+    pub fill_row_gaps: bool,
+
+    /// How sizes with a width or height of zero are treated.
+    pub zero_size_policy: ZeroSizePolicy,
+
+    /// Whether identical sizes are packed only once.
+    ///
+    /// If `true`, all sizes with the same width and height share the same rectangle in the result,
+    /// which saves room in atlases with many repeated tiles. `RectanglePackingResult::validate` then
+    /// allows identical rectangles to overlap.
+    pub deduplicate_sizes: bool,
+
+    /// How packers trade off the dimensions of the container when choosing its size, if there is no max size.
+    ///
+    /// If `None`, every packer uses its own heuristic. `HeightRectPacker` then minimizes the area of the container,
+    /// preferring square containers among those of nearly the same area.
+    pub objective: Option<ObjectiveWeights>,
+
+    /// The maximum number of bytes of temporary memory a packer may use besides its result,
+    /// e.g. to bound the memory of untrusted inputs in a service packing for many clients.
+    ///
+    /// Packers fall back to simpler strategies that need less memory where they can,
+    /// and fail with a `ConfigError::MemoryBudgetExceeded` in `RectanglePackingError::config_error` otherwise.
+    /// If `None`, the memory is not limited.
+    pub memory_budget: Option<usize>,
+
+    /// What happens if the rectangles do not fit into the max size, see `OverflowPolicy`.
+    ///
+    /// The packers always fail if the rectangles do not fit into a single container, and `pack_pages` applies the policy.
+    pub overflow: OverflowPolicy,
+
+    /// The maximum number of pages `pack_pages` may open with `OverflowPolicy::NewPage`, e.g. the number of
+    /// textures an engine can bind at once. If more pages are needed, `pack_pages` fails with `PagingError::TooManyPages`.
+    ///
+    /// If `None`, the number of pages is not limited.
+    pub max_pages: Option<usize>,
+
+    /// How `pack_pages` distributes the rectangles over the pages with `OverflowPolicy::NewPage`, see `PageFill`.
+    pub page_fill: PageFill,
+
+    /// Whether `pack_pages` splits sizes that do not fit into an empty page into tiles placed separately,
+    /// instead of failing with `OverflowPolicy::NewPage`, e.g. for giant backgrounds drawn by tiled renderers.
+    /// The tiles are reported as `TilePlacement`s of the split size.
+    pub split_oversized: bool,
 }
 
 impl Default for RectanglePackerConfig {
     /// A default `RectanglePackerConfig` with the following values:
     /// - `max_size`: `None`
     /// - `rectangle_padding`: `0`
+    /// - `owned_padding`: `0`
     /// - `border_padding`: `0`
+    /// - `column_gutter`: `0`
+    /// - `row_gutter`: `0`
+    /// - `row_height_multiple`: `None`
+    /// - `fill_row_gaps`: `false`
+    /// - `zero_size_policy`: `ZeroSizePolicy::Place`
+    /// - `deduplicate_sizes`: `false`
+    /// - `objective`: `None`
+    /// - `memory_budget`: `None`
+    /// - `overflow`: `OverflowPolicy::Error`
+    /// - `max_pages`: `None`
+    /// - `page_fill`: `PageFill::Sequential`
+    /// - `split_oversized`: `false`
     /// 
     /// # Returns
     /// A default `RectanglePackerConfig`.
@@ -81,9 +505,85 @@ impl Default for RectanglePackerConfig {
         RectanglePackerConfig {
             max_size: None,
             rectangle_padding: 0,
+            owned_padding: 0,
             border_padding: 0,
+            column_gutter: 0,
+            row_gutter: 0,
+            row_height_multiple: None,
+            fill_row_gaps: false,
+            zero_size_policy: ZeroSizePolicy::Place,
+            deduplicate_sizes: false,
+            objective: None,
+            memory_budget: None,
+            overflow: OverflowPolicy::Error,
+            max_pages: None,
+            page_fill: PageFill::Sequential,
+            split_oversized: false,
+        }
+    }
+}
+
+impl RectanglePackerConfig {
+    /// Returns the size of the rectangle packed for a size, which includes the `owned_padding` on every side.
+    ///
+    /// Sizes with a width or height of zero are returned unchanged, so that the `zero_size_policy` applies to them.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{RectanglePackerConfig, Size};
+    ///
+    /// let config = RectanglePackerConfig { owned_padding: 2, ..Default::default() };
+    ///
+    /// assert_eq!(config.padded_size(Size::new(8, 4)), Size::new(12, 8));
+    /// assert_eq!(config.padded_size(Size::new(0, 4)), Size::new(0, 4));
+    /// ```
+    pub fn padded_size(&self, size: Size) -> Size {
+        match size.width == 0 || size.height == 0 {
+            true => size,
+            false => Size::new(size.width + 2 * self.owned_padding, size.height + 2 * self.owned_padding),
         }
     }
+
+    /// Returns the area the packed rectangle of a size takes up, including its padding on both sides.
+    pub(crate) fn padded_area(&self, size: Size) -> usize {
+        let size = self.padded_size(size);
+        let padding = 2 * self.rectangle_padding;
+        (size.width + padding) * (size.height + padding)
+    }
+
+    /// Returns the sizes of the rectangles packed for the sizes, see `padded_size`.
+    pub fn padded_sizes(&self, sizes: &[Size]) -> Vec<Size> {
+        sizes.iter().map(|&size| self.padded_size(size)).collect()
+    }
+}
+
+/// Buffers a packer can reuse between calls of `RectanglePacker::pack_with_scratch`.
+///
+/// Packing many small inputs in a loop allocates and frees the same temporary buffers over and over.
+/// Passing the same `PackingScratch` to every call lets the packers keep them instead.
+///
+/// # Examples
+/// ```
+/// use rpack::{HeightRectPacker, PackingScratch, RectanglePacker, RectanglePackerConfig, Size};
+///
+/// let mut scratch = PackingScratch::new();
+/// for i in 1..10 {
+///     let sizes = vec![Size::new(i, 2 * i); 10];
+///     let result = HeightRectPacker::pack_with_scratch(&sizes, &RectanglePackerConfig::default(), &mut scratch).unwrap();
+///     assert_eq!(result.rectangles.len(), 10);
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct PackingScratch {
+    /// The order in which the sizes are placed.
+    pub(crate) order: Vec<usize>,
+}
+
+impl PackingScratch {
+    /// Creates empty buffers, which grow as needed.
+    pub fn new() -> PackingScratch {
+        PackingScratch::default()
+    }
 }
 
 /// A trait for packing a list of rectangle sizes into a single container rectangle.
@@ -93,6 +593,10 @@ pub trait RectanglePacker {
     /// This method attempts to optimally arrange a list of rectangle sizes within a container rectangle
     /// such that the overall area or dimensions of the container are minimized, depending on the packing algorithm used.
     ///
+    /// An empty list of sizes is packed into the max size, or, without a max size, into a container
+    /// consisting only of the border padding.
+    /// Packing fails if the config is not valid, see `RectanglePackerConfig::validate`.
+    ///
     /// # Arguments
     /// * `elements` - A list of `Size` structs representing the dimensions of the rectangles to be packed.
     /// * `max_size` - An optional `Size` representing the maximum dimensions of the container rectangle.
@@ -103,7 +607,93 @@ pub trait RectanglePacker {
     /// A `Result` containing either:
     ///   - A `RectanglePackingResult` with the list of packed `Rectangle`s and the dimensions of the container rectangle.
     ///   - A `RectanglePackingError` if the packing algorithm encounters an error or the provided `max_size` is insufficient to pack all the rectangles.
-    fn pack(sizes: &Vec<Size>, config: &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError>;
+    fn pack(sizes: &[Size], config: &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError>;
+
+    /// Packs a list of rectangle sizes like `pack`, reusing the buffers of `scratch` for temporary data.
+    ///
+    /// Packers that do not need temporary buffers simply call `pack`.
+    fn pack_with_scratch(
+        sizes: &[Size],
+        config: &RectanglePackerConfig,
+        scratch: &mut PackingScratch,
+    ) -> Result<RectanglePackingResult, RectanglePackingError> {
+        let _ = scratch;
+        Self::pack(sizes, config)
+    }
+
+    /// Packs a list of rectangle sizes like `pack`, notifying the observer of every event of the packing run.
+    ///
+    /// Packers that do not report events while packing replay them from the result: they open a single page
+    /// of the size of the result, place every packed size and reject every skipped one. If packing fails,
+    /// no events are replayed. They only check `PackingObserver::is_cancelled` before they start.
+    ///
+    /// # Arguments
+    /// * `sizes` - The sizes of the rectangles to pack.
+    /// * `config` - The configuration of the packer.
+    /// * `observer` - The observer notified of the events, e.g. a `PackingTrace`.
+    ///
+    /// # Returns
+    /// The same result as `pack`.
+    fn pack_observed(
+        sizes: &[Size],
+        config: &RectanglePackerConfig,
+        observer: &mut dyn PackingObserver,
+    ) -> Result<RectanglePackingResult, RectanglePackingError> {
+        if observer.is_cancelled() {
+            return Err(RectanglePackingError::cancelled());
+        }
+        let result = Self::pack(sizes, config)?;
+        observer.on_open_page(0, result.size);
+        for (index, rectangle) in result.rectangles.iter().enumerate() {
+            match result.skipped.binary_search(&index) {
+                Ok(_) => observer.on_reject(index, &sizes[index]),
+                Err(_) => observer.on_place(index, rectangle),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Packs items that carry arbitrary data of type `T`, moving the data of every item into its placement.
+    ///
+    /// This carries handles such as image buffers, asset ids or database keys through packing
+    /// without keeping a list parallel to the sizes.
+    ///
+    /// # Arguments
+    /// * `items` - The size and data of every item.
+    /// * `config` - The configuration of the packer.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - The `PackedItems` with the data and rectangle of every item, in the order of the input.
+    ///   - The `RectanglePackingError` together with the data of all items, in the order of the input, if packing failed.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+    ///
+    /// let items = vec![(Size::new(16, 16), "player.png"), (Size::new(8, 8), "coin.png")];
+    /// let packed = HeightRectPacker::pack_items(items, &RectanglePackerConfig::default()).unwrap();
+    ///
+    /// assert_eq!(packed.items[1].data, "coin.png");
+    /// assert_eq!(packed.items[1].rectangle.to_size(), Size::new(8, 8));
+    /// ```
+    // The error hands the data of the items back to the caller, which is why it is returned unboxed.
+    #[allow(clippy::result_large_err)]
+    fn pack_items<T>(items: Vec<(Size, T)>, config: &RectanglePackerConfig) -> Result<PackedItems<T>, (RectanglePackingError, Vec<T>)> {
+        let (sizes, data): (Vec<Size>, Vec<T>) = items.into_iter().unzip();
+        match Self::pack(&sizes, config) {
+            Ok(result) => Ok(PackedItems {
+                items: result.rectangles
+                    .into_iter()
+                    .zip(data)
+                    .map(|(rectangle, data)| PackedItem { rectangle, data })
+                    .collect(),
+                size: result.size,
+                skipped: result.skipped,
+            }),
+            Err(error) => Err((error, data)),
+        }
+    }
 
     /// Checks that all the sizes can fit in the max size.
     /// 
@@ -115,11 +705,11 @@ pub trait RectanglePacker {
     /// A `Result` containing either:
     ///   - `Ok(())` if all the sizes can fit in the max size.
     ///   - `Err(RectanglePackingError)` if any of the sizes are greater than the max size.
-    fn check_sizes(sizes: &Vec<Size>, config: &RectanglePackerConfig) -> Result<(), RectanglePackingError> {
+    fn check_sizes(sizes: &[Size], config: &RectanglePackerConfig) -> Result<(), RectanglePackingError> {
         let max_size = match config.max_size {
             Some(max_size) => Size {
-                width: max_size.width - 2 * (config.border_padding + config.rectangle_padding), 
-                height: max_size.height - 2 * (config.border_padding + config.rectangle_padding)
+                width: max_size.width.saturating_sub(2 * (config.border_padding + config.rectangle_padding)),
+                height: max_size.height.saturating_sub(2 * (config.border_padding + config.rectangle_padding))
             },
             None => return Ok(())
         };
@@ -128,9 +718,11 @@ pub trait RectanglePacker {
                size.height > max_size.height {
                 return Err(RectanglePackingError {
                     message: format!("Rectangle size {:?} is greater than max size {:?}", size, max_size),
+                    config_error: None,
                     result: RectanglePackingResult {
                         rectangles: Vec::new(),
                         size: Size::new(0, 0),
+                        skipped: Vec::new(),
                     },
                 });
             }
@@ -138,4 +730,70 @@ pub trait RectanglePacker {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result() -> RectanglePackingResult {
+        RectanglePackingResult {
+            rectangles: vec![Rectangle::new(2, 2, 4, 2), Rectangle::new(8, 2, 2, 2)],
+            size: Size::new(12, 8),
+            skipped: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn packing_ratio_works() {
+        assert_eq!(result().packing_ratio(), 12.0 / 96.0);
+    }
+
+    #[test]
+    fn padded_packing_ratio_works() {
+        let config = RectanglePackerConfig {
+            rectangle_padding: 1,
+            border_padding: 1,
+            ..Default::default()
+        };
+
+        // 24 + 16 for the padded rectangles, 96 - 60 for the border
+        assert_eq!(result().padded_packing_ratio(&config), 76.0 / 96.0);
+        assert_eq!(result().padded_packing_ratio(&Default::default()), result().packing_ratio());
+    }
+
+    #[test]
+    fn normalized_packing_ratio_works() {
+        let config = RectanglePackerConfig {
+            rectangle_padding: 1,
+            border_padding: 1,
+            ..Default::default()
+        };
+
+        // The padded rectangles are 4 high and cover 40 of an inner width of 10.
+        assert_eq!(result().normalized_packing_ratio(&config), (12.0 * 6.0) / 96.0);
+
+        // Without padding, the rectangles need an area of 12 in a row of width 12,
+        // but the container has to be at least as high as the highest rectangle.
+        assert_eq!(result().normalized_packing_ratio(&Default::default()), 24.0 / 96.0);
+    }
+
+    #[test]
+    fn pack_items_works() {
+        let items = vec![(Size::new(4, 4), vec![1]), (Size::new(2, 6), vec![2, 3])];
+        let config = RectanglePackerConfig::default();
+
+        let packed = crate::HeightRectPacker::pack_items(items, &config).unwrap();
+        let result = crate::HeightRectPacker::pack(&[Size::new(4, 4), Size::new(2, 6)], &config).unwrap();
+        assert_eq!(packed.size, result.size);
+        assert_eq!(packed.items, vec![
+            PackedItem { rectangle: result.rectangles[0], data: vec![1] },
+            PackedItem { rectangle: result.rectangles[1], data: vec![2, 3] },
+        ]);
+
+        let config = RectanglePackerConfig { max_size: Some(Size::new(4, 4)), ..Default::default() };
+        let (error, data) = crate::HeightRectPacker::pack_items(vec![(Size::new(8, 8), "a"), (Size::new(1, 1), "b")], &config).unwrap_err();
+        assert!(!error.message.is_empty());
+        assert_eq!(data, ["a", "b"]);
+    }
+}
@@ -0,0 +1,6 @@
+/// Returns the hue in degrees of the rectangle with the given index in the renderings of a packing.
+///
+/// The hues are spread with the golden angle, so that neighbours are easy to tell apart.
+pub(crate) fn rectangle_hue(index: usize) -> usize {
+    (index * 137) % 360
+}
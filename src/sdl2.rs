@@ -0,0 +1,102 @@
+use std::error::Error;
+use std::fmt;
+use sdl2::rect::{max_int_value, Rect};
+use crate::{AtlasLayout, Rectangle, RectanglePackingResult};
+
+/// The error returned when converting a rectangle that exceeds the range SDL supports into an SDL `Rect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SdlRectError {
+    /// The rectangle that could not be converted.
+    pub rectangle: Rectangle,
+}
+
+impl fmt::Display for SdlRectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The rectangle {:?} exceeds the range of an SDL rectangle", self.rectangle)
+    }
+}
+
+impl Error for SdlRectError {}
+
+impl TryFrom<Rectangle> for Rect {
+    type Error = SdlRectError;
+
+    /// Converts the rectangle into an SDL `Rect`, failing if the position or size exceeds the range SDL supports.
+    ///
+    /// SDL rectangles can not be empty, so a width or height of `0` becomes `1`.
+    fn try_from(r: Rectangle) -> Result<Self, Self::Error> {
+        // SDL clamps larger values, which would silently move or shrink the rectangle.
+        let max = max_int_value() as usize;
+        if r.x > max || r.y > max || r.width > max || r.height > max {
+            return Err(SdlRectError { rectangle: r });
+        }
+        Ok(Rect::new(r.x as i32, r.y as i32, r.width as u32, r.height as u32))
+    }
+}
+
+impl TryFrom<Rect> for Rectangle {
+    type Error = std::num::TryFromIntError;
+
+    /// Converts an SDL `Rect` into a rectangle, failing if it has a negative position.
+    fn try_from(r: Rect) -> Result<Self, Self::Error> {
+        Ok(Rectangle::new(
+            usize::try_from(r.x())?,
+            usize::try_from(r.y())?,
+            r.width() as usize,
+            r.height() as usize,
+        ))
+    }
+}
+
+impl RectanglePackingResult {
+    /// Returns the packed rectangles as SDL `Rect`s, e.g. to be used as source rectangles of `Canvas::copy`.
+    ///
+    /// Fails with the first rectangle that exceeds the range SDL supports.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+    ///
+    /// let sizes = vec![Size::new(10, 20), Size::new(30, 5)];
+    /// let result = HeightRectPacker::pack(&sizes, &RectanglePackerConfig::default()).unwrap();
+    ///
+    /// let rects = result.to_sdl_rects().unwrap();
+    /// assert_eq!(rects[1].width(), 30);
+    /// ```
+    pub fn to_sdl_rects(&self) -> Result<Vec<Rect>, SdlRectError> {
+        self.rectangles.iter().map(|&r| Rect::try_from(r)).collect()
+    }
+}
+
+impl AtlasLayout {
+    /// Returns the SDL `Rect` of the sprite with the given name, or `None` if there is no such sprite.
+    ///
+    /// Fails if the rectangle of the sprite exceeds the range SDL supports.
+    pub fn sdl_rect(&self, name: &str) -> Result<Option<Rect>, SdlRectError> {
+        self.get(name).map(|sprite| Rect::try_from(sprite.rectangle)).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions_work() {
+        let rectangle = Rectangle::new(1, 2, 3, 4);
+        let rect = Rect::try_from(rectangle).unwrap();
+
+        assert_eq!(rect, Rect::new(1, 2, 3, 4));
+        assert_eq!(Rectangle::try_from(rect), Ok(rectangle));
+        assert!(Rectangle::try_from(Rect::new(-1, 0, 3, 4)).is_err());
+    }
+
+    #[test]
+    fn rectangles_out_of_range_fail_to_convert() {
+        let too_far = Rectangle::new(max_int_value() as usize + 1, 0, 3, 4);
+        let too_wide = Rectangle::new(0, 0, max_int_value() as usize + 1, 4);
+
+        assert_eq!(Rect::try_from(too_far), Err(SdlRectError { rectangle: too_far }));
+        assert_eq!(Rect::try_from(too_wide), Err(SdlRectError { rectangle: too_wide }));
+    }
+}
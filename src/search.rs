@@ -0,0 +1,421 @@
+use crate::{ObjectiveWeights, RectanglePacker, RectanglePackerConfig, RectanglePackingError, RectanglePackingResult, Size};
+
+/// The shapes of containers `smallest_container` searches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerShape {
+    /// Square containers of any side length.
+    Square,
+
+    /// Containers whose width and height are powers of two, e.g. for textures on older GPUs.
+    PowerOfTwo,
+
+    /// Square containers whose side length is a power of two.
+    PowerOfTwoSquare,
+}
+
+/// Searches the smallest container of the given shape that the packer `P` fits all sizes into.
+///
+/// The packer serves as the oracle deciding whether the sizes fit into a container. Square containers are found
+/// by a binary search over the side length, starting from a lower bound given by the area of the padded sizes and
+/// the largest size. Containers with sides that are powers of two are tried by increasing area, and among those
+/// with the same area from the most to the least square one, with the wider one first.
+/// As packers are heuristics, a packer may fail for a container although it succeeds for a smaller one,
+/// so the result is the smallest container found, not necessarily the smallest one the packer could fill.
+///
+/// # Arguments
+/// * `sizes` - The sizes of the rectangles to pack.
+/// * `config` - The configuration of the packer. Its max size, if any, limits the containers searched.
+/// * `shape` - The shape of the containers to search.
+///
+/// # Returns
+/// A `Result` containing either:
+///   - The `RectanglePackingResult` of packing the sizes into the smallest container found.
+///   - A `RectanglePackingError` if the sizes do not fit into any container of the shape within the max size
+///     of the config.
+///
+/// # Examples
+/// ```
+/// use rpack::{smallest_container, ContainerShape, HeightRectPacker, RectanglePackerConfig, Size};
+///
+/// let sizes = vec![Size::new(16, 16); 5];
+/// let result = smallest_container::<HeightRectPacker>(&sizes, &RectanglePackerConfig::default(), ContainerShape::PowerOfTwo).unwrap();
+///
+/// assert_eq!(result.size, Size::new(64, 32));
+/// ```
+pub fn smallest_container<P: RectanglePacker>(
+    sizes: &[Size],
+    config: &RectanglePackerConfig,
+    shape: ContainerShape,
+) -> Result<RectanglePackingResult, RectanglePackingError> {
+    let padding = 2 * config.rectangle_padding;
+    let border = 2 * config.border_padding;
+    let padded = config.padded_sizes(sizes);
+
+    // Without a max size, a square as wide as all sizes side by side and as high as all sizes on top of each other
+    // fits all sizes with any sensible packer, including the gutters. It is rounded up to a power of two to fit all shapes.
+    let limit = config.max_size.unwrap_or_else(|| {
+        let gutter = config.column_gutter.max(config.row_gutter);
+        let side = padded.iter().map(|s| s.width.max(s.height) + padding + gutter).sum::<usize>() + border;
+        Size::new(side.next_power_of_two(), side.next_power_of_two())
+    });
+
+    // No container can be smaller than the padded area of all sizes, or narrower than the widest size.
+    let area: usize = padded.iter().map(|s| (s.width + padding) * (s.height + padding)).sum();
+    let min_side = square_root_ceil(area) + border;
+    let min_width = padded.iter().map(|s| s.width + padding).max().unwrap_or(0) + border;
+    let min_height = padded.iter().map(|s| s.height + padding).max().unwrap_or(0) + border;
+    let pack = |size: Size| P::pack(sizes, &with_max_size(config, size));
+
+    match shape {
+        ContainerShape::Square => {
+            let side_limit = limit.width.min(limit.height);
+            let mut low = min_side.max(min_width).max(min_height).max(1);
+            if low > side_limit {
+                return Err(no_container(shape, limit));
+            }
+
+            // Find a feasible side by doubling, then the smallest one by bisection.
+            let mut high = low;
+            let mut best = loop {
+                match pack(Size::new(high, high)) {
+                    Ok(result) => break result,
+                    Err(error) if high >= side_limit => return Err(error),
+                    Err(_) => {
+                        low = high + 1;
+                        high = (high * 2).min(side_limit);
+                    }
+                }
+            };
+            while low < high {
+                let side = low + (high - low) / 2;
+                match pack(Size::new(side, side)) {
+                    Ok(result) => {
+                        best = result;
+                        high = side;
+                    }
+                    Err(_) => low = side + 1,
+                }
+            }
+            Ok(best)
+        }
+        ContainerShape::PowerOfTwo | ContainerShape::PowerOfTwoSquare => {
+            let square = shape == ContainerShape::PowerOfTwoSquare;
+            let (max_a, max_b) = (exponent(limit.width, false), exponent(limit.height, false));
+            let (min_a, min_b) = (exponent(min_width, true), exponent(min_height, true));
+            let min_total = exponent(area.max(1), true);
+
+            let mut last_error = None;
+            for total in min_total.max(min_a + min_b)..=max_a + max_b {
+                // Try the splits of the area from the most to the least square one, wider containers first.
+                let mut splits: Vec<(u32, u32)> = (min_a..=max_a.min(total))
+                    .map(|a| (a, total - a))
+                    .filter(|&(a, b)| b >= min_b && b <= max_b && (!square || a == b))
+                    .collect();
+                splits.sort_by_key(|&(a, b)| (a.abs_diff(b), std::cmp::Reverse(a)));
+
+                for (a, b) in splits {
+                    match pack(Size::new(1 << a, 1 << b)) {
+                        Ok(result) => return Ok(result),
+                        Err(error) => last_error = Some(error),
+                    }
+                }
+            }
+
+            Err(last_error.unwrap_or_else(|| no_container(shape, limit)))
+        }
+    }
+}
+
+/// Packs the sizes into a strip, a container of unlimited height, of the width whose packing has the lowest cost
+/// by the given weights, e.g. `ObjectiveWeights { area: 1.0, ..ObjectiveWeights::NONE }` for the smallest area.
+///
+/// The widths from the widest padded size to all sizes side by side are swept, more finely around the side
+/// of a square holding all sizes, and the sizes are packed into each with the packer `P`.
+/// The container of each packing is cropped to the rectangles before its cost is measured,
+/// so the packer may fill the strip from the top left. Ties are broken by the smaller width.
+///
+/// # Arguments
+/// * `sizes` - The sizes of the rectangles to pack.
+/// * `config` - The configuration of the packer. Its max size, if any, limits the width and the height of the strip.
+/// * `weights` - The weights of the objectives to minimize.
+///
+/// # Returns
+/// A `Result` containing either:
+///   - The `RectanglePackingResult` of the packing with the lowest cost, with its container cropped.
+///   - A `RectanglePackingError` if the sizes do not fit into a strip of any of the widths.
+///
+/// # Examples
+/// ```
+/// use rpack::{best_strip_width, HeightRectPacker, ObjectiveWeights, RectanglePackerConfig, Size};
+///
+/// let sizes = vec![Size::new(8, 8), Size::new(8, 8), Size::new(16, 8)];
+/// let weights = ObjectiveWeights { area: 1.0, squareness: 0.1, ..ObjectiveWeights::NONE };
+/// let result = best_strip_width::<HeightRectPacker>(&sizes, &RectanglePackerConfig::default(), &weights).unwrap();
+///
+/// assert_eq!(result.size, Size::new(16, 16));
+/// ```
+pub fn best_strip_width<P: RectanglePacker>(
+    sizes: &[Size],
+    config: &RectanglePackerConfig,
+    weights: &ObjectiveWeights,
+) -> Result<RectanglePackingResult, RectanglePackingError> {
+    let area: usize = sizes.iter().map(|&size| config.padded_area(size)).sum();
+    let mut best: Option<(f64, RectanglePackingResult)> = None;
+    for result in strips::<P>(sizes, config)? {
+        let cost = weights.cost(result.size, area);
+        if best.as_ref().is_none_or(|(lowest, _)| cost < *lowest) {
+            best = Some((cost, result));
+        }
+    }
+    Ok(best.expect("strips returns at least one packing").1)
+}
+
+/// Returns the packings whose containers are not larger in both dimensions than the container of another packing,
+/// so the caller can choose among them by external constraints, e.g. the texture size limit of a GPU or a paper size.
+///
+/// The packings are found by sweeping the width of a strip like `best_strip_width`, with the containers cropped
+/// to the rectangles. Of the packings with containers of the same size, only the first one found is kept.
+///
+/// # Arguments
+/// * `sizes` - The sizes of the rectangles to pack.
+/// * `config` - The configuration of the packer. Its max size, if any, limits the containers.
+///
+/// # Returns
+/// A `Result` containing either:
+///   - The `RectanglePackingResult`s on the front, by increasing width and thus decreasing height.
+///   - A `RectanglePackingError` if the sizes do not fit into a strip of any of the widths.
+///
+/// # Examples
+/// ```
+/// use rpack::{pareto_front, HeightRectPacker, RectanglePackerConfig, Size};
+///
+/// let sizes = vec![Size::new(8, 8); 4];
+/// let front = pareto_front::<HeightRectPacker>(&sizes, &RectanglePackerConfig::default()).unwrap();
+/// let containers: Vec<Size> = front.iter().map(|result| result.size).collect();
+///
+/// assert_eq!(containers, vec![Size::new(8, 32), Size::new(16, 16), Size::new(32, 8)]);
+/// ```
+pub fn pareto_front<P: RectanglePacker>(
+    sizes: &[Size],
+    config: &RectanglePackerConfig,
+) -> Result<Vec<RectanglePackingResult>, RectanglePackingError> {
+    let mut results = strips::<P>(sizes, config)?;
+    results.sort_by_key(|result| (result.size.width, result.size.height));
+
+    // Scanning by increasing width, a packing is on the front if it is lower than all narrower ones.
+    let mut front: Vec<RectanglePackingResult> = Vec::new();
+    for result in results {
+        if front.last().is_none_or(|last| result.size.height < last.size.height) {
+            front.push(result);
+        }
+    }
+    Ok(front)
+}
+
+/// Packs the sizes into strips of the widths returned by `candidate_widths` with the packer `P`,
+/// and returns the packings that succeeded with their containers cropped to the rectangles,
+/// or the last error if none did.
+fn strips<P: RectanglePacker>(
+    sizes: &[Size],
+    config: &RectanglePackerConfig,
+) -> Result<Vec<RectanglePackingResult>, RectanglePackingError> {
+    let padding = 2 * config.rectangle_padding;
+    let border = 2 * config.border_padding;
+    let padded = config.padded_sizes(sizes);
+    let narrowest = padded.iter().map(|s| s.width + padding).max().unwrap_or(0) + border;
+    let (widest, height) = match config.max_size {
+        Some(max_size) => (max_size.width, max_size.height),
+        None => (
+            padded.iter().map(|s| s.width + padding + config.column_gutter).sum::<usize>() + border,
+            padded.iter().map(|s| s.height + padding + config.row_gutter).sum::<usize>() + border,
+        ),
+    };
+
+    let mut results = Vec::new();
+    let mut last_error = None;
+    let area: usize = sizes.iter().map(|&size| config.padded_area(size)).sum();
+    for width in candidate_widths(narrowest.max(1), widest.max(1), area, border) {
+        match P::pack(sizes, &with_max_size(config, Size::new(width, height.max(1)))) {
+            Ok(mut result) => {
+                // Crop the strip to the rectangles, keeping the padding around them and the border.
+                let placed = (0..result.rectangles.len())
+                    .filter(|index| result.skipped.binary_search(index).is_err())
+                    .map(|index| result.rectangles[index]);
+                let (right, bottom) = placed.fold((0, 0), |(right, bottom), r| {
+                    (right.max(r.x + r.width + config.rectangle_padding), bottom.max(r.y + r.height + config.rectangle_padding))
+                });
+                result.size.width = result.size.width.min(right.max(config.border_padding) + config.border_padding);
+                result.size.height = result.size.height.min(bottom.max(config.border_padding) + config.border_padding);
+                results.push(result);
+            }
+            Err(error) => last_error = Some(error),
+        }
+    }
+
+    match results.is_empty() {
+        true => Err(last_error.expect("at least one width is swept")),
+        false => Ok(results),
+    }
+}
+
+/// The number of evenly spaced widths swept over each range by `candidate_widths`.
+const SWEEP_WIDTHS: usize = 64;
+
+/// The memory of the widths returned by `candidate_widths`, in bytes.
+pub(crate) const SWEEP_MEMORY: usize = 2 * SWEEP_WIDTHS * std::mem::size_of::<usize>();
+
+/// Returns the widths to sweep when searching the best width of a strip, in increasing order.
+///
+/// Evenly spaced widths between `narrowest` and `widest` are returned, together with evenly spaced widths
+/// between `narrowest` and twice the side of a square of the given area plus the border,
+/// where compact containers are found.
+pub(crate) fn candidate_widths(narrowest: usize, widest: usize, area: usize, border: usize) -> Vec<usize> {
+    let widest = widest.max(narrowest);
+    let compact = (2 * square_root_ceil(area) + border).clamp(narrowest, widest);
+
+    let mut widths: Vec<usize> = [widest, compact]
+        .into_iter()
+        .flat_map(|end| (0..SWEEP_WIDTHS).map(move |k| narrowest + (end - narrowest) * k / (SWEEP_WIDTHS - 1)))
+        .collect();
+    widths.sort_unstable();
+    widths.dedup();
+    widths
+}
+
+/// Returns the smallest integer whose square is at least `value`, computed without floats to be exact on all platforms.
+pub(crate) fn square_root_ceil(value: usize) -> usize {
+    let root = value.isqrt();
+    match root * root < value {
+        true => root + 1,
+        false => root,
+    }
+}
+
+/// Returns the error for sizes that do not fit into any container of the given shape within the limit.
+fn no_container(shape: ContainerShape, limit: Size) -> RectanglePackingError {
+    RectanglePackingError {
+        message: format!("No container of shape {:?} within max size {:?} fits all rectangles", shape, limit),
+        config_error: None,
+        result: RectanglePackingResult { rectangles: Vec::new(), size: Size::new(0, 0), skipped: Vec::new() },
+    }
+}
+
+/// Returns the config with the given max size.
+pub(crate) fn with_max_size(config: &RectanglePackerConfig, max_size: Size) -> RectanglePackerConfig {
+    RectanglePackerConfig { max_size: Some(max_size), ..config.clone() }
+}
+
+/// Returns the exponent of the power of two nearest to `value`, rounding up if `up` is set and down otherwise.
+fn exponent(value: usize, up: bool) -> u32 {
+    match (up, value.max(1)) {
+        (true, value) => value.next_power_of_two().trailing_zeros(),
+        (false, value) => value.ilog2(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::HeightRectPacker;
+
+    use super::*;
+
+    #[test]
+    fn smallest_square_works() {
+        let sizes: Vec<Size> = (0..20).map(|i| Size::new(i % 6 + 3, i % 4 + 3)).collect();
+        let config = RectanglePackerConfig { rectangle_padding: 1, ..Default::default() };
+        let result = smallest_container::<HeightRectPacker>(&sizes, &config, ContainerShape::Square).unwrap();
+
+        let side = result.size.width;
+        assert_eq!(result.size, Size::new(side, side));
+        assert!(result.validate(&config).is_ok());
+        assert!(HeightRectPacker::pack(&sizes, &with_max_size(&config, Size::new(side - 1, side - 1))).is_err());
+    }
+
+    #[test]
+    fn smallest_power_of_two_works() {
+        let sizes = vec![Size::new(30, 10), Size::new(30, 10), Size::new(4, 4)];
+        let config = RectanglePackerConfig::default();
+
+        let result = smallest_container::<HeightRectPacker>(&sizes, &config, ContainerShape::PowerOfTwo).unwrap();
+        assert_eq!(result.size, Size::new(32, 32));
+
+        let result = smallest_container::<HeightRectPacker>(&[Size::new(40, 4)], &config, ContainerShape::PowerOfTwo).unwrap();
+        assert_eq!(result.size, Size::new(64, 4));
+
+        let result = smallest_container::<HeightRectPacker>(&[Size::new(40, 4)], &config, ContainerShape::PowerOfTwoSquare).unwrap();
+        assert_eq!(result.size, Size::new(64, 64));
+    }
+
+    #[test]
+    fn smallest_container_respects_max_size() {
+        let sizes = vec![Size::new(16, 16); 4];
+        let config = RectanglePackerConfig { max_size: Some(Size::new(24, 64)), ..Default::default() };
+
+        assert!(smallest_container::<HeightRectPacker>(&sizes, &config, ContainerShape::Square).is_err());
+        assert!(smallest_container::<HeightRectPacker>(&sizes, &config, ContainerShape::PowerOfTwoSquare).is_err());
+        assert_eq!(
+            smallest_container::<HeightRectPacker>(&sizes, &config, ContainerShape::PowerOfTwo).unwrap().size,
+            Size::new(16, 64)
+        );
+    }
+
+    #[test]
+    fn smallest_container_handles_no_sizes() {
+        let result = smallest_container::<HeightRectPacker>(&[], &RectanglePackerConfig::default(), ContainerShape::Square).unwrap();
+
+        assert_eq!(result.size, Size::new(1, 1));
+    }
+
+    #[test]
+    fn best_strip_width_minimizes_cost() {
+        let sizes: Vec<Size> = (0..30).map(|i| Size::new(i % 7 + 2, i % 5 + 2)).collect();
+        let config = RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() };
+        let weights = ObjectiveWeights { area: 1.0, ..ObjectiveWeights::NONE };
+        let result = best_strip_width::<HeightRectPacker>(&sizes, &config, &weights).unwrap();
+
+        assert!(result.validate(&config).is_ok());
+        let area = |size: Size| size.width * size.height;
+        for width in [12, 20, 30, 45] {
+            let strip = HeightRectPacker::pack(&sizes, &with_max_size(&config, Size::new(width, 1000))).unwrap();
+            let height = strip.rectangles.iter().map(|r| r.y + r.height).max().unwrap() + 2;
+            assert!(area(result.size) <= area(Size::new(width, height)));
+        }
+
+        let flat = best_strip_width::<HeightRectPacker>(&sizes, &config, &ObjectiveWeights { height: 1.0, ..ObjectiveWeights::NONE }).unwrap();
+        assert_eq!(flat.size.height, 6 + 2 * 2);
+    }
+
+    #[test]
+    fn best_strip_width_respects_max_size() {
+        let sizes = vec![Size::new(16, 16); 4];
+        let weights = ObjectiveWeights { height: 1.0, ..ObjectiveWeights::NONE };
+
+        let config = RectanglePackerConfig { max_size: Some(Size::new(40, 64)), ..Default::default() };
+        assert_eq!(best_strip_width::<HeightRectPacker>(&sizes, &config, &weights).unwrap().size, Size::new(32, 32));
+
+        let config = RectanglePackerConfig { max_size: Some(Size::new(40, 24)), ..Default::default() };
+        assert!(best_strip_width::<HeightRectPacker>(&sizes, &config, &weights).is_err());
+    }
+
+    #[test]
+    fn pareto_front_works() {
+        let sizes: Vec<Size> = (0..30).map(|i| Size::new(i % 7 + 2, i % 5 + 2)).collect();
+        let config = RectanglePackerConfig { rectangle_padding: 1, ..Default::default() };
+        let front = pareto_front::<HeightRectPacker>(&sizes, &config).unwrap();
+
+        assert!(front.len() > 2);
+        for result in &front {
+            assert!(result.validate(&config).is_ok());
+        }
+        for pair in front.windows(2) {
+            assert!(pair[0].size.width < pair[1].size.width);
+            assert!(pair[0].size.height > pair[1].size.height);
+        }
+
+        // The smallest area found by the strip search lies on the front.
+        let weights = ObjectiveWeights { area: 1.0, ..ObjectiveWeights::NONE };
+        let best = best_strip_width::<HeightRectPacker>(&sizes, &config, &weights).unwrap();
+        let smallest = front.iter().map(|result| result.size.width * result.size.height).min().unwrap();
+        assert_eq!(smallest, best.size.width * best.size.height);
+    }
+}
@@ -148,6 +148,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn is_square_works() {
         let size = Size::new(10, 20);
         assert_eq!(size.is_square(), false);
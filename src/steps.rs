@@ -0,0 +1,165 @@
+use crate::free_rectangles::FreeRectangles;
+use crate::{PackingEvent, PackingTrace, Rectangle, RectanglePacker, RectanglePackerConfig, RectanglePackingError, Size};
+
+/// An intermediate state of a packing, after placing one more rectangle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackingStep {
+    /// The index of the size that was placed.
+    pub index: usize,
+
+    /// The rectangle the size was placed at.
+    pub rectangle: Rectangle,
+
+    /// The maximal free rectangles of the container after placing the rectangle.
+    ///
+    /// The padding around each placed rectangle and the border padding are treated as occupied,
+    /// like in `RectanglePackingResult::fragmentation_histogram`. The free rectangles may overlap each other.
+    pub free_rectangles: Vec<Rectangle>,
+}
+
+/// An iterator over the steps of a packing, see `pack_steps`.
+///
+/// The free space of each step is computed when the step is taken,
+/// so skipping steps with `nth` or `last` still computes the free space of all steps before.
+pub struct PackingSteps {
+    placements: std::vec::IntoIter<(usize, Rectangle)>,
+    free: FreeRectangles,
+    padding: usize,
+    size: Size,
+}
+
+impl PackingSteps {
+    /// Returns the size of the container the rectangles are placed in, which is the size of the final packing.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl Iterator for PackingSteps {
+    type Item = PackingStep;
+
+    fn next(&mut self) -> Option<PackingStep> {
+        let (index, rectangle) = self.placements.next()?;
+        self.free.occupy(&rectangle.expanded(self.padding));
+        Some(PackingStep { index, rectangle, free_rectangles: self.free.iter().collect() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.placements.size_hint()
+    }
+}
+
+impl ExactSizeIterator for PackingSteps {}
+
+/// Packs the sizes with the packer `P` and returns the steps that built the packing, in the order the rectangles were placed,
+/// e.g. to animate how a packer works.
+///
+/// The free space of every step lies within the container of the final packing,
+/// so a packer growing its container starts out with all of the final container free.
+///
+/// # Arguments
+/// * `sizes` - The sizes of the rectangles to pack.
+/// * `config` - The configuration of the packer.
+///
+/// # Returns
+/// A `Result` containing either an iterator over the steps, or the `RectanglePackingError` if packing failed.
+///
+/// # Examples
+/// ```
+/// use rpack::{pack_steps, HeightRectPacker, RectanglePackerConfig, Size};
+///
+/// let sizes = vec![Size::new(4, 4), Size::new(8, 8)];
+/// let steps: Vec<_> = pack_steps::<HeightRectPacker>(&sizes, &RectanglePackerConfig::default()).unwrap().collect();
+///
+/// // The larger size is placed first.
+/// assert_eq!(steps[0].index, 1);
+/// assert_eq!(steps.len(), 2);
+/// ```
+pub fn pack_steps<P: RectanglePacker>(sizes: &[Size], config: &RectanglePackerConfig) -> Result<PackingSteps, RectanglePackingError> {
+    let mut trace = PackingTrace::new();
+    let result = P::pack_observed(sizes, config, &mut trace)?;
+
+    let placements: Vec<(usize, Rectangle)> = trace.events
+        .into_iter()
+        .filter_map(|event| match event {
+            PackingEvent::Place { index, rectangle } => Some((index, rectangle)),
+            _ => None,
+        })
+        .collect();
+
+    let border = config.border_padding;
+    let inner = Rectangle::new(
+        border,
+        border,
+        result.size.width.saturating_sub(2 * border),
+        result.size.height.saturating_sub(2 * border),
+    );
+
+    Ok(PackingSteps {
+        placements: placements.into_iter(),
+        free: FreeRectangles::new(inner),
+        padding: config.rectangle_padding,
+        size: result.size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeightRectPacker, RectanglePackingResult};
+
+    use super::*;
+
+    #[test]
+    fn pack_steps_works() {
+        let sizes = vec![Size::new(4, 2), Size::new(4, 4), Size::new(2, 2)];
+        let config = RectanglePackerConfig { max_size: Some(Size::new(8, 6)), ..Default::default() };
+        let mut steps = pack_steps::<HeightRectPacker>(&sizes, &config).unwrap();
+
+        assert_eq!(steps.size(), Size::new(8, 6));
+        assert_eq!(steps.len(), 3);
+
+        let first = steps.next().unwrap();
+        assert_eq!(first, PackingStep {
+            index: 1,
+            rectangle: Rectangle::new(0, 0, 4, 4),
+            free_rectangles: vec![Rectangle::new(4, 0, 4, 6), Rectangle::new(0, 4, 8, 2)],
+        });
+
+        let placed: Vec<Rectangle> = steps.map(|step| {
+            assert!(step.free_rectangles.iter().all(|free| !free.intersects(&step.rectangle)));
+            step.rectangle
+        }).collect();
+        assert_eq!(placed.len(), 2);
+    }
+
+    #[test]
+    fn last_step_matches_the_packing() {
+        let sizes: Vec<Size> = (0..16).map(|i| Size::new(i % 5 + 1, i % 3 + 1)).collect();
+        let config = RectanglePackerConfig { rectangle_padding: 1, border_padding: 1, ..Default::default() };
+        let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+        let steps: Vec<PackingStep> = pack_steps::<HeightRectPacker>(&sizes, &config).unwrap().collect();
+
+        let mut rectangles = vec![Rectangle::new(0, 0, 0, 0); sizes.len()];
+        for step in &steps {
+            rectangles[step.index] = step.rectangle;
+        }
+        assert_eq!(rectangles, result.rectangles);
+
+        // The free rectangles are the same, but not necessarily in the same order.
+        let key = |r: &Rectangle| (r.x, r.y, r.width, r.height);
+        let mut final_free = RectanglePackingResult { rectangles, size: result.size, skipped: Vec::new() }
+            .fragmentation_histogram(&config)
+            .free_rectangles;
+        final_free.sort_by_key(key);
+        let mut last_free = steps.last().unwrap().free_rectangles.clone();
+        last_free.sort_by_key(key);
+        assert_eq!(last_free, final_free);
+    }
+
+    #[test]
+    fn pack_steps_fails_if_packing_fails() {
+        let config = RectanglePackerConfig { max_size: Some(Size::new(4, 4)), ..Default::default() };
+
+        assert!(pack_steps::<HeightRectPacker>(&[Size::new(8, 8)], &config).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+//! Generators of sizes, rectangles and configs for property testing and fuzzing.
+//!
+//! With the `proptest` feature, `Size`, `Rectangle` and `RectanglePackerConfig` implement proptest's `Arbitrary`,
+//! and the functions of this module return strategies with custom bounds.
+//! With the `arbitrary` feature, they implement `arbitrary::Arbitrary`, e.g. for fuzzing with `cargo fuzz`.
+//!
+//! All generated dimensions are at most `MAX_DIMENSION` unless other bounds are given, so packing them
+//! can not overflow, and all generated configs are valid, see `RectanglePackerConfig::validate`.
+
+#[cfg(feature = "proptest")]
+use proptest::prelude::*;
+
+use crate::{OverflowPolicy, PageFill, Rectangle, RectanglePackerConfig, Size, ZeroSizePolicy};
+
+/// The largest width, height and position generated by default.
+pub const MAX_DIMENSION: usize = 1024;
+
+/// The largest padding generated for configs.
+pub const MAX_PADDING: usize = 8;
+
+/// Returns a strategy generating sizes with a width and height of at most `max`, including zero.
+///
+/// # Examples
+/// ```
+/// use proptest::strategy::{Strategy, ValueTree};
+/// use proptest::test_runner::TestRunner;
+/// use rpack::strategies;
+///
+/// let size = strategies::size(16).new_tree(&mut TestRunner::default()).unwrap().current();
+/// assert!(size.width <= 16 && size.height <= 16);
+/// ```
+#[cfg(feature = "proptest")]
+pub fn size(max: usize) -> impl Strategy<Value = Size> {
+    (0..=max, 0..=max).prop_map(|(width, height)| Size::new(width, height))
+}
+
+/// Returns a strategy generating rectangles that lie within a container of the given size.
+#[cfg(feature = "proptest")]
+pub fn rectangle(bounds: Size) -> impl Strategy<Value = Rectangle> {
+    (0..=bounds.width, 0..=bounds.height).prop_flat_map(move |(x, y)| {
+        (0..=bounds.width - x, 0..=bounds.height - y).prop_map(move |(width, height)| Rectangle::new(x, y, width, height))
+    })
+}
+
+/// Returns a strategy generating valid configs with paddings of at most `MAX_PADDING`
+/// and, if any, a max size of at most `MAX_DIMENSION`.
+///
+/// # Examples
+/// ```
+/// use proptest::prelude::*;
+/// use rpack::{strategies, HeightRectPacker, RectanglePacker, Size};
+///
+/// proptest! {
+///     fn packings_are_valid(sizes in prop::collection::vec(any::<Size>(), 0..16), config in strategies::config()) {
+///         if let Ok(result) = HeightRectPacker::pack(&sizes, &config) {
+///             prop_assert!(result.validate(&config).is_ok());
+///         }
+///     }
+/// }
+/// packings_are_valid();
+/// ```
+#[cfg(feature = "proptest")]
+pub fn config() -> impl Strategy<Value = RectanglePackerConfig> {
+    (0..=MAX_PADDING, 0..=MAX_PADDING, any::<bool>(), 0..3u8, any::<bool>())
+        .prop_flat_map(|(rectangle_padding, border_padding, bounded, policy, deduplicate_sizes)| {
+            let min = 2 * (rectangle_padding + border_padding) + 1;
+            let max_size = match bounded {
+                true => size(MAX_DIMENSION - min).prop_map(move |s| Some(Size::new(s.width + min, s.height + min))).boxed(),
+                false => Just(None).boxed(),
+            };
+            max_size.prop_map(move |max_size| RectanglePackerConfig {
+                max_size,
+                rectangle_padding,
+                owned_padding: 0,
+                border_padding,
+                column_gutter: 0,
+                row_gutter: 0,
+                row_height_multiple: None,
+                fill_row_gaps: false,
+                zero_size_policy: zero_size_policy(policy),
+                deduplicate_sizes,
+                objective: None,
+                memory_budget: None,
+                overflow: OverflowPolicy::Error,
+                max_pages: None,
+                page_fill: PageFill::Sequential,
+                split_oversized: false,
+            })
+        })
+}
+
+#[cfg(feature = "proptest")]
+impl Arbitrary for Size {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Size>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        size(MAX_DIMENSION).boxed()
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl Arbitrary for Rectangle {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Rectangle>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        rectangle(Size::new(MAX_DIMENSION, MAX_DIMENSION)).boxed()
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl Arbitrary for RectanglePackerConfig {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<RectanglePackerConfig>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        config().boxed()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Size {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Size::new(u.int_in_range(0..=MAX_DIMENSION)?, u.int_in_range(0..=MAX_DIMENSION)?))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Rectangle {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let x = u.int_in_range(0..=MAX_DIMENSION)?;
+        let y = u.int_in_range(0..=MAX_DIMENSION)?;
+        let width = u.int_in_range(0..=MAX_DIMENSION - x)?;
+        let height = u.int_in_range(0..=MAX_DIMENSION - y)?;
+        Ok(Rectangle::new(x, y, width, height))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for RectanglePackerConfig {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let rectangle_padding = u.int_in_range(0..=MAX_PADDING)?;
+        let border_padding = u.int_in_range(0..=MAX_PADDING)?;
+        let min = 2 * (rectangle_padding + border_padding) + 1;
+        let max_size = match u.arbitrary::<bool>()? {
+            true => Some(Size::new(u.int_in_range(min..=MAX_DIMENSION)?, u.int_in_range(min..=MAX_DIMENSION)?)),
+            false => None,
+        };
+        Ok(RectanglePackerConfig {
+            max_size,
+            rectangle_padding,
+            owned_padding: 0,
+            border_padding,
+            column_gutter: 0,
+            row_gutter: 0,
+            row_height_multiple: None,
+            fill_row_gaps: false,
+            zero_size_policy: zero_size_policy(u.int_in_range(0..=2)?),
+            deduplicate_sizes: u.arbitrary()?,
+            objective: None,
+            memory_budget: None,
+            overflow: OverflowPolicy::Error,
+            max_pages: None,
+            page_fill: PageFill::Sequential,
+            split_oversized: false,
+        })
+    }
+}
+
+/// Returns the zero size policy with the given number, counting in the order of declaration.
+fn zero_size_policy(number: u8) -> ZeroSizePolicy {
+    match number {
+        0 => ZeroSizePolicy::Place,
+        1 => ZeroSizePolicy::Reject,
+        _ => ZeroSizePolicy::Skip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "proptest")]
+    proptest! {
+        #[test]
+        fn generated_configs_are_valid(config in any::<RectanglePackerConfig>()) {
+            prop_assert!(config.validate().is_ok());
+        }
+
+        #[test]
+        fn generated_rectangles_lie_within_bounds(r in rectangle(Size::new(20, 10))) {
+            prop_assert!(Rectangle::new(0, 0, 20, 10).contains(&r));
+        }
+
+        #[test]
+        fn height_rect_packer_packings_are_valid(
+            sizes in prop::collection::vec(size(64), 0..24),
+            config in any::<RectanglePackerConfig>(),
+        ) {
+            use crate::{HeightRectPacker, RectanglePacker};
+
+            if let Ok(result) = HeightRectPacker::pack(&sizes, &config) {
+                prop_assert_eq!(result.validate(&config), Ok(()));
+            }
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_configs_are_valid() {
+        use arbitrary::Unstructured;
+
+        let data: Vec<u8> = (0..=255).cycle().step_by(7).take(4096).collect();
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let config: RectanglePackerConfig = arbitrary::Arbitrary::arbitrary(&mut u).unwrap();
+            assert!(config.validate().is_ok());
+            let r: Rectangle = arbitrary::Arbitrary::arbitrary(&mut u).unwrap();
+            assert!(r.x + r.width <= MAX_DIMENSION && r.y + r.height <= MAX_DIMENSION);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use std::cmp::max;
+use crate::{Rectangle, RectanglePackingError, RectanglePackingResult, Size};
+
+/// A packer placing rectangles in rows as they arrive, using a constant amount of memory.
+///
+/// Unlike the `RectanglePacker`s, the streaming packer never sees all sizes at once and does not keep the placed
+/// rectangles, so it can pack millions of rectangles, e.g. read lazily from a file, without materializing the result.
+/// Each rectangle is placed to the right of the previous one, or at the start of a new row if it does not fit
+/// into the width of the container. As the rectangles are not sorted, the packing is less dense than that of
+/// the `HeightRectPacker`, in particular for rectangles of very different heights.
+///
+/// # Examples
+/// ```
+/// use rpack::{Size, StreamingPacker};
+///
+/// let mut packer = StreamingPacker::new(100).with_padding(1, 0);
+/// let sizes = (0..1000).map(|i| Size::new(i % 17 + 1, i % 13 + 1));
+///
+/// let mut area = 0;
+/// let size = packer.pack_iter(sizes, |_index, rectangle| area += rectangle.width * rectangle.height).unwrap();
+///
+/// assert_eq!(size.width, 100);
+/// assert!(area < size.width * size.height);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamingPacker {
+    width: usize,
+    max_height: Option<usize>,
+    rectangle_padding: usize,
+    owned_padding: usize,
+    border_padding: usize,
+    column_gutter: usize,
+    row_gutter: usize,
+    x: usize,
+    y: usize,
+    row_height: usize,
+}
+
+impl StreamingPacker {
+    /// Creates a packer for a container of the given width, without padding and without a maximum height.
+    pub fn new(width: usize) -> StreamingPacker {
+        StreamingPacker {
+            width,
+            max_height: None,
+            rectangle_padding: 0,
+            owned_padding: 0,
+            border_padding: 0,
+            column_gutter: 0,
+            row_gutter: 0,
+            x: 0,
+            y: 0,
+            row_height: 0,
+        }
+        .restart()
+    }
+
+    /// Sets the padding kept free around each rectangle and along the edges of the container,
+    /// with the same meaning as in `RectanglePackerConfig`.
+    ///
+    /// The packer starts over, so this should be called before placing any rectangles.
+    pub fn with_padding(mut self, rectangle_padding: usize, border_padding: usize) -> StreamingPacker {
+        self.rectangle_padding = rectangle_padding;
+        self.border_padding = border_padding;
+        self.restart()
+    }
+
+    /// Sets the padding owned by each rectangle, which is added to its size on every side and is part of
+    /// the placed rectangle, with the same meaning as `owned_padding` in `RectanglePackerConfig`.
+    pub fn with_owned_padding(mut self, owned_padding: usize) -> StreamingPacker {
+        self.owned_padding = owned_padding;
+        self
+    }
+
+    /// Sets the space added between neighbouring rectangles of a row and between the rows,
+    /// with the same meaning as `column_gutter` and `row_gutter` in `RectanglePackerConfig`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{Rectangle, Size, StreamingPacker};
+    ///
+    /// let mut packer = StreamingPacker::new(10).with_gutters(2, 1);
+    /// assert_eq!(packer.place(Size::new(4, 4)).unwrap(), Rectangle::new(0, 0, 4, 4));
+    /// assert_eq!(packer.place(Size::new(4, 4)).unwrap(), Rectangle::new(6, 0, 4, 4));
+    /// assert_eq!(packer.place(Size::new(4, 4)).unwrap(), Rectangle::new(0, 5, 4, 4));
+    /// ```
+    pub fn with_gutters(mut self, column_gutter: usize, row_gutter: usize) -> StreamingPacker {
+        self.column_gutter = column_gutter;
+        self.row_gutter = row_gutter;
+        self
+    }
+
+    /// Sets the maximum height of the container, after which placing further rectangles fails.
+    pub fn with_max_height(mut self, max_height: usize) -> StreamingPacker {
+        self.max_height = Some(max_height);
+        self
+    }
+
+    /// Returns the size of the container holding all rectangles placed so far.
+    ///
+    /// The width is always the width the packer was created with.
+    pub fn size(&self) -> Size {
+        let start = self.border_padding + self.rectangle_padding;
+        let height = match (self.x, self.y) {
+            (x, y) if x == start && y == start => 2 * self.border_padding,
+            _ => self.y + self.row_height + self.rectangle_padding + self.border_padding,
+        };
+        Size::new(self.width, height)
+    }
+
+    /// Places a single rectangle.
+    ///
+    /// # Returns
+    /// A `Result` containing either the placed rectangle or a `RectanglePackingError` if the rectangle is wider
+    /// than the container or would exceed the maximum height. The result of the error has no rectangles,
+    /// as the packer does not keep them, but the size of the container so far.
+    pub fn place(&mut self, size: Size) -> Result<Rectangle, RectanglePackingError> {
+        let padding = 2 * self.owned_padding;
+        let size = match size.width == 0 || size.height == 0 {
+            true => size,
+            false => Size::new(size.width + padding, size.height + padding),
+        };
+        let start = self.border_padding + self.rectangle_padding;
+        let end = self.width.saturating_sub(self.border_padding);
+
+        if start + size.width + self.rectangle_padding > end {
+            return Err(self.error(format!("Rectangle size {:?} is wider than the container", size)));
+        }
+
+        // Move to the next row if the rectangle does not fit into the current one.
+        if self.x > start && self.x + size.width + self.rectangle_padding > end {
+            self.x = start;
+            self.y += self.row_height + 2 * self.rectangle_padding + self.row_gutter;
+            self.row_height = 0;
+        }
+
+        if let Some(max_height) = self.max_height {
+            if self.y + size.height + self.rectangle_padding > max_height.saturating_sub(self.border_padding) {
+                return Err(self.error("Could not fit all rectangles in max size".to_string()));
+            }
+        }
+
+        let rectangle = Rectangle::from_size(self.x, self.y, &size);
+        self.x += size.width + 2 * self.rectangle_padding + self.column_gutter;
+        self.row_height = max(self.row_height, size.height);
+        Ok(rectangle)
+    }
+
+    /// Places all rectangles of an iterator, reporting each placement to a callback instead of collecting them.
+    ///
+    /// # Arguments
+    /// * `sizes` - The sizes of the rectangles to place.
+    /// * `place` - Called with the index of each size and the rectangle it was placed at.
+    ///
+    /// # Returns
+    /// A `Result` containing either the size of the container or the error of the first rectangle that could not be placed.
+    pub fn pack_iter<I, F>(&mut self, sizes: I, mut place: F) -> Result<Size, RectanglePackingError>
+    where
+        I: IntoIterator<Item = Size>,
+        F: FnMut(usize, Rectangle),
+    {
+        for (index, size) in sizes.into_iter().enumerate() {
+            place(index, self.place(size)?);
+        }
+        Ok(self.size())
+    }
+
+    /// Resets the position to the start of the first row.
+    fn restart(mut self) -> StreamingPacker {
+        self.x = self.border_padding + self.rectangle_padding;
+        self.y = self.border_padding + self.rectangle_padding;
+        self.row_height = 0;
+        self
+    }
+
+    fn error(&self, message: String) -> RectanglePackingError {
+        RectanglePackingError {
+            message,
+            config_error: None,
+            result: RectanglePackingResult { rectangles: Vec::new(), size: self.size(), skipped: Vec::new() },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RectanglePackerConfig;
+
+    use super::*;
+
+    #[test]
+    fn pack_iter_works() {
+        let sizes: Vec<Size> = (0..200).map(|i| Size::new(i % 11 + 1, i % 7 + 1)).collect();
+        let mut packer = StreamingPacker::new(50).with_padding(1, 2);
+        let mut rectangles = vec![Rectangle::new(0, 0, 0, 0); sizes.len()];
+
+        let size = packer.pack_iter(sizes.iter().copied(), |index, rectangle| rectangles[index] = rectangle).unwrap();
+
+        let result = RectanglePackingResult { rectangles, size, skipped: Vec::new() };
+        let config = RectanglePackerConfig { rectangle_padding: 1, border_padding: 2, ..Default::default() };
+        assert_eq!(result.validate(&config), Ok(()));
+        assert_eq!(size.width, 50);
+    }
+
+    #[test]
+    fn place_fills_rows() {
+        let mut packer = StreamingPacker::new(10);
+
+        assert_eq!(packer.place(Size::new(6, 2)).unwrap(), Rectangle::new(0, 0, 6, 2));
+        assert_eq!(packer.place(Size::new(4, 3)).unwrap(), Rectangle::new(6, 0, 4, 3));
+        assert_eq!(packer.place(Size::new(5, 1)).unwrap(), Rectangle::new(0, 3, 5, 1));
+        assert_eq!(packer.size(), Size::new(10, 4));
+    }
+
+    #[test]
+    fn owned_padding_is_part_of_the_rectangles() {
+        let mut packer = StreamingPacker::new(20).with_padding(1, 0).with_owned_padding(2);
+
+        assert_eq!(packer.place(Size::new(6, 2)).unwrap(), Rectangle::new(1, 1, 10, 6));
+        assert_eq!(packer.place(Size::new(0, 2)).unwrap(), Rectangle::new(13, 1, 0, 2));
+        assert!(packer.place(Size::new(16, 2)).is_err());
+    }
+
+    #[test]
+    fn place_fails_if_rectangles_do_not_fit() {
+        let mut packer = StreamingPacker::new(10).with_max_height(4);
+
+        assert!(packer.place(Size::new(11, 1)).is_err());
+        assert!(packer.place(Size::new(10, 4)).is_ok());
+        let error = packer.place(Size::new(1, 1)).unwrap_err();
+        assert_eq!(error.result.size, Size::new(10, 4));
+    }
+}
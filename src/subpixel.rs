@@ -0,0 +1,254 @@
+use std::error::Error;
+use std::fmt;
+
+/// The tolerance for floating point comparisons of coordinates.
+const EPSILON: f32 = 1e-4;
+
+/// The size of a rectangle with fractional coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SizeF32 {
+    /// The width.
+    pub width: f32,
+
+    /// The height.
+    pub height: f32,
+}
+
+impl SizeF32 {
+    /// Creates a new size.
+    pub fn new(width: f32, height: f32) -> SizeF32 {
+        SizeF32 { width, height }
+    }
+}
+
+/// A rectangle with fractional coordinates, e.g. a glyph positioned at a subpixel offset.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RectangleF32 {
+    /// The x coordinate of the top left corner.
+    pub x: f32,
+
+    /// The y coordinate of the top left corner.
+    pub y: f32,
+
+    /// The width.
+    pub width: f32,
+
+    /// The height.
+    pub height: f32,
+}
+
+impl RectangleF32 {
+    /// Creates a new rectangle.
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> RectangleF32 {
+        RectangleF32 { x, y, width, height }
+    }
+
+    /// Returns `true` if the rectangle overlaps with another rectangle by more than the rounding errors of `f32`.
+    ///
+    /// Rectangles that only touch each other do not overlap.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::RectangleF32;
+    ///
+    /// let a = RectangleF32::new(0.0, 0.0, 1.5, 1.5);
+    /// assert!(a.intersects(&RectangleF32::new(1.0, 1.0, 1.0, 1.0)));
+    /// assert!(!a.intersects(&RectangleF32::new(1.5, 0.0, 1.0, 1.0)));
+    /// ```
+    pub fn intersects(&self, other: &RectangleF32) -> bool {
+        self.x < other.x + other.width - EPSILON
+            && other.x < self.x + self.width - EPSILON
+            && self.y < other.y + other.height - EPSILON
+            && other.y < self.y + self.height - EPSILON
+    }
+}
+
+/// The configuration of the subpixel packer, with the same meaning as `RectanglePackerConfig` but fractional values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubpixelPackerConfig {
+    /// The maximum size of the container.
+    ///
+    /// If `None`, the container is as wide as a square holding all rectangles, and as high as needed.
+    pub max_size: Option<SizeF32>,
+
+    /// The amount of padding to add around each rectangle.
+    pub rectangle_padding: f32,
+
+    /// The amount of padding to add around the container rectangle.
+    pub border_padding: f32,
+}
+
+/// The result of packing rectangles with fractional sizes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubpixelPackingResult {
+    /// The packed rectangles, in the order of the sizes.
+    pub rectangles: Vec<RectangleF32>,
+
+    /// The size of the container.
+    pub size: SizeF32,
+}
+
+impl SubpixelPackingResult {
+    /// Returns the ratio of the total area of the rectangles to the area of the container.
+    ///
+    /// A container without any area has a packing ratio of `0.0`.
+    pub fn packing_ratio(&self) -> f32 {
+        let area = self.size.width * self.size.height;
+        match area > 0.0 {
+            true => self.rectangles.iter().map(|r| r.width * r.height).sum::<f32>() / area,
+            false => 0.0,
+        }
+    }
+}
+
+/// The error returned if rectangles with fractional sizes could not be packed.
+#[derive(Debug, Clone)]
+pub struct SubpixelPackingError {
+    /// The error message.
+    pub message: String,
+}
+
+impl fmt::Display for SubpixelPackingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for SubpixelPackingError {}
+
+/// Packs rectangles with fractional sizes without snapping them to integer coordinates,
+/// e.g. for glyph atlases with subpixel positioning or caches of vector graphics tiles.
+///
+/// Like `HeightRectPacker`, the rectangles are placed in rows from the highest to the lowest one,
+/// starting a new row whenever a rectangle does not fit into the current one. Coordinates are compared
+/// with a tolerance for the rounding errors of `f32`, so rectangles may overlap by a tiny fraction of a unit.
+///
+/// # Arguments
+/// * `sizes` - The sizes of the rectangles to pack.
+/// * `config` - The configuration of the packer.
+///
+/// # Returns
+/// A `Result` containing either:
+///   - A `SubpixelPackingResult` with the packed rectangles, in the order of the sizes, and the size of the container.
+///   - A `SubpixelPackingError` if a size is negative or not finite, or the rectangles do not fit into the max size.
+///
+/// # Examples
+/// ```
+/// use rpack::{pack_subpixel, RectangleF32, SizeF32, SubpixelPackerConfig};
+///
+/// let config = SubpixelPackerConfig { max_size: Some(SizeF32::new(8.0, 8.0)), rectangle_padding: 0.25, ..Default::default() };
+/// let result = pack_subpixel(&[SizeF32::new(2.5, 3.5), SizeF32::new(1.5, 1.5)], &config).unwrap();
+///
+/// assert_eq!(result.rectangles[0], RectangleF32::new(0.25, 0.25, 2.5, 3.5));
+/// assert_eq!(result.rectangles[1], RectangleF32::new(3.25, 0.25, 1.5, 1.5));
+/// ```
+pub fn pack_subpixel(sizes: &[SizeF32], config: &SubpixelPackerConfig) -> Result<SubpixelPackingResult, SubpixelPackingError> {
+    let valid = |value: f32| value.is_finite() && value >= 0.0;
+    if let Some(index) = sizes.iter().position(|s| !valid(s.width) || !valid(s.height)) {
+        return Err(SubpixelPackingError { message: format!("Rectangle {} has an invalid size: {:?}", index, sizes[index]) });
+    }
+    if !valid(config.rectangle_padding) || !valid(config.border_padding) {
+        return Err(SubpixelPackingError { message: "The padding must be finite and not negative".to_string() });
+    }
+
+    let padding = config.rectangle_padding;
+    let border = config.border_padding;
+    let width = match config.max_size {
+        Some(max_size) => max_size.width,
+        None => {
+            let area: f32 = sizes.iter().map(|s| (s.width + 2.0 * padding) * (s.height + 2.0 * padding)).sum();
+            let widest = sizes.iter().map(|s| s.width + 2.0 * padding).fold(0.0, f32::max);
+            area.sqrt().max(widest) + 2.0 * border
+        }
+    };
+    let max_height = config.max_size.map_or(f32::INFINITY, |max_size| max_size.height);
+
+    // Equal heights are ordered by their indices, so that the layout does not depend on the sort.
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|&a, &b| sizes[b].height.total_cmp(&sizes[a].height).then(a.cmp(&b)));
+
+    let start = border + padding;
+    let (mut x, mut y, mut row_height) = (start, start, 0.0f32);
+    let mut rectangles = vec![RectangleF32::default(); sizes.len()];
+    let mut right: f32 = 0.0;
+    for index in order {
+        let size = sizes[index];
+        if x > start && x + size.width + padding > width - border + EPSILON {
+            x = start;
+            y += row_height + 2.0 * padding;
+            row_height = 0.0;
+        }
+        if x + size.width + padding > width - border + EPSILON || y + size.height + padding > max_height - border + EPSILON {
+            return Err(SubpixelPackingError {
+                message: format!("Could not fit rectangle {} with size {:?} in max size", index, size),
+            });
+        }
+
+        rectangles[index] = RectangleF32::new(x, y, size.width, size.height);
+        x += size.width + 2.0 * padding;
+        row_height = row_height.max(size.height);
+        right = right.max(x - padding);
+    }
+
+    let size = match config.max_size {
+        Some(max_size) => max_size,
+        None if sizes.is_empty() => SizeF32::new(2.0 * border, 2.0 * border),
+        None => SizeF32::new(right + border, y + row_height + padding + border),
+    };
+    Ok(SubpixelPackingResult { rectangles, size })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_valid(result: &SubpixelPackingResult, config: &SubpixelPackerConfig) {
+        let padding = config.rectangle_padding;
+        let border = config.border_padding;
+        for (index, r) in result.rectangles.iter().enumerate() {
+            assert!(r.x >= border + padding - EPSILON && r.y >= border + padding - EPSILON);
+            assert!(r.x + r.width <= result.size.width - border - padding + EPSILON);
+            assert!(r.y + r.height <= result.size.height - border - padding + EPSILON);
+            let padded = |r: &RectangleF32| RectangleF32::new(r.x - padding, r.y - padding, r.width + 2.0 * padding, r.height + 2.0 * padding);
+            for other in &result.rectangles[index + 1..] {
+                assert!(!padded(r).intersects(&padded(other)));
+            }
+        }
+    }
+
+    #[test]
+    fn pack_subpixel_works() {
+        let sizes: Vec<SizeF32> = (0..50).map(|i| SizeF32::new(1.0 + (i % 7) as f32 * 0.37, 1.0 + (i % 5) as f32 * 0.61)).collect();
+        let config = SubpixelPackerConfig { rectangle_padding: 0.125, border_padding: 0.5, ..Default::default() };
+        let result = pack_subpixel(&sizes, &config).unwrap();
+
+        for (r, s) in result.rectangles.iter().zip(&sizes) {
+            assert_eq!((r.width, r.height), (s.width, s.height));
+        }
+        assert_valid(&result, &config);
+        assert!(result.packing_ratio() > 0.6);
+        // Positions are not snapped to whole units.
+        assert!(result.rectangles.iter().any(|r| r.x.fract() != 0.0));
+    }
+
+    #[test]
+    fn pack_subpixel_handles_empty_inputs() {
+        let config = SubpixelPackerConfig { border_padding: 1.5, ..Default::default() };
+        let result = pack_subpixel(&[], &config).unwrap();
+
+        assert!(result.rectangles.is_empty());
+        assert_eq!(result.size, SizeF32::new(3.0, 3.0));
+        assert_eq!(result.packing_ratio(), 0.0);
+    }
+
+    #[test]
+    fn pack_subpixel_fails_for_invalid_inputs() {
+        let config = SubpixelPackerConfig { max_size: Some(SizeF32::new(4.0, 4.0)), ..Default::default() };
+
+        assert!(pack_subpixel(&[SizeF32::new(-1.0, 1.0)], &config).is_err());
+        assert!(pack_subpixel(&[SizeF32::new(f32::NAN, 1.0)], &config).is_err());
+        assert!(pack_subpixel(&[SizeF32::new(4.5, 1.0)], &config).is_err());
+        assert!(pack_subpixel(&[SizeF32::new(2.0, 2.0); 4], &config).is_ok());
+        assert!(pack_subpixel(&[SizeF32::new(2.0, 2.0); 5], &config).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt::Write;
+use crate::render::rectangle_hue;
+use crate::{RectanglePackingResult, RectanglePackerConfig};
+
+impl RectanglePackingResult {
+    /// Renders the packing as an SVG image.
+    ///
+    /// The image shows the container, every packed rectangle labeled with its index
+    /// and dashed guides for the border padding and the padding around each rectangle.
+    /// One SVG unit corresponds to one unit of the packing.
+    ///
+    /// # Arguments
+    /// * `config` - The config the result was packed with, used to draw the padding guides.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+    ///
+    /// let sizes = vec![Size::new(10, 20), Size::new(30, 5)];
+    /// let config = RectanglePackerConfig::default();
+    /// let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+    ///
+    /// let svg = result.to_svg(&config);
+    /// assert!(svg.starts_with("<svg"));
+    /// ```
+    pub fn to_svg(&self, config: &RectanglePackerConfig) -> String {
+        let width = self.size.width;
+        let height = self.size.height;
+        let border = config.border_padding;
+        let padding = config.rectangle_padding;
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        ).unwrap();
+        writeln!(svg, r##"  <rect x="0" y="0" width="{width}" height="{height}" fill="#f4f4f4" stroke="#000000"/>"##).unwrap();
+
+        if border > 0 {
+            writeln!(
+                svg,
+                r##"  <rect x="{border}" y="{border}" width="{}" height="{}" fill="none" stroke="#888888" stroke-dasharray="4 2"/>"##,
+                width.saturating_sub(2 * border),
+                height.saturating_sub(2 * border)
+            ).unwrap();
+        }
+
+        let font_size = self.rectangles
+            .iter()
+            .map(|r| r.width.min(r.height))
+            .min()
+            .unwrap_or(0)
+            .clamp(4, 16);
+
+        for (index, rectangle) in self.rectangles.iter().enumerate() {
+            if padding > 0 {
+                let padded = rectangle.expanded(padding);
+                writeln!(
+                    svg,
+                    r##"  <rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#888888" stroke-dasharray="2 2"/>"##,
+                    padded.x, padded.y, padded.width, padded.height
+                ).unwrap();
+            }
+
+            let hue = rectangle_hue(index);
+            writeln!(
+                svg,
+                r##"  <rect x="{}" y="{}" width="{}" height="{}" fill="hsl({hue}, 60%, 70%)" stroke="#000000"/>"##,
+                rectangle.x, rectangle.y, rectangle.width, rectangle.height
+            ).unwrap();
+            writeln!(
+                svg,
+                r#"  <text x="{}" y="{}" font-family="monospace" font-size="{font_size}" text-anchor="middle" dominant-baseline="central">{index}</text>"#,
+                rectangle.x as f64 + rectangle.width as f64 / 2.0,
+                rectangle.y as f64 + rectangle.height as f64 / 2.0
+            ).unwrap();
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Rectangle, Size};
+
+    use super::*;
+
+    #[test]
+    fn to_svg_works() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(2, 2, 4, 4), Rectangle::new(8, 2, 2, 6)],
+            size: Size::new(12, 10),
+            skipped: Vec::new(),
+        };
+        let config = RectanglePackerConfig {
+            rectangle_padding: 1,
+            border_padding: 1,
+            ..Default::default()
+        };
+        let svg = result.to_svg(&config);
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="12" height="10" viewBox="0 0 12 10">"#));
+        assert!(svg.contains(r##"<rect x="1" y="1" width="10" height="8" fill="none" stroke="#888888" stroke-dasharray="4 2"/>"##));
+        assert!(svg.contains(r##"<rect x="7" y="1" width="4" height="8" fill="none" stroke="#888888" stroke-dasharray="2 2"/>"##));
+        assert!(svg.contains(r##"<rect x="2" y="2" width="4" height="4" fill="hsl(0, 60%, 70%)" stroke="#000000"/>"##));
+        assert!(svg.contains(">1</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn to_svg_without_padding_has_no_guides() {
+        let result = RectanglePackingResult {
+            rectangles: vec![Rectangle::new(0, 0, 4, 4)],
+            size: Size::new(4, 4),
+            skipped: Vec::new(),
+        };
+        let svg = result.to_svg(&RectanglePackerConfig::default());
+
+        assert!(!svg.contains("stroke-dasharray"));
+    }
+}
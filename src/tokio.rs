@@ -0,0 +1,180 @@
+//! Packing on the blocking thread pool of tokio, so that services packing atlases on demand do not block their executors.
+
+use std::future::Future;
+use std::panic;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tokio::task::JoinHandle;
+
+use crate::{PackingObserver, RectanglePacker, RectanglePackerConfig, RectanglePackingError, RectanglePackingResult, Size};
+
+/// A packing running on the blocking thread pool of tokio, started with `pack_async`.
+///
+/// Awaiting the task returns the result of the packer. The task is cancelled if it is dropped or `cancel` is called.
+/// A packer that is already running stops at the next point it checks `PackingObserver::is_cancelled`,
+/// e.g. between two placements of the `HeightRectPacker`.
+pub struct PackingTask {
+    handle: JoinHandle<Result<RectanglePackingResult, RectanglePackingError>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl PackingTask {
+    /// Cancels the packing. Awaiting the task then returns an error with the message `RectanglePackingError::CANCELLED`,
+    /// unless the packer finished before it noticed the cancellation.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.handle.abort();
+    }
+
+    /// Returns `true` if the packing finished, was cancelled or panicked.
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+}
+
+impl Future for PackingTask {
+    type Output = Result<RectanglePackingResult, RectanglePackingError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.handle).poll(cx).map(|joined| match joined {
+            Ok(result) => result,
+            Err(error) if error.is_panic() => panic::resume_unwind(error.into_panic()),
+            Err(_) => Err(RectanglePackingError::cancelled()),
+        })
+    }
+}
+
+impl Drop for PackingTask {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// The observer of a packing started with `pack_async`, stopping the packer once the task is cancelled.
+struct Cancellation(Arc<AtomicBool>);
+
+impl PackingObserver for Cancellation {
+    fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Packs the sizes with the packer `P` on the blocking thread pool of the current tokio runtime.
+///
+/// # Arguments
+/// * `sizes` - The sizes of the rectangles to pack.
+/// * `config` - The configuration of the packer.
+///
+/// # Returns
+/// A `PackingTask` resolving to the result of the packer, or to an error if it was cancelled.
+/// A panic of the packer is resumed when the task is awaited.
+///
+/// # Panics
+/// Panics if called outside of a tokio runtime.
+///
+/// # Examples
+/// ```
+/// use rpack::tokio::pack_async;
+/// use rpack::{HeightRectPacker, RectanglePackerConfig, Size};
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let result = runtime.block_on(async {
+///     pack_async::<HeightRectPacker>(&[Size::new(16, 16), Size::new(8, 8)], &RectanglePackerConfig::default()).await
+/// });
+///
+/// assert_eq!(result.unwrap().rectangles.len(), 2);
+/// ```
+pub fn pack_async<P: RectanglePacker + 'static>(sizes: &[Size], config: &RectanglePackerConfig) -> PackingTask {
+    let sizes = sizes.to_vec();
+    let config = config.clone();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let mut observer = Cancellation(cancelled.clone());
+    let handle = tokio::task::spawn_blocking(move || P::pack_observed(&sizes, &config, &mut observer));
+    PackingTask { handle, cancelled }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::runtime::{Builder, Runtime};
+
+    use crate::HeightRectPacker;
+
+    use super::*;
+
+    fn runtime() -> Runtime {
+        Builder::new_current_thread().max_blocking_threads(1).build().unwrap()
+    }
+
+    #[test]
+    fn pack_async_works() {
+        let sizes: Vec<Size> = (0..20).map(|i| Size::new(i % 5 + 1, i % 3 + 1)).collect();
+        let config = RectanglePackerConfig { rectangle_padding: 1, ..Default::default() };
+        let result = runtime().block_on(async { pack_async::<HeightRectPacker>(&sizes, &config).await }).unwrap();
+
+        assert_eq!(result.rectangles, HeightRectPacker::pack(&sizes, &config).unwrap().rectangles);
+    }
+
+    #[test]
+    fn pack_async_returns_errors_of_the_packer() {
+        let config = RectanglePackerConfig { max_size: Some(Size::new(4, 4)), ..Default::default() };
+        let error = runtime().block_on(async { pack_async::<HeightRectPacker>(&[Size::new(8, 8)], &config).await }).unwrap_err();
+
+        assert_ne!(error.message, RectanglePackingError::CANCELLED);
+    }
+
+    #[test]
+    fn cancelled_packings_return_an_error() {
+        let runtime = runtime();
+        let error = runtime.block_on(async {
+            // The only blocking thread is busy, so the packing does not start before it is cancelled.
+            let busy = tokio::task::spawn_blocking(|| std::thread::sleep(std::time::Duration::from_millis(200)));
+            let task = pack_async::<HeightRectPacker>(&[Size::new(8, 8)], &RectanglePackerConfig::default());
+            task.cancel();
+            let result = task.await;
+            busy.await.unwrap();
+            result
+        });
+
+        assert_eq!(error.unwrap_err().message, RectanglePackingError::CANCELLED);
+    }
+
+    /// A packer that waits for its packing to be cancelled, signalling through `STARTED` that it is running.
+    struct WaitingPacker;
+
+    static STARTED: AtomicBool = AtomicBool::new(false);
+
+    impl RectanglePacker for WaitingPacker {
+        fn pack(sizes: &[Size], config: &RectanglePackerConfig) -> Result<RectanglePackingResult, RectanglePackingError> {
+            HeightRectPacker::pack(sizes, config)
+        }
+
+        fn pack_observed(
+            _sizes: &[Size],
+            _config: &RectanglePackerConfig,
+            observer: &mut dyn PackingObserver,
+        ) -> Result<RectanglePackingResult, RectanglePackingError> {
+            STARTED.store(true, Ordering::Relaxed);
+            while !observer.is_cancelled() {
+                std::thread::yield_now();
+            }
+            Err(RectanglePackingError::cancelled())
+        }
+    }
+
+    #[test]
+    fn running_packings_stop_when_cancelled() {
+        let error = runtime().block_on(async {
+            let task = pack_async::<WaitingPacker>(&[Size::new(8, 8)], &RectanglePackerConfig::default());
+            while !STARTED.load(Ordering::Relaxed) {
+                tokio::task::yield_now().await;
+            }
+            task.cancel();
+            task.await
+        });
+
+        assert_eq!(error.unwrap_err().message, RectanglePackingError::CANCELLED);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{Rectangle, Size};
+
+/// Lays out weights as a squarified treemap, tiling the container with one rectangle per weight.
+///
+/// Every rectangle gets an area proportional to its weight, and the rectangles are laid out in rows along
+/// the shorter side of the remaining space, which keeps their aspect ratios close to 1, following
+/// Bruls, Huizing and van Wijk, "Squarified Treemaps". The edges are rounded to whole pixels in a way
+/// that keeps the tiling exact: the rectangles never overlap and together cover the whole container.
+///
+/// # Arguments
+/// * `weights` - The weight of every rectangle, e.g. the size of a file or the time spent in a function.
+/// * `size` - The size of the container.
+///
+/// # Returns
+/// The rectangle of every weight, in the order of the weights. If all weights are zero,
+/// every rectangle is empty.
+///
+/// # Panics
+/// Panics if a weight is negative or not finite.
+///
+/// # Examples
+/// ```
+/// use rpack::{squarify, Rectangle, Size};
+///
+/// let rectangles = squarify(&[6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0], Size::new(6, 4));
+///
+/// // The two largest weights form the first column.
+/// assert_eq!(rectangles[0], Rectangle::new(0, 0, 3, 2));
+/// assert_eq!(rectangles[1], Rectangle::new(0, 2, 3, 2));
+/// ```
+pub fn squarify(weights: &[f64], size: Size) -> Vec<Rectangle> {
+    assert!(weights.iter().all(|w| w.is_finite() && *w >= 0.0), "Expected finite and non-negative weights");
+
+    let mut rectangles = vec![Rectangle::new(0, 0, 0, 0); weights.len()];
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return rectangles;
+    }
+
+    // Scale the weights to areas and lay them out from the largest to the smallest.
+    let scale = (size.width * size.height) as f64 / total;
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by(|&a, &b| weights[b].total_cmp(&weights[a]));
+
+    // The remaining space as its left, top, right and bottom edges.
+    let (mut left, mut top, right, bottom) = (0.0, 0.0, size.width as f64, size.height as f64);
+    let mut start = 0;
+    while start < order.len() {
+        let (width, height) = (right - left, bottom - top);
+        let side = width.min(height);
+
+        // Grow the row as long as its worst aspect ratio does not get worse.
+        let mut end = start + 1;
+        while end < order.len()
+            && worst(&order[start..end + 1], weights, scale, side) <= worst(&order[start..end], weights, scale, side)
+        {
+            end += 1;
+        }
+
+        // The row is as thick as its area requires, except for the last row, which takes up all of the remaining space.
+        let long = width.max(height);
+        let row_area: f64 = order[start..end].iter().map(|&i| weights[i] * scale).sum();
+        let thickness = match end == order.len() || side <= 0.0 {
+            true => long,
+            false => (row_area / side).min(long),
+        };
+
+        // Lay out the row as a column on the left if the space is wide, or as a row at the top otherwise.
+        let origin = match width >= height { true => top, false => left };
+        let mut offset = origin;
+        for (k, &index) in order[start..end].iter().enumerate() {
+            let next = match (k == end - start - 1, row_area > 0.0) {
+                (true, _) => origin + side,
+                (false, true) => offset + weights[index] * scale / row_area * side,
+                (false, false) => offset,
+            };
+            rectangles[index] = match width >= height {
+                true => round(left, offset, left + thickness, next),
+                false => round(offset, top, next, top + thickness),
+            };
+            offset = next;
+        }
+
+        match width >= height {
+            true => left += thickness,
+            false => top += thickness,
+        }
+        start = end;
+    }
+
+    rectangles
+}
+
+/// Returns the worst aspect ratio of the rectangles of a row laid out along a side of the given length.
+fn worst(row: &[usize], weights: &[f64], scale: f64, side: f64) -> f64 {
+    let areas = row.iter().map(|&i| weights[i] * scale);
+    let sum: f64 = areas.clone().sum();
+    let (min, max) = areas.fold((f64::MAX, 0.0f64), |(min, max), area| (min.min(area), max.max(area)));
+    let side = side * side;
+    (side * max / (sum * sum)).max(sum * sum / (side * min))
+}
+
+/// Returns the rectangle with the given edges rounded to whole pixels.
+fn round(left: f64, top: f64, right: f64, bottom: f64) -> Rectangle {
+    let (left, top) = (left.round() as usize, top.round() as usize);
+    let (right, bottom) = (right.round() as usize, bottom.round() as usize);
+    Rectangle::new(left, top, right - left, bottom - top)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Area;
+
+    use super::*;
+
+    fn assert_tiles(rectangles: &[Rectangle], size: Size) {
+        let container = Rectangle::from_size(0, 0, &size);
+        for (i, a) in rectangles.iter().enumerate() {
+            assert!(container.contains(a));
+            for b in &rectangles[i + 1..] {
+                assert!(!a.intersects(b));
+            }
+        }
+        assert_eq!(rectangles.iter().map(|r| r.area()).sum::<usize>(), size.width * size.height);
+    }
+
+    #[test]
+    fn squarify_works() {
+        let weights = [6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0];
+        let rectangles = squarify(&weights, Size::new(600, 400));
+
+        assert_tiles(&rectangles, Size::new(600, 400));
+        assert_eq!(rectangles[2], Rectangle::new(300, 0, 171, 233));
+        for r in &rectangles {
+            assert!(r.width.max(r.height) as f64 / r.width.min(r.height) as f64 <= 3.0);
+        }
+    }
+
+    #[test]
+    fn squarify_tiles_exactly() {
+        let weights: Vec<f64> = (1..50).map(|i| ((i * 37) % 23 + 1) as f64).collect();
+        let rectangles = squarify(&weights, Size::new(97, 61));
+
+        assert_tiles(&rectangles, Size::new(97, 61));
+    }
+
+    #[test]
+    fn squarify_handles_zero_weights() {
+        assert_eq!(squarify(&[0.0, 0.0], Size::new(4, 4)), vec![Rectangle::new(0, 0, 0, 0); 2]);
+
+        let rectangles = squarify(&[1.0, 0.0, 1.0], Size::new(4, 2));
+        assert_tiles(&rectangles, Size::new(4, 2));
+        assert_eq!(rectangles[1].area(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn squarify_panics_for_negative_weights() {
+        squarify(&[1.0, -1.0], Size::new(4, 4));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{Rectangle, Size};
+
+/// The normalized texture coordinates of a rectangle within a container, ranging from `0.0` to `1.0`.
+///
+/// The coordinates are measured from the top-left corner of the container, like the coordinates of a `Rectangle`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UvRect {
+    /// The top-left corner.
+    pub min: [f32; 2],
+
+    /// The bottom-right corner.
+    pub max: [f32; 2],
+}
+
+impl UvRect {
+    /// The inset of half a texel, which keeps bilinear filtering from sampling the neighbours of a rectangle.
+    pub const HALF_TEXEL: f32 = 0.5;
+
+    /// Returns the texture coordinates of a rectangle within a container of the given size.
+    ///
+    /// The coordinates are divided by the dimensions of the container. Along a dimension
+    /// in which the container has no extent, all coordinates are `0.0`.
+    ///
+    /// # Arguments
+    /// * `rectangle` - The rectangle in pixels.
+    /// * `size` - The size of the container, e.g. of the texture, in pixels.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{Rectangle, Size, UvRect};
+    ///
+    /// let uv = UvRect::new(&Rectangle::new(16, 32, 16, 64), &Size::new(64, 128));
+    /// assert_eq!(uv.min, [0.25, 0.25]);
+    /// assert_eq!(uv.max, [0.5, 0.75]);
+    /// ```
+    pub fn new(rectangle: &Rectangle, size: &Size) -> UvRect {
+        UvRect::with_inset(rectangle, size, 0.0)
+    }
+
+    /// Returns the texture coordinates of a rectangle within a container of the given size, moved inwards on every side.
+    ///
+    /// Insetting the coordinates by half a texel, see [`HALF_TEXEL`](Self::HALF_TEXEL), makes them point to the centers
+    /// of the outermost texels, so that bilinear filtering never samples the texels around the rectangle.
+    /// Engines disagree on the convention, so any inset can be given, e.g. a small epsilon.
+    /// If the inset is larger than half the rectangle, the coordinates collapse to its center.
+    ///
+    /// # Arguments
+    /// * `rectangle` - The rectangle in pixels.
+    /// * `size` - The size of the container, e.g. of the texture, in pixels.
+    /// * `inset` - The distance in texels by which each side is moved inwards.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{Rectangle, Size, UvRect};
+    ///
+    /// let uv = UvRect::with_inset(&Rectangle::new(0, 0, 4, 2), &Size::new(8, 8), UvRect::HALF_TEXEL);
+    /// assert_eq!(uv.min, [0.0625, 0.0625]);
+    /// assert_eq!(uv.max, [0.4375, 0.1875]);
+    /// ```
+    pub fn with_inset(rectangle: &Rectangle, size: &Size, inset: f32) -> UvRect {
+        let inset_x = inset.min(rectangle.width as f32 / 2.0);
+        let inset_y = inset.min(rectangle.height as f32 / 2.0);
+        let u = |x: f32| normalize(x, size.width);
+        let v = |y: f32| normalize(y, size.height);
+        UvRect {
+            min: [u(rectangle.x as f32 + inset_x), v(rectangle.y as f32 + inset_y)],
+            max: [u((rectangle.x + rectangle.width) as f32 - inset_x), v((rectangle.y + rectangle.height) as f32 - inset_y)],
+        }
+    }
+
+    /// Returns the width of the texture coordinates.
+    pub fn width(&self) -> f32 {
+        self.max[0] - self.min[0]
+    }
+
+    /// Returns the height of the texture coordinates.
+    pub fn height(&self) -> f32 {
+        self.max[1] - self.min[1]
+    }
+}
+
+/// Divides the coordinate by the extent, or returns `0.0` if the extent is zero.
+fn normalize(coordinate: f32, extent: usize) -> f32 {
+    match extent {
+        0 => 0.0,
+        _ => coordinate / extent as f32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_works() {
+        let uv = UvRect::new(&Rectangle::new(1, 0, 2, 3), &Size::new(4, 3));
+
+        assert_eq!(uv, UvRect { min: [0.25, 0.0], max: [0.75, 1.0] });
+        assert_eq!(uv.width(), 0.5);
+        assert_eq!(uv.height(), 1.0);
+    }
+
+    #[test]
+    fn with_inset_works() {
+        let rectangle = Rectangle::new(2, 4, 4, 1);
+        let size = Size::new(8, 8);
+
+        assert_eq!(UvRect::with_inset(&rectangle, &size, 0.0), UvRect::new(&rectangle, &size));
+        assert_eq!(UvRect::with_inset(&rectangle, &size, 1.0), UvRect { min: [0.375, 0.5625], max: [0.625, 0.5625] });
+        assert_eq!(UvRect::with_inset(&rectangle, &size, 0.25), UvRect { min: [0.28125, 0.53125], max: [0.71875, 0.59375] });
+    }
+
+    #[test]
+    fn empty_containers_work() {
+        let uv = UvRect::new(&Rectangle::new(0, 1, 0, 2), &Size::new(0, 4));
+
+        assert_eq!(uv, UvRect { min: [0.0, 0.25], max: [0.0, 0.75] });
+    }
+}
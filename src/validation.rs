@@ -0,0 +1,384 @@
+use std::error::Error;
+use std::fmt;
+use crate::{Rectangle, RectanglePackingResult, RectanglePackerConfig, Size};
+
+/// The reason why a `RectanglePackingResult` is not a valid packing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The rectangle at `index` lies (partly) outside of the container.
+    OutOfBounds {
+        /// The index of the rectangle in the result.
+        index: usize,
+        /// The offending rectangle.
+        rectangle: Rectangle,
+    },
+
+    /// The rectangle at `index` (including its padding) reaches into the border padding of the container.
+    BorderViolation {
+        /// The index of the rectangle in the result.
+        index: usize,
+        /// The offending rectangle.
+        rectangle: Rectangle,
+    },
+
+    /// The rectangles at `first` and `second` overlap.
+    Overlap {
+        /// The index of the first rectangle in the result.
+        first: usize,
+        /// The index of the second rectangle in the result.
+        second: usize,
+    },
+
+    /// The rectangles at `first` and `second` do not overlap, but their paddings do.
+    PaddingViolation {
+        /// The index of the first rectangle in the result.
+        first: usize,
+        /// The index of the second rectangle in the result.
+        second: usize,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::OutOfBounds { index, rectangle } => {
+                write!(f, "Rectangle {} {:?} lies outside of the container", index, rectangle)
+            }
+            ValidationError::BorderViolation { index, rectangle } => {
+                write!(f, "Rectangle {} {:?} reaches into the border padding", index, rectangle)
+            }
+            ValidationError::Overlap { first, second } => {
+                write!(f, "Rectangles {} and {} overlap", first, second)
+            }
+            ValidationError::PaddingViolation { first, second } => {
+                write!(f, "The paddings of rectangles {} and {} overlap", first, second)
+            }
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// The reason why a `RectanglePackerConfig` can not be packed with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The max size has a width or height of zero.
+    EmptyMaxSize {
+        /// The offending max size.
+        max_size: Size,
+    },
+
+    /// The border padding, the margin and the owned padding of a rectangle on both sides leave no room for rectangles
+    /// within the max size.
+    PaddingExceedsMaxSize {
+        /// The max size.
+        max_size: Size,
+        /// The padding on each side of a rectangle next to the border, `border_padding + rectangle_padding + owned_padding`.
+        padding: usize,
+    },
+
+    /// The `row_height_multiple` of the config is zero, so no row height is a multiple of it.
+    ZeroRowHeightMultiple,
+
+    /// A packer needs more temporary memory than the `memory_budget` of the config allows, even with its simplest strategy.
+    MemoryBudgetExceeded {
+        /// The number of bytes the packer needs at least.
+        required: usize,
+        /// The memory budget in bytes.
+        budget: usize,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::EmptyMaxSize { max_size } => {
+                write!(f, "Max size {:?} has no area", max_size)
+            }
+            ConfigError::PaddingExceedsMaxSize { max_size, padding } => {
+                write!(f, "Padding of {} on each side leaves no room within max size {:?}", padding, max_size)
+            }
+            ConfigError::ZeroRowHeightMultiple => {
+                write!(f, "Row heights can not be rounded to a multiple of zero")
+            }
+            ConfigError::MemoryBudgetExceeded { required, budget } => {
+                write!(f, "Packing needs {} bytes of memory, exceeding the memory budget of {} bytes", required, budget)
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl RectanglePackingResult {
+    /// Checks that the result is a valid packing for the given config.
+    ///
+    /// A packing is valid if
+    /// - every rectangle lies within the container,
+    /// - every rectangle keeps a distance of `border_padding + rectangle_padding` to the container edges,
+    /// - no two rectangles overlap and
+    /// - any two rectangles are at least `2 * rectangle_padding` apart.
+    ///
+    /// The rectangles of skipped sizes are ignored, and with `deduplicate_sizes`, identical rectangles may overlap.
+    ///
+    /// # Arguments
+    /// * `config` - The config the result was packed with.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - `Ok(())` if the packing is valid.
+    ///   - `Err(ValidationError)` describing the first violation that was found.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+    ///
+    /// let sizes = vec![Size::new(10, 20), Size::new(30, 5), Size::new(8, 8)];
+    /// let config = RectanglePackerConfig {
+    ///     rectangle_padding: 1,
+    ///     border_padding: 2,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let result = HeightRectPacker::pack(&sizes, &config).unwrap();
+    /// assert!(result.validate(&config).is_ok());
+    /// ```
+    pub fn validate(&self, config: &RectanglePackerConfig) -> Result<(), ValidationError> {
+        let padding = config.rectangle_padding;
+        let margin = config.border_padding + padding;
+
+        let placed = |index: &usize| self.skipped.binary_search(index).is_err();
+
+        for (index, rectangle) in self.rectangles.iter().enumerate().filter(|(index, _)| placed(index)) {
+            if rectangle.x + rectangle.width > self.size.width
+                || rectangle.y + rectangle.height > self.size.height
+            {
+                return Err(ValidationError::OutOfBounds { index, rectangle: *rectangle });
+            }
+
+            if rectangle.x < margin
+                || rectangle.y < margin
+                || rectangle.x + rectangle.width + margin > self.size.width
+                || rectangle.y + rectangle.height + margin > self.size.height
+            {
+                return Err(ValidationError::BorderViolation { index, rectangle: *rectangle });
+            }
+        }
+
+        // Sweep over the rectangles from left to right, so that each rectangle
+        // only has to be compared to the rectangles starting within its horizontal reach.
+        let mut order: Vec<usize> = (0..self.rectangles.len()).filter(placed).collect();
+        order.sort_by_key(|&i| self.rectangles[i].x);
+
+        for (position, &i) in order.iter().enumerate() {
+            let a = &self.rectangles[i];
+            for &j in &order[position + 1..] {
+                let b = &self.rectangles[j];
+                if b.x >= a.x + a.width + 2 * padding {
+                    break;
+                }
+
+                if config.deduplicate_sizes && a == b {
+                    continue;
+                }
+
+                let (first, second) = (i.min(j), i.max(j));
+                if a.intersects(b) {
+                    return Err(ValidationError::Overlap { first, second });
+                }
+                if padding > 0
+                    && b.y < a.y + a.height + 2 * padding
+                    && a.y < b.y + b.height + 2 * padding
+                {
+                    return Err(ValidationError::PaddingViolation { first, second });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RectanglePackerConfig {
+    /// Checks that rectangles can be packed with the config at all.
+    ///
+    /// A config is valid if its `row_height_multiple`, if any, is larger than zero, and if it has no max size,
+    /// or if the max size
+    /// - has a width and height larger than zero and
+    /// - leaves room for rectangles within `border_padding + rectangle_padding + owned_padding` of each edge.
+    ///
+    /// Packers check the config before packing, so that impossible combinations fail up front instead of
+    /// failing with an error for every size.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - `Ok(())` if the config is valid.
+    ///   - `Err(ConfigError)` describing the first problem that was found.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{ConfigError, RectanglePackerConfig, Size};
+    ///
+    /// let config = RectanglePackerConfig {
+    ///     max_size: Some(Size::new(16, 16)),
+    ///     rectangle_padding: 4,
+    ///     border_padding: 4,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(config.validate(), Err(ConfigError::PaddingExceedsMaxSize { max_size: Size::new(16, 16), padding: 8 }));
+    /// ```
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.row_height_multiple == Some(0) {
+            return Err(ConfigError::ZeroRowHeightMultiple);
+        }
+
+        let Some(max_size) = self.max_size else {
+            return Ok(());
+        };
+
+        if max_size.width == 0 || max_size.height == 0 {
+            return Err(ConfigError::EmptyMaxSize { max_size });
+        }
+
+        let padding = self.border_padding.saturating_add(self.rectangle_padding).saturating_add(self.owned_padding);
+        if padding.saturating_mul(2) >= max_size.width.min(max_size.height) {
+            return Err(ConfigError::PaddingExceedsMaxSize { max_size, padding });
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the given number of bytes of temporary memory fits into the `memory_budget`.
+    ///
+    /// # Returns
+    /// A `Result` containing either:
+    ///   - `Ok(())` if there is no memory budget, or the memory fits into it.
+    ///   - `Err(ConfigError::MemoryBudgetExceeded)` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use rpack::{ConfigError, RectanglePackerConfig};
+    ///
+    /// let config = RectanglePackerConfig { memory_budget: Some(1024), ..Default::default() };
+    ///
+    /// assert!(config.check_memory(1024).is_ok());
+    /// assert_eq!(config.check_memory(2048), Err(ConfigError::MemoryBudgetExceeded { required: 2048, budget: 1024 }));
+    /// ```
+    pub fn check_memory(&self, required: usize) -> Result<(), ConfigError> {
+        match self.memory_budget {
+            Some(budget) if required > budget => Err(ConfigError::MemoryBudgetExceeded { required, budget }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(rectangles: Vec<Rectangle>, size: Size) -> RectanglePackingResult {
+        RectanglePackingResult { rectangles, size, skipped: Vec::new() }
+    }
+
+    #[test]
+    fn config_validation_works() {
+        let config = |max_size, rectangle_padding, border_padding| RectanglePackerConfig {
+            max_size,
+            rectangle_padding,
+            border_padding,
+            ..Default::default()
+        };
+
+        assert_eq!(config(None, 100, 100).validate(), Ok(()));
+        assert_eq!(config(Some(Size::new(5, 3)), 0, 1).validate(), Ok(()));
+        assert_eq!(
+            config(Some(Size::new(0, 8)), 0, 0).validate(),
+            Err(ConfigError::EmptyMaxSize { max_size: Size::new(0, 8) })
+        );
+        assert_eq!(
+            config(Some(Size::new(8, 2)), 1, 0).validate(),
+            Err(ConfigError::PaddingExceedsMaxSize { max_size: Size::new(8, 2), padding: 1 })
+        );
+        assert!(config(Some(Size::new(8, 8)), usize::MAX, 1).validate().is_err());
+    }
+
+    #[test]
+    fn valid_packing_works() {
+        let config = RectanglePackerConfig {
+            rectangle_padding: 1,
+            border_padding: 1,
+            ..Default::default()
+        };
+        let result = result(
+            vec![Rectangle::new(2, 2, 4, 4), Rectangle::new(8, 2, 4, 4)],
+            Size::new(14, 8),
+        );
+
+        assert_eq!(result.validate(&config), Ok(()));
+    }
+
+    #[test]
+    fn out_of_bounds_is_detected() {
+        let result = result(vec![Rectangle::new(8, 0, 4, 4)], Size::new(10, 10));
+
+        assert_eq!(
+            result.validate(&RectanglePackerConfig::default()),
+            Err(ValidationError::OutOfBounds { index: 0, rectangle: Rectangle::new(8, 0, 4, 4) })
+        );
+    }
+
+    #[test]
+    fn border_violation_is_detected() {
+        let config = RectanglePackerConfig {
+            border_padding: 2,
+            ..Default::default()
+        };
+        let result = result(vec![Rectangle::new(2, 1, 4, 4)], Size::new(10, 10));
+
+        assert_eq!(
+            result.validate(&config),
+            Err(ValidationError::BorderViolation { index: 0, rectangle: Rectangle::new(2, 1, 4, 4) })
+        );
+    }
+
+    #[test]
+    fn overlap_is_detected() {
+        let result = result(
+            vec![Rectangle::new(0, 0, 5, 5), Rectangle::new(6, 0, 2, 2), Rectangle::new(4, 4, 2, 2)],
+            Size::new(10, 10),
+        );
+
+        assert_eq!(
+            result.validate(&RectanglePackerConfig::default()),
+            Err(ValidationError::Overlap { first: 0, second: 2 })
+        );
+    }
+
+    #[test]
+    fn padding_violation_is_detected() {
+        let config = RectanglePackerConfig {
+            rectangle_padding: 1,
+            ..Default::default()
+        };
+        let result = result(
+            vec![Rectangle::new(1, 1, 3, 3), Rectangle::new(5, 1, 3, 3)],
+            Size::new(10, 10),
+        );
+
+        assert_eq!(
+            result.validate(&config),
+            Err(ValidationError::PaddingViolation { first: 0, second: 1 })
+        );
+    }
+
+    #[test]
+    fn touching_rectangles_are_valid() {
+        let result = result(
+            vec![Rectangle::new(0, 0, 5, 5), Rectangle::new(5, 0, 5, 5), Rectangle::new(0, 5, 10, 5)],
+            Size::new(10, 10),
+        );
+
+        assert_eq!(result.validate(&RectanglePackerConfig::default()), Ok(()));
+    }
+}
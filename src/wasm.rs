@@ -0,0 +1,116 @@
+use wasm_bindgen::prelude::*;
+use crate::{HeightRectPacker, RectanglePacker, RectanglePackerConfig, Size};
+
+/// The result of packing rectangles from JavaScript.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedRectangles {
+    width: u32,
+    height: u32,
+    rectangles: Vec<u32>,
+}
+
+#[wasm_bindgen]
+impl PackedRectangles {
+    /// The width of the container.
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the container.
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The packed rectangles as a flat `Uint32Array` of `x, y, width, height` quadruples,
+    /// in the same order as the sizes they were packed from.
+    #[wasm_bindgen(getter)]
+    pub fn rectangles(&self) -> Vec<u32> {
+        self.rectangles.clone()
+    }
+}
+
+/// Packs rectangles with the `HeightRectPacker`, exported to JavaScript as `packRectangles`.
+///
+/// # Arguments
+/// * `sizes` - The sizes of the rectangles as a flat `Uint32Array` of `width, height` pairs.
+/// * `rectangle_padding` - The margin to leave around each rectangle, see `RectanglePackerConfig::rectangle_padding`.
+/// * `border_padding` - The amount of padding to add around the container rectangle.
+/// * `max_width` - The maximum width of the container, or `undefined` to determine the size dynamically.
+/// * `max_height` - The maximum height of the container, or `undefined` to determine the size dynamically.
+///
+/// # Errors
+/// Throws an `Error` if the sizes are not given as pairs or the rectangles can not be packed.
+#[wasm_bindgen(js_name = packRectangles)]
+pub fn pack_rectangles(
+    sizes: &[u32],
+    rectangle_padding: u32,
+    border_padding: u32,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+) -> Result<PackedRectangles, JsError> {
+    pack(sizes, rectangle_padding, border_padding, max_width, max_height).map_err(|e| JsError::new(&e))
+}
+
+/// Packs the rectangles given as flat `width, height` pairs, reporting errors as messages.
+fn pack(
+    sizes: &[u32],
+    rectangle_padding: u32,
+    border_padding: u32,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+) -> Result<PackedRectangles, String> {
+    if !sizes.len().is_multiple_of(2) {
+        return Err("Expected the sizes as pairs of width and height".to_string());
+    }
+
+    let sizes: Vec<Size> = sizes
+        .chunks_exact(2)
+        .map(|s| Size::new(s[0] as usize, s[1] as usize))
+        .collect();
+    let max_size = match (max_width, max_height) {
+        (Some(width), Some(height)) => Some(Size::new(width as usize, height as usize)),
+        (None, None) => None,
+        _ => return Err("Expected either both or none of the maximum width and height".to_string()),
+    };
+    let config = RectanglePackerConfig {
+        max_size,
+        rectangle_padding: rectangle_padding as usize,
+        border_padding: border_padding as usize,
+        ..Default::default()
+    };
+
+    let result = HeightRectPacker::pack(&sizes, &config).map_err(|e| e.message)?;
+    Ok(PackedRectangles {
+        width: result.size.width as u32,
+        height: result.size.height as u32,
+        rectangles: result.rectangles
+            .iter()
+            .flat_map(|r| [r.x as u32, r.y as u32, r.width as u32, r.height as u32])
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_works() {
+        let packed = pack(&[10, 20, 30, 5], 1, 1, None, None).unwrap();
+
+        assert_eq!(packed.rectangles().len(), 8);
+        assert_eq!(&packed.rectangles()[2..4], &[10, 20]);
+        assert_eq!(&packed.rectangles()[6..8], &[30, 5]);
+        assert!(packed.width() >= 12 && packed.height() >= 22);
+    }
+
+    #[test]
+    fn pack_fails_on_invalid_input() {
+        assert!(pack(&[10, 20, 30], 0, 0, None, None).is_err());
+        assert!(pack(&[10, 20], 0, 0, Some(100), None).is_err());
+        assert!(pack(&[10, 20], 0, 0, Some(5), Some(5)).is_err());
+    }
+}
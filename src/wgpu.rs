@@ -0,0 +1,188 @@
+use wgpu::{
+    Device, Extent3d, Origin3d, Queue, TexelCopyBufferLayout, TexelCopyTextureInfo, Texture, TextureAspect,
+    TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+};
+use crate::{AllocationId, AtlasAllocator, Rectangle, Size};
+pub use crate::UvRect;
+
+/// An image uploaded to a `WgpuTextureAtlas`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Upload {
+    /// The identifier to remove the image with.
+    pub id: AllocationId,
+
+    /// The region of the texture containing the image, in pixels.
+    pub rectangle: Rectangle,
+
+    /// The texture coordinates of the image.
+    pub uv: UvRect,
+}
+
+/// A wgpu texture that RGBA images are packed into at runtime.
+///
+/// Images are placed with an `AtlasAllocator` and copied into the texture with `Queue::write_texture`,
+/// so the copies are executed with the next submission of the queue.
+///
+/// # Examples
+/// ```no_run
+/// use rpack::wgpu::WgpuTextureAtlas;
+/// use rpack::Size;
+///
+/// # fn example(device: &wgpu::Device, queue: &wgpu::Queue) {
+/// let mut atlas = WgpuTextureAtlas::new(device, Size::new(1024, 1024), 1);
+/// let upload = atlas.upload(queue, Size::new(2, 1), &[255, 0, 0, 255, 0, 0, 255, 255]).unwrap();
+///
+/// let view = atlas.texture().create_view(&wgpu::TextureViewDescriptor::default());
+/// // Bind the view and draw a quad using `upload.uv`.
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct WgpuTextureAtlas {
+    allocator: AtlasAllocator,
+    texture: Texture,
+}
+
+impl WgpuTextureAtlas {
+    /// The format of the texture.
+    pub const FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
+    /// Creates an atlas with an empty texture.
+    ///
+    /// # Arguments
+    /// * `device` - The device to create the texture on.
+    /// * `size` - The size of the texture in pixels.
+    /// * `padding` - The amount of padding kept free around each image, so that images do not bleed into each other.
+    pub fn new(device: &Device, size: Size, padding: usize) -> WgpuTextureAtlas {
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("rpack atlas"),
+            size: Extent3d { width: size.width as u32, height: size.height as u32, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: Self::FORMAT,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        WgpuTextureAtlas {
+            allocator: AtlasAllocator::new(size).with_padding(padding, 0),
+            texture,
+        }
+    }
+
+    /// Returns the texture containing the images.
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Returns the size of the texture.
+    pub fn size(&self) -> Size {
+        self.allocator.size()
+    }
+
+    /// Places an image in the atlas and writes it to the texture.
+    ///
+    /// # Arguments
+    /// * `queue` - The queue to write the image with.
+    /// * `size` - The size of the image.
+    /// * `rgba` - The pixels of the image, row by row, with 4 bytes per pixel.
+    ///
+    /// # Returns
+    /// The `Upload` describing where the image was placed, or `None` if there is no free space large enough for it.
+    ///
+    /// # Panics
+    /// Panics if the length of `rgba` does not match the size of the image.
+    pub fn upload(&mut self, queue: &Queue, size: Size, rgba: &[u8]) -> Option<Upload> {
+        assert_eq!(rgba.len(), size.width * size.height * 4, "pixel data does not match the image size");
+
+        let upload = place(&mut self.allocator, size)?;
+        if let Some((origin, layout, extent)) = texel_copy(&upload.rectangle) {
+            let destination = TexelCopyTextureInfo { texture: &self.texture, mip_level: 0, origin, aspect: TextureAspect::All };
+            queue.write_texture(destination, rgba, layout, extent);
+        }
+
+        Some(upload)
+    }
+
+    /// Removes an image, making its space available to new images.
+    ///
+    /// The pixels of the texture are left untouched until they are overwritten by another image.
+    ///
+    /// # Returns
+    /// `true` if the image was part of the atlas.
+    pub fn remove(&mut self, id: AllocationId) -> bool {
+        self.allocator.deallocate(id).is_some()
+    }
+
+    /// Returns the texture coordinates of an image, or `None` if it is not part of the atlas.
+    pub fn uv(&self, id: AllocationId) -> Option<UvRect> {
+        self.allocator.get(id).map(|r| UvRect::new(&r, &self.size()))
+    }
+}
+
+/// Places an image of the given size with the allocator, returning where it was placed
+/// or `None` if there is no free space large enough for it.
+fn place(allocator: &mut AtlasAllocator, size: Size) -> Option<Upload> {
+    let allocation = allocator.allocate(size)?;
+    let r = allocation.rectangle;
+    Some(Upload { id: allocation.id, rectangle: r, uv: UvRect::new(&r, &allocator.size()) })
+}
+
+/// Returns the origin in the texture, the layout of the pixels and the extent of the copy of an image
+/// into the given region, or `None` if the region is empty and there is nothing to copy.
+fn texel_copy(r: &Rectangle) -> Option<(Origin3d, TexelCopyBufferLayout, Extent3d)> {
+    if r.width == 0 || r.height == 0 {
+        return None;
+    }
+    Some((
+        Origin3d { x: r.x as u32, y: r.y as u32, z: 0 },
+        TexelCopyBufferLayout { offset: 0, bytes_per_row: Some(r.width as u32 * 4), rows_per_image: None },
+        Extent3d { width: r.width as u32, height: r.height as u32, depth_or_array_layers: 1 },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uv_rect_works() {
+        let uv = UvRect::new(&Rectangle::new(16, 32, 16, 64), &Size::new(64, 128));
+
+        assert_eq!(uv.min, [0.25, 0.25]);
+        assert_eq!(uv.max, [0.5, 0.75]);
+    }
+
+    #[test]
+    fn uploads_are_placed_with_padding() {
+        let mut allocator = AtlasAllocator::new(Size::new(8, 4)).with_padding(1, 0);
+        let first = place(&mut allocator, Size::new(2, 2)).unwrap();
+        let second = place(&mut allocator, Size::new(2, 2)).unwrap();
+
+        assert_eq!(first.rectangle, Rectangle::new(1, 1, 2, 2));
+        assert_eq!(first.uv, UvRect::new(&first.rectangle, &Size::new(8, 4)));
+        assert!(!first.rectangle.expanded(1).intersects(&second.rectangle));
+        assert_eq!(second.uv, UvRect::new(&second.rectangle, &Size::new(8, 4)));
+        assert!(place(&mut allocator, Size::new(8, 4)).is_none());
+    }
+
+    #[test]
+    fn removed_uploads_free_their_space() {
+        let mut allocator = AtlasAllocator::new(Size::new(4, 4));
+        let upload = place(&mut allocator, Size::new(4, 4)).unwrap();
+
+        assert!(place(&mut allocator, Size::new(2, 2)).is_none());
+        assert_eq!(allocator.deallocate(upload.id), Some(upload.rectangle));
+        assert!(allocator.get(upload.id).is_none());
+        assert!(place(&mut allocator, Size::new(2, 2)).is_some());
+    }
+
+    #[test]
+    fn texel_copies_cover_the_region() {
+        let (origin, layout, extent) = texel_copy(&Rectangle::new(2, 3, 4, 5)).unwrap();
+
+        assert_eq!(origin, Origin3d { x: 2, y: 3, z: 0 });
+        assert_eq!((layout.offset, layout.bytes_per_row, layout.rows_per_image), (0, Some(16), None));
+        assert_eq!(extent, Extent3d { width: 4, height: 5, depth_or_array_layers: 1 });
+        assert!(texel_copy(&Rectangle::new(2, 3, 0, 5)).is_none());
+    }
+}